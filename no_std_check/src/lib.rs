@@ -0,0 +1,43 @@
+//! Compiles the std-free core of statistical-tests-rs under `#![no_std]`.
+//! Build it on its own, so the root crate's default features do not leak in:
+//!
+//! ```sh
+//! cargo build -p no-std-check --target thumbv7em-none-eabihf
+//! ```
+
+#![no_std]
+
+use statistical_tests_rs::distributions::{ChiSquare, FisherF, Normal, StudentsT};
+use statistical_tests_rs::special::{erf, ln_gamma};
+use statistical_tests_rs::{
+    mean_of, sample_standard_deviation_of, welch_t_test, GetStatistics, RunningStatistics,
+    SampleStatistics, TTestResult,
+};
+
+/// A Welch t-test of two slices, the core path from raw data to a p-value.
+pub fn compare(a: &[f64], b: &[f64]) -> TTestResult {
+    welch_t_test(
+        &SampleStatistics::from_array(a),
+        &SampleStatistics::from_array(b),
+    )
+}
+
+/// Summaries of a slice of f32 readings, as from a sensor.
+pub fn summarize(readings: &[f32]) -> (f64, f64, f64) {
+    let running: RunningStatistics = readings.iter().copied().collect();
+    (
+        mean_of(readings),
+        sample_standard_deviation_of(readings),
+        running.max(),
+    )
+}
+
+/// Touches each distribution and special function so all of them are built.
+pub fn distributions(x: f64) -> f64 {
+    Normal::standard().cdf(x)
+        + StudentsT::new(4.5).quantile(0.975)
+        + ChiSquare::new(3.0).sf(x)
+        + FisherF::new(2.0, 9.0).pdf(x)
+        + erf(x)
+        + ln_gamma(x)
+}
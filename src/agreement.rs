@@ -0,0 +1,389 @@
+use std::fmt;
+
+use crate::distributions::Normal;
+use crate::format::{significant, write_test_line};
+use crate::{ConfidenceInterval, HypothesisTest, StatsError};
+
+/// Confidence level of the interval reported by `cohens_kappa`.
+const KAPPA_CONFIDENCE: f64 = 0.95;
+
+/// Credit given to partial disagreements between ordinal categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KappaWeights {
+    /// Only exact agreement counts, Cohen's original kappa.
+    Unweighted,
+    /// Agreement weight 1 - |i - j| / (k - 1).
+    Linear,
+    /// Agreement weight 1 - (i - j)² / (k - 1)².
+    Quadratic,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KappaResult {
+    pub kappa: f64,
+    /// Large-sample standard error of kappa (Fleiss, Cohen and Everitt, 1969).
+    pub standard_error: f64,
+    /// 95% Wald interval kappa ± z standard_error.
+    pub confidence_interval: ConfidenceInterval,
+    /// kappa over its standard error under the null hypothesis of chance
+    /// agreement.
+    pub z: f64,
+    /// Two-sided p-value for kappa = 0.
+    pub p_value: f64,
+}
+
+impl fmt::Display for KappaResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "κ = {}, ", significant(self.kappa, 3))?;
+        write_test_line(f, "z", &[], self.z, self.p_value)
+    }
+}
+
+impl HypothesisTest for KappaResult {
+    fn statistic(&self) -> f64 {
+        self.z
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Cohen's kappa"
+    }
+}
+
+/// Cohen's kappa between two raters' category labels, given as indices
+/// 0..k. With weights, the distance between categories i and j is |i - j|,
+/// so labels should be in their ordinal order; the value matches
+/// scikit-learn's `cohen_kappa_score` when every label in 0..k is used.
+pub fn cohens_kappa(
+    rater_a: &[usize],
+    rater_b: &[usize],
+    weights: KappaWeights,
+) -> Result<KappaResult, StatsError> {
+    if rater_a.len() != rater_b.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: rater_a.len(),
+            right: rater_b.len(),
+        });
+    }
+    let Some(&largest) = rater_a.iter().chain(rater_b).max() else {
+        return Err(StatsError::EmptyInput);
+    };
+
+    let mut matrix = vec![vec![0.0; largest + 1]; largest + 1];
+    for (&a, &b) in rater_a.iter().zip(rater_b) {
+        matrix[a][b] += 1.0;
+    }
+    cohens_kappa_from_matrix(&matrix, weights)
+}
+
+/// Cohen's kappa from a k x k confusion matrix of counts, with rows for the
+/// first rater's categories and columns for the second's.
+pub fn cohens_kappa_from_matrix(
+    matrix: &[Vec<f64>],
+    weights: KappaWeights,
+) -> Result<KappaResult, StatsError> {
+    let k = matrix.len();
+    if let Some(row) = matrix.iter().find(|row| row.len() != k) {
+        return Err(StatsError::MismatchedLengths {
+            left: k,
+            right: row.len(),
+        });
+    }
+    if matrix.iter().flatten().any(|&count| count < 0.0) {
+        return Err(StatsError::InvalidParameter("counts must not be negative"));
+    }
+    let n: f64 = matrix.iter().flatten().sum();
+    if n == 0.0 {
+        return Err(StatsError::EmptyInput);
+    }
+    if k < 2 {
+        return Err(StatsError::InvalidParameter(
+            "kappa needs at least two categories",
+        ));
+    }
+
+    let weight = |i: usize, j: usize| {
+        let distance = i.abs_diff(j) as f64;
+        let span = (k - 1) as f64;
+        match weights {
+            KappaWeights::Unweighted => f64::from(u8::from(i == j)),
+            KappaWeights::Linear => 1.0 - distance / span,
+            KappaWeights::Quadratic => 1.0 - (distance / span).powi(2),
+        }
+    };
+    let proportions: Vec<Vec<f64>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|count| count / n).collect())
+        .collect();
+    let rows: Vec<f64> = proportions.iter().map(|row| row.iter().sum()).collect();
+    let columns: Vec<f64> = (0..k)
+        .map(|j| proportions.iter().map(|row| row[j]).sum())
+        .collect();
+
+    let mut observed = 0.0;
+    let mut expected = 0.0;
+    for (i, row) in proportions.iter().enumerate() {
+        for (j, &p) in row.iter().enumerate() {
+            observed += weight(i, j) * p;
+            expected += weight(i, j) * rows[i] * columns[j];
+        }
+    }
+    if expected == 1.0 {
+        return Err(StatsError::InvalidParameter(
+            "kappa is undefined when chance agreement is perfect",
+        ));
+    }
+    let kappa = (observed - expected) / (1.0 - expected);
+
+    // Mean weights of each row and column category under the margins
+    let row_weights: Vec<f64> = (0..k)
+        .map(|i| (0..k).map(|j| columns[j] * weight(i, j)).sum())
+        .collect();
+    let column_weights: Vec<f64> = (0..k)
+        .map(|j| (0..k).map(|i| rows[i] * weight(i, j)).sum())
+        .collect();
+    let mut alternative_sum = 0.0;
+    let mut null_sum = 0.0;
+    for (i, row) in proportions.iter().enumerate() {
+        for (j, &p) in row.iter().enumerate() {
+            let margin_weight = row_weights[i] + column_weights[j];
+            alternative_sum += p * (weight(i, j) - margin_weight * (1.0 - kappa)).powi(2);
+            null_sum += rows[i] * columns[j] * (weight(i, j) - margin_weight).powi(2);
+        }
+    }
+    let scale = n * (1.0 - expected).powi(2);
+    let standard_error =
+        ((alternative_sum - (kappa - expected * (1.0 - kappa)).powi(2)) / scale).sqrt();
+    let null_standard_error = ((null_sum - expected * expected) / scale).sqrt();
+
+    let normal = Normal::standard();
+    let margin = normal.quantile(0.5 + KAPPA_CONFIDENCE / 2.0) * standard_error;
+    let z = kappa / null_standard_error;
+    Ok(KappaResult {
+        kappa,
+        standard_error,
+        confidence_interval: ConfidenceInterval {
+            lower: kappa - margin,
+            upper: kappa + margin,
+            point_estimate: kappa,
+            confidence: KAPPA_CONFIDENCE,
+        },
+        z,
+        p_value: normal.two_tailed(z),
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FleissKappaResult {
+    pub kappa: f64,
+    /// Standard error of kappa under the null hypothesis of chance agreement
+    /// (Fleiss, Nee and Landis, 1979).
+    pub standard_error: f64,
+    pub z: f64,
+    /// Two-sided p-value for kappa = 0.
+    pub p_value: f64,
+}
+
+impl fmt::Display for FleissKappaResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "κ = {}, ", significant(self.kappa, 3))?;
+        write_test_line(f, "z", &[], self.z, self.p_value)
+    }
+}
+
+impl HypothesisTest for FleissKappaResult {
+    fn statistic(&self) -> f64 {
+        self.z
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Fleiss' kappa"
+    }
+}
+
+/// Fleiss' kappa for agreement among m raters per item. Each row of `counts`
+/// holds how many raters put one item in each of the k categories, so every
+/// row must sum to the same m, which must be at least 2. The test statistic
+/// is that of R's `irr::kappam.fleiss`.
+pub fn fleiss_kappa(counts: &[Vec<usize>]) -> Result<FleissKappaResult, StatsError> {
+    let Some(first) = counts.first() else {
+        return Err(StatsError::EmptyInput);
+    };
+    let k = first.len();
+    let raters: usize = first.iter().sum();
+    for (row, ratings) in counts.iter().enumerate() {
+        if ratings.len() != k {
+            return Err(StatsError::MismatchedLengths {
+                left: k,
+                right: ratings.len(),
+            });
+        }
+        let total = ratings.iter().sum();
+        if total != raters {
+            return Err(StatsError::InconsistentRow {
+                row,
+                expected: raters,
+                actual: total,
+            });
+        }
+    }
+    if raters < 2 {
+        return Err(StatsError::InvalidParameter(
+            "each item needs at least two ratings",
+        ));
+    }
+
+    let items = counts.len() as f64;
+    let m = raters as f64;
+    let category_proportions: Vec<f64> = (0..k)
+        .map(|j| counts.iter().map(|row| row[j] as f64).sum::<f64>() / (items * m))
+        .collect();
+    let observed = counts
+        .iter()
+        .map(|row| {
+            let pairs: f64 = row.iter().map(|&c| (c * c) as f64).sum();
+            (pairs - m) / (m * (m - 1.0))
+        })
+        .sum::<f64>()
+        / items;
+    let expected: f64 = category_proportions.iter().map(|p| p * p).sum();
+    if expected == 1.0 {
+        return Err(StatsError::InvalidParameter(
+            "kappa is undefined when every rating is in one category",
+        ));
+    }
+    let kappa = (observed - expected) / (1.0 - expected);
+
+    let spread: f64 = category_proportions.iter().map(|p| p * (1.0 - p)).sum();
+    let skew: f64 = category_proportions
+        .iter()
+        .map(|p| p * (1.0 - p) * (1.0 - 2.0 * p))
+        .sum();
+    let standard_error =
+        (2.0 / (items * m * (m - 1.0))).sqrt() * (spread * spread - skew).sqrt() / spread;
+    let z = kappa / standard_error;
+    Ok(FleissKappaResult {
+        kappa,
+        standard_error,
+        z,
+        p_value: Normal::standard().two_tailed(z),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const RATER_A: [usize; 20] = [0, 1, 2, 1, 0, 2, 2, 1, 0, 1, 2, 2, 0, 1, 1, 2, 0, 0, 1, 2];
+    const RATER_B: [usize; 20] = [0, 1, 1, 1, 0, 2, 2, 2, 0, 0, 2, 1, 0, 1, 1, 2, 1, 0, 1, 2];
+
+    #[test]
+    fn cohens_kappa_test() {
+        // sklearn: cohen_kappa_score(a, b); standard errors from Fleiss et al.
+        let result = cohens_kappa(&RATER_A, &RATER_B, KappaWeights::Unweighted).unwrap();
+        assert_close(result.kappa, 0.62406015037594, 1e-14);
+        assert_close(result.standard_error, 0.146511686943908, 1e-14);
+        assert_close(result.z, 3.95709458667662, 1e-12);
+        assert_close(result.p_value, 7.58668956380196e-5, 1e-15);
+        assert_close(result.confidence_interval.lower, 0.336902520651673, 1e-12);
+        assert_close(result.confidence_interval.upper, 0.911217780100206, 1e-12);
+    }
+
+    #[test]
+    fn weighted_kappa_test() {
+        // sklearn: cohen_kappa_score(a, b, weights = "linear" / "quadratic")
+        let result = cohens_kappa(&RATER_A, &RATER_B, KappaWeights::Linear).unwrap();
+        assert_close(result.kappa, 0.709302325581395, 1e-14);
+        assert_close(result.standard_error, 0.118829969907048, 1e-14);
+        assert_close(result.z, 4.0982076326176, 1e-12);
+
+        let result = cohens_kappa(&RATER_A, &RATER_B, KappaWeights::Quadratic).unwrap();
+        assert_close(result.kappa, 0.8, 1e-14);
+        assert_close(result.standard_error, 0.0876794160564496, 1e-14);
+        assert_close(result.p_value, 0.000333889313117741, 1e-15);
+
+        let matrix = vec![
+            vec![5.0, 1.0, 0.0],
+            vec![1.0, 5.0, 1.0],
+            vec![0.0, 2.0, 5.0],
+        ];
+        let from_matrix = cohens_kappa_from_matrix(&matrix, KappaWeights::Quadratic).unwrap();
+        assert_close(from_matrix.kappa, 0.8, 1e-14);
+    }
+
+    #[test]
+    fn cohens_kappa_invalid_input_test() {
+        assert_eq!(
+            cohens_kappa(&[0, 1], &[0], KappaWeights::Unweighted).err(),
+            Some(StatsError::MismatchedLengths { left: 2, right: 1 })
+        );
+        assert_eq!(
+            cohens_kappa(&[], &[], KappaWeights::Unweighted).err(),
+            Some(StatsError::EmptyInput)
+        );
+        assert!(matches!(
+            cohens_kappa(&[1, 1, 1], &[1, 1, 1], KappaWeights::Unweighted),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            cohens_kappa(&[0, 0], &[0, 0], KappaWeights::Linear),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn fleiss_kappa_test() {
+        // The 14-rater, 5-category worked example of Fleiss' kappa on
+        // Wikipedia (kappa = 0.210); R: irr::kappam.fleiss on the ratings
+        let counts = vec![
+            vec![0, 0, 0, 0, 14],
+            vec![0, 2, 6, 4, 2],
+            vec![0, 0, 3, 5, 6],
+            vec![0, 3, 9, 2, 0],
+            vec![2, 2, 8, 1, 1],
+            vec![7, 7, 0, 0, 0],
+            vec![3, 2, 6, 3, 0],
+            vec![2, 5, 3, 2, 2],
+            vec![6, 5, 2, 1, 0],
+            vec![0, 2, 2, 3, 7],
+        ];
+        let result = fleiss_kappa(&counts).unwrap();
+        assert_close(result.kappa, 0.209930704421955, 1e-14);
+        assert_close(result.standard_error, 0.0169650692243931, 1e-15);
+        assert_close(result.z, 12.3742910591905, 1e-11);
+        assert_close(result.p_value, 3.60059432346653e-35, 1e-45);
+    }
+
+    #[test]
+    fn fleiss_kappa_invalid_input_test() {
+        assert_eq!(
+            fleiss_kappa(&[vec![1, 2], vec![3, 0], vec![2, 2]]).err(),
+            Some(StatsError::InconsistentRow {
+                row: 2,
+                expected: 3,
+                actual: 4
+            })
+        );
+        assert_eq!(fleiss_kappa(&[]).err(), Some(StatsError::EmptyInput));
+        assert!(matches!(
+            fleiss_kappa(&[vec![1, 0], vec![0, 1]]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
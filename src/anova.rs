@@ -0,0 +1,577 @@
+use std::fmt;
+
+use crate::distributions::{FisherF, StudentizedRange};
+use crate::format::write_test_line;
+use crate::{mean, sample_variance, HypothesisTest, StatsError};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnovaResult {
+    pub ss_between: f64,
+    pub ss_within: f64,
+    pub df_between: f64,
+    pub df_within: f64,
+    pub ms_between: f64,
+    pub ms_within: f64,
+    pub f: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for AnovaResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(
+            f,
+            "F",
+            &[self.df_between, self.df_within],
+            self.f,
+            self.p_value,
+        )
+    }
+}
+
+impl HypothesisTest for AnovaResult {
+    fn statistic(&self) -> f64 {
+        self.f
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "one-way ANOVA"
+    }
+}
+
+impl AnovaResult {
+    /// Proportion of total variation explained by group membership,
+    /// SS_between / SS_total. Biased upward in small samples.
+    pub fn eta_squared(&self) -> f64 {
+        self.ss_between / (self.ss_between + self.ss_within)
+    }
+
+    /// The less biased (SS_between - df_between MS_within) / (SS_total +
+    /// MS_within). The estimate falls below zero when F < 1; with
+    /// `clamp_negative` such values are reported as 0, as R's `effectsize`
+    /// does.
+    pub fn omega_squared(&self, clamp_negative: bool) -> f64 {
+        let omega = (self.ss_between - self.df_between * self.ms_within)
+            / (self.ss_between + self.ss_within + self.ms_within);
+        if clamp_negative {
+            omega.max(0.0)
+        } else {
+            omega
+        }
+    }
+}
+
+/// One-way analysis of variance across two or more groups, each with at
+/// least two observations.
+pub fn one_way_anova(groups: &[&[f64]]) -> Result<AnovaResult, StatsError> {
+    validate_groups(groups)?;
+
+    let n: usize = groups.iter().map(|g| g.len()).sum();
+    let grand_mean = groups.iter().flat_map(|g| g.iter()).sum::<f64>() / n as f64;
+
+    let mut ss_between = 0.0;
+    let mut ss_within = 0.0;
+    for group in groups {
+        let group_mean = mean(group);
+        ss_between += group.len() as f64 * (group_mean - grand_mean).powi(2);
+        ss_within += group.iter().map(|x| (x - group_mean).powi(2)).sum::<f64>();
+    }
+
+    let df_between = (groups.len() - 1) as f64;
+    let df_within = (n - groups.len()) as f64;
+    let ms_between = ss_between / df_between;
+    let ms_within = ss_within / df_within;
+    let f = ms_between / ms_within;
+    let p_value = FisherF::new(df_between, df_within).sf(f);
+
+    Ok(AnovaResult {
+        ss_between,
+        ss_within,
+        df_between,
+        df_within,
+        ms_between,
+        ms_within,
+        f,
+        p_value,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WelchAnovaResult {
+    pub f: f64,
+    pub df_between: f64,
+    pub df_within: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for WelchAnovaResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(
+            f,
+            "F",
+            &[self.df_between, self.df_within],
+            self.f,
+            self.p_value,
+        )
+    }
+}
+
+impl HypothesisTest for WelchAnovaResult {
+    fn statistic(&self) -> f64 {
+        self.f
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Welch's ANOVA"
+    }
+}
+
+/// Welch's one-way ANOVA, which drops the equal-variance assumption of
+/// `one_way_anova` by weighting each group by n / s² and adjusting the
+/// denominator degrees of freedom.
+pub fn welch_anova(groups: &[&[f64]]) -> Result<WelchAnovaResult, StatsError> {
+    validate_groups(groups)?;
+
+    let k = groups.len() as f64;
+    let means: Vec<f64> = groups.iter().map(|g| mean(g)).collect();
+    let weights: Vec<f64> = groups
+        .iter()
+        .map(|g| g.len() as f64 / sample_variance(g))
+        .collect();
+    if weights.iter().any(|w| !w.is_finite()) {
+        return Err(StatsError::InvalidParameter(
+            "every group needs a non-zero variance",
+        ));
+    }
+    let weight_sum: f64 = weights.iter().sum();
+    let weighted_mean = weights.iter().zip(&means).map(|(w, m)| w * m).sum::<f64>() / weight_sum;
+
+    let between = weights
+        .iter()
+        .zip(&means)
+        .map(|(w, m)| w * (m - weighted_mean).powi(2))
+        .sum::<f64>()
+        / (k - 1.0);
+    let lambda = weights
+        .iter()
+        .zip(groups)
+        .map(|(w, g)| (1.0 - w / weight_sum).powi(2) / (g.len() as f64 - 1.0))
+        .sum::<f64>()
+        / (k * k - 1.0);
+
+    let f = between / (1.0 + 2.0 * (k - 2.0) * lambda);
+    let df_between = k - 1.0;
+    let df_within = 1.0 / (3.0 * lambda);
+    let p_value = FisherF::new(df_between, df_within).sf(f);
+
+    Ok(WelchAnovaResult {
+        f,
+        df_between,
+        df_within,
+        p_value,
+    })
+}
+
+/// One pairwise comparison from `tukey_hsd`, between the groups at indices
+/// `group_1` and `group_2` of the input.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TukeyComparison {
+    pub group_1: usize,
+    pub group_2: usize,
+    /// Mean of `group_2` minus mean of `group_1`.
+    pub mean_difference: f64,
+    pub lower: f64,
+    pub upper: f64,
+    pub p_value: f64,
+    pub significant: bool,
+}
+
+/// Tukey's honestly significant difference test for every pair of groups,
+/// with simultaneous `1 - alpha` confidence intervals from the studentized
+/// range distribution. Uses the Tukey-Kramer standard error for unequal
+/// group sizes.
+pub fn tukey_hsd(groups: &[&[f64]], alpha: f64) -> Result<Vec<TukeyComparison>, StatsError> {
+    if !(alpha > 0.0 && alpha < 1.0) {
+        return Err(StatsError::InvalidParameter(
+            "alpha must be between 0 and 1",
+        ));
+    }
+    let anova = one_way_anova(groups)?;
+
+    let range = StudentizedRange::new(groups.len() as f64, anova.df_within);
+    let critical_value = range.quantile(1.0 - alpha);
+    let means: Vec<f64> = groups.iter().map(|g| mean(g)).collect();
+
+    let mut comparisons = Vec::new();
+    for i in 0..groups.len() {
+        for j in i + 1..groups.len() {
+            let mean_difference = means[j] - means[i];
+            let standard_error = (anova.ms_within / 2.0
+                * (1.0 / groups[i].len() as f64 + 1.0 / groups[j].len() as f64))
+                .sqrt();
+            let margin = critical_value * standard_error;
+            let p_value = range.sf(mean_difference.abs() / standard_error);
+            comparisons.push(TukeyComparison {
+                group_1: i,
+                group_2: j,
+                mean_difference,
+                lower: mean_difference - margin,
+                upper: mean_difference + margin,
+                p_value,
+                significant: p_value < alpha,
+            });
+        }
+    }
+
+    Ok(comparisons)
+}
+
+/// One effect row of an ANOVA table.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnovaEffect {
+    pub ss: f64,
+    pub df: f64,
+    pub ms: f64,
+    pub f: f64,
+    pub p_value: f64,
+}
+
+/// The residual row of an ANOVA table.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnovaResidual {
+    pub ss: f64,
+    pub df: f64,
+    pub ms: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoWayAnovaResult {
+    pub factor_a: AnovaEffect,
+    pub factor_b: AnovaEffect,
+    pub interaction: AnovaEffect,
+    pub residual: AnovaResidual,
+}
+
+/// Two-way ANOVA with interaction for a balanced design, where `data[i][j]`
+/// holds the replicate observations for level i of factor A and level j of
+/// factor B. Every cell must have the same number of replicates, at least two.
+pub fn two_way_anova(data: &[Vec<Vec<f64>>]) -> Result<TwoWayAnovaResult, StatsError> {
+    let a = data.len();
+    let b = data.first().map_or(0, |row| row.len());
+    let r = data
+        .first()
+        .and_then(|row| row.first())
+        .map_or(0, |cell| cell.len());
+    if a < 2 || b < 2 {
+        return Err(StatsError::InvalidParameter(
+            "both factors need at least two levels",
+        ));
+    }
+    if r < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: r,
+        });
+    }
+    if data
+        .iter()
+        .any(|row| row.len() != b || row.iter().any(|cell| cell.len() != r))
+    {
+        return Err(StatsError::InvalidParameter(
+            "two-way ANOVA requires a balanced design",
+        ));
+    }
+
+    let cell_means: Vec<Vec<f64>> = data
+        .iter()
+        .map(|row| row.iter().map(|cell| mean(cell)).collect())
+        .collect();
+    let a_means: Vec<f64> = cell_means.iter().map(|row| mean(row)).collect();
+    let b_means: Vec<f64> = (0..b)
+        .map(|j| cell_means.iter().map(|row| row[j]).sum::<f64>() / a as f64)
+        .collect();
+    let grand_mean = mean(&a_means);
+
+    let (a_f, b_f, r_f) = (a as f64, b as f64, r as f64);
+    let ss_a = b_f
+        * r_f
+        * a_means
+            .iter()
+            .map(|m| (m - grand_mean).powi(2))
+            .sum::<f64>();
+    let ss_b = a_f
+        * r_f
+        * b_means
+            .iter()
+            .map(|m| (m - grand_mean).powi(2))
+            .sum::<f64>();
+    let mut ss_interaction = 0.0;
+    let mut ss_residual = 0.0;
+    for (i, row) in data.iter().enumerate() {
+        for (j, cell) in row.iter().enumerate() {
+            let cell_mean = cell_means[i][j];
+            ss_interaction += (cell_mean - a_means[i] - b_means[j] + grand_mean).powi(2);
+            ss_residual += cell.iter().map(|x| (x - cell_mean).powi(2)).sum::<f64>();
+        }
+    }
+    ss_interaction *= r_f;
+
+    let df_residual = a_f * b_f * (r_f - 1.0);
+    let residual = AnovaResidual {
+        ss: ss_residual,
+        df: df_residual,
+        ms: ss_residual / df_residual,
+    };
+    let effect = |ss: f64, df: f64| {
+        let ms = ss / df;
+        let f = ms / residual.ms;
+        AnovaEffect {
+            ss,
+            df,
+            ms,
+            f,
+            p_value: FisherF::new(df, df_residual).sf(f),
+        }
+    };
+
+    Ok(TwoWayAnovaResult {
+        factor_a: effect(ss_a, a_f - 1.0),
+        factor_b: effect(ss_b, b_f - 1.0),
+        interaction: effect(ss_interaction, (a_f - 1.0) * (b_f - 1.0)),
+        residual,
+    })
+}
+
+/// Requires at least two groups, each with at least two observations.
+fn validate_groups(groups: &[&[f64]]) -> Result<(), StatsError> {
+    if groups.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: groups.len(),
+        });
+    }
+    if let Some(group) = groups.iter().find(|g| g.len() < 2) {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: group.len(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const CTRL: [f64; 10] = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+    const TRT1: [f64; 10] = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+    const TRT2: [f64; 10] = [6.31, 5.12, 5.54, 5.50, 5.37, 5.29, 4.92, 6.15, 5.80, 5.26];
+
+    #[test]
+    fn one_way_anova_test() {
+        // R: summary(aov(weight ~ group, data = PlantGrowth))
+        let result = one_way_anova(&[&CTRL, &TRT1, &TRT2]).unwrap();
+        assert_close(result.ss_between, 3.76634, 1e-10);
+        assert_close(result.ss_within, 10.49209, 1e-10);
+        assert_eq!(result.df_between, 2.0);
+        assert_eq!(result.df_within, 27.0);
+        assert_close(result.ms_between, 1.88317, 1e-10);
+        assert_close(result.ms_within, 0.388595925925926, 1e-12);
+        assert_close(result.f, 4.84608786238013, 1e-10);
+        assert_close(result.p_value, 0.0159099583256229, 1e-10);
+    }
+
+    #[test]
+    fn anova_display_test() {
+        let result = one_way_anova(&[&CTRL, &TRT1, &TRT2]).unwrap();
+        assert_eq!(result.to_string(), "F(2, 27) = 4.85, p = 0.0159");
+    }
+
+    #[test]
+    fn anova_effect_size_test() {
+        // R: effectsize::eta_squared(aov(weight ~ group, data = PlantGrowth))
+        let result = one_way_anova(&[&CTRL, &TRT1, &TRT2]).unwrap();
+        assert_close(result.eta_squared(), 0.26414829683212, 1e-12);
+        assert_close(result.omega_squared(false), 0.204078845989971, 1e-12);
+        assert_eq!(result.omega_squared(true), result.omega_squared(false));
+
+        let result =
+            one_way_anova(&[&[1.0, 2.0, 3.0], &[1.5, 2.5, 2.0], &[2.0, 1.0, 3.0, 2.5]]).unwrap();
+        assert_close(result.eta_squared(), 0.00793650793650794, 1e-14);
+        assert_close(result.omega_squared(false), -0.241310824230387, 1e-12);
+        assert_eq!(result.omega_squared(true), 0.0);
+    }
+
+    #[test]
+    fn one_way_anova_unequal_sizes_test() {
+        let result = one_way_anova(&[
+            &[1.0, 2.0, 3.0],
+            &[2.0, 3.0, 4.0, 5.0],
+            &[5.0, 6.0, 7.0, 8.0, 9.0],
+        ])
+        .unwrap();
+        assert_close(result.ss_between, 53.9166666666667, 1e-10);
+        assert_close(result.ss_within, 17.0, 1e-10);
+        assert_close(result.f, 14.2720588235294, 1e-10);
+        assert_close(result.p_value, 0.00161678671900237, 1e-12);
+    }
+
+    #[test]
+    fn one_way_anova_invalid_input_test() {
+        assert_eq!(
+            one_way_anova(&[&CTRL]).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            one_way_anova(&[&CTRL, &[1.0]]).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn welch_anova_test() {
+        // R: oneway.test(weight ~ group, data = PlantGrowth, var.equal = FALSE)
+        let result = welch_anova(&[&CTRL, &TRT1, &TRT2]).unwrap();
+        assert_close(result.f, 5.18097240811319, 1e-10);
+        assert_eq!(result.df_between, 2.0);
+        assert_close(result.df_within, 17.1284186166441, 1e-10);
+        assert_close(result.p_value, 0.01739282149017, 1e-10);
+    }
+
+    #[test]
+    fn welch_anova_heteroscedastic_test() {
+        let result = welch_anova(&[
+            &[10.1, 9.8, 10.4, 10.0, 9.7, 10.2],
+            &[11.0, 10.6, 11.3, 10.9, 11.1],
+            &[6.0, 14.5, 9.2, 16.8, 3.1, 12.7, 8.4],
+        ])
+        .unwrap();
+        assert_close(result.f, 17.0942923018749, 1e-10);
+        assert_close(result.df_within, 9.44677605653661, 1e-10);
+        assert_close(result.p_value, 0.000726188063992822, 1e-12);
+    }
+
+    #[test]
+    fn welch_anova_zero_variance_test() {
+        assert!(matches!(
+            welch_anova(&[&[1.0, 1.0, 1.0], &[2.0, 3.0, 4.0]]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn tukey_hsd_test() {
+        // R: TukeyHSD(aov(weight ~ group, data = PlantGrowth))
+        let comparisons = tukey_hsd(&[&CTRL, &TRT1, &TRT2], 0.05).unwrap();
+        assert_eq!(comparisons.len(), 3);
+        let expected = [
+            (0, 1, -0.371, -1.0622161, 0.3202161, 0.3908711, false),
+            (0, 2, 0.494, -0.1972161, 1.1852161, 0.1979960, false),
+            (1, 2, 0.865, 0.1737839, 1.5562161, 0.0120064, true),
+        ];
+        for (comparison, &(group_1, group_2, diff, lower, upper, p_value, significant)) in
+            comparisons.iter().zip(&expected)
+        {
+            assert_eq!(comparison.group_1, group_1);
+            assert_eq!(comparison.group_2, group_2);
+            assert_close(comparison.mean_difference, diff, 1e-12);
+            assert_close(comparison.lower, lower, 1e-6);
+            assert_close(comparison.upper, upper, 1e-6);
+            assert_close(comparison.p_value, p_value, 1e-6);
+            assert_eq!(comparison.significant, significant);
+        }
+    }
+
+    #[test]
+    fn tukey_hsd_invalid_alpha_test() {
+        assert!(matches!(
+            tukey_hsd(&[&CTRL, &TRT1], 1.5),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    fn battery_life() -> Vec<Vec<Vec<f64>>> {
+        vec![
+            vec![
+                vec![130.0, 155.0, 74.0, 180.0],
+                vec![34.0, 40.0, 80.0, 75.0],
+                vec![20.0, 70.0, 82.0, 58.0],
+            ],
+            vec![
+                vec![150.0, 188.0, 159.0, 126.0],
+                vec![136.0, 122.0, 106.0, 115.0],
+                vec![25.0, 70.0, 58.0, 45.0],
+            ],
+            vec![
+                vec![138.0, 110.0, 168.0, 160.0],
+                vec![174.0, 120.0, 150.0, 139.0],
+                vec![96.0, 104.0, 82.0, 60.0],
+            ],
+        ]
+    }
+
+    #[test]
+    fn two_way_anova_test() {
+        // Montgomery, Design and Analysis of Experiments: battery life by
+        // material type (A) and temperature (B)
+        let result = two_way_anova(&battery_life()).unwrap();
+        assert_close(result.factor_a.ss, 10683.7222222222, 1e-8);
+        assert_eq!(result.factor_a.df, 2.0);
+        assert_close(result.factor_a.f, 7.91137226938003, 1e-10);
+        assert_close(result.factor_a.p_value, 0.00197608259090752, 1e-12);
+
+        assert_close(result.factor_b.ss, 39118.7222222222, 1e-8);
+        assert_close(result.factor_b.f, 28.9676919490421, 1e-10);
+        assert_close(result.factor_b.p_value, 1.9085958974332e-7, 1e-15);
+
+        assert_close(result.interaction.ss, 9613.77777777778, 1e-8);
+        assert_eq!(result.interaction.df, 4.0);
+        assert_close(result.interaction.f, 3.5595354003538, 1e-10);
+        assert_close(result.interaction.p_value, 0.0186111681889417, 1e-12);
+
+        assert_close(result.residual.ss, 18230.75, 1e-8);
+        assert_eq!(result.residual.df, 27.0);
+        assert_close(result.residual.ms, 675.212962962963, 1e-10);
+    }
+
+    #[test]
+    fn two_way_anova_unbalanced_test() {
+        let mut data = battery_life();
+        data[1][2].pop();
+        assert!(matches!(
+            two_way_anova(&data),
+            Err(StatsError::InvalidParameter(_))
+        ));
+
+        let single_replicate = vec![vec![vec![1.0], vec![2.0]], vec![vec![3.0], vec![4.0]]];
+        assert_eq!(
+            two_way_anova(&single_replicate).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+}
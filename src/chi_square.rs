@@ -0,0 +1,999 @@
+use std::fmt;
+
+use crate::distributions::{ChiSquare, Normal};
+use crate::format::write_test_line;
+use crate::hypothesis::validate_alpha;
+use crate::{adjust_p_values, BinSpec, CorrectionMethod, Histogram, HypothesisTest, StatsError};
+
+/// Expected counts below this are commonly considered too small for the
+/// chi-square approximation to be reliable.
+const MIN_EXPECTED_COUNT: f64 = 5.0;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChiSquareResult {
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+    /// Whether any expected count is below 5, making the approximation suspect.
+    pub low_expected_count: bool,
+}
+
+impl fmt::Display for ChiSquareResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "χ²", &[self.df], self.statistic, self.p_value)
+    }
+}
+
+impl HypothesisTest for ChiSquareResult {
+    fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "chi-square goodness-of-fit test"
+    }
+}
+
+/// Pearson's chi-square goodness-of-fit test with df = k - 1.
+pub fn chi_square_gof(observed: &[f64], expected: &[f64]) -> Result<ChiSquareResult, StatsError> {
+    chi_square_gof_with_params(observed, expected, 0)
+}
+
+/// Pearson's chi-square goodness-of-fit test where `estimated_params`
+/// parameters of the expected distribution were estimated from the data,
+/// giving df = k - 1 - estimated_params.
+pub fn chi_square_gof_with_params(
+    observed: &[f64],
+    expected: &[f64],
+    estimated_params: usize,
+) -> Result<ChiSquareResult, StatsError> {
+    validate_gof(observed, expected, estimated_params)?;
+
+    let statistic = observed
+        .iter()
+        .zip(expected)
+        .map(|(o, e)| (o - e).powi(2) / e)
+        .sum();
+    Ok(gof_result(statistic, expected, estimated_params))
+}
+
+/// G-test (likelihood ratio) goodness-of-fit test, G = 2 sum O ln(O / E),
+/// with df = k - 1. Categories with no observations contribute zero.
+pub fn g_test_gof(observed: &[f64], expected: &[f64]) -> Result<ChiSquareResult, StatsError> {
+    validate_gof(observed, expected, 0)?;
+
+    let statistic = 2.0
+        * observed
+            .iter()
+            .zip(expected)
+            .map(|(&o, &e)| likelihood_ratio_term(o, e))
+            .sum::<f64>();
+    Ok(gof_result(statistic, expected, 0))
+}
+
+/// Chi-square goodness-of-fit test against expected category probabilities,
+/// which are scaled to the observed total.
+pub fn chi_square_gof_probabilities(
+    observed: &[f64],
+    probabilities: &[f64],
+) -> Result<ChiSquareResult, StatsError> {
+    let total: f64 = observed.iter().sum();
+    let probability_total: f64 = probabilities.iter().sum();
+    let expected: Vec<f64> = probabilities
+        .iter()
+        .map(|p| p / probability_total * total)
+        .collect();
+
+    chi_square_gof(observed, &expected)
+}
+
+/// A chi-square goodness-of-fit test of binned data, with the bins it was
+/// computed on.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinnedGofResult {
+    pub result: ChiSquareResult,
+    /// Edges of the bins after merging, one more than the number of bins.
+    /// The outer bins also cover the tails of the distribution beyond the
+    /// first and last edges.
+    pub edges: Vec<f64>,
+    pub observed: Vec<f64>,
+    pub expected: Vec<f64>,
+}
+
+impl fmt::Display for BinnedGofResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.result.fmt(f)
+    }
+}
+
+/// Chi-square goodness-of-fit test of continuous `data` against the
+/// distribution with cumulative distribution function `cdf`. The data are
+/// binned by `bins` as `Histogram::new` does, the expected counts come from
+/// differences of `cdf` across the edges, and bins are merged until every
+/// expected count is at least 5: the bin with the smallest expected count
+/// joins its smaller neighbour, which folds the sparse tails inwards first.
+/// df = k - 1 - `estimated_params` for the k bins that remain, where
+/// `estimated_params` parameters of the distribution were fitted to `data`.
+/// Values outside explicit `BinSpec::Edges` are not counted.
+///
+/// Fails as `Histogram::new` does, with `StatsError::InsufficientData` if
+/// merging leaves a single bin and with `StatsError::InvalidParameter` if
+/// too few bins remain for `estimated_params`.
+pub fn chi_square_gof_distribution(
+    data: &[f64],
+    cdf: impl Fn(f64) -> f64,
+    estimated_params: usize,
+    bins: BinSpec,
+) -> Result<BinnedGofResult, StatsError> {
+    let histogram = Histogram::new(data, bins)?;
+    let (mut observed, mut expected) = histogram.observed_expected(cdf);
+    let mut edges = histogram.edges().to_vec();
+
+    while expected.len() > 1 {
+        let (smallest, &count) = expected
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .expect("at least one bin");
+        if count >= MIN_EXPECTED_COUNT {
+            break;
+        }
+        let neighbour = match smallest {
+            0 => 1,
+            i if i == expected.len() - 1 => i - 1,
+            i if expected[i - 1] <= expected[i + 1] => i - 1,
+            i => i + 1,
+        };
+        // Bins i and i + 1 merge by dropping the edge between them
+        let left = smallest.min(neighbour);
+        let merged = observed.remove(left + 1);
+        observed[left] += merged;
+        let merged = expected.remove(left + 1);
+        expected[left] += merged;
+        edges.remove(left + 1);
+    }
+
+    Ok(BinnedGofResult {
+        result: chi_square_gof_with_params(&observed, &expected, estimated_params)?,
+        edges,
+        observed,
+        expected,
+    })
+}
+
+fn validate_gof(
+    observed: &[f64],
+    expected: &[f64],
+    estimated_params: usize,
+) -> Result<(), StatsError> {
+    if observed.len() != expected.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: observed.len(),
+            right: expected.len(),
+        });
+    }
+    if observed.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: observed.len(),
+        });
+    }
+    if observed.len() <= estimated_params + 1 {
+        return Err(StatsError::InvalidParameter(
+            "too many estimated parameters for the number of categories",
+        ));
+    }
+    if observed.iter().chain(expected).any(|&x| x < 0.0) {
+        return Err(StatsError::InvalidParameter("counts must not be negative"));
+    }
+    if expected.contains(&0.0) {
+        return Err(StatsError::InvalidParameter(
+            "expected counts must not be zero",
+        ));
+    }
+    Ok(())
+}
+
+fn gof_result(statistic: f64, expected: &[f64], estimated_params: usize) -> ChiSquareResult {
+    let df = (expected.len() - 1 - estimated_params) as f64;
+    let p_value = ChiSquare::new(df).sf(statistic);
+
+    ChiSquareResult {
+        statistic,
+        df,
+        p_value,
+        low_expected_count: expected.iter().any(|&e| e < MIN_EXPECTED_COUNT),
+    }
+}
+
+/// O ln(O / E), taking 0 ln(0) as 0.
+fn likelihood_ratio_term(observed: f64, expected: f64) -> f64 {
+    if observed == 0.0 {
+        0.0
+    } else {
+        observed * (observed / expected).ln()
+    }
+}
+
+/// An r x c table of observed counts.
+pub struct ContingencyTable {
+    counts: Vec<Vec<f64>>,
+    row_totals: Vec<f64>,
+    column_totals: Vec<f64>,
+    total: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContingencyTestResult {
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+    /// Expected counts under independence, in the same layout as the table.
+    pub expected: Vec<Vec<f64>>,
+    /// Pearson residuals (O - E) / sqrt(E).
+    pub residuals: Vec<Vec<f64>>,
+    /// Standardized residuals (O - E) / sqrt(E (1 - row / n) (1 - column / n)),
+    /// approximately standard normal under independence.
+    pub standardized_residuals: Vec<Vec<f64>>,
+}
+
+impl fmt::Display for ContingencyTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "χ²", &[self.df], self.statistic, self.p_value)
+    }
+}
+
+impl HypothesisTest for ContingencyTestResult {
+    fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "chi-square test of independence"
+    }
+}
+
+/// Post-hoc test of one cell of a contingency table.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CellResidualTest {
+    pub row: usize,
+    pub column: usize,
+    /// The adjusted standardized residual of the cell.
+    pub residual: f64,
+    /// Two-sided p-value of `residual` against the standard normal.
+    pub p_value: f64,
+    /// `p_value` corrected over every cell of the table.
+    pub adjusted_p_value: f64,
+    /// Whether `adjusted_p_value` is below the chosen alpha.
+    pub significant: bool,
+}
+
+/// Test of independence on the sub-table of two rows of a contingency table.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairwiseRowTest {
+    pub row_1: usize,
+    pub row_2: usize,
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+    /// `p_value` corrected over every pair of rows.
+    pub adjusted_p_value: f64,
+    /// Whether `adjusted_p_value` is below the chosen alpha.
+    pub significant: bool,
+}
+
+impl ContingencyTestResult {
+    /// Which cells depart from independence: each cell's standardized
+    /// residual, as R's `chisq.test(x)$stdres`, with its two-sided normal
+    /// p-value corrected for the r c cells by `method` and compared to
+    /// `alpha`. Cells are listed in row-major order.
+    pub fn cell_post_hoc(
+        &self,
+        alpha: f64,
+        method: CorrectionMethod,
+    ) -> Result<Vec<CellResidualTest>, StatsError> {
+        validate_alpha(alpha)?;
+        let residuals: Vec<f64> = self
+            .standardized_residuals
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+        let p_values: Vec<f64> = residuals
+            .iter()
+            .map(|&z| Normal::standard().two_tailed(z))
+            .collect();
+        let adjusted = adjust_p_values(&p_values, method)?;
+        let columns = self.expected[0].len();
+        Ok(residuals
+            .iter()
+            .zip(p_values)
+            .zip(adjusted)
+            .enumerate()
+            .map(
+                |(index, ((&residual, p_value), adjusted_p_value))| CellResidualTest {
+                    row: index / columns,
+                    column: index % columns,
+                    residual,
+                    p_value,
+                    adjusted_p_value,
+                    significant: adjusted_p_value < alpha,
+                },
+            )
+            .collect())
+    }
+}
+
+/// A chi-square based measure of association, with the statistic and sample
+/// size it was computed from.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssociationResult {
+    pub value: f64,
+    /// Pearson's chi-square statistic without continuity correction.
+    pub chi_square: f64,
+    /// Total count of the table.
+    pub n: f64,
+}
+
+impl ContingencyTable {
+    /// Builds a table from row-major counts. The table must be at least 2x2,
+    /// rectangular, non-negative, and have no empty rows or columns.
+    pub fn new(counts: Vec<Vec<f64>>) -> Result<Self, StatsError> {
+        let rows = counts.len();
+        let columns = counts.first().map_or(0, |row| row.len());
+        if rows < 2 || columns < 2 {
+            return Err(StatsError::InvalidParameter(
+                "a contingency table needs at least two rows and two columns",
+            ));
+        }
+        if let Some(row) = counts.iter().find(|row| row.len() != columns) {
+            return Err(StatsError::MismatchedLengths {
+                left: columns,
+                right: row.len(),
+            });
+        }
+        if counts.iter().flatten().any(|&x| x < 0.0) {
+            return Err(StatsError::InvalidParameter("counts must not be negative"));
+        }
+
+        let row_totals: Vec<f64> = counts.iter().map(|row| row.iter().sum()).collect();
+        let column_totals: Vec<f64> = (0..columns)
+            .map(|j| counts.iter().map(|row| row[j]).sum())
+            .collect();
+        if row_totals.contains(&0.0) || column_totals.contains(&0.0) {
+            return Err(StatsError::InvalidParameter(
+                "rows and columns must not be entirely zero",
+            ));
+        }
+        let total = row_totals.iter().sum();
+
+        Ok(ContingencyTable {
+            counts,
+            row_totals,
+            column_totals,
+            total,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn columns(&self) -> usize {
+        self.column_totals.len()
+    }
+
+    /// Expected counts under independence, row total * column total / n.
+    pub fn expected(&self) -> Vec<Vec<f64>> {
+        self.row_totals
+            .iter()
+            .map(|r| {
+                self.column_totals
+                    .iter()
+                    .map(|c| r * c / self.total)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Pearson's chi-square test of independence.
+    pub fn chi_square_test(&self) -> ContingencyTestResult {
+        self.pearson_test(0.0)
+    }
+
+    /// Pearson's chi-square test with Yates' continuity correction, only
+    /// defined for 2x2 tables.
+    pub fn yates_chi_square_test(&self) -> Result<ContingencyTestResult, StatsError> {
+        if self.rows() != 2 || self.columns() != 2 {
+            return Err(StatsError::InvalidParameter(
+                "Yates' correction only applies to 2x2 tables",
+            ));
+        }
+
+        let expected = self.expected();
+        let smallest_deviation = self
+            .cells(&expected)
+            .map(|(o, e)| (o - e).abs())
+            .fold(f64::INFINITY, f64::min);
+        Ok(self.pearson_test(smallest_deviation.min(0.5)))
+    }
+
+    /// G-test (likelihood ratio test) of independence, G = 2 sum O ln(O / E).
+    pub fn g_test(&self) -> ContingencyTestResult {
+        let expected = self.expected();
+        let statistic = 2.0
+            * self
+                .cells(&expected)
+                .map(|(o, e)| likelihood_ratio_term(o, e))
+                .sum::<f64>();
+        self.independence_result(statistic, expected)
+    }
+
+    /// Cramér's V, sqrt(X² / (n (min(r, c) - 1))), between 0 and 1. With
+    /// `bias_corrected`, uses the Bergsma correction as rcompanion's
+    /// `cramerV(bias.correct = TRUE)`, which shrinks V for small tables and
+    /// samples and is 0 when X² is below its expectation under independence.
+    pub fn cramers_v(&self, bias_corrected: bool) -> AssociationResult {
+        let chi_square = self.chi_square_test().statistic;
+        let n = self.total;
+        let rows = self.rows() as f64;
+        let columns = self.columns() as f64;
+        let value = if bias_corrected {
+            let phi_squared =
+                (chi_square / n - (rows - 1.0) * (columns - 1.0) / (n - 1.0)).max(0.0);
+            let rows = rows - (rows - 1.0).powi(2) / (n - 1.0);
+            let columns = columns - (columns - 1.0).powi(2) / (n - 1.0);
+            (phi_squared / (rows.min(columns) - 1.0)).sqrt()
+        } else {
+            (chi_square / (n * (rows.min(columns) - 1.0))).sqrt()
+        };
+        AssociationResult {
+            value,
+            chi_square,
+            n,
+        }
+    }
+
+    /// The phi coefficient of a 2x2 table [[a, b], [c, d]],
+    /// (ad - bc) / sqrt(row and column totals), the Pearson correlation of the
+    /// two binary variables. Its magnitude is sqrt(X² / n).
+    pub fn phi(&self) -> Result<AssociationResult, StatsError> {
+        if self.rows() != 2 || self.columns() != 2 {
+            return Err(StatsError::InvalidParameter(
+                "phi is only defined for 2x2 tables",
+            ));
+        }
+        let [a, b] = [self.counts[0][0], self.counts[0][1]];
+        let [c, d] = [self.counts[1][0], self.counts[1][1]];
+        let margins: f64 = self.row_totals.iter().chain(&self.column_totals).product();
+        Ok(AssociationResult {
+            value: (a * d - b * c) / margins.sqrt(),
+            chi_square: self.chi_square_test().statistic,
+            n: self.total,
+        })
+    }
+
+    /// Compares every pair of rows, as rcompanion's
+    /// `pairwiseNominalIndependence`: `test`, such as
+    /// `ContingencyTable::chi_square_test` or `ContingencyTable::g_test`, is
+    /// run on the 2 x c sub-table of the two rows, and the p-values are
+    /// corrected for the r (r - 1) / 2 pairs by `method` and compared to
+    /// `alpha`. Columns empty in both rows are left out of their sub-table.
+    /// If fewer than two columns remain, both rows fall in one column and
+    /// the pair gets a statistic of 0 on 0 df with p-value 1.
+    pub fn pairwise_row_tests(
+        &self,
+        test: impl Fn(&ContingencyTable) -> ContingencyTestResult,
+        alpha: f64,
+        method: CorrectionMethod,
+    ) -> Result<Vec<PairwiseRowTest>, StatsError> {
+        validate_alpha(alpha)?;
+        let mut pairs = Vec::new();
+        let mut results = Vec::new();
+        for row_1 in 0..self.rows() {
+            for row_2 in row_1 + 1..self.rows() {
+                let occupied: Vec<usize> = (0..self.columns())
+                    .filter(|&j| self.counts[row_1][j] + self.counts[row_2][j] > 0.0)
+                    .collect();
+                let result = if occupied.len() < 2 {
+                    (0.0, 0.0, 1.0)
+                } else {
+                    let sub_table = ContingencyTable::new(
+                        [row_1, row_2]
+                            .iter()
+                            .map(|&i| occupied.iter().map(|&j| self.counts[i][j]).collect())
+                            .collect(),
+                    )?;
+                    let result = test(&sub_table);
+                    (result.statistic, result.df, result.p_value)
+                };
+                pairs.push((row_1, row_2));
+                results.push(result);
+            }
+        }
+        let p_values: Vec<f64> = results.iter().map(|&(_, _, p)| p).collect();
+        let adjusted = adjust_p_values(&p_values, method)?;
+        Ok(pairs
+            .into_iter()
+            .zip(results)
+            .zip(adjusted)
+            .map(
+                |(((row_1, row_2), (statistic, df, p_value)), adjusted_p_value)| PairwiseRowTest {
+                    row_1,
+                    row_2,
+                    statistic,
+                    df,
+                    p_value,
+                    adjusted_p_value,
+                    significant: adjusted_p_value < alpha,
+                },
+            )
+            .collect())
+    }
+
+    fn pearson_test(&self, correction: f64) -> ContingencyTestResult {
+        let expected = self.expected();
+        let statistic = self
+            .cells(&expected)
+            .map(|(o, e)| ((o - e).abs() - correction).powi(2) / e)
+            .sum();
+        self.independence_result(statistic, expected)
+    }
+
+    fn independence_result(
+        &self,
+        statistic: f64,
+        expected: Vec<Vec<f64>>,
+    ) -> ContingencyTestResult {
+        let df = ((self.rows() - 1) * (self.columns() - 1)) as f64;
+        let p_value = ChiSquare::new(df).sf(statistic);
+
+        let mut residuals = Vec::with_capacity(self.rows());
+        let mut standardized_residuals = Vec::with_capacity(self.rows());
+        for (i, row) in self.counts.iter().enumerate() {
+            let row_fraction = self.row_totals[i] / self.total;
+            let mut residual_row = Vec::with_capacity(self.columns());
+            let mut standardized_row = Vec::with_capacity(self.columns());
+            for (j, &o) in row.iter().enumerate() {
+                let e = expected[i][j];
+                let column_fraction = self.column_totals[j] / self.total;
+                residual_row.push((o - e) / e.sqrt());
+                standardized_row
+                    .push((o - e) / (e * (1.0 - row_fraction) * (1.0 - column_fraction)).sqrt());
+            }
+            residuals.push(residual_row);
+            standardized_residuals.push(standardized_row);
+        }
+
+        ContingencyTestResult {
+            statistic,
+            df,
+            p_value,
+            expected,
+            residuals,
+            standardized_residuals,
+        }
+    }
+
+    /// Pairs of (observed, expected) counts over every cell.
+    fn cells<'a>(&'a self, expected: &'a [Vec<f64>]) -> impl Iterator<Item = (f64, f64)> + 'a {
+        self.counts
+            .iter()
+            .flatten()
+            .zip(expected.iter().flatten())
+            .map(|(&o, &e)| (o, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const DICE: [f64; 6] = [16.0, 18.0, 16.0, 14.0, 12.0, 12.0];
+
+    #[test]
+    fn chi_square_gof_dice_test() {
+        // scipy: chisquare([16, 18, 16, 14, 12, 12])
+        let expected = [88.0 / 6.0; 6];
+        let result = chi_square_gof(&DICE, &expected).unwrap();
+        assert_close(result.statistic, 2.0, 1e-12);
+        assert_eq!(result.df, 5.0);
+        assert_close(result.p_value, 0.84914503608461, 1e-12);
+        assert!(!result.low_expected_count);
+
+        // scipy: chisquare([16, 18, 16, 14, 12, 12], f_exp=[16, 16, 16, 16, 16, 8])
+        let expected = [16.0, 16.0, 16.0, 16.0, 16.0, 8.0];
+        let result = chi_square_gof(&DICE, &expected).unwrap();
+        assert_close(result.statistic, 3.5, 1e-12);
+        assert_close(result.p_value, 0.623387627749582, 1e-12);
+    }
+
+    #[test]
+    fn chi_square_display_test() {
+        let result = chi_square_gof(&DICE, &[88.0 / 6.0; 6]).unwrap();
+        assert_eq!(result.to_string(), "χ²(5) = 2, p = 0.849");
+    }
+
+    #[test]
+    fn chi_square_gof_with_params_test() {
+        // scipy: chisquare([16, 18, 16, 14, 12, 12], ddof=1)
+        let expected = [88.0 / 6.0; 6];
+        let result = chi_square_gof_with_params(&DICE, &expected, 1).unwrap();
+        assert_eq!(result.df, 4.0);
+        assert_close(result.p_value, 0.735758882342885, 1e-12);
+    }
+
+    #[test]
+    fn chi_square_gof_probabilities_test() {
+        let result = chi_square_gof_probabilities(&DICE, &[1.0 / 6.0; 6]).unwrap();
+        assert_close(result.statistic, 2.0, 1e-12);
+        assert_close(result.p_value, 0.84914503608461, 1e-12);
+
+        let result = chi_square_gof_probabilities(&[3.0, 1.0, 2.0], &[0.5, 0.25, 0.25]).unwrap();
+        assert!(result.low_expected_count);
+    }
+
+    #[test]
+    fn chi_square_gof_distribution_merging_test() {
+        // Under a standard normal, 40 values give expected counts of about
+        // 0.91, 5.44, 13.65, 13.65, 5.44 and 0.91 in these bins; each tail
+        // bin is merged into its neighbour
+        let data = crate::simulate::normal_sample(40, 0.0, 1.0, 137).unwrap();
+        let edges = vec![-3.0, -2.0, -1.0, 0.0, 1.0, 2.0, 3.0];
+        let normal = Normal::standard();
+        let binned =
+            chi_square_gof_distribution(&data, |x| normal.cdf(x), 0, BinSpec::Edges(edges))
+                .unwrap();
+        assert_eq!(binned.edges, [-3.0, -1.0, 0.0, 1.0, 3.0]);
+        let (lower, middle) = (normal.cdf(-1.0), normal.cdf(0.0) - normal.cdf(-1.0));
+        for (actual, expected) in binned.expected.iter().zip([lower, middle, middle, lower]) {
+            assert_close(*actual, 40.0 * expected, 1e-12);
+        }
+        assert_eq!(binned.observed.iter().sum::<f64>(), 40.0);
+        assert!(binned.expected.iter().all(|&e| e >= 5.0));
+        assert!(!binned.result.low_expected_count);
+        assert_eq!(binned.result.df, 3.0);
+        let direct = chi_square_gof(&binned.observed, &binned.expected).unwrap();
+        assert_eq!(binned.result.statistic, direct.statistic);
+        assert_eq!(binned.to_string(), direct.to_string());
+    }
+
+    #[test]
+    fn chi_square_gof_distribution_fit_test() {
+        // Normal data fit a normal fitted to them, with two estimated
+        // parameters
+        let data = crate::simulate::normal_sample(500, 10.0, 2.0, 137).unwrap();
+        let fit = crate::fit_normal(&data).unwrap().distribution();
+        let binned =
+            chi_square_gof_distribution(&data, |x| fit.cdf(x), 2, BinSpec::Sturges).unwrap();
+        assert_eq!(binned.result.df, (binned.observed.len() - 3) as f64);
+        assert!(binned.result.p_value > 0.05, "{}", binned.result.p_value);
+
+        // Exponential data do not
+        let data: Vec<f64> = crate::simulate::uniform_sample(500, 0.0, 1.0, 137)
+            .unwrap()
+            .iter()
+            .map(|&u| crate::distributions::Exponential::new(0.5).quantile(u))
+            .collect();
+        let fit = crate::fit_normal(&data).unwrap().distribution();
+        let binned =
+            chi_square_gof_distribution(&data, |x| fit.cdf(x), 2, BinSpec::Sturges).unwrap();
+        assert!(binned.result.p_value < 1e-10, "{}", binned.result.p_value);
+        assert!(binned.expected.iter().all(|&e| e >= 5.0));
+    }
+
+    #[test]
+    fn chi_square_gof_distribution_invalid_input_test() {
+        let normal = Normal::standard();
+        assert_eq!(
+            chi_square_gof_distribution(&[], |x| normal.cdf(x), 0, BinSpec::Count(4)).err(),
+            Some(StatsError::EmptyInput)
+        );
+        // Four values cannot fill two bins of five expected
+        assert_eq!(
+            chi_square_gof_distribution(
+                &[-1.0, 0.0, 0.5, 1.0],
+                |x| normal.cdf(x),
+                0,
+                BinSpec::Count(4)
+            )
+            .err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        // Twelve values fill at most two bins, too few for an estimated
+        // parameter
+        let data = crate::simulate::uniform_sample(12, -1.0, 1.0, 1).unwrap();
+        let edges = BinSpec::Edges(vec![-1.0, 0.0, 1.0]);
+        assert!(matches!(
+            chi_square_gof_distribution(&data, |x| normal.cdf(x), 1, edges),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn chi_square_gof_invalid_input_test() {
+        assert_eq!(
+            chi_square_gof(&[1.0, 2.0], &[1.0, 2.0, 3.0]).err(),
+            Some(StatsError::MismatchedLengths { left: 2, right: 3 })
+        );
+        assert!(matches!(
+            chi_square_gof(&[1.0, -2.0], &[1.0, 2.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            chi_square_gof(&[1.0, 2.0], &[0.0, 3.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            chi_square_gof_with_params(&[1.0, 2.0], &[1.5, 1.5], 1),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn contingency_association_test() {
+        // R: rcompanion::cramerV(table, bias.correct = c(FALSE, TRUE)), rcompanion::phi
+        let table = ContingencyTable::new(vec![vec![12.0, 5.0], vec![7.0, 9.0]]).unwrap();
+        let v = table.cramers_v(false);
+        assert_close(v.value, 0.27139234260884, 1e-13);
+        assert_close(v.chi_square, 2.43057551968156, 1e-12);
+        assert_eq!(v.n, 33.0);
+        assert_close(table.cramers_v(true).value, 0.209216797269803, 1e-13);
+        assert_close(table.phi().unwrap().value, 0.27139234260884, 1e-13);
+        let flipped = ContingencyTable::new(vec![vec![5.0, 12.0], vec![9.0, 7.0]]).unwrap();
+        assert_close(flipped.phi().unwrap().value, -0.27139234260884, 1e-13);
+
+        let table = ContingencyTable::new(vec![
+            vec![20.0, 15.0, 10.0],
+            vec![10.0, 25.0, 20.0],
+            vec![5.0, 10.0, 30.0],
+        ])
+        .unwrap();
+        assert_close(table.cramers_v(false).value, 0.304211263013868, 1e-13);
+        assert_close(table.cramers_v(true).value, 0.282424200555129, 1e-13);
+        assert!(matches!(table.phi(), Err(StatsError::InvalidParameter(_))));
+
+        let weak = ContingencyTable::new(vec![vec![10.0, 12.0], vec![11.0, 10.0], vec![9.0, 13.0]])
+            .unwrap();
+        assert_close(weak.cramers_v(false).value, 0.0940948700415186, 1e-13);
+        assert_eq!(weak.cramers_v(true).value, 0.0);
+    }
+
+    #[test]
+    fn contingency_table_chi_square_test() {
+        // R: chisq.test(matrix(c(20, 30, 10, 15, 25, 40), ncol = 2), correct = FALSE)
+        let table =
+            ContingencyTable::new(vec![vec![20.0, 15.0], vec![30.0, 25.0], vec![10.0, 40.0]])
+                .unwrap();
+        let result = table.chi_square_test();
+        assert_close(result.statistic, 16.6515151515152, 1e-12);
+        assert_eq!(result.df, 2.0);
+        assert_close(result.p_value, 0.000242197372407696, 1e-15);
+        assert_close(result.expected[0][0], 15.0, 1e-12);
+        assert_close(result.expected[0][1], 20.0, 1e-12);
+        assert_close(result.residuals[2][0], -2.46885359939347, 1e-12);
+        assert_close(result.residuals[2][1], 2.1380899352994, 1e-12);
+        assert_close(
+            result.standardized_residuals[2][0],
+            -4.07340061773852,
+            1e-12,
+        );
+        assert_close(result.standardized_residuals[2][1], 4.07340061773852, 1e-12);
+    }
+
+    #[test]
+    fn contingency_table_yates_test() {
+        let table = ContingencyTable::new(vec![vec![12.0, 5.0], vec![7.0, 16.0]]).unwrap();
+        let result = table.chi_square_test();
+        assert_close(result.statistic, 6.31989180111404, 1e-12);
+        assert_close(result.p_value, 0.0119390913779123, 1e-12);
+
+        // R: chisq.test(matrix(c(12, 7, 5, 16), nrow = 2))
+        let result = table.yates_chi_square_test().unwrap();
+        assert_close(result.statistic, 4.81228647065233, 1e-12);
+        assert_close(result.p_value, 0.0282575285263613, 1e-12);
+
+        let table = ContingencyTable::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+        assert!(table.yates_chi_square_test().is_err());
+    }
+
+    #[test]
+    fn contingency_table_invalid_input_test() {
+        assert!(ContingencyTable::new(vec![vec![1.0, 2.0]]).is_err());
+        assert_eq!(
+            ContingencyTable::new(vec![vec![1.0, 2.0], vec![3.0]]).err(),
+            Some(StatsError::MismatchedLengths { left: 2, right: 1 })
+        );
+        assert!(ContingencyTable::new(vec![vec![1.0, -2.0], vec![3.0, 4.0]]).is_err());
+        assert!(ContingencyTable::new(vec![vec![0.0, 2.0], vec![0.0, 4.0]]).is_err());
+    }
+
+    #[test]
+    fn g_test_test() {
+        let table =
+            ContingencyTable::new(vec![vec![20.0, 15.0], vec![30.0, 25.0], vec![10.0, 40.0]])
+                .unwrap();
+        let result = table.g_test();
+        assert_close(result.statistic, 17.5794434008478, 1e-12);
+        assert_eq!(result.df, 2.0);
+        assert_close(result.p_value, 0.000152290344126563, 1e-15);
+
+        // 0 ln(0) is treated as 0
+        let table =
+            ContingencyTable::new(vec![vec![0.0, 15.0], vec![30.0, 25.0], vec![10.0, 40.0]])
+                .unwrap();
+        let result = table.g_test();
+        assert_close(result.statistic, 26.9321418045038, 1e-12);
+        assert_close(result.p_value, 1.41827260494048e-6, 1e-17);
+    }
+
+    #[test]
+    fn g_test_converges_to_pearson_test() {
+        let table = ContingencyTable::new(vec![
+            vec![5050.0, 4950.0, 5000.0],
+            vec![4980.0, 5020.0, 5030.0],
+        ])
+        .unwrap();
+        let g = table.g_test();
+        let pearson = table.chi_square_test();
+        assert_close(g.statistic, 1.03977773041673, 1e-10);
+        assert_close(pearson.statistic, 1.03977063538083, 1e-10);
+        assert_close(g.p_value, pearson.p_value, 1e-5);
+    }
+
+    #[test]
+    fn g_test_gof_test() {
+        let expected = [88.0 / 6.0; 6];
+        let result = g_test_gof(&DICE, &expected).unwrap();
+        assert_close(result.statistic, 2.00657316263253, 1e-12);
+        assert_close(result.p_value, 0.848234767794639, 1e-12);
+
+        let result = g_test_gof(&[0.0, 10.0], &[5.0, 5.0]).unwrap();
+        assert_close(result.statistic, 20.0 * 2.0_f64.ln(), 1e-12);
+    }
+
+    fn post_hoc_table() -> ContingencyTable {
+        ContingencyTable::new(vec![
+            vec![20.0, 15.0, 5.0],
+            vec![10.0, 25.0, 15.0],
+            vec![5.0, 10.0, 30.0],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn cell_post_hoc_test() {
+        let result = post_hoc_table().chi_square_test();
+        // R: z <- chisq.test(tab)$stdres; p <- 2 * pnorm(-abs(z));
+        //    p.adjust(p, "BH")
+        let expected = [
+            (4.14175510361801, 3.44658242732883e-05, 0.000155096209229798),
+            (0.072280413697864, 0.942378751525581, 0.942378751525581),
+            (
+                -3.83086192598679,
+                0.000127695151516184,
+                0.000383085454548552,
+            ),
+            (-1.20503146860824, 0.228191153858292, 0.256715048090578),
+            (2.39213677265752, 0.0167505989254643, 0.0251258983881964),
+            (-1.29858853372837, 0.194085175086255, 0.249538082253756),
+            (-2.77746029931765, 0.00547855324254737, 0.0123267447957316),
+            (-2.52050415125042, 0.0117186855997686, 0.0210936340795835),
+            (5.04100830250084, 4.63085373050752e-07, 4.16776835745677e-06),
+        ];
+        let cells = result
+            .cell_post_hoc(0.05, CorrectionMethod::BenjaminiHochberg)
+            .unwrap();
+        assert_eq!(cells.len(), 9);
+        for (index, (cell, (residual, p_value, adjusted))) in cells.iter().zip(expected).enumerate()
+        {
+            assert_eq!((cell.row, cell.column), (index / 3, index % 3));
+            assert_close(cell.residual, residual, 1e-12);
+            assert_close(cell.p_value, p_value, 1e-12);
+            assert_close(cell.adjusted_p_value, adjusted, 1e-12);
+            assert_eq!(cell.significant, adjusted < 0.05);
+        }
+
+        // Bonferroni is stricter: (1, 1) and (2, 1) no longer pass.
+        let bonferroni = result
+            .cell_post_hoc(0.05, CorrectionMethod::Bonferroni)
+            .unwrap();
+        let significant: Vec<(usize, usize)> = bonferroni
+            .iter()
+            .filter(|cell| cell.significant)
+            .map(|cell| (cell.row, cell.column))
+            .collect();
+        assert_eq!(significant, [(0, 0), (0, 2), (2, 0), (2, 2)]);
+        assert_close(bonferroni[8].adjusted_p_value, 4.16776835745677e-06, 1e-12);
+        assert!(result.cell_post_hoc(0.0, CorrectionMethod::Holm).is_err());
+    }
+
+    #[test]
+    fn pairwise_row_tests_test() {
+        let table = post_hoc_table();
+        // rcompanion::pairwiseNominalIndependence(tab, method = "holm")
+        let pairs = table
+            .pairwise_row_tests(
+                ContingencyTable::chi_square_test,
+                0.05,
+                CorrectionMethod::Holm,
+            )
+            .unwrap();
+        let expected = [
+            ((0, 1), 9.84375, 0.00728545779893899),
+            ((0, 2), 27.6587301587302, 9.86241010787672e-07),
+            ((1, 2), 12.8677248677249, 0.00160623486190125),
+        ];
+        assert_eq!(pairs.len(), 3);
+        for (pair, (rows, statistic, p_value)) in pairs.iter().zip(expected) {
+            assert_eq!((pair.row_1, pair.row_2), rows);
+            assert_close(pair.statistic, statistic, 1e-12);
+            assert_eq!(pair.df, 2.0);
+            assert_close(pair.p_value, p_value, 1e-12);
+            assert!(pair.significant);
+        }
+        assert_close(pairs[0].adjusted_p_value, 0.00728545779893899, 1e-12);
+        assert_close(pairs[1].adjusted_p_value, 3.0 * 9.86241010787672e-07, 1e-12);
+        assert_close(pairs[2].adjusted_p_value, 2.0 * 0.00160623486190125, 1e-12);
+
+        let pairs = table
+            .pairwise_row_tests(ContingencyTable::g_test, 0.05, CorrectionMethod::Bonferroni)
+            .unwrap();
+        assert_close(pairs[0].statistic, 10.0437688524385, 1e-12);
+        assert_close(pairs[1].p_value, 2.82117214287546e-07, 1e-12);
+        assert_close(pairs[2].p_value, 0.00137809014286667, 1e-12);
+    }
+
+    #[test]
+    fn pairwise_row_tests_empty_columns_test() {
+        let table = ContingencyTable::new(vec![
+            vec![12.0, 0.0, 0.0],
+            vec![6.0, 9.0, 0.0],
+            vec![5.0, 0.0, 7.0],
+            vec![4.0, 0.0, 0.0],
+        ])
+        .unwrap();
+        let pairs = table
+            .pairwise_row_tests(
+                ContingencyTable::chi_square_test,
+                0.05,
+                CorrectionMethod::Holm,
+            )
+            .unwrap();
+        assert_eq!(pairs.len(), 6);
+        // Rows 0 and 1 leave out the empty last column.
+        let direct = ContingencyTable::new(vec![vec![12.0, 0.0], vec![6.0, 9.0]])
+            .unwrap()
+            .chi_square_test();
+        assert_eq!((pairs[0].row_1, pairs[0].row_2), (0, 1));
+        assert_eq!(pairs[0].statistic, direct.statistic);
+        assert_eq!(pairs[0].df, 1.0);
+        assert_eq!((pairs[3].row_1, pairs[3].row_2), (1, 2));
+        assert_eq!(pairs[3].df, 2.0);
+        // Rows 0 and 3 both fall entirely in the first column.
+        assert_eq!((pairs[2].row_1, pairs[2].row_2), (0, 3));
+        assert_eq!((pairs[2].statistic, pairs[2].df), (0.0, 0.0));
+        assert_eq!(pairs[2].p_value, 1.0);
+        assert!(!pairs[2].significant);
+    }
+}
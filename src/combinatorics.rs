@@ -0,0 +1,270 @@
+#[cfg(not(feature = "std"))]
+use crate::math::F64Ext;
+use crate::special::ln_gamma;
+use crate::StatsError;
+
+/// n! for every n whose factorial is finite as an f64. The products are
+/// exact up to 22! and within a few dozen ulps beyond it.
+const FACTORIALS: [f64; 171] = {
+    let mut table = [1.0; 171];
+    let mut n = 1;
+    while n < table.len() {
+        table[n] = table[n - 1] * n as f64;
+        n += 1;
+    }
+    table
+};
+
+/// Natural logarithm of n!, from a table of factorials where they fit in an
+/// f64 and from `ln_gamma` beyond.
+pub fn ln_factorial(n: u64) -> f64 {
+    if n < FACTORIALS.len() as u64 {
+        FACTORIALS[n as usize].ln()
+    } else {
+        ln_gamma(n as f64 + 1.0)
+    }
+}
+
+/// Natural logarithm of the binomial coefficient C(n, k). Returns
+/// `StatsError::InvalidParameter` if `k > n`.
+pub fn ln_binomial(n: u64, k: u64) -> Result<f64, StatsError> {
+    if k > n {
+        return Err(StatsError::InvalidParameter("k must not exceed n"));
+    }
+    Ok(ln_choose(n, k))
+}
+
+/// ln C(n, k) for k <= n.
+pub(crate) fn ln_choose(n: u64, k: u64) -> f64 {
+    ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k)
+}
+
+/// The binomial coefficient C(n, k) exactly, or `None` if it does not fit
+/// in a u128. Returns `StatsError::InvalidParameter` if `k > n`.
+pub fn binomial_coefficient(n: u64, k: u64) -> Result<Option<u128>, StatsError> {
+    if k > n {
+        return Err(StatsError::InvalidParameter("k must not exceed n"));
+    }
+    let k = k.min(n - k);
+    // C(n, i + 1) = C(n, i) (n - i) / (i + 1). Dividing out the common
+    // factor first keeps every intermediate value at most C(n, k), so the
+    // multiplication overflows only when the result would.
+    let mut coefficient: u128 = 1;
+    for i in 0..k {
+        let numerator = (n - i) as u128;
+        let denominator = (i + 1) as u128;
+        let common = gcd(coefficient, denominator);
+        let reduced = denominator / common;
+        coefficient = match (coefficient / common).checked_mul(numerator / reduced) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+    }
+    Ok(Some(coefficient))
+}
+
+/// P(X = k) for X ~ Binomial(n, p), evaluated in log space. Returns
+/// `StatsError::InvalidParameter` if `k > n` or `p` is outside [0, 1].
+pub fn binomial_pmf(k: u64, n: u64, p: f64) -> Result<f64, StatsError> {
+    if k > n {
+        return Err(StatsError::InvalidParameter("k must not exceed n"));
+    }
+    if !(0.0..=1.0).contains(&p) {
+        return Err(StatsError::InvalidParameter("p must be between 0 and 1"));
+    }
+    // The log-space formula is 0 * ln(0) at the ends.
+    if p == 0.0 || p == 1.0 {
+        let certain = if p == 0.0 { 0 } else { n };
+        return Ok(if k == certain { 1.0 } else { 0.0 });
+    }
+    Ok(binomial_probability(k, n, p))
+}
+
+/// `binomial_pmf` without argument checks, for k <= n and 0 < p < 1.
+pub(crate) fn binomial_probability(k: u64, n: u64, p: f64) -> f64 {
+    let ln_pmf = ln_choose(n, k) + k as f64 * p.ln() + (n - k) as f64 * (-p).ln_1p();
+    ln_pmf.exp()
+}
+
+/// P(X = k) when drawing `draws` items without replacement from `total`
+/// items of which `successes` are successes (k, K, n and N in the usual
+/// notation). Values of `k` that need more failures than the population has
+/// have probability 0. Returns `StatsError::InvalidParameter` if `successes`
+/// or `draws` exceeds `total`, or `k` exceeds `successes` or `draws`.
+pub fn hypergeometric_pmf(
+    k: u64,
+    successes: u64,
+    draws: u64,
+    total: u64,
+) -> Result<f64, StatsError> {
+    if successes > total || draws > total {
+        return Err(StatsError::InvalidParameter(
+            "successes and draws must not exceed total",
+        ));
+    }
+    if k > successes || k > draws {
+        return Err(StatsError::InvalidParameter(
+            "k must not exceed successes or draws",
+        ));
+    }
+    if draws - k > total - successes {
+        return Ok(0.0);
+    }
+    Ok(hypergeometric_probability(k, successes, draws, total))
+}
+
+/// `hypergeometric_pmf` without argument checks, for k inside the support.
+pub(crate) fn hypergeometric_probability(k: u64, successes: u64, draws: u64, total: u64) -> f64 {
+    (ln_choose(successes, k) + ln_choose(total - successes, draws - k) - ln_choose(total, draws))
+        .exp()
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn ln_factorial_test() {
+        assert_eq!(ln_factorial(0), 0.0);
+        assert_eq!(ln_factorial(1), 0.0);
+        assert_close(ln_factorial(10), 3_628_800.0_f64.ln(), 1e-15);
+        // mpmath: loggamma(26), loggamma(171), loggamma(172)
+        assert_close(ln_factorial(25), 58.00360522298052, 1e-14);
+        assert_close(ln_factorial(170), 706.5730622457873, 1e-12);
+        assert_close(ln_factorial(171), 711.71472580229, 1e-12);
+        // R: lfactorial(1000)
+        assert_close(ln_factorial(1000), 5912.12817848816, 1e-9);
+        // mpmath: loggamma(10**6 + 1), loggamma(5 * 10**6 + 1)
+        assert_close(ln_factorial(1_000_000), 12815518.38465817, 1e-7);
+        assert_close(ln_factorial(5_000_000), 72124750.98340466, 1e-6);
+    }
+
+    #[test]
+    fn binomial_coefficient_test() {
+        assert_eq!(binomial_coefficient(0, 0), Ok(Some(1)));
+        assert_eq!(binomial_coefficient(5, 0), Ok(Some(1)));
+        assert_eq!(binomial_coefficient(5, 5), Ok(Some(1)));
+        assert_eq!(binomial_coefficient(5, 2), Ok(Some(10)));
+        assert_eq!(binomial_coefficient(10, 3), Ok(Some(120)));
+        assert_eq!(binomial_coefficient(52, 5), Ok(Some(2_598_960)));
+        assert_eq!(binomial_coefficient(1000, 3), Ok(Some(166_167_000)));
+        assert_eq!(
+            binomial_coefficient(1_000_000, 2),
+            Ok(Some(499_999_500_000))
+        );
+        assert_eq!(
+            binomial_coefficient(60, 30),
+            Ok(Some(118_264_581_564_861_424))
+        );
+        // Python: math.comb(131, 65) is the largest central coefficient
+        // below 2^128.
+        assert_eq!(
+            binomial_coefficient(131, 65),
+            Ok(Some(188_694_833_082_770_476_622_296_176_145_946_360_850))
+        );
+        assert_eq!(binomial_coefficient(132, 66), Ok(None));
+        assert_eq!(
+            binomial_coefficient(u64::MAX, 1),
+            Ok(Some(u64::MAX as u128))
+        );
+        assert_eq!(binomial_coefficient(u64::MAX, 3), Ok(None));
+
+        for n in 0..60 {
+            let mut row = 1u128;
+            for k in 0..=n {
+                assert_eq!(binomial_coefficient(n, k), Ok(Some(row)));
+                row = row * (n - k) as u128 / (k + 1) as u128;
+            }
+        }
+        assert!(binomial_coefficient(3, 4).is_err());
+    }
+
+    #[test]
+    fn ln_binomial_test() {
+        assert_eq!(ln_binomial(7, 0), Ok(0.0));
+        assert_close(ln_binomial(10, 3).unwrap(), 120.0_f64.ln(), 1e-14);
+        let exact = binomial_coefficient(60, 30).unwrap().unwrap() as f64;
+        assert_close(ln_binomial(60, 30).unwrap(), exact.ln(), 1e-13);
+        // mpmath: loggamma(n + 1) - loggamma(k + 1) - loggamma(n - k + 1)
+        assert_close(
+            ln_binomial(2_000_000, 1_000_000).unwrap(),
+            1386286.8809995437,
+            1e-7,
+        );
+        assert_close(
+            ln_binomial(1_000_000, 300_000).unwrap(),
+            610857.2556846419,
+            1e-7,
+        );
+        assert_close(ln_binomial(3_000_000, 7).unwrap(), 95.87369156535622, 1e-7);
+        assert!(ln_binomial(3, 4).is_err());
+    }
+
+    #[test]
+    fn binomial_pmf_test() {
+        // R: dbinom(3, 10, 0.25)
+        assert_close(
+            binomial_pmf(3, 10, 0.25).unwrap(),
+            0.25028228759765625,
+            1e-15,
+        );
+        // mpmath: binomial(10**6, 5 * 10**5) / 2**(10**6)
+        assert_close(
+            binomial_pmf(500_000, 1_000_000, 0.5).unwrap(),
+            0.0007978843613317501,
+            1e-11,
+        );
+        // mpmath: binomial(2 * 10**6, 600100) * 0.3**600100 * 0.7**1399900
+        assert_close(
+            binomial_pmf(600_100, 2_000_000, 0.3).unwrap(),
+            0.0006082675764851436,
+            1e-11,
+        );
+        let total: f64 = (0..=40).map(|k| binomial_pmf(k, 40, 0.37).unwrap()).sum();
+        assert_close(total, 1.0, 1e-14);
+
+        assert_eq!(binomial_pmf(0, 5, 0.0), Ok(1.0));
+        assert_eq!(binomial_pmf(1, 5, 0.0), Ok(0.0));
+        assert_eq!(binomial_pmf(5, 5, 1.0), Ok(1.0));
+        assert_eq!(binomial_pmf(4, 5, 1.0), Ok(0.0));
+        assert!(binomial_pmf(6, 5, 0.5).is_err());
+        assert!(binomial_pmf(2, 5, 1.5).is_err());
+        assert!(binomial_pmf(2, 5, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn hypergeometric_pmf_test() {
+        // R: dhyper(2, 5, 45, 10)
+        assert_close(
+            hypergeometric_pmf(2, 5, 10, 50).unwrap(),
+            0.2098397175706545,
+            1e-14,
+        );
+        // mpmath: binomial(10**6, 1000) * binomial(4 * 10**6, 4000)
+        //         / binomial(5 * 10**6, 5000)
+        assert_close(
+            hypergeometric_pmf(1000, 1_000_000, 5000, 5_000_000).unwrap(),
+            0.014110562522741359,
+            1e-9,
+        );
+        let total: f64 = (0..=12)
+            .map(|k| hypergeometric_pmf(k, 12, 20, 30).unwrap())
+            .sum();
+        assert_close(total, 1.0, 1e-14);
+
+        // Drawing 20 from 30 with only 18 failures needs at least 2 successes.
+        assert_eq!(hypergeometric_pmf(1, 12, 20, 30), Ok(0.0));
+        assert!(hypergeometric_pmf(2, 31, 10, 30).is_err());
+        assert!(hypergeometric_pmf(2, 5, 31, 30).is_err());
+        assert!(hypergeometric_pmf(6, 5, 10, 50).is_err());
+        assert!(hypergeometric_pmf(3, 5, 2, 50).is_err());
+    }
+}
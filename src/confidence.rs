@@ -0,0 +1,363 @@
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+use crate::distributions::{Beta, Normal, StudentsT};
+#[cfg(feature = "alloc")]
+use crate::format::significant;
+#[cfg(not(feature = "std"))]
+use crate::math::F64Ext;
+use crate::{GetStatistics, SampleStatistics, StatsError};
+
+/// A two-sided or one-sided confidence interval. One-sided intervals have an
+/// infinite (or natural, such as zero for a ratio) bound on the open side.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfidenceInterval {
+    pub lower: f64,
+    pub upper: f64,
+    /// The estimate the interval is built around, such as x̄ for a mean.
+    pub point_estimate: f64,
+    /// Confidence level, such as 0.95.
+    pub confidence: f64,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ConfidenceInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}% CI [{}, {}]",
+            significant(self.confidence * 100.0, 3),
+            significant(self.lower, 3),
+            significant(self.upper, 3)
+        )
+    }
+}
+
+impl ConfidenceInterval {
+    /// Whether `value` lies within the interval, bounds included.
+    pub fn contains(&self, value: f64) -> bool {
+        self.lower <= value && value <= self.upper
+    }
+}
+
+/// Two-sided t interval for the mean of a normal population,
+/// x̄ ± t(1 - alpha / 2, n - 1) s / sqrt(n), as R's `t.test(x)$conf.int`.
+pub fn mean_confidence_interval(
+    data: &[f64],
+    confidence: f64,
+) -> Result<ConfidenceInterval, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    SampleStatistics::from_array(data).mean_confidence_interval(confidence)
+}
+
+/// Two-sided interval for a single future observation from the same normal
+/// population, x̄ ± t(1 - alpha / 2, n - 1) s sqrt(1 + 1 / n). Unlike the
+/// interval for the mean it stays about ± t s wide however large n grows.
+pub fn prediction_interval(
+    data: &[f64],
+    confidence: f64,
+) -> Result<ConfidenceInterval, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    SampleStatistics::from_array(data).prediction_interval(confidence)
+}
+
+impl SampleStatistics {
+    /// Two-sided t interval for the population mean; see
+    /// `mean_confidence_interval`.
+    pub fn mean_confidence_interval(
+        &self,
+        confidence: f64,
+    ) -> Result<ConfidenceInterval, StatsError> {
+        if self.n < 2 {
+            return Err(StatsError::InsufficientData {
+                required: 2,
+                actual: self.n,
+            });
+        }
+        validate_confidence(confidence)?;
+
+        let t = StudentsT::new(self.n as f64 - 1.0).quantile(0.5 + confidence / 2.0);
+        let margin = t * self.standard_error();
+        Ok(ConfidenceInterval {
+            lower: self.sample_mean - margin,
+            upper: self.sample_mean + margin,
+            point_estimate: self.sample_mean,
+            confidence,
+        })
+    }
+
+    /// Two-sided interval for a single future observation; see
+    /// `prediction_interval`.
+    pub fn prediction_interval(&self, confidence: f64) -> Result<ConfidenceInterval, StatsError> {
+        if self.n < 2 {
+            return Err(StatsError::InsufficientData {
+                required: 2,
+                actual: self.n,
+            });
+        }
+        validate_confidence(confidence)?;
+
+        let t = StudentsT::new(self.n as f64 - 1.0).quantile(0.5 + confidence / 2.0);
+        let margin = t * self.standard_deviation * (1.0 + 1.0 / self.n as f64).sqrt();
+        Ok(ConfidenceInterval {
+            lower: self.sample_mean - margin,
+            upper: self.sample_mean + margin,
+            point_estimate: self.sample_mean,
+            confidence,
+        })
+    }
+}
+
+/// How `proportion_confidence_interval` builds its interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProportionCiMethod {
+    /// p̂ ± z sqrt(p̂(1 - p̂) / n). Simple but badly undercovers near 0 and 1,
+    /// where it collapses to a single point.
+    Wald,
+    /// Inverts the score test; good coverage even for small n.
+    Wilson,
+    /// Exact interval from beta quantiles, conservative by construction.
+    ClopperPearson,
+}
+
+/// Two-sided confidence interval for a binomial proportion, matching
+/// statsmodels' `proportion_confint` with methods `normal`, `wilson` and
+/// `beta`. Every method is clipped to [0, 1], and its lower bound is exactly
+/// zero when there are no successes and its upper bound exactly one when
+/// there are no failures.
+pub fn proportion_confidence_interval(
+    successes: usize,
+    n: usize,
+    confidence: f64,
+    method: ProportionCiMethod,
+) -> Result<ConfidenceInterval, StatsError> {
+    if n == 0 {
+        return Err(StatsError::EmptyInput);
+    }
+    if successes > n {
+        return Err(StatsError::InvalidParameter(
+            "successes cannot exceed the number of trials",
+        ));
+    }
+    validate_confidence(confidence)?;
+
+    let x = successes as f64;
+    let trials = n as f64;
+    let p = x / trials;
+    let alpha = 1.0 - confidence;
+    let z = Normal::standard().quantile(1.0 - alpha / 2.0);
+    let (lower, upper) = match method {
+        ProportionCiMethod::Wald => {
+            let margin = z * (p * (1.0 - p) / trials).sqrt();
+            (p - margin, p + margin)
+        }
+        ProportionCiMethod::Wilson => {
+            let z_sq = z * z;
+            let denominator = 1.0 + z_sq / trials;
+            let center = (p + z_sq / (2.0 * trials)) / denominator;
+            let margin =
+                z * (p * (1.0 - p) / trials + z_sq / (4.0 * trials * trials)).sqrt() / denominator;
+            (center - margin, center + margin)
+        }
+        ProportionCiMethod::ClopperPearson => {
+            let lower = if successes == 0 {
+                0.0
+            } else {
+                Beta::new(x, trials - x + 1.0).quantile(alpha / 2.0)
+            };
+            let upper = if successes == n {
+                1.0
+            } else {
+                Beta::new(x + 1.0, trials - x).quantile(1.0 - alpha / 2.0)
+            };
+            (lower, upper)
+        }
+    };
+
+    Ok(ConfidenceInterval {
+        lower: if successes == 0 { 0.0 } else { lower.max(0.0) },
+        upper: if successes == n { 1.0 } else { upper.min(1.0) },
+        point_estimate: p,
+        confidence,
+    })
+}
+
+pub(crate) fn validate_confidence(confidence: f64) -> Result<(), StatsError> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(StatsError::InvalidParameter(
+            "confidence must be between 0 and 1",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn contains_test() {
+        let interval = ConfidenceInterval {
+            lower: 1.0,
+            upper: 2.0,
+            point_estimate: 1.5,
+            confidence: 0.95,
+        };
+        assert!(interval.contains(1.0));
+        assert!(interval.contains(1.5));
+        assert!(!interval.contains(2.5));
+    }
+
+    #[test]
+    fn mean_confidence_interval_test() {
+        // R: t.test(x, conf.level = 0.95)$conf.int
+        let data = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 4.7, 5.5];
+        let interval = mean_confidence_interval(&data, 0.95).unwrap();
+        assert_close(interval.lower, 4.98878702567787, 1e-12);
+        assert_close(interval.upper, 5.73621297432213, 1e-12);
+        assert_close(interval.point_estimate, 5.3625, 1e-15);
+        assert_eq!(interval.confidence, 0.95);
+
+        let interval = SampleStatistics::from_array(&data)
+            .mean_confidence_interval(0.99)
+            .unwrap();
+        assert_close(interval.lower, 4.80943017896498, 1e-11);
+        assert_close(interval.upper, 5.91556982103502, 1e-11);
+    }
+
+    #[test]
+    fn mean_confidence_interval_invalid_input_test() {
+        assert_eq!(
+            mean_confidence_interval(&[1.0], 0.95),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            mean_confidence_interval(&[], 0.95),
+            Err(StatsError::EmptyInput)
+        );
+        for confidence in [0.0, 1.0, 1.5, f64::NAN] {
+            assert!(matches!(
+                mean_confidence_interval(&[1.0, 2.0], confidence),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn prediction_interval_test() {
+        // R: predict(lm(x ~ 1), data.frame(1), interval = "prediction")
+        let data = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 4.7, 5.5];
+        let interval = prediction_interval(&data, 0.95).unwrap();
+        assert_close(interval.lower, 4.241361077033596, 1e-12);
+        assert_close(interval.upper, 6.483638922966405, 1e-12);
+        assert_close(interval.point_estimate, 5.3625, 1e-15);
+        let mean = mean_confidence_interval(&data, 0.95).unwrap();
+        assert!(interval.lower < mean.lower && interval.upper > mean.upper);
+
+        let interval = SampleStatistics::from_array(&data)
+            .prediction_interval(0.9)
+            .unwrap();
+        assert_close(interval.lower, 4.464223745176409, 1e-12);
+        assert_close(interval.upper, 6.2607762548235915, 1e-12);
+
+        assert_eq!(prediction_interval(&[], 0.95), Err(StatsError::EmptyInput));
+        assert_eq!(
+            prediction_interval(&[1.0], 0.95),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert!(matches!(
+            prediction_interval(&[1.0, 2.0], 0.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn proportion_confidence_interval_test() {
+        // statsmodels: proportion_confint(7, 30, method=...)
+        let expected = [
+            (
+                ProportionCiMethod::Wald,
+                0.0819844758168445,
+                0.384682190849822,
+            ),
+            (
+                ProportionCiMethod::Wilson,
+                0.117923881444895,
+                0.409283261581222,
+            ),
+            (
+                ProportionCiMethod::ClopperPearson,
+                0.0993378649580198,
+                0.422836522978219,
+            ),
+        ];
+        for (method, lower, upper) in expected {
+            let interval = proportion_confidence_interval(7, 30, 0.95, method).unwrap();
+            assert_close(interval.lower, lower, 1e-12);
+            assert_close(interval.upper, upper, 1e-12);
+            assert_close(interval.point_estimate, 7.0 / 30.0, 1e-15);
+        }
+
+        let expected = [
+            (
+                ProportionCiMethod::Wald,
+                0.0301202478898154,
+                0.369879752110185,
+            ),
+            (
+                ProportionCiMethod::Wilson,
+                0.082898301825887,
+                0.408786320940197,
+            ),
+            (
+                ProportionCiMethod::ClopperPearson,
+                0.0568468675902468,
+                0.439784435981556,
+            ),
+        ];
+        for (method, lower, upper) in expected {
+            let interval = proportion_confidence_interval(3, 15, 0.9, method).unwrap();
+            assert_close(interval.lower, lower, 1e-12);
+            assert_close(interval.upper, upper, 1e-12);
+        }
+    }
+
+    #[test]
+    fn proportion_confidence_interval_edges_test() {
+        let none = [
+            (ProportionCiMethod::Wald, 0.0),
+            (ProportionCiMethod::Wilson, 0.161125158052819),
+            (ProportionCiMethod::ClopperPearson, 0.168433470983085),
+        ];
+        for (method, upper) in none {
+            let interval = proportion_confidence_interval(0, 20, 0.95, method).unwrap();
+            assert_eq!(interval.lower, 0.0);
+            assert_close(interval.upper, upper, 1e-12);
+
+            let interval = proportion_confidence_interval(20, 20, 0.95, method).unwrap();
+            assert_eq!(interval.upper, 1.0);
+            assert_close(interval.lower, 1.0 - upper, 1e-12);
+        }
+
+        assert_eq!(
+            proportion_confidence_interval(1, 0, 0.95, ProportionCiMethod::Wilson),
+            Err(StatsError::EmptyInput)
+        );
+        assert!(matches!(
+            proportion_confidence_interval(6, 5, 0.95, ProportionCiMethod::Wilson),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
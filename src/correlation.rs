@@ -0,0 +1,641 @@
+use std::fmt;
+
+use crate::distributions::{Normal, StudentsT};
+use crate::format::write_test_line;
+use crate::rank::{merge_sort_exchanges, midranks, TieCounts};
+use crate::{covariance_matrix, mean, ConfidenceInterval, HypothesisTest, StatsError};
+
+/// Confidence level of the Fisher-z interval reported by `pearson_r`.
+const PEARSON_CONFIDENCE: f64 = 0.95;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CorrelationResult {
+    pub r: f64,
+    /// t = r sqrt((n - 2) / (1 - r²)).
+    pub t: f64,
+    pub df: f64,
+    /// Two-sided p-value for the null hypothesis of no correlation.
+    pub p_value: f64,
+    /// 95% confidence interval for r from Fisher's z transform. Spans
+    /// [-1, 1] when there are only three observations.
+    pub confidence_interval: ConfidenceInterval,
+}
+
+impl fmt::Display for CorrelationResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "r", &[self.df], self.r, self.p_value)
+    }
+}
+
+impl HypothesisTest for CorrelationResult {
+    fn statistic(&self) -> f64 {
+        self.t
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Pearson correlation test"
+    }
+}
+
+/// Pearson product-moment correlation with a t-test of r = 0, as in R's
+/// `cor.test`.
+pub fn pearson_r(x: &[f64], y: &[f64]) -> Result<CorrelationResult, StatsError> {
+    validate_pairs(x, y, 3)?;
+    let r = correlation(x, y)?;
+
+    let n = x.len() as f64;
+    let df = n - 2.0;
+    let t = r * (df / (1.0 - r * r)).sqrt();
+    let p_value = StudentsT::new(df).two_tailed(t);
+
+    let z = r.atanh();
+    let margin = Normal::standard().quantile(0.5 + PEARSON_CONFIDENCE / 2.0) / (n - 3.0).sqrt();
+    let confidence_interval = ConfidenceInterval {
+        lower: (z - margin).tanh(),
+        upper: (z + margin).tanh(),
+        point_estimate: r,
+        confidence: PEARSON_CONFIDENCE,
+    };
+
+    Ok(CorrelationResult {
+        r,
+        t,
+        df,
+        p_value,
+        confidence_interval,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpearmanResult {
+    pub rho: f64,
+    /// Two-sided p-value from the t approximation with n - 2 degrees of
+    /// freedom.
+    pub p_value: f64,
+    pub n: usize,
+}
+
+impl fmt::Display for SpearmanResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "ρ", &[], self.rho, self.p_value)
+    }
+}
+
+impl HypothesisTest for SpearmanResult {
+    fn statistic(&self) -> f64 {
+        self.rho
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Spearman rank correlation test"
+    }
+}
+
+/// Spearman's rank correlation: Pearson's r between the midranks of `x` and
+/// `y`, so ties get the average of the ranks they span.
+pub fn spearman_rho(x: &[f64], y: &[f64]) -> Result<SpearmanResult, StatsError> {
+    validate_pairs(x, y, 3)?;
+    let rho = correlation(&midranks(x), &midranks(y))?;
+
+    let df = x.len() as f64 - 2.0;
+    let t = rho * (df / (1.0 - rho * rho)).sqrt();
+    Ok(SpearmanResult {
+        rho,
+        p_value: StudentsT::new(df).two_tailed(t),
+        n: x.len(),
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KendallResult {
+    pub tau: f64,
+    /// Normal statistic for C - D, using the variance corrected for ties in
+    /// both variables.
+    pub z: f64,
+    /// Two-sided p-value from the normal approximation.
+    pub p_value: f64,
+}
+
+impl fmt::Display for KendallResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "τ", &[], self.tau, self.p_value)
+    }
+}
+
+impl HypothesisTest for KendallResult {
+    fn statistic(&self) -> f64 {
+        self.z
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Kendall rank correlation test"
+    }
+}
+
+/// Kendall's tau-b, matching scipy's `kendalltau` with the asymptotic
+/// p-value.
+///
+/// Concordant minus discordant pairs are counted with Knight's algorithm:
+/// sort by (x, y), then count the exchanges a merge sort on y needs, so the
+/// whole test runs in O(n log n).
+pub fn kendall_tau(x: &[f64], y: &[f64]) -> Result<KendallResult, StatsError> {
+    validate_pairs(x, y, 3)?;
+    let n = x.len();
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| x[i].total_cmp(&x[j]).then(y[i].total_cmp(&y[j])));
+
+    let sorted_x: Vec<f64> = order.iter().map(|&i| x[i]).collect();
+    let mut sorted_y: Vec<f64> = order.iter().map(|&i| y[i]).collect();
+    let x_ties = TieCounts::from_sorted(&sorted_x);
+
+    // Pairs tied in both x and y are runs of equal y within an x run
+    let mut joint_ties = 0.0;
+    let mut start = 0;
+    for end in 1..=n {
+        if end == n || sorted_x[end] != sorted_x[start] || sorted_y[end] != sorted_y[start] {
+            let t = (end - start) as f64;
+            joint_ties += t * (t - 1.0) / 2.0;
+            start = end;
+        }
+    }
+
+    let mut buffer = vec![0.0; n];
+    let exchanges = merge_sort_exchanges(&mut sorted_y, &mut buffer) as f64;
+    let y_ties = TieCounts::from_sorted(&sorted_y);
+
+    let nf = n as f64;
+    let total = nf * (nf - 1.0) / 2.0;
+    if x_ties.pairs == total || y_ties.pairs == total {
+        return Err(StatsError::InvalidParameter(
+            "correlation is undefined when either variable is constant",
+        ));
+    }
+    let score = total - x_ties.pairs - y_ties.pairs + joint_ties - 2.0 * exchanges;
+    let tau =
+        (score / ((total - x_ties.pairs).sqrt() * (total - y_ties.pairs).sqrt())).clamp(-1.0, 1.0);
+
+    let m = nf * (nf - 1.0);
+    let variance = (m * (2.0 * nf + 5.0) - x_ties.v1 - y_ties.v1) / 18.0
+        + 2.0 * x_ties.pairs * y_ties.pairs / m
+        + x_ties.v2 * y_ties.v2 / (9.0 * m * (nf - 2.0));
+    let z = score / variance.sqrt();
+
+    Ok(KendallResult {
+        tau,
+        z,
+        p_value: Normal::standard().two_tailed(z),
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointBiserialResult {
+    pub r: f64,
+    /// t = r sqrt((n - 2) / (1 - r²)), identical to the pooled two-sample t
+    /// statistic comparing the `true` group against the `false` group.
+    pub t: f64,
+    pub df: f64,
+    /// Two-sided p-value, equal to the pooled t-test p-value.
+    pub p_value: f64,
+}
+
+impl fmt::Display for PointBiserialResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "r", &[self.df], self.r, self.p_value)
+    }
+}
+
+impl HypothesisTest for PointBiserialResult {
+    fn statistic(&self) -> f64 {
+        self.t
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "point-biserial correlation test"
+    }
+}
+
+/// Point-biserial correlation between group membership and a continuous
+/// variable: Pearson's r with `true` coded as 1 and `false` as 0. It is
+/// positive when the `true` group has the larger mean.
+pub fn point_biserial(binary: &[bool], values: &[f64]) -> Result<PointBiserialResult, StatsError> {
+    let indicator: Vec<f64> = binary.iter().map(|&b| if b { 1.0 } else { 0.0 }).collect();
+    validate_pairs(&indicator, values, 3)?;
+    if binary.iter().all(|&b| b) || binary.iter().all(|&b| !b) {
+        return Err(StatsError::InvalidParameter(
+            "point-biserial correlation needs observations in both groups",
+        ));
+    }
+    let r = correlation(&indicator, values)?;
+
+    let df = values.len() as f64 - 2.0;
+    let t = r * (df / (1.0 - r * r)).sqrt();
+    Ok(PointBiserialResult {
+        r,
+        t,
+        df,
+        p_value: StudentsT::new(df).two_tailed(t),
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartialCorrelationResult {
+    /// First-order partial correlation r_xy·z.
+    pub r: f64,
+    /// t = r sqrt((n - 3) / (1 - r²)).
+    pub t: f64,
+    pub df: f64,
+    /// Two-sided p-value for the null hypothesis of no partial correlation.
+    pub p_value: f64,
+}
+
+impl fmt::Display for PartialCorrelationResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "r", &[self.df], self.r, self.p_value)
+    }
+}
+
+impl HypothesisTest for PartialCorrelationResult {
+    fn statistic(&self) -> f64 {
+        self.t
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "partial correlation test"
+    }
+}
+
+/// Correlation between `x` and `y` after removing the linear effect of `z`,
+/// built from the three pairwise Pearson correlations and tested on n - 3
+/// degrees of freedom.
+///
+/// Returns `InvalidParameter` when `z` is perfectly correlated with either
+/// variable, since nothing is left of it once `z` is removed.
+pub fn partial_correlation(
+    x: &[f64],
+    y: &[f64],
+    z: &[f64],
+) -> Result<PartialCorrelationResult, StatsError> {
+    validate_pairs(x, y, 4)?;
+    validate_pairs(x, z, 4)?;
+    let r_xy = correlation(x, y)?;
+    let r_xz = correlation(x, z)?;
+    let r_yz = correlation(y, z)?;
+    if r_xz.abs() == 1.0 || r_yz.abs() == 1.0 {
+        return Err(StatsError::InvalidParameter(
+            "partial correlation is undefined when the control variable is perfectly correlated with x or y",
+        ));
+    }
+
+    let r = ((r_xy - r_xz * r_yz) / ((1.0 - r_xz * r_xz) * (1.0 - r_yz * r_yz)).sqrt())
+        .clamp(-1.0, 1.0);
+    let df = x.len() as f64 - 3.0;
+    let t = r * (df / (1.0 - r * r)).sqrt();
+    Ok(PartialCorrelationResult {
+        r,
+        t,
+        df,
+        p_value: StudentsT::new(df).two_tailed(t),
+    })
+}
+
+/// Pearson correlation matrix of several variables, one slice per variable,
+/// derived from `covariance_matrix`. The diagonal is exactly one.
+pub fn correlation_matrix(columns: &[&[f64]]) -> Result<Vec<Vec<f64>>, StatsError> {
+    let mut matrix = covariance_matrix(columns)?;
+    let sd: Vec<f64> = (0..matrix.len()).map(|i| matrix[i][i].sqrt()).collect();
+    if sd.contains(&0.0) {
+        return Err(StatsError::InvalidParameter(
+            "correlation is undefined when either variable is constant",
+        ));
+    }
+    for (i, row) in matrix.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            *entry = if i == j {
+                1.0
+            } else {
+                (*entry / (sd[i] * sd[j])).clamp(-1.0, 1.0)
+            };
+        }
+    }
+    Ok(matrix)
+}
+
+/// Pearson's r, rejecting constant input.
+pub(crate) fn correlation(x: &[f64], y: &[f64]) -> Result<f64, StatsError> {
+    let x_mean = mean(x);
+    let y_mean = mean(y);
+    let mut sxy = 0.0;
+    let mut sxx = 0.0;
+    let mut syy = 0.0;
+    for (a, b) in x.iter().zip(y) {
+        let dx = a - x_mean;
+        let dy = b - y_mean;
+        sxy += dx * dy;
+        sxx += dx * dx;
+        syy += dy * dy;
+    }
+    if sxx == 0.0 || syy == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "correlation is undefined when either variable is constant",
+        ));
+    }
+
+    // Rounding can push |r| a hair past one for perfectly linear data
+    Ok((sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0))
+}
+
+pub(crate) fn validate_pairs(x: &[f64], y: &[f64], required: usize) -> Result<(), StatsError> {
+    if x.len() != y.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: x.len(),
+            right: y.len(),
+        });
+    }
+    if x.len() < required {
+        return Err(StatsError::InsufficientData {
+            required,
+            actual: x.len(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, pooled_t_test, GetStatistics, SampleStatistics};
+
+    #[test]
+    fn pearson_r_test() {
+        // R: cor.test(x, y), from Hollander & Wolfe
+        let x = [44.4, 45.9, 41.9, 53.3, 44.7, 44.1, 50.7, 45.2, 60.1];
+        let y = [2.6, 3.1, 2.5, 5.0, 3.6, 4.0, 5.2, 2.8, 3.8];
+        let result = pearson_r(&x, &y).unwrap();
+        assert_close(result.r, 0.571181557563829, 1e-13);
+        assert_close(result.t, 1.84108263811534, 1e-12);
+        assert_eq!(result.df, 7.0);
+        assert_close(result.p_value, 0.108173054349494, 1e-12);
+        assert_close(result.confidence_interval.lower, -0.149742633036757, 1e-12);
+        assert_close(result.confidence_interval.upper, 0.895579461838739, 1e-12);
+    }
+
+    #[test]
+    fn correlation_display_test() {
+        let x = [44.4, 45.9, 41.9, 53.3, 44.7, 44.1, 50.7, 45.2, 60.1];
+        let y = [2.6, 3.1, 2.5, 5.0, 3.6, 4.0, 5.2, 2.8, 3.8];
+        let result = pearson_r(&x, &y).unwrap();
+        assert_eq!(result.to_string(), "r(7) = 0.571, p = 0.108");
+        assert_eq!(
+            result.confidence_interval.to_string(),
+            "95% CI [-0.15, 0.896]"
+        );
+    }
+
+    #[test]
+    fn pearson_r_perfect_correlation_test() {
+        let result = pearson_r(&[1.0, 2.0, 3.0, 4.0], &[-2.0, -4.0, -6.0, -8.0]).unwrap();
+        assert_eq!(result.r, -1.0);
+        assert_eq!(result.p_value, 0.0);
+        assert_eq!(result.confidence_interval.lower, -1.0);
+    }
+
+    #[test]
+    fn pearson_r_invalid_input_test() {
+        assert_eq!(
+            pearson_r(&[1.0, 2.0, 3.0], &[1.0, 2.0]).err(),
+            Some(StatsError::MismatchedLengths { left: 3, right: 2 })
+        );
+        assert_eq!(
+            pearson_r(&[1.0, 2.0], &[1.0, 2.0]).err(),
+            Some(StatsError::InsufficientData {
+                required: 3,
+                actual: 2
+            })
+        );
+        assert!(matches!(
+            pearson_r(&[1.0, 2.0, 3.0], &[5.0, 5.0, 5.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn spearman_rho_ties_test() {
+        // scipy.stats.spearmanr
+        let x = [
+            1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 4.0, 5.0, 5.0, 5.0, 5.0, 6.0, 7.0, 7.0,
+        ];
+        let y = [
+            2.0, 1.0, 3.0, 3.0, 2.0, 4.0, 4.0, 4.0, 5.0, 6.0, 5.0, 7.0, 6.0, 6.0, 8.0,
+        ];
+        let result = spearman_rho(&x, &y).unwrap();
+        assert_close(result.rho, 0.920343562832223, 1e-13);
+        assert_close(result.p_value, 1.16808431852918e-6, 1e-17);
+        assert_eq!(result.n, 15);
+    }
+
+    #[test]
+    fn spearman_rho_monotone_test() {
+        let x = [-3.0, -1.5, 0.0, 0.5, 2.0, 4.0, 7.0];
+        let y: Vec<f64> = x.iter().map(|v| v * v * v).collect();
+        let result = spearman_rho(&x, &y).unwrap();
+        assert_eq!(result.rho, 1.0);
+        assert_eq!(result.p_value, 0.0);
+        assert!(pearson_r(&x, &y).unwrap().r < 1.0);
+    }
+
+    #[test]
+    fn kendall_tau_ties_test() {
+        // scipy.stats.kendalltau, asymptotic p-value
+        let x = [
+            1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 4.0, 5.0, 5.0, 5.0, 5.0, 6.0, 7.0, 7.0,
+        ];
+        let y = [
+            2.0, 1.0, 3.0, 3.0, 2.0, 4.0, 4.0, 4.0, 5.0, 6.0, 5.0, 7.0, 6.0, 6.0, 8.0,
+        ];
+        let result = kendall_tau(&x, &y).unwrap();
+        assert_close(result.tau, 0.825500825501238, 1e-13);
+        assert_close(result.z, 3.98192527756068, 1e-12);
+        assert_close(result.p_value, 6.83592729097632e-5, 1e-15);
+
+        let result = kendall_tau(&[12.0, 2.0, 1.0, 12.0, 2.0], &[1.0, 4.0, 7.0, 1.0, 0.0]).unwrap();
+        assert_close(result.tau, -0.471404520791032, 1e-13);
+        assert_close(result.z, -1.07417231105915, 1e-12);
+        assert_close(result.p_value, 0.282745459932775, 1e-12);
+    }
+
+    #[test]
+    fn kendall_tau_matches_pairwise_count_test() {
+        let x = [44.4, 45.9, 41.9, 53.3, 44.7, 44.1, 50.7, 45.2, 60.1];
+        let y = [2.6, 3.1, 2.5, 5.0, 3.6, 4.0, 5.2, 2.8, 3.8];
+        let result = kendall_tau(&x, &y).unwrap();
+
+        let mut score = 0.0;
+        for i in 0..x.len() {
+            for j in i + 1..x.len() {
+                score += ((x[i] - x[j]) * (y[i] - y[j])).signum();
+            }
+        }
+        assert_close(result.tau, score / 36.0, 1e-15);
+        assert_close(result.tau, 0.444444444444444, 1e-14);
+        assert_close(result.z, 1.6681153124566, 1e-12);
+        assert_close(result.p_value, 0.0952928380234566, 1e-12);
+    }
+
+    #[test]
+    fn kendall_tau_invalid_input_test() {
+        assert!(matches!(
+            kendall_tau(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            kendall_tau(&[1.0, 2.0], &[1.0, 2.0, 3.0]).err(),
+            Some(StatsError::MismatchedLengths { left: 2, right: 3 })
+        );
+    }
+
+    #[test]
+    fn point_biserial_test() {
+        let groups = [
+            true, false, true, true, false, false, true, false, true, true, false,
+        ];
+        let values = [5.2, 3.1, 4.8, 6.0, 2.9, 3.5, 5.5, 4.0, 4.4, 5.9, 3.3];
+        let result = point_biserial(&groups, &values).unwrap();
+        assert_close(result.r, 0.890758360489223, 1e-13);
+        assert_close(result.t, 5.87988784846329, 1e-12);
+        assert_eq!(result.df, 9.0);
+        assert_close(result.p_value, 0.000234860401531537, 1e-15);
+
+        // Same test as a pooled t-test of the true group against the false one
+        let split = |flag: bool| -> Vec<f64> {
+            groups
+                .iter()
+                .zip(&values)
+                .filter(|(&g, _)| g == flag)
+                .map(|(_, &v)| v)
+                .collect()
+        };
+        let pooled = pooled_t_test(
+            &SampleStatistics::from_array(&split(true)),
+            &SampleStatistics::from_array(&split(false)),
+        );
+        assert_close(result.t, pooled.t, 1e-12);
+        assert_eq!(result.df, pooled.df);
+        assert_close(result.p_value, pooled.p_value, 1e-15);
+    }
+
+    #[test]
+    fn point_biserial_invalid_input_test() {
+        assert!(matches!(
+            point_biserial(&[true, true, true], &[1.0, 2.0, 3.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            point_biserial(&[true, false], &[1.0, 2.0, 3.0]).err(),
+            Some(StatsError::MismatchedLengths { left: 2, right: 3 })
+        );
+    }
+
+    #[test]
+    fn partial_correlation_test() {
+        let x = [44.4, 45.9, 41.9, 53.3, 44.7, 44.1, 50.7, 45.2, 60.1];
+        let y = [2.6, 3.1, 2.5, 5.0, 3.6, 4.0, 5.2, 2.8, 3.8];
+        let z = [1.0, 2.0, 1.5, 4.0, 2.5, 3.5, 3.0, 2.0, 5.0];
+        let result = partial_correlation(&x, &y, &z).unwrap();
+        assert_close(result.r, -0.0324726212763153, 1e-13);
+        assert_close(result.t, -0.0795833229629817, 1e-12);
+        assert_eq!(result.df, 6.0);
+        assert_close(result.p_value, 0.939156623369097, 1e-12);
+
+        // Same as correlating the residuals of x and y regressed on z
+        let residuals = |v: &[f64]| -> Vec<f64> {
+            let (v_mean, z_mean) = (mean(v), mean(&z));
+            let slope = v
+                .iter()
+                .zip(&z)
+                .map(|(a, b)| (a - v_mean) * (b - z_mean))
+                .sum::<f64>()
+                / z.iter().map(|b| (b - z_mean).powi(2)).sum::<f64>();
+            v.iter()
+                .zip(&z)
+                .map(|(a, b)| a - v_mean - slope * (b - z_mean))
+                .collect()
+        };
+        assert_close(
+            correlation(&residuals(&x), &residuals(&y)).unwrap(),
+            result.r,
+            1e-13,
+        );
+    }
+
+    #[test]
+    fn partial_correlation_degenerate_test() {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let y = [2.0, 1.0, 4.0, 3.0, 6.0];
+        let z = [3.0, 5.0, 7.0, 9.0, 11.0];
+        assert!(matches!(
+            partial_correlation(&x, &y, &z),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            partial_correlation(&x, &y, &z[..4]).err(),
+            Some(StatsError::MismatchedLengths { left: 5, right: 4 })
+        );
+    }
+
+    #[test]
+    fn correlation_matrix_test() {
+        let x = [2.1, 2.5, 3.6, 4.0];
+        let y = [8.0, 10.0, 12.0, 14.0];
+        let z = [1.0, -1.0, 0.5, 0.0];
+        let matrix = correlation_matrix(&[&x, &y, &z]).unwrap();
+        assert_eq!(matrix[1][1], 1.0);
+        assert_close(matrix[0][1], 0.979457043256652, 1e-14);
+        assert_close(matrix[2][0], -0.0544411713514229, 1e-14);
+        assert_close(matrix[1][2], correlation(&y, &z).unwrap(), 1e-15);
+        assert!(matches!(
+            correlation_matrix(&[&x, &[1.0, 1.0, 1.0, 1.0]]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
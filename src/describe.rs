@@ -0,0 +1,267 @@
+use std::fmt;
+
+use crate::format::significant;
+use crate::{
+    mean, quantiles, sample_standard_deviation, skewness, NanPolicy, QuantileMethod, StatsError,
+};
+
+/// Descriptive statistics of one column of a table. The statistics are
+/// `None` when the column has too few values for them: all of them for an
+/// empty column, and `sd` and `skewness` for a single value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColumnSummary {
+    /// Values the statistics were computed from: NaNs are counted under
+    /// `NanPolicy::Propagate` and dropped under `NanPolicy::Ignore`.
+    pub n: usize,
+    /// NaNs found in the column, whatever the policy did with them.
+    pub nan_count: usize,
+    pub mean: Option<f64>,
+    /// Sample standard deviation.
+    pub sd: Option<f64>,
+    pub min: Option<f64>,
+    /// First quartile, linearly interpolated as R's `quantile`.
+    pub q1: Option<f64>,
+    pub median: Option<f64>,
+    /// Third quartile, linearly interpolated as R's `quantile`.
+    pub q3: Option<f64>,
+    pub max: Option<f64>,
+    /// The biased sample skewness of `skewness`.
+    pub skewness: Option<f64>,
+}
+
+impl ColumnSummary {
+    /// Summarizes one column under `policy`.
+    pub fn new(column: &[f64], policy: NanPolicy) -> Result<Self, StatsError> {
+        let nan_count = column.iter().filter(|x| x.is_nan()).count();
+        let values = policy.apply(column)?;
+        let n = values.len();
+        if n == 0 {
+            return Ok(ColumnSummary {
+                n,
+                nan_count,
+                mean: None,
+                sd: None,
+                min: None,
+                q1: None,
+                median: None,
+                q3: None,
+                max: None,
+                skewness: None,
+            });
+        }
+        let q = quantiles(
+            &values,
+            &[0.0, 0.25, 0.5, 0.75, 1.0],
+            QuantileMethod::Linear,
+        )?;
+        let spread = |statistic: fn(&[f64]) -> f64| (n >= 2).then(|| statistic(&values));
+        Ok(ColumnSummary {
+            n,
+            nan_count,
+            mean: Some(mean(&values)),
+            sd: spread(sample_standard_deviation),
+            min: Some(q[0]),
+            q1: Some(q[1]),
+            median: Some(q[2]),
+            q3: Some(q[3]),
+            max: Some(q[4]),
+            skewness: spread(skewness),
+        })
+    }
+}
+
+/// The summaries of every column of a table, as `describe_columns` returns
+/// them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColumnSummaries {
+    pub columns: Vec<ColumnSummary>,
+}
+
+/// A table with a row per statistic and a column per input column, headed
+/// by the column's index, in the manner of pandas' `describe()`. Values are
+/// shown to six significant digits and missing statistics as "-".
+impl fmt::Display for ColumnSummaries {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:<8}", "")?;
+        for index in 0..self.columns.len() {
+            write!(f, "{index:>12}")?;
+        }
+        writeln!(f)?;
+
+        let cells: Vec<[String; 10]> = self.columns.iter().map(table_cells).collect();
+        for (row, label) in TABLE_ROWS.iter().enumerate() {
+            write!(f, "{label:<8}")?;
+            for column in &cells {
+                write!(f, "{:>12}", column[row])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Row labels of the `ColumnSummaries` table.
+const TABLE_ROWS: [&str; 10] = [
+    "n", "nan", "mean", "sd", "min", "q1", "median", "q3", "max", "skewness",
+];
+
+/// One column of the `ColumnSummaries` table, in the order of `TABLE_ROWS`.
+fn table_cells(summary: &ColumnSummary) -> [String; 10] {
+    let cell = |value: Option<f64>| value.map_or("-".to_string(), |v| significant(v, 6));
+    [
+        summary.n.to_string(),
+        summary.nan_count.to_string(),
+        cell(summary.mean),
+        cell(summary.sd),
+        cell(summary.min),
+        cell(summary.q1),
+        cell(summary.median),
+        cell(summary.q3),
+        cell(summary.max),
+        cell(summary.skewness),
+    ]
+}
+
+/// Summaries of independent columns, which may differ in length. An empty
+/// column, or one left empty by `NanPolicy::Ignore`, gets a summary with
+/// n = 0 rather than failing the call. Under `NanPolicy::Error` a NaN
+/// anywhere returns `StatsError::NanInInput` with its index in its column.
+pub fn describe_columns(
+    columns: &[&[f64]],
+    policy: NanPolicy,
+) -> Result<ColumnSummaries, StatsError> {
+    Ok(ColumnSummaries {
+        columns: columns
+            .iter()
+            .map(|column| ColumnSummary::new(column, policy))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// `describe_columns` for a table stored as rows, each holding one value
+/// per column. Returns `StatsError::MismatchedLengths` if the rows differ in
+/// length.
+pub fn describe_rows(rows: &[Vec<f64>], policy: NanPolicy) -> Result<ColumnSummaries, StatsError> {
+    let width = rows.first().map_or(0, |row| row.len());
+    if let Some(row) = rows.iter().find(|row| row.len() != width) {
+        return Err(StatsError::MismatchedLengths {
+            left: width,
+            right: row.len(),
+        });
+    }
+    let columns: Vec<Vec<f64>> = (0..width)
+        .map(|j| rows.iter().map(|row| row[j]).collect())
+        .collect();
+    let columns: Vec<&[f64]> = columns.iter().map(|c| c.as_slice()).collect();
+    describe_columns(&columns, policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, five_number_summary};
+
+    const HEIGHTS: [f64; 8] = [61.2, 72.5, 58.9, 80.1, 66.0, 70.4, 64.8, 75.3];
+    const WEIGHTS: [f64; 5] = [120.0, 155.5, 101.25, 180.0, 143.0];
+
+    #[test]
+    fn describe_columns_test() {
+        // Each column matches the single-column functions
+        let summaries = describe_columns(&[&HEIGHTS, &WEIGHTS], NanPolicy::Propagate).unwrap();
+        assert_eq!(summaries.columns.len(), 2);
+        for (summary, column) in summaries.columns.iter().zip([&HEIGHTS[..], &WEIGHTS]) {
+            assert_eq!(summary.n, column.len());
+            assert_eq!(summary.nan_count, 0);
+            assert_eq!(summary.mean, Some(mean(column)));
+            assert_eq!(summary.sd, Some(sample_standard_deviation(column)));
+            assert_eq!(summary.skewness, Some(skewness(column)));
+            let five = five_number_summary(column).unwrap();
+            let quartiles = [
+                summary.min,
+                summary.q1,
+                summary.median,
+                summary.q3,
+                summary.max,
+            ];
+            assert_eq!(
+                quartiles,
+                [five.min, five.q1, five.median, five.q3, five.max].map(Some)
+            );
+        }
+        let heights = &summaries.columns[0];
+        assert_close(heights.mean.unwrap(), 68.65, 1e-13);
+        assert_close(heights.q1.unwrap(), 63.9, 1e-13);
+        assert_close(heights.median.unwrap(), 68.2, 1e-13);
+        assert_close(heights.q3.unwrap(), 73.2, 1e-13);
+    }
+
+    #[test]
+    fn describe_columns_nan_and_empty_test() {
+        let with_nan = [1.0, f64::NAN, 3.0, 8.0];
+        let columns: [&[f64]; 4] = [&with_nan, &[], &[5.0], &[f64::NAN]];
+
+        let summaries = describe_columns(&columns, NanPolicy::Ignore).unwrap();
+        let [with_nan, empty, single, all_nan] = summaries.columns[..] else {
+            panic!("expected four summaries");
+        };
+        assert_eq!((with_nan.n, with_nan.nan_count), (3, 1));
+        assert_eq!(with_nan.mean, Some(4.0));
+        assert_eq!(with_nan.median, Some(3.0));
+        assert_eq!((empty.n, empty.nan_count), (0, 0));
+        assert_eq!(empty.mean, None);
+        assert_eq!(empty.max, None);
+        assert_eq!(single.n, 1);
+        assert_eq!(single.median, Some(5.0));
+        assert_eq!((single.sd, single.skewness), (None, None));
+        assert_eq!((all_nan.n, all_nan.nan_count), (0, 1));
+        assert_eq!(all_nan.mean, None);
+
+        let summaries = describe_columns(&columns, NanPolicy::Propagate).unwrap();
+        let with_nan = summaries.columns[0];
+        assert_eq!((with_nan.n, with_nan.nan_count), (4, 1));
+        assert!(with_nan.mean.unwrap().is_nan());
+        assert!(with_nan.median.unwrap().is_nan());
+
+        assert_eq!(
+            describe_columns(&columns, NanPolicy::Error),
+            Err(StatsError::NanInInput { index: 1 })
+        );
+    }
+
+    #[test]
+    fn describe_rows_test() {
+        let rows: Vec<Vec<f64>> = HEIGHTS
+            .iter()
+            .zip(HEIGHTS.iter().rev())
+            .map(|(&a, &b)| vec![a, b * 2.0])
+            .collect();
+        let by_rows = describe_rows(&rows, NanPolicy::Propagate).unwrap();
+        let doubled: Vec<f64> = HEIGHTS.iter().rev().map(|h| h * 2.0).collect();
+        let by_columns = describe_columns(&[&HEIGHTS, &doubled], NanPolicy::Propagate).unwrap();
+        assert_eq!(by_rows, by_columns);
+
+        assert_eq!(
+            describe_rows(&[vec![1.0, 2.0], vec![3.0]], NanPolicy::Propagate),
+            Err(StatsError::MismatchedLengths { left: 2, right: 1 })
+        );
+        assert!(describe_rows(&[], NanPolicy::Propagate)
+            .unwrap()
+            .columns
+            .is_empty());
+    }
+
+    #[test]
+    fn describe_display_test() {
+        let summaries = describe_columns(&[&WEIGHTS, &[], &[2.5]], NanPolicy::Propagate).unwrap();
+        let table = summaries.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert!(lines.iter().all(|line| line.len() == 8 + 3 * 12));
+        assert_eq!(lines[0].trim_start(), "0           1           2");
+        assert_eq!(lines[1], "n                  5           0           1");
+        assert_eq!(lines[3], "mean          139.95           -         2.5");
+        assert_eq!(lines[4], "sd           30.6219           -           -");
+    }
+}
@@ -0,0 +1,1202 @@
+use crate::{mean, population_standard_deviation, GetStatistics, SampleStatistics, StatsError};
+
+/// The conventional Tukey fence multiplier for outliers, for `tukey_outliers`.
+pub const TUKEY_OUTLIER_FENCE: f64 = 1.5;
+
+/// The Tukey fence multiplier for "extreme" outliers.
+pub const TUKEY_EXTREME_FENCE: f64 = 3.0;
+
+/// 1 / Φ⁻¹(0.75), which scales the MAD to estimate the standard deviation of
+/// normal data.
+pub const MAD_NORMAL_CONSISTENCY: f64 = 1.482602218505602;
+
+/// The robust z-score cutoff of Iglewicz and Hoaglin, for `mad_outliers`.
+pub const MAD_OUTLIER_THRESHOLD: f64 = 3.5;
+
+/// Sample skewness g1 = m3 / m2^(3/2), where mk is the kth central moment
+/// with divisor n. This is the biased form; see `adjusted_skewness`.
+pub fn skewness(data: &[f64]) -> f64 {
+    let [m2, m3, _] = central_moments(data);
+    m3 / m2.powf(1.5)
+}
+
+/// Sample excess kurtosis g2 = m4 / m2² - 3, where mk is the kth central
+/// moment with divisor n. A normal distribution has excess kurtosis zero.
+/// This is the biased form; see `adjusted_kurtosis`.
+pub fn kurtosis(data: &[f64]) -> f64 {
+    let [m2, _, m4] = central_moments(data);
+    m4 / (m2 * m2) - 3.0
+}
+
+/// The standardized moment m_k / m_2^(k/2) of the given order, where m_k is
+/// the kth central moment with divisor n. The third is `skewness` and the
+/// fourth is `kurtosis` plus 3. NaN if `data` is constant.
+pub fn standardized_moment(data: &[f64], order: u32) -> Result<f64, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    let data_mean = mean(data);
+    let n = data.len() as f64;
+    let central = |k: i32| data.iter().map(|x| (x - data_mean).powi(k)).sum::<f64>() / n;
+    Ok(central(order as i32) / central(2).powf(order as f64 / 2.0))
+}
+
+/// Adjusted Fisher-Pearson skewness G1 = g1 sqrt(n (n - 1)) / (n - 2), the
+/// bias-corrected form reported by pandas' `skew` and Excel's `SKEW`.
+pub fn adjusted_skewness(data: &[f64]) -> Result<f64, StatsError> {
+    if data.len() < 3 {
+        return Err(StatsError::InsufficientData {
+            required: 3,
+            actual: data.len(),
+        });
+    }
+    let n = data.len() as f64;
+    Ok(skewness(data) * (n * (n - 1.0)).sqrt() / (n - 2.0))
+}
+
+/// Bias-corrected excess kurtosis G2 = ((n + 1) g2 + 6) (n - 1) / ((n - 2)
+/// (n - 3)), as pandas' `kurt` and Excel's `KURT`. Like `kurtosis`, zero for
+/// a normal distribution.
+pub fn adjusted_kurtosis(data: &[f64]) -> Result<f64, StatsError> {
+    if data.len() < 4 {
+        return Err(StatsError::InsufficientData {
+            required: 4,
+            actual: data.len(),
+        });
+    }
+    let n = data.len() as f64;
+    Ok(((n + 1.0) * kurtosis(data) + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0)))
+}
+
+/// Coefficient of variation s / x̄ with the sample standard deviation. Only
+/// meaningful for ratio-scale data, so a mean that is not clearly positive is
+/// an error.
+pub fn coefficient_of_variation(data: &[f64]) -> Result<f64, StatsError> {
+    if data.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: data.len(),
+        });
+    }
+    SampleStatistics::from_array(data).coefficient_of_variation()
+}
+
+/// Coefficient of variation σ / μ with the population standard deviation.
+pub fn population_coefficient_of_variation(data: &[f64]) -> Result<f64, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    variation_ratio(population_standard_deviation(data), mean(data))
+}
+
+/// Coefficient of variation with the small-sample correction (1 + 1 / (4n))
+/// s / x̄ for normal data.
+pub fn adjusted_coefficient_of_variation(data: &[f64]) -> Result<f64, StatsError> {
+    let n = data.len() as f64;
+    Ok((1.0 + 1.0 / (4.0 * n)) * coefficient_of_variation(data)?)
+}
+
+/// `standard_deviation / mean`, rejecting means that are negative or
+/// negligible next to the spread.
+pub(crate) fn variation_ratio(standard_deviation: f64, mean: f64) -> Result<f64, StatsError> {
+    if mean.is_nan() || mean <= f64::EPSILON * standard_deviation {
+        return Err(StatsError::InvalidParameter(
+            "coefficient of variation needs a positive mean",
+        ));
+    }
+    Ok(standard_deviation / mean)
+}
+
+/// Sample covariance of paired observations, with divisor n - 1.
+pub fn sample_covariance(x: &[f64], y: &[f64]) -> Result<f64, StatsError> {
+    validate_covariance_pair(x, y, 2)?;
+    Ok(cross_product(x, y, mean(x), mean(y)) / (x.len() as f64 - 1.0))
+}
+
+/// Population covariance of paired observations, with divisor n.
+pub fn population_covariance(x: &[f64], y: &[f64]) -> Result<f64, StatsError> {
+    validate_covariance_pair(x, y, 1)?;
+    Ok(cross_product(x, y, mean(x), mean(y)) / x.len() as f64)
+}
+
+/// Sample covariance matrix of several variables, one slice per variable.
+/// Entry [i][j] is `sample_covariance(columns[i], columns[j])`; only the upper
+/// triangle is computed and then mirrored.
+pub fn covariance_matrix(columns: &[&[f64]]) -> Result<Vec<Vec<f64>>, StatsError> {
+    let Some(first) = columns.first() else {
+        return Err(StatsError::EmptyInput);
+    };
+    for column in columns {
+        validate_covariance_pair(first, column, 2)?;
+    }
+
+    let means: Vec<f64> = columns.iter().map(|column| mean(column)).collect();
+    let divisor = first.len() as f64 - 1.0;
+    let k = columns.len();
+    let mut matrix = vec![vec![0.0; k]; k];
+    for i in 0..k {
+        for j in i..k {
+            let c = cross_product(columns[i], columns[j], means[i], means[j]) / divisor;
+            matrix[i][j] = c;
+            matrix[j][i] = c;
+        }
+    }
+    Ok(matrix)
+}
+
+fn cross_product(x: &[f64], y: &[f64], x_mean: f64, y_mean: f64) -> f64 {
+    x.iter()
+        .zip(y)
+        .map(|(a, b)| (a - x_mean) * (b - y_mean))
+        .sum()
+}
+
+fn validate_covariance_pair(x: &[f64], y: &[f64], required: usize) -> Result<(), StatsError> {
+    if x.len() != y.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: x.len(),
+            right: y.len(),
+        });
+    }
+    if x.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if x.len() < required {
+        return Err(StatsError::InsufficientData {
+            required,
+            actual: x.len(),
+        });
+    }
+    Ok(())
+}
+
+/// The middle value of `data`, or the mean of the two middle values when the
+/// length is even. Uses quickselect on a copy, so it runs in linear average
+/// time and leaves `data` untouched. NaN if `data` contains NaN.
+pub fn median(data: &[f64]) -> Result<f64, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if data.iter().any(|x| x.is_nan()) {
+        return Ok(f64::NAN);
+    }
+    let mut values = data.to_vec();
+    let middle = values.len() / 2;
+    let (lower, &mut upper, _) = values.select_nth_unstable_by(middle, f64::total_cmp);
+    if data.len().is_multiple_of(2) {
+        // The lower partition holds the smaller half, so its maximum is the
+        // other middle value
+        let below = lower.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Ok((below + upper) / 2.0)
+    } else {
+        Ok(upper)
+    }
+}
+
+/// Geometric mean, exp of the mean of the logs so that long products do not
+/// overflow, as scipy's `gmean`. Every value must be positive.
+pub fn geometric_mean(data: &[f64]) -> Result<f64, StatsError> {
+    validate_positive(data, "geometric mean needs positive values")?;
+    Ok((data.iter().map(|x| x.ln()).sum::<f64>() / data.len() as f64).exp())
+}
+
+/// Geometric mean weighted by `weights`, exp(Σ wᵢ ln xᵢ / Σ wᵢ), for index
+/// construction. Weights must be non-negative and not all zero.
+pub fn weighted_geometric_mean(data: &[f64], weights: &[f64]) -> Result<f64, StatsError> {
+    validate_weights(data, weights)?;
+    validate_positive(data, "geometric mean needs positive values")?;
+    let weighted_logs: f64 = data.iter().zip(weights).map(|(x, w)| w * x.ln()).sum();
+    Ok((weighted_logs / weights.iter().sum::<f64>()).exp())
+}
+
+/// Harmonic mean n / Σ (1 / xᵢ), as scipy's `hmean`. Every value must be
+/// positive: scipy returns zero when a value is zero, but a single zero then
+/// hides the rest of the data, so it is treated as an error here.
+pub fn harmonic_mean(data: &[f64]) -> Result<f64, StatsError> {
+    validate_positive(data, "harmonic mean needs positive values")?;
+    Ok(data.len() as f64 / data.iter().map(|x| 1.0 / x).sum::<f64>())
+}
+
+/// Harmonic mean weighted by `weights`, Σ wᵢ / Σ (wᵢ / xᵢ). Weights must be
+/// non-negative and not all zero.
+pub fn weighted_harmonic_mean(data: &[f64], weights: &[f64]) -> Result<f64, StatsError> {
+    validate_weights(data, weights)?;
+    validate_positive(data, "harmonic mean needs positive values")?;
+    let weighted_reciprocals: f64 = data.iter().zip(weights).map(|(x, w)| w / x).sum();
+    Ok(weights.iter().sum::<f64>() / weighted_reciprocals)
+}
+
+/// How observation weights are interpreted by `weighted_variance`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightKind {
+    /// Each weight counts repeated observations, so the divisor is Σ w - 1,
+    /// as if every observation were listed w times.
+    Frequency,
+    /// Weights express relative precision and only their ratios matter; the
+    /// divisor is Σ w - Σ w² / Σ w.
+    Reliability,
+}
+
+/// Weighted mean Σ wᵢ xᵢ / Σ wᵢ, as numpy's `average(values, weights=...)`.
+/// Weights must be non-negative and not all zero.
+pub fn weighted_mean(values: &[f64], weights: &[f64]) -> Result<f64, StatsError> {
+    validate_weights(values, weights)?;
+    Ok(weighted_sum(values, weights) / weights.iter().sum::<f64>())
+}
+
+/// Unbiased weighted variance Σ wᵢ (xᵢ - x̄w)² / divisor, with the divisor set
+/// by how the weights are interpreted.
+pub fn weighted_variance(
+    values: &[f64],
+    weights: &[f64],
+    kind: WeightKind,
+) -> Result<f64, StatsError> {
+    let weighted_mean = weighted_mean(values, weights)?;
+    let total: f64 = weights.iter().sum();
+    let divisor = match kind {
+        WeightKind::Frequency => total - 1.0,
+        WeightKind::Reliability => total - weights.iter().map(|w| w * w).sum::<f64>() / total,
+    };
+    if divisor <= 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "weights describe fewer than two observations",
+        ));
+    }
+    let squared_deviations: f64 = values
+        .iter()
+        .zip(weights)
+        .map(|(x, w)| w * (x - weighted_mean).powi(2))
+        .sum();
+    Ok(squared_deviations / divisor)
+}
+
+/// Kish's effective sample size (Σ w)² / Σ w², the number of equally
+/// weighted observations carrying the same information as `weights`.
+pub(crate) fn effective_sample_size(weights: &[f64]) -> f64 {
+    weights.iter().sum::<f64>().powi(2) / weights.iter().map(|w| w * w).sum::<f64>()
+}
+
+fn weighted_sum(values: &[f64], weights: &[f64]) -> f64 {
+    values.iter().zip(weights).map(|(x, w)| w * x).sum()
+}
+
+/// Mean of `data` after dropping the smallest and the largest
+/// floor(`proportion` n) values, as scipy's `trim_mean`. `proportion` must
+/// lie in [0, 0.5).
+pub fn trimmed_mean(data: &[f64], proportion: f64) -> Result<f64, StatsError> {
+    let cut = trim_count(data, proportion)?;
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    Ok(mean(&sorted[cut..data.len() - cut]))
+}
+
+/// Mean of `winsorize(data, proportion)`.
+pub fn winsorized_mean(data: &[f64], proportion: f64) -> Result<f64, StatsError> {
+    Ok(mean(&winsorize(data, proportion)?))
+}
+
+/// `data` in its original order with the smallest floor(`proportion` n)
+/// values raised to the next smallest and the largest as many lowered to the
+/// next largest, as scipy's `mstats.winsorize(data, limits=proportion)`.
+/// `proportion` must lie in [0, 0.5).
+pub fn winsorize(data: &[f64], proportion: f64) -> Result<Vec<f64>, StatsError> {
+    let cut = trim_count(data, proportion)?;
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let (low, high) = (sorted[cut], sorted[data.len() - 1 - cut]);
+    Ok(data.iter().map(|x| x.clamp(low, high)).collect())
+}
+
+/// The number of values cut from each end for a trimming `proportion`.
+fn trim_count(data: &[f64], proportion: f64) -> Result<usize, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if !(0.0..0.5).contains(&proportion) {
+        return Err(StatsError::InvalidParameter(
+            "trimming proportion must lie in [0, 0.5)",
+        ));
+    }
+    Ok((proportion * data.len() as f64).floor() as usize)
+}
+
+fn validate_positive(data: &[f64], message: &'static str) -> Result<(), StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if data.iter().any(|&x| x <= 0.0 || x.is_nan()) {
+        return Err(StatsError::InvalidParameter(message));
+    }
+    Ok(())
+}
+
+/// Checks observation weights against their values: equal lengths, no
+/// negative or NaN weight, and a positive total.
+fn validate_weights(data: &[f64], weights: &[f64]) -> Result<(), StatsError> {
+    if data.len() != weights.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: data.len(),
+            right: weights.len(),
+        });
+    }
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if weights.iter().any(|&w| w < 0.0 || w.is_nan()) {
+        return Err(StatsError::InvalidParameter("weights must be non-negative"));
+    }
+    if weights.iter().all(|&w| w == 0.0) {
+        return Err(StatsError::InvalidParameter("weights must not all be zero"));
+    }
+    Ok(())
+}
+
+/// How a quantile between order statistics is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuantileMethod {
+    /// Linear interpolation between the order statistics either side of
+    /// position q (n - 1): R's type 7 and numpy's default `linear`.
+    #[default]
+    Linear,
+    /// The smallest value with at least a fraction q of the data at or below
+    /// it, the ceil(q n)-th order statistic: R's type 1 and numpy's
+    /// `inverted_cdf`.
+    NearestRank,
+}
+
+/// The q-th quantile of `data`, for q in [0, 1]. NaN if `data` contains NaN.
+pub fn quantile(data: &[f64], q: f64, method: QuantileMethod) -> Result<f64, StatsError> {
+    Ok(quantiles(data, &[q], method)?[0])
+}
+
+/// The p-th percentile of `data`, for p in [0, 100]; see `quantile`.
+pub fn percentile(data: &[f64], p: f64, method: QuantileMethod) -> Result<f64, StatsError> {
+    if !(0.0..=100.0).contains(&p) {
+        return Err(StatsError::InvalidParameter(
+            "percentile must be between 0 and 100",
+        ));
+    }
+    quantile(data, p / 100.0, method)
+}
+
+/// Several quantiles of `data` from a single sort, in the order of `qs`.
+pub fn quantiles(data: &[f64], qs: &[f64], method: QuantileMethod) -> Result<Vec<f64>, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if !qs.iter().all(|q| (0.0..=1.0).contains(q)) {
+        return Err(StatsError::InvalidParameter(
+            "quantile must be between 0 and 1",
+        ));
+    }
+    if data.iter().any(|x| x.is_nan()) {
+        return Ok(vec![f64::NAN; qs.len()]);
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    Ok(qs
+        .iter()
+        .map(|&q| sorted_quantile(&sorted, q, method))
+        .collect())
+}
+
+/// Quantile of sorted, non-empty data, with q clamped to [0, 1]. NaN when
+/// `q` is NaN.
+pub(crate) fn sorted_quantile(sorted: &[f64], q: f64, method: QuantileMethod) -> f64 {
+    if q.is_nan() {
+        return f64::NAN;
+    }
+    let q = q.clamp(0.0, 1.0);
+    match method {
+        QuantileMethod::Linear => {
+            let position = q * (sorted.len() - 1) as f64;
+            let below = position.floor() as usize;
+            let above = position.ceil() as usize;
+            sorted[below] + (position - below as f64) * (sorted[above] - sorted[below])
+        }
+        QuantileMethod::NearestRank => {
+            let rank = (q * sorted.len() as f64).ceil() as usize;
+            sorted[rank.max(1) - 1]
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FiveNumberSummary {
+    pub min: f64,
+    /// First quartile.
+    pub q1: f64,
+    pub median: f64,
+    /// Third quartile.
+    pub q3: f64,
+    pub max: f64,
+}
+
+/// Minimum, quartiles and maximum of `data`, with the quartiles from
+/// `QuantileMethod::Linear` as R's `quantile` (not `fivenum`, which uses
+/// Tukey's hinges).
+pub fn five_number_summary(data: &[f64]) -> Result<FiveNumberSummary, StatsError> {
+    let q = quantiles(data, &[0.0, 0.25, 0.5, 0.75, 1.0], QuantileMethod::Linear)?;
+    Ok(FiveNumberSummary {
+        min: q[0],
+        q1: q[1],
+        median: q[2],
+        q3: q[3],
+        max: q[4],
+    })
+}
+
+/// Interquartile range q3 - q1, with linearly interpolated quartiles.
+pub fn iqr(data: &[f64]) -> Result<f64, StatsError> {
+    let summary = five_number_summary(data)?;
+    Ok(summary.q3 - summary.q1)
+}
+
+/// Indices of the values outside Tukey's fences [q1 - k IQR, q3 + k IQR],
+/// in ascending order. `TUKEY_OUTLIER_FENCE` (1.5) is the usual k and
+/// `TUKEY_EXTREME_FENCE` (3.0) flags only extreme outliers.
+pub fn tukey_outliers(data: &[f64], k: f64) -> Result<Vec<usize>, StatsError> {
+    if !(k >= 0.0 && k.is_finite()) {
+        return Err(StatsError::InvalidParameter(
+            "fence multiplier must be non-negative and finite",
+        ));
+    }
+    let summary = five_number_summary(data)?;
+    let spread = summary.q3 - summary.q1;
+    let lower = summary.q1 - k * spread;
+    let upper = summary.q3 + k * spread;
+    Ok(data
+        .iter()
+        .enumerate()
+        .filter(|&(_, &x)| x < lower || x > upper)
+        .map(|(i, _)| i)
+        .collect())
+}
+
+/// Standardizes `data` by its own mean and sample standard deviation, so
+/// the result has mean 0 and standard deviation 1.
+pub fn z_scores(data: &[f64]) -> Result<Vec<f64>, StatsError> {
+    if data.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: data.len(),
+        });
+    }
+    let samp = SampleStatistics::from_array(data);
+    z_scores_against(data, samp.sample_mean, samp.standard_deviation)
+}
+
+/// Standardizes `data` against a reference mean and standard deviation, such
+/// as those of a training set, for scoring new observations.
+pub fn z_scores_against(
+    data: &[f64],
+    mean: f64,
+    standard_deviation: f64,
+) -> Result<Vec<f64>, StatsError> {
+    if !(standard_deviation > 0.0 && standard_deviation.is_finite()) {
+        return Err(StatsError::InvalidParameter(
+            "standard deviation must be positive and finite",
+        ));
+    }
+    Ok(data
+        .iter()
+        .map(|&x| z_score(x, mean, standard_deviation))
+        .collect())
+}
+
+/// (value - mean) / standard_deviation.
+pub fn z_score(value: f64, mean: f64, standard_deviation: f64) -> f64 {
+    (value - mean) / standard_deviation
+}
+
+/// The value at `z` standard deviations from `mean`; the inverse of
+/// `z_score`.
+pub fn from_z_score(z: f64, mean: f64, standard_deviation: f64) -> f64 {
+    mean + z * standard_deviation
+}
+
+/// Median absolute deviation from the median, multiplied by `consistency`:
+/// pass 1.0 for the raw MAD or `MAD_NORMAL_CONSISTENCY` to estimate a normal
+/// standard deviation, as R's `mad`.
+pub fn mad(data: &[f64], consistency: f64) -> Result<f64, StatsError> {
+    let center = median(data)?;
+    let deviations: Vec<f64> = data.iter().map(|x| (x - center).abs()).collect();
+    Ok(consistency * median(&deviations)?)
+}
+
+/// (x - median) / (`MAD_NORMAL_CONSISTENCY` MAD) for each value, the modified
+/// z-score of Iglewicz and Hoaglin. More than half the values being equal
+/// makes the MAD zero, which is an error rather than a division by zero.
+pub fn robust_z_scores(data: &[f64]) -> Result<Vec<f64>, StatsError> {
+    let center = median(data)?;
+    let scale = mad(data, MAD_NORMAL_CONSISTENCY)?;
+    if scale == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "median absolute deviation is zero",
+        ));
+    }
+    Ok(data.iter().map(|x| (x - center) / scale).collect())
+}
+
+/// Indices of the values whose robust z-score exceeds `threshold` in
+/// absolute value; `MAD_OUTLIER_THRESHOLD` is the usual choice.
+pub fn mad_outliers(data: &[f64], threshold: f64) -> Result<Vec<usize>, StatsError> {
+    if !(threshold >= 0.0 && threshold.is_finite()) {
+        return Err(StatsError::InvalidParameter(
+            "outlier threshold must be non-negative and finite",
+        ));
+    }
+    Ok(robust_z_scores(data)?
+        .iter()
+        .enumerate()
+        .filter(|&(_, z)| z.abs() > threshold)
+        .map(|(i, _)| i)
+        .collect())
+}
+
+/// Every value tied for the highest frequency, in ascending order. -0.0 and
+/// 0.0 count as the same value and NaN values are ignored, so data that is
+/// empty or all NaN has no mode.
+pub fn mode(data: &[f64]) -> Vec<f64> {
+    modes(frequency_table(data))
+}
+
+/// Each distinct value with its number of occurrences, most frequent first
+/// and ascending by value among equal counts. Values are compared as in
+/// `mode`.
+pub fn frequency_table(data: &[f64]) -> Vec<(f64, usize)> {
+    // Adding zero turns -0.0 into 0.0 so the two sort into one run
+    let mut values: Vec<f64> = data
+        .iter()
+        .filter(|x| !x.is_nan())
+        .map(|x| x + 0.0)
+        .collect();
+    values.sort_by(f64::total_cmp);
+    by_count(runs(&values))
+}
+
+/// `mode` for discrete data such as integer category codes.
+pub fn mode_discrete<T: Ord + Clone>(data: &[T]) -> Vec<T> {
+    modes(frequency_table_discrete(data))
+}
+
+/// `frequency_table` for discrete data such as integer category codes.
+pub fn frequency_table_discrete<T: Ord + Clone>(data: &[T]) -> Vec<(T, usize)> {
+    let mut values = data.to_vec();
+    values.sort();
+    by_count(runs(&values))
+}
+
+/// Lengths of the runs of equal values in sorted data.
+fn runs<T: PartialEq + Clone>(sorted: &[T]) -> Vec<(T, usize)> {
+    let mut table: Vec<(T, usize)> = Vec::new();
+    for value in sorted {
+        match table.last_mut() {
+            Some((last, count)) if last == value => *count += 1,
+            _ => table.push((value.clone(), 1)),
+        }
+    }
+    table
+}
+
+fn by_count<T>(mut table: Vec<(T, usize)>) -> Vec<(T, usize)> {
+    // Stable, so ties keep their ascending value order
+    table.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    table
+}
+
+fn modes<T>(table: Vec<(T, usize)>) -> Vec<T> {
+    let highest = table.first().map_or(0, |&(_, count)| count);
+    table
+        .into_iter()
+        .take_while(|&(_, count)| count == highest)
+        .map(|(value, _)| value)
+        .collect()
+}
+
+/// The second, third and fourth central moments, with divisor n.
+pub(crate) fn central_moments(data: &[f64]) -> [f64; 3] {
+    let data_mean = mean(data);
+    let mut moments = [0.0; 3];
+    for x in data {
+        let d = x - data_mean;
+        let d2 = d * d;
+        moments[0] += d2;
+        moments[1] += d2 * d;
+        moments[2] += d2 * d2;
+    }
+    moments.map(|m| m / data.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn skewness_test() {
+        assert_eq!(skewness(&[1.0, 2.0, 3.0]), 0.0);
+        // scipy.stats.skew
+        let data = [
+            0.05, 0.11, 0.13, 0.21, 0.26, 0.33, 0.38, 0.41, 0.52, 0.57, 0.66, 0.74, 0.81, 0.95,
+            1.02, 1.13, 1.31, 1.46, 1.62, 1.88, 2.09, 2.37, 2.71, 3.15, 3.88, 4.62, 5.94, 7.83,
+        ];
+        assert_close(skewness(&data), 1.76706543172485, 1e-13);
+    }
+
+    #[test]
+    fn kurtosis_test() {
+        // Two equally likely values: m4 / m2² = 1
+        assert_close(kurtosis(&[-1.0, 1.0, -1.0, 1.0]), -2.0, 1e-15);
+        // scipy.stats.kurtosis
+        let data = [
+            0.05, 0.11, 0.13, 0.21, 0.26, 0.33, 0.38, 0.41, 0.52, 0.57, 0.66, 0.74, 0.81, 0.95,
+            1.02, 1.13, 1.31, 1.46, 1.62, 1.88, 2.09, 2.37, 2.71, 3.15, 3.88, 4.62, 5.94, 7.83,
+        ];
+        assert_close(kurtosis(&data), 2.6720689047902, 1e-12);
+    }
+
+    #[test]
+    fn standardized_moment_test() {
+        let data = [
+            0.05, 0.11, 0.13, 0.21, 0.26, 0.33, 0.38, 0.41, 0.52, 0.57, 0.66, 0.74, 0.81, 0.95,
+            1.02, 1.13, 1.31, 1.46, 1.62, 1.88, 2.09, 2.37, 2.71, 3.15, 3.88, 4.62, 5.94, 7.83,
+        ];
+        assert_close(standardized_moment(&data, 2).unwrap(), 1.0, 1e-15);
+        assert_close(
+            standardized_moment(&data, 3).unwrap(),
+            skewness(&data),
+            1e-14,
+        );
+        assert_close(
+            standardized_moment(&data, 4).unwrap(),
+            kurtosis(&data) + 3.0,
+            1e-13,
+        );
+        // mpmath: m5 / m2^(5/2)
+        assert_close(
+            standardized_moment(&data, 5).unwrap(),
+            16.6456936529207,
+            1e-12,
+        );
+        assert_eq!(standardized_moment(&[], 3), Err(StatsError::EmptyInput));
+        assert!(standardized_moment(&[2.0, 2.0], 3).unwrap().is_nan());
+    }
+
+    #[test]
+    fn adjusted_moments_test() {
+        // pandas: Series(data).skew() and .kurt()
+        let data = [
+            0.05, 0.11, 0.13, 0.21, 0.26, 0.33, 0.38, 0.41, 0.52, 0.57, 0.66, 0.74, 0.81, 0.95,
+            1.02, 1.13, 1.31, 1.46, 1.62, 1.88, 2.09, 2.37, 2.71, 3.15, 3.88, 4.62, 5.94, 7.83,
+        ];
+        assert_close(adjusted_skewness(&data).unwrap(), 1.86870256126591, 1e-13);
+        assert_close(adjusted_kurtosis(&data).unwrap(), 3.46804608069342, 1e-12);
+        assert_close(
+            adjusted_skewness(&[1.0, 2.0, 3.0, 10.0]).unwrap(),
+            1.76363261480389,
+            1e-13,
+        );
+        assert_close(
+            adjusted_kurtosis(&[1.0, 2.0, 3.0, 10.0]).unwrap(),
+            3.228,
+            1e-12,
+        );
+        assert_close(
+            adjusted_kurtosis(&[2.0, 7.0, 1.0, 8.0, 2.0, 8.0]).unwrap(),
+            -3.05813615909421,
+            1e-12,
+        );
+
+        assert_eq!(
+            adjusted_skewness(&[1.0, 2.0]),
+            Err(StatsError::InsufficientData {
+                required: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            adjusted_kurtosis(&[1.0, 2.0, 3.0]),
+            Err(StatsError::InsufficientData {
+                required: 4,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn median_test() {
+        assert_eq!(median(&[3.0, 1.0, 2.0]), Ok(2.0));
+        assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), Ok(2.5));
+        assert_eq!(median(&[7.0]), Ok(7.0));
+        assert_eq!(median(&[2.0, 2.0, 1.0, 2.0]), Ok(2.0));
+        assert_eq!(median(&[-0.5, f64::INFINITY]), Ok(f64::INFINITY));
+        assert!(median(&[1.0, f64::NAN, 2.0]).unwrap().is_nan());
+        assert_eq!(median(&[]), Err(StatsError::EmptyInput));
+
+        let data = [5.0, 9.0, 1.0, 7.0, 3.0, 8.0];
+        assert_eq!(median(&data), Ok(6.0));
+        assert_eq!(data, [5.0, 9.0, 1.0, 7.0, 3.0, 8.0]);
+
+        let large: Vec<f64> = (0..100_001).rev().map(f64::from).collect();
+        assert_eq!(median(&large), Ok(50_000.0));
+    }
+
+    const SPREAD: [f64; 5] = [0.001, 0.5, 12.0, 350.0, 8000.0];
+    const SPREAD_WEIGHTS: [f64; 5] = [1.0, 2.0, 0.5, 3.0, 1.0];
+
+    #[test]
+    fn geometric_and_harmonic_mean_test() {
+        // scipy.stats.gmean and hmean, with and without weights=
+        let geometric = geometric_mean(&SPREAD).unwrap();
+        let harmonic = harmonic_mean(&SPREAD).unwrap();
+        assert_close(geometric, 6.99941681245545, 1e-13);
+        assert_close(harmonic, 0.00498959014086926, 1e-16);
+        assert!(mean(&SPREAD) >= geometric && geometric >= harmonic);
+        assert_close(
+            weighted_geometric_mean(&SPREAD, &SPREAD_WEIGHTS).unwrap(),
+            13.4806231099187,
+            1e-12,
+        );
+        assert_close(
+            weighted_harmonic_mean(&SPREAD, &SPREAD_WEIGHTS).unwrap(),
+            0.00746974482124518,
+            1e-16,
+        );
+
+        let constant = [4.0; 6];
+        assert_close(geometric_mean(&constant).unwrap(), 4.0, 1e-15);
+        assert_close(harmonic_mean(&constant).unwrap(), 4.0, 1e-15);
+
+        // The product of these overflows, but the mean of logs does not
+        let large = [1e300; 4];
+        assert_close(geometric_mean(&large).unwrap(), 1e300, 1e287);
+    }
+
+    const VALUES: [f64; 6] = [3.2, 4.8, 5.1, 2.9, 6.4, 5.5];
+    const WEIGHTS: [f64; 6] = [1.0, 2.5, 0.5, 3.0, 1.5, 2.0];
+
+    #[test]
+    fn weighted_mean_and_variance_test() {
+        // numpy.average(values, weights=weights)
+        assert_close(
+            weighted_mean(&VALUES, &WEIGHTS).unwrap(),
+            4.48095238095238,
+            1e-14,
+        );
+        assert_close(
+            weighted_variance(&VALUES, &WEIGHTS, WeightKind::Frequency).unwrap(),
+            1.80907268170426,
+            1e-14,
+        );
+        assert_close(
+            weighted_variance(&VALUES, &WEIGHTS, WeightKind::Reliability).unwrap(),
+            2.06234285714286,
+            1e-14,
+        );
+
+        // Unit weights reduce both forms to the ordinary sample variance
+        let ones = [1.0; 6];
+        for kind in [WeightKind::Frequency, WeightKind::Reliability] {
+            assert_close(
+                weighted_variance(&VALUES, &ones, kind).unwrap(),
+                crate::sample_variance(&VALUES),
+                1e-14,
+            );
+        }
+        // Integer frequency weights match listing each value that many times
+        let expanded = [3.2, 4.8, 4.8, 4.8, 2.9, 2.9];
+        assert_close(
+            weighted_variance(&[3.2, 4.8, 2.9], &[1.0, 3.0, 2.0], WeightKind::Frequency).unwrap(),
+            crate::sample_variance(&expanded),
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn weighted_invalid_input_test() {
+        assert_eq!(
+            weighted_mean(&VALUES, &WEIGHTS[..2]),
+            Err(StatsError::MismatchedLengths { left: 6, right: 2 })
+        );
+        assert!(matches!(
+            weighted_mean(&[1.0, 2.0], &[1.0, -0.5]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            weighted_mean(&[1.0, 2.0], &[0.0, 0.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            weighted_variance(&[1.0, 2.0], &[0.0, 3.0], WeightKind::Reliability),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            weighted_variance(&[1.0, 2.0], &[0.4, 0.4], WeightKind::Frequency),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    const HEAVY_TAILED: [f64; 11] = [0.2, 15.0, 3.1, 4.4, -8.0, 6.7, 2.2, 9.9, 5.0, 120.0, 3.3];
+
+    #[test]
+    fn trimmed_and_winsorized_mean_test() {
+        // scipy.stats.trim_mean and scipy.stats.mstats.winsorize; 15% of 11
+        // values cuts one from each end and 25% cuts two
+        assert_close(
+            trimmed_mean(&HEAVY_TAILED, 0.15).unwrap(),
+            5.53333333333333,
+            1e-14,
+        );
+        assert_close(
+            trimmed_mean(&HEAVY_TAILED, 0.25).unwrap(),
+            4.94285714285714,
+            1e-14,
+        );
+        assert_close(
+            winsorized_mean(&HEAVY_TAILED, 0.15).unwrap(),
+            5.90909090909091,
+            1e-14,
+        );
+        assert_close(
+            winsorized_mean(&HEAVY_TAILED, 0.25).unwrap(),
+            5.34545454545455,
+            1e-14,
+        );
+        assert_eq!(
+            winsorize(&HEAVY_TAILED, 0.25).unwrap(),
+            [2.2, 9.9, 3.1, 4.4, 2.2, 6.7, 2.2, 9.9, 5.0, 9.9, 3.3]
+        );
+
+        assert_eq!(trimmed_mean(&HEAVY_TAILED, 0.0), Ok(mean(&HEAVY_TAILED)));
+        assert_eq!(winsorize(&HEAVY_TAILED, 0.05).unwrap(), HEAVY_TAILED);
+    }
+
+    #[test]
+    fn trimmed_mean_invalid_input_test() {
+        assert!(matches!(
+            trimmed_mean(&HEAVY_TAILED, 0.5),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            winsorize(&HEAVY_TAILED, -0.1),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(winsorized_mean(&[], 0.1), Err(StatsError::EmptyInput));
+    }
+
+    #[test]
+    fn geometric_and_harmonic_mean_invalid_input_test() {
+        assert!(matches!(
+            geometric_mean(&[1.0, -2.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            harmonic_mean(&[1.0, 0.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(geometric_mean(&[]), Err(StatsError::EmptyInput));
+        assert_eq!(
+            weighted_harmonic_mean(&SPREAD, &[1.0]),
+            Err(StatsError::MismatchedLengths { left: 5, right: 1 })
+        );
+        assert!(matches!(
+            weighted_geometric_mean(&[1.0, 2.0], &[1.0, -1.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            weighted_geometric_mean(&[1.0, 2.0], &[0.0, 0.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn quantile_test() {
+        // numpy.percentile(data, [0, 25, 50, 75, 100], method = "linear" / "inverted_cdf")
+        let odd = [7.0, 1.0, 3.0, 9.5, 4.0, 6.0, 2.0, 8.0, 5.5];
+        let even = [7.0, 1.0, 3.0, 9.5, 4.0, 6.0, 2.0, 8.0];
+        let qs = [0.0, 0.25, 0.5, 0.75, 1.0];
+        let cases: [(&[f64], QuantileMethod, [f64; 5]); 4] = [
+            (&odd, QuantileMethod::Linear, [1.0, 3.0, 5.5, 7.0, 9.5]),
+            (&odd, QuantileMethod::NearestRank, [1.0, 3.0, 5.5, 7.0, 9.5]),
+            (&even, QuantileMethod::Linear, [1.0, 2.75, 5.0, 7.25, 9.5]),
+            (
+                &even,
+                QuantileMethod::NearestRank,
+                [1.0, 2.0, 4.0, 7.0, 9.5],
+            ),
+        ];
+        for (data, method, expected) in cases {
+            assert_eq!(quantiles(data, &qs, method).unwrap(), expected);
+            for (q, expected) in qs.iter().zip(expected) {
+                assert_eq!(percentile(data, q * 100.0, method), Ok(expected));
+            }
+        }
+        assert_close(
+            quantile(&odd, 0.1, QuantileMethod::Linear).unwrap(),
+            1.8,
+            1e-15,
+        );
+        assert_eq!(quantile(&odd, 0.9, QuantileMethod::NearestRank), Ok(9.5));
+
+        for data in [&odd[..], &even[..]] {
+            assert_eq!(quantile(data, 0.5, QuantileMethod::Linear), median(data));
+        }
+    }
+
+    #[test]
+    fn sorted_quantile_test() {
+        let sorted = [1.0, 2.0, 4.0, 8.0];
+        let linear = QuantileMethod::Linear;
+        assert_eq!(sorted_quantile(&sorted, 0.0, linear), 1.0);
+        assert_eq!(sorted_quantile(&sorted, 1.0, linear), 8.0);
+        assert_close(sorted_quantile(&sorted, 0.5, linear), 3.0, 1e-15);
+        assert_close(sorted_quantile(&sorted, 0.9, linear), 6.8, 1e-14);
+        assert_eq!(sorted_quantile(&sorted, -0.5, linear), 1.0);
+        assert_eq!(
+            sorted_quantile(&sorted, 2.0, QuantileMethod::NearestRank),
+            8.0
+        );
+        assert!(sorted_quantile(&sorted, f64::NAN, linear).is_nan());
+    }
+
+    #[test]
+    fn quantile_invalid_input_test() {
+        let method = QuantileMethod::Linear;
+        assert_eq!(quantile(&[], 0.5, method), Err(StatsError::EmptyInput));
+        assert!(matches!(
+            quantile(&[1.0], 1.5, method),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            quantile(&[1.0], f64::NAN, method),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            percentile(&[1.0], -1.0, method),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(quantile(&[1.0, f64::NAN], 0.5, method).unwrap().is_nan());
+    }
+
+    #[test]
+    fn five_number_summary_test() {
+        // R: quantile(c(2, 4, 4, 5, 6, 7, 8, 9, 12, 30))
+        let data = [7.0, 4.0, 30.0, 2.0, 9.0, 5.0, 12.0, 4.0, 8.0, 6.0];
+        assert_eq!(
+            five_number_summary(&data),
+            Ok(FiveNumberSummary {
+                min: 2.0,
+                q1: 4.25,
+                median: 6.5,
+                q3: 8.75,
+                max: 30.0,
+            })
+        );
+        assert_eq!(iqr(&data), Ok(4.5));
+        assert_eq!(iqr(&[]), Err(StatsError::EmptyInput));
+    }
+
+    #[test]
+    fn tukey_outliers_test() {
+        // Fences at 4.25 - 6.75 = -2.5 and 8.75 + 6.75 = 15.5, then -9.25 and 22.25
+        let data = [7.0, 4.0, 30.0, 2.0, 9.0, 5.0, 16.0, 4.0, 8.0, 6.0];
+        assert_eq!(tukey_outliers(&data, TUKEY_OUTLIER_FENCE), Ok(vec![2, 6]));
+        assert_eq!(tukey_outliers(&data, TUKEY_EXTREME_FENCE), Ok(vec![2]));
+        assert!(tukey_outliers(&[1.0, 2.0, 3.0], 1.5).unwrap().is_empty());
+        assert!(matches!(
+            tukey_outliers(&data, -1.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn z_scores_test() {
+        let data = [7.0, 4.0, 30.0, 2.0, 9.0, 5.0, 16.0, 4.0, 8.0, 6.0];
+        let z = z_scores(&data).unwrap();
+        assert_close(mean(&z), 0.0, 1e-15);
+        assert_close(crate::sample_standard_deviation(&z), 1.0, 1e-15);
+
+        let samp = SampleStatistics::from_array(&data);
+        for (z, x) in z.iter().zip(data) {
+            assert_close(
+                from_z_score(*z, samp.sample_mean, samp.standard_deviation),
+                x,
+                1e-13,
+            );
+        }
+        assert_eq!(z_score(13.0, 10.0, 2.0), 1.5);
+        assert_eq!(
+            z_scores_against(&[8.0, 10.0, 13.0], 10.0, 2.0),
+            Ok(vec![-1.0, 0.0, 1.5])
+        );
+    }
+
+    #[test]
+    fn z_scores_invalid_input_test() {
+        assert!(matches!(
+            z_scores(&[4.0, 4.0, 4.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            z_scores_against(&[1.0], 0.0, -1.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            z_scores(&[4.0]),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn mad_test() {
+        // R: mad(data, constant = 1) and mad(data)
+        let data = [7.0, 4.0, 30.0, 2.0, 9.0, 5.0, 16.0, 4.0, 8.0, 6.0];
+        assert_eq!(mad(&data, 1.0), Ok(2.5));
+        assert_close(
+            mad(&data, MAD_NORMAL_CONSISTENCY).unwrap(),
+            3.706505546264,
+            1e-14,
+        );
+
+        let z = robust_z_scores(&data).unwrap();
+        let expected = [
+            0.134897950039216,
+            -0.674489750196082,
+            6.34020365184317,
+            -1.21408155035295,
+        ];
+        for (actual, expected) in z.iter().zip(expected) {
+            assert_close(*actual, expected, 1e-14);
+        }
+        assert_eq!(mad_outliers(&data, MAD_OUTLIER_THRESHOLD), Ok(vec![2]));
+        assert_eq!(mad_outliers(&data, 2.0), Ok(vec![2, 6]));
+    }
+
+    #[test]
+    fn mad_invalid_input_test() {
+        assert_eq!(mad(&[], 1.0), Err(StatsError::EmptyInput));
+        assert_eq!(mad(&[3.0, 3.0, 3.0, 8.0], 1.0), Ok(0.0));
+        assert!(matches!(
+            robust_z_scores(&[3.0, 3.0, 3.0, 8.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            mad_outliers(&[1.0, 2.0, 4.0], f64::NAN),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn mode_test() {
+        assert_eq!(mode(&[1.0, 2.0, 2.0, 3.0]), vec![2.0]);
+        assert_eq!(mode(&[3.0, 1.0, 3.0, 1.0, 2.0]), vec![1.0, 3.0]);
+        assert_eq!(mode(&[-0.0, 0.0, 1.0]), vec![0.0]);
+        assert_eq!(mode(&[f64::NAN, f64::NAN, 4.0]), vec![4.0]);
+        assert!(mode(&[]).is_empty());
+
+        assert_eq!(
+            frequency_table(&[2.5, 1.0, 2.5, 7.0, 1.0, 2.5]),
+            vec![(2.5, 3), (1.0, 2), (7.0, 1)]
+        );
+        assert_eq!(mode_discrete(&[3, 1, 2, 3, 1]), vec![1, 3]);
+        assert_eq!(
+            frequency_table_discrete(&["b", "a", "b"]),
+            vec![("b", 2), ("a", 1)]
+        );
+    }
+
+    #[test]
+    fn coefficient_of_variation_test() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_close(
+            coefficient_of_variation(&data).unwrap(),
+            0.427617987059879,
+            1e-15,
+        );
+        assert_close(
+            population_coefficient_of_variation(&data).unwrap(),
+            0.4,
+            1e-15,
+        );
+        assert_close(
+            adjusted_coefficient_of_variation(&data).unwrap(),
+            0.4409810491555,
+            1e-15,
+        );
+        assert_eq!(coefficient_of_variation(&[3.0, 3.0]), Ok(0.0));
+    }
+
+    #[test]
+    fn coefficient_of_variation_invalid_input_test() {
+        for data in [[-1.0, 1.0], [-2.0, -4.0], [0.0, 0.0]] {
+            assert!(matches!(
+                coefficient_of_variation(&data),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        assert_eq!(
+            coefficient_of_variation(&[1.0]),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            population_coefficient_of_variation(&[]),
+            Err(StatsError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn covariance_test() {
+        let x = [2.1, 2.5, 3.6, 4.0];
+        let y = [8.0, 10.0, 12.0, 14.0];
+        // numpy.cov(x, y) and numpy.cov(x, y, bias=True)
+        assert_close(sample_covariance(&x, &y).unwrap(), 2.26666666666667, 1e-14);
+        assert_close(population_covariance(&x, &y).unwrap(), 1.7, 1e-14);
+        assert_close(
+            sample_covariance(&x, &x).unwrap(),
+            crate::sample_variance(&x),
+            1e-14,
+        );
+        assert_eq!(
+            sample_covariance(&x, &y[..3]),
+            Err(StatsError::MismatchedLengths { left: 4, right: 3 })
+        );
+        assert_eq!(
+            sample_covariance(&[1.0], &[2.0]),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(population_covariance(&[1.0], &[2.0]), Ok(0.0));
+        assert_eq!(population_covariance(&[], &[]), Err(StatsError::EmptyInput));
+    }
+
+    #[test]
+    fn covariance_matrix_test() {
+        let x = [2.1, 2.5, 3.6, 4.0];
+        let y = [8.0, 10.0, 12.0, 14.0];
+        let z = [1.0, -1.0, 0.5, 0.0];
+        let matrix = covariance_matrix(&[&x, &y, &z]).unwrap();
+        assert_eq!(matrix[1][0], matrix[0][1]);
+        assert_eq!(matrix[2][0], matrix[0][2]);
+        assert_eq!(matrix[2][1], matrix[1][2]);
+        assert_close(matrix[0][0], 0.803333333333333, 1e-14);
+        assert_close(matrix[0][1], 2.26666666666667, 1e-14);
+        assert_close(matrix[1][1], 6.66666666666667, 1e-13);
+        assert_close(matrix[1][2], -0.5, 1e-14);
+        assert_close(matrix[2][2], 0.729166666666667, 1e-14);
+        assert_eq!(
+            covariance_matrix(&[&x, &z[..2]]).err(),
+            Some(StatsError::MismatchedLengths { left: 4, right: 2 })
+        );
+        assert_eq!(covariance_matrix(&[]).err(), Some(StatsError::EmptyInput));
+    }
+}
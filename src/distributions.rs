@@ -0,0 +1,1215 @@
+// Some crate-private items here serve only the tests that need std.
+#![cfg_attr(not(feature = "std"), allow(dead_code))]
+
+#[cfg(not(feature = "std"))]
+use crate::math::F64Ext;
+use crate::special::{
+    erfc, inverse_regularized_incomplete_beta, inverse_regularized_lower_incomplete_gamma,
+    ln_gamma, regularized_incomplete_beta, regularized_lower_incomplete_gamma,
+    regularized_upper_incomplete_gamma,
+};
+use crate::Alternative;
+
+/// Normal distribution with the given mean and standard deviation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normal {
+    mean: f64,
+    sd: f64,
+}
+
+impl Normal {
+    /// The standard deviation `sd` must be positive.
+    pub fn new(mean: f64, sd: f64) -> Self {
+        Normal { mean, sd }
+    }
+
+    /// The standard normal distribution, with mean 0 and standard deviation 1.
+    pub const fn standard() -> Self {
+        Normal { mean: 0.0, sd: 1.0 }
+    }
+
+    /// The probability density at `x`.
+    pub fn pdf(&self, x: f64) -> f64 {
+        let z = (x - self.mean) / self.sd;
+        (-z * z / 2.0).exp() / (self.sd * (2.0 * core::f64::consts::PI).sqrt())
+    }
+
+    /// P(X <= x).
+    pub fn cdf(&self, x: f64) -> f64 {
+        let z = (x - self.mean) / self.sd;
+        erfc(-z / core::f64::consts::SQRT_2) / 2.0
+    }
+
+    /// P(X > x), accurate far into the upper tail.
+    pub fn sf(&self, x: f64) -> f64 {
+        let z = (x - self.mean) / self.sd;
+        erfc(z / core::f64::consts::SQRT_2) / 2.0
+    }
+
+    /// The value x with P(X <= x) = p. Uses Acklam's rational approximation
+    /// followed by one Halley step, which brings it to full double precision.
+    pub fn quantile(&self, p: f64) -> f64 {
+        if p <= 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        if p >= 1.0 {
+            return f64::INFINITY;
+        }
+
+        let z = if p < NORMAL_QUANTILE_LOW {
+            let q = (-2.0 * p.ln()).sqrt();
+            rational_tail(q)
+        } else if p > 1.0 - NORMAL_QUANTILE_LOW {
+            let q = (-2.0 * (-p).ln_1p()).sqrt();
+            -rational_tail(q)
+        } else {
+            let q = p - 0.5;
+            let r = q * q;
+            let [a0, a1, a2, a3, a4, a5] = NORMAL_QUANTILE_A;
+            let [b0, b1, b2, b3, b4] = NORMAL_QUANTILE_B;
+            (((((a0 * r + a1) * r + a2) * r + a3) * r + a4) * r + a5) * q
+                / (((((b0 * r + b1) * r + b2) * r + b3) * r + b4) * r + 1.0)
+        };
+
+        // Halley refinement against the CDF
+        let error = erfc(-z / core::f64::consts::SQRT_2) / 2.0 - p;
+        let u = error * (2.0 * core::f64::consts::PI).sqrt() * (z * z / 2.0).exp();
+        let z = z - u / (1.0 + z * u / 2.0);
+
+        self.mean + self.sd * z
+    }
+
+    /// P(|Z| >= |z - mean|), the two-sided p-value for a z statistic.
+    pub(crate) fn two_tailed(&self, z: f64) -> f64 {
+        let z = (z - self.mean) / self.sd;
+        erfc(z.abs() / core::f64::consts::SQRT_2)
+    }
+
+    /// The p-value of a z statistic against `alternative`: the tail below z
+    /// for `Less`, above it for `Greater`.
+    pub(crate) fn p_value(&self, z: f64, alternative: Alternative) -> f64 {
+        match alternative {
+            Alternative::TwoSided => self.two_tailed(z),
+            Alternative::Less => self.cdf(z),
+            Alternative::Greater => self.sf(z),
+        }
+    }
+}
+
+const NORMAL_QUANTILE_LOW: f64 = 0.02425;
+const NORMAL_QUANTILE_A: [f64; 6] = [
+    -3.96968302866538e1,
+    2.20946098424521e2,
+    -2.75928510446969e2,
+    1.38357751867269e2,
+    -3.06647980661472e1,
+    2.50662827745924,
+];
+const NORMAL_QUANTILE_B: [f64; 5] = [
+    -5.44760987982241e1,
+    1.61585836858041e2,
+    -1.55698979859887e2,
+    6.68013118877197e1,
+    -1.32806815528857e1,
+];
+const NORMAL_QUANTILE_C: [f64; 6] = [
+    -7.78489400243029e-3,
+    -3.22396458041136e-1,
+    -2.40075827716184,
+    -2.54973253934373,
+    4.37466414146497,
+    2.93816398269878,
+];
+const NORMAL_QUANTILE_D: [f64; 4] = [
+    7.78469570904146e-3,
+    3.22467129070040e-1,
+    2.44513413714300,
+    3.75440866190742,
+];
+
+/// Acklam's approximation to the lower-tail normal quantile in terms of
+/// q = sqrt(-2 ln p).
+fn rational_tail(q: f64) -> f64 {
+    let [c0, c1, c2, c3, c4, c5] = NORMAL_QUANTILE_C;
+    let [d0, d1, d2, d3] = NORMAL_QUANTILE_D;
+    (((((c0 * q + c1) * q + c2) * q + c3) * q + c4) * q + c5)
+        / ((((d0 * q + d1) * q + d2) * q + d3) * q + 1.0)
+}
+
+/// Student's t distribution with `df` degrees of freedom. The degrees of
+/// freedom need not be an integer, as in Welch's test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StudentsT {
+    df: f64,
+}
+
+impl StudentsT {
+    /// The degrees of freedom `df` must be positive.
+    pub fn new(df: f64) -> Self {
+        StudentsT { df }
+    }
+
+    /// The probability density at `t`.
+    pub fn pdf(&self, t: f64) -> f64 {
+        let df = self.df;
+        let ln_scale = ln_gamma_half_ratio(df / 2.0) - (df * core::f64::consts::PI).ln() / 2.0;
+        (ln_scale - (df + 1.0) / 2.0 * (t * t / df).ln_1p()).exp()
+    }
+
+    /// P(|T| >= |t|), the two-sided p-value for a t statistic.
+    pub(crate) fn two_tailed(&self, t: f64) -> f64 {
+        let x = self.df / (self.df + t * t);
+        regularized_incomplete_beta(self.df / 2.0, 0.5, x)
+    }
+
+    /// The p-value of a t statistic against `alternative`; see
+    /// `Normal::p_value`.
+    pub(crate) fn p_value(&self, t: f64, alternative: Alternative) -> f64 {
+        match alternative {
+            Alternative::TwoSided => self.two_tailed(t),
+            Alternative::Less => self.cdf(t),
+            Alternative::Greater => self.sf(t),
+        }
+    }
+
+    /// P(T <= t), from the regularized incomplete beta function.
+    pub fn cdf(&self, t: f64) -> f64 {
+        let tail = self.two_tailed(t) / 2.0;
+        if t > 0.0 {
+            1.0 - tail
+        } else {
+            tail
+        }
+    }
+
+    /// P(T > t), accurate far into the upper tail.
+    pub fn sf(&self, t: f64) -> f64 {
+        self.cdf(-t)
+    }
+
+    /// The value t with P(T <= t) = p, by inverting the incomplete beta
+    /// function that gives the cdf and polishing with a Newton step on the
+    /// lower tail.
+    pub fn quantile(&self, p: f64) -> f64 {
+        if p <= 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        if p >= 1.0 {
+            return f64::INFINITY;
+        }
+        if p == 0.5 {
+            return 0.0;
+        }
+        // P(|T| >= t) = I_x(df / 2, 1 / 2) with x = df / (df + t²)
+        let tail = p.min(1.0 - p);
+        let x = inverse_regularized_incomplete_beta(self.df / 2.0, 0.5, 2.0 * tail);
+        let t = -(self.df * (1.0 - x) / x).sqrt();
+        let t = -(t - (self.cdf(t) - tail) / self.pdf(t));
+        if p < 0.5 {
+            -t
+        } else {
+            t
+        }
+    }
+}
+
+/// ln Γ(x + 1/2) - ln Γ(x). For large x the two log-gammas nearly cancel,
+/// so the difference is taken from its asymptotic series instead.
+fn ln_gamma_half_ratio(x: f64) -> f64 {
+    if x < 25.0 {
+        return ln_gamma(x + 0.5) - ln_gamma(x);
+    }
+    let r = 1.0 / (x * x);
+    x.ln() / 2.0
+        - (1.0 / 8.0
+            - r * (1.0 / 192.0 - r * (1.0 / 640.0 - r * (17.0 / 14336.0 - r * 31.0 / 18432.0))))
+            / x
+}
+
+/// Noncentral t distribution with `df` degrees of freedom and noncentrality
+/// `ncp`, the distribution of a t statistic when the true standardized
+/// effect is nonzero.
+pub(crate) struct NoncentralT {
+    df: f64,
+    ncp: f64,
+}
+
+impl NoncentralT {
+    pub(crate) fn new(df: f64, ncp: f64) -> Self {
+        NoncentralT { df, ncp }
+    }
+
+    /// P(T <= t), by Lenth's algorithm AS 243 as in R's `pt` with `ncp`:
+    /// a Poisson-weighted sum of incomplete beta functions, accurate to
+    /// about 1e-12.
+    pub(crate) fn cdf(&self, t: f64) -> f64 {
+        const MAX_TERMS: usize = 1000;
+        const ERROR_BOUND: f64 = 1e-12;
+
+        let (t, delta, negated) = if t >= 0.0 {
+            (t, self.ncp, false)
+        } else {
+            (-t, -self.ncp, true)
+        };
+        let normal = Normal::standard();
+
+        // The Poisson weights underflow for large noncentrality, where the
+        // normal approximation of Abramowitz and Stegun 26.7.10 is accurate
+        if delta * delta > 1400.0 {
+            let s = 1.0 / (4.0 * self.df);
+            let z = (t * (1.0 - s) - delta) / (t * t * 2.0 * s + 1.0).sqrt();
+            let lower = normal.cdf(z);
+            return if negated { 1.0 - lower } else { lower };
+        }
+
+        let x = t * t / (t * t + self.df);
+        let mut total = 0.0;
+        if x > 0.0 {
+            let lambda = delta * delta;
+            let mut p = 0.5 * (-0.5 * lambda).exp();
+            let mut q = (2.0 / core::f64::consts::PI).sqrt() * p * delta;
+            let mut s = 0.5 - p;
+            let mut a = 0.5;
+            let b = 0.5 * self.df;
+            let rxb = (1.0 - x).powf(b);
+            let ln_beta = 0.5 * core::f64::consts::PI.ln() + ln_gamma(b) - ln_gamma(0.5 + b);
+            let mut x_odd = regularized_incomplete_beta(a, b, x);
+            let mut g_odd = 2.0 * rxb * (a * x.ln() - ln_beta).exp();
+            let mut x_even = 1.0 - rxb;
+            let mut g_even = b * x * rxb;
+            total = p * x_odd + q * x_even;
+
+            for j in 1..=MAX_TERMS {
+                a += 1.0;
+                x_odd -= g_odd;
+                x_even -= g_even;
+                g_odd *= x * (a + b - 1.0) / a;
+                g_even *= x * (a + b - 0.5) / (a + 0.5);
+                p *= lambda / (2.0 * j as f64);
+                q *= lambda / (2.0 * j as f64 + 1.0);
+                s -= p;
+                total += p * x_odd + q * x_even;
+                if 2.0 * s * (x_odd - g_odd) <= ERROR_BOUND {
+                    break;
+                }
+            }
+        }
+        let lower = (total + normal.cdf(-delta)).clamp(0.0, 1.0);
+        if negated {
+            1.0 - lower
+        } else {
+            lower
+        }
+    }
+}
+
+/// Exponential distribution with the given rate, the reciprocal of its mean.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exponential {
+    rate: f64,
+}
+
+impl Exponential {
+    /// The rate must be positive.
+    pub fn new(rate: f64) -> Self {
+        Exponential { rate }
+    }
+
+    /// The probability density at `x`, zero below 0.
+    pub fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        self.rate * (-self.rate * x).exp()
+    }
+
+    /// P(X <= x), accurate for small x.
+    pub fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        -(-self.rate * x).exp_m1()
+    }
+
+    /// P(X > x).
+    pub fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 1.0;
+        }
+        (-self.rate * x).exp()
+    }
+
+    /// The value x with P(X <= x) = p, infinite at p = 1.
+    pub fn quantile(&self, p: f64) -> f64 {
+        -(-p).ln_1p() / self.rate
+    }
+}
+
+/// Chi-square distribution with `df` degrees of freedom, which need not be
+/// an integer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquare {
+    df: f64,
+}
+
+impl ChiSquare {
+    /// The degrees of freedom `df` must be positive.
+    pub fn new(df: f64) -> Self {
+        ChiSquare { df }
+    }
+
+    /// The probability density at `x`, infinite at 0 when df < 2.
+    pub fn pdf(&self, x: f64) -> f64 {
+        let k = self.df / 2.0;
+        if x < 0.0 {
+            return 0.0;
+        }
+        if x == 0.0 {
+            return if k < 1.0 {
+                f64::INFINITY
+            } else if k == 1.0 {
+                0.5
+            } else {
+                0.0
+            };
+        }
+        ((k - 1.0) * (x / 2.0).ln() - x / 2.0 - ln_gamma(k)).exp() / 2.0
+    }
+
+    /// P(X <= x), from the regularized lower incomplete gamma function.
+    pub fn cdf(&self, x: f64) -> f64 {
+        regularized_lower_incomplete_gamma(self.df / 2.0, x / 2.0)
+    }
+
+    /// The value x with P(X <= x) = p.
+    pub fn quantile(&self, p: f64) -> f64 {
+        2.0 * inverse_regularized_lower_incomplete_gamma(self.df / 2.0, p)
+    }
+
+    /// P(X > x), the upper-tail p-value for a chi-square statistic. Computed
+    /// from the upper incomplete gamma function directly, so it stays
+    /// accurate where `1 - cdf(x)` would round to zero.
+    pub fn sf(&self, x: f64) -> f64 {
+        regularized_upper_incomplete_gamma(self.df / 2.0, x / 2.0)
+    }
+}
+
+/// The p-th quantile of the chi-square distribution with `df` degrees of
+/// freedom, NaN when p is outside [0, 1] or df is not positive.
+pub fn chi_square_quantile(p: f64, df: f64) -> f64 {
+    if !(0.0..=1.0).contains(&p) || df.is_nan() || df <= 0.0 {
+        return f64::NAN;
+    }
+    ChiSquare::new(df).quantile(p)
+}
+
+/// Beta distribution with shape parameters `a` and `b`.
+pub(crate) struct Beta {
+    a: f64,
+    b: f64,
+}
+
+impl Beta {
+    pub(crate) fn new(a: f64, b: f64) -> Self {
+        Beta { a, b }
+    }
+
+    /// The value x with P(X <= x) = p.
+    pub(crate) fn quantile(&self, p: f64) -> f64 {
+        inverse_regularized_incomplete_beta(self.a, self.b, p)
+    }
+}
+
+/// F distribution with `df1` numerator and `df2` denominator degrees of
+/// freedom, neither of which need be an integer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FisherF {
+    df1: f64,
+    df2: f64,
+}
+
+impl FisherF {
+    /// Both degrees of freedom must be positive.
+    pub fn new(df1: f64, df2: f64) -> Self {
+        FisherF { df1, df2 }
+    }
+
+    /// The probability density at `f`, infinite at 0 when df1 < 2.
+    pub fn pdf(&self, f: f64) -> f64 {
+        let (a, b) = (self.df1 / 2.0, self.df2 / 2.0);
+        if f < 0.0 {
+            return 0.0;
+        }
+        if f == 0.0 {
+            return if a < 1.0 {
+                f64::INFINITY
+            } else if a == 1.0 {
+                1.0
+            } else {
+                0.0
+            };
+        }
+        let x = self.df1 * f / (self.df1 * f + self.df2);
+        let ln_beta = ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b);
+        (a * x.ln() + b * (-x).ln_1p() - ln_beta).exp() / f
+    }
+
+    /// P(X <= f), from the regularized incomplete beta function.
+    pub fn cdf(&self, f: f64) -> f64 {
+        if f <= 0.0 {
+            return 0.0;
+        }
+        let x = self.df1 * f / (self.df1 * f + self.df2);
+        regularized_incomplete_beta(self.df1 / 2.0, self.df2 / 2.0, x)
+    }
+
+    /// The value f with P(X <= f) = p. Above the median the incomplete beta
+    /// is inverted in its complement, so upper quantiles keep full precision.
+    pub fn quantile(&self, p: f64) -> f64 {
+        let (a, b) = (self.df1 / 2.0, self.df2 / 2.0);
+        if p > 0.5 {
+            let y = inverse_regularized_incomplete_beta(b, a, 1.0 - p);
+            return self.df2 * (1.0 - y) / (self.df1 * y);
+        }
+        let x = inverse_regularized_incomplete_beta(a, b, p);
+        self.df2 * x / (self.df1 * (1.0 - x))
+    }
+
+    /// P(X > f), the upper-tail p-value for an F statistic.
+    pub fn sf(&self, f: f64) -> f64 {
+        if f <= 0.0 {
+            return 1.0;
+        }
+        let x = self.df2 / (self.df2 + self.df1 * f);
+        regularized_incomplete_beta(self.df2 / 2.0, self.df1 / 2.0, x)
+    }
+}
+
+/// The Kolmogorov distribution, the limiting distribution of sqrt(n) D for
+/// the Kolmogorov-Smirnov statistic D.
+pub(crate) struct Kolmogorov;
+
+/// Below this point the Jacobi theta form of the CDF converges faster than
+/// the alternating series for the survival function.
+const KOLMOGOROV_SERIES_CUTOFF: f64 = 1.18;
+
+impl Kolmogorov {
+    /// P(K > x).
+    pub(crate) fn sf(x: f64) -> f64 {
+        if x <= 0.0 {
+            return 1.0;
+        }
+
+        if x < KOLMOGOROV_SERIES_CUTOFF {
+            // P(K <= x) = sqrt(2 pi) / x sum exp(-(2k - 1)^2 pi^2 / (8 x^2))
+            let pi = core::f64::consts::PI;
+            let mut sum = 0.0;
+            for k in 1..=100 {
+                let odd = (2 * k - 1) as f64;
+                let term = (-odd * odd * pi * pi / (8.0 * x * x)).exp();
+                sum += term;
+                if term <= sum * 1e-16 {
+                    break;
+                }
+            }
+            return 1.0 - (2.0 * pi).sqrt() / x * sum;
+        }
+
+        // P(K > x) = 2 sum (-1)^(k - 1) exp(-2 k^2 x^2)
+        let mut sum = 0.0;
+        let mut sign = 1.0;
+        for k in 1..=100 {
+            let k = k as f64;
+            let term = (-2.0 * k * k * x * x).exp();
+            sum += sign * term;
+            if term <= sum.abs() * 1e-16 {
+                break;
+            }
+            sign = -sign;
+        }
+        2.0 * sum
+    }
+}
+
+/// Studentized range distribution for the range of `k` means with `df`
+/// degrees of freedom for the error variance, as used by Tukey's HSD.
+///
+/// The CDF is a port of R's `ptukey` (Copenhaver & Holland, 1988): an outer
+/// Gauss-Legendre integral over the error variance around an inner integral
+/// over the normal range.
+pub(crate) struct StudentizedRange {
+    k: f64,
+    df: f64,
+}
+
+/// Above this many degrees of freedom the error variance is treated as known.
+const STUDENTIZED_RANGE_LARGE_DF: f64 = 25_000.0;
+
+const RANGE_LEGENDRE_NODES: [f64; 6] = [
+    0.981560634246719,
+    0.904117256370475,
+    0.769902674194305,
+    0.587317954286617,
+    0.367831498998180,
+    0.125233408511469,
+];
+const RANGE_LEGENDRE_WEIGHTS: [f64; 6] = [
+    0.0471753363865118,
+    0.106939325995318,
+    0.160078328543346,
+    0.203167426723066,
+    0.233492536538355,
+    0.249147045813403,
+];
+
+const VARIANCE_LEGENDRE_NODES: [f64; 8] = [
+    0.989400934991650,
+    0.944575023073233,
+    0.865631202387832,
+    0.755404408355003,
+    0.617876244402644,
+    0.458016777657227,
+    0.281603550779259,
+    0.0950125098376374,
+];
+const VARIANCE_LEGENDRE_WEIGHTS: [f64; 8] = [
+    0.0271524594117541,
+    0.0622535239386479,
+    0.0951585116824928,
+    0.124628971255534,
+    0.149595988816577,
+    0.169156519395003,
+    0.182603415044924,
+    0.189450610455068,
+];
+
+impl StudentizedRange {
+    pub(crate) fn new(k: f64, df: f64) -> Self {
+        StudentizedRange { k, df }
+    }
+
+    /// P(Q <= q).
+    pub(crate) fn cdf(&self, q: f64) -> f64 {
+        if q <= 0.0 {
+            return 0.0;
+        }
+        if self.df > STUDENTIZED_RANGE_LARGE_DF {
+            return self.range_cdf(q);
+        }
+
+        let half_df = self.df / 2.0;
+        let step: f64 = if self.df <= 100.0 {
+            1.0
+        } else if self.df <= 800.0 {
+            0.5
+        } else if self.df <= 5000.0 {
+            0.25
+        } else {
+            0.125
+        };
+        let ln_front =
+            half_df * self.df.ln() - self.df * core::f64::consts::LN_2 - ln_gamma(half_df)
+                + step.ln();
+
+        let mut total = 0.0;
+        for i in 1..=50 {
+            let center = (2 * i - 1) as f64 * step;
+            let mut interval_sum = 0.0;
+            for (node, weight) in VARIANCE_LEGENDRE_NODES
+                .iter()
+                .zip(VARIANCE_LEGENDRE_WEIGHTS)
+            {
+                for u in [center - node * step, center + node * step] {
+                    let ln_density = ln_front + (half_df - 1.0) * u.ln() - u * self.df / 4.0;
+                    if ln_density >= -30.0 {
+                        let range = self.range_cdf(q * (u / 2.0).sqrt());
+                        interval_sum += range * weight * ln_density.exp();
+                    }
+                }
+            }
+            if i as f64 * step >= 1.0 && interval_sum <= 1e-14 {
+                break;
+            }
+            total += interval_sum;
+        }
+
+        total.min(1.0)
+    }
+
+    /// P(Q > q).
+    pub(crate) fn sf(&self, q: f64) -> f64 {
+        1.0 - self.cdf(q)
+    }
+
+    /// The value q with P(Q <= q) = p, found by bisection on the CDF.
+    pub(crate) fn quantile(&self, p: f64) -> f64 {
+        let mut low = 0.0;
+        let mut high = 1.0;
+        while self.cdf(high) < p {
+            low = high;
+            high *= 2.0;
+        }
+        while high - low > 1e-12 * high {
+            let mid = (low + high) / 2.0;
+            if self.cdf(mid) < p {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (low + high) / 2.0
+    }
+
+    /// P(range of k standard normals <= w), with the variance known.
+    fn range_cdf(&self, w: f64) -> f64 {
+        let normal = Normal::standard();
+        let half_w = w / 2.0;
+        if half_w >= 8.0 {
+            return 1.0;
+        }
+
+        // The probability that all k values lie within ±w/2 of zero, plus an
+        // integral over the position of the smallest value above -w/2.
+        let mut probability = (2.0 * normal.cdf(half_w) - 1.0).powf(self.k);
+        let intervals = if w > 3.0 { 2 } else { 3 };
+        let width = (8.0 - half_w) / intervals as f64;
+        let mut lower = half_w;
+        for _ in 0..intervals {
+            let center = lower + width / 2.0;
+            let half_width = width / 2.0;
+            let mut sum = 0.0;
+            for (node, weight) in RANGE_LEGENDRE_NODES.iter().zip(RANGE_LEGENDRE_WEIGHTS) {
+                for x in [center - half_width * node, center + half_width * node] {
+                    if x * x > 60.0 {
+                        continue;
+                    }
+                    let inside = normal.cdf(x) - normal.cdf(x - w);
+                    if inside >= (-30.0 / (self.k - 1.0)).exp() {
+                        sum += weight * (-0.5 * x * x).exp() * inside.powf(self.k - 1.0);
+                    }
+                }
+            }
+            probability += sum * 2.0 * half_width * self.k / (2.0 * core::f64::consts::PI).sqrt();
+            lower += width;
+        }
+
+        probability.min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn normal_two_tailed_test() {
+        let normal = Normal::standard();
+        assert_eq!(normal.two_tailed(0.0), 1.0);
+        assert_close(normal.two_tailed(1.96), 0.0499957902964409, 1e-15);
+        assert_close(normal.two_tailed(-2.5), 0.0124193306515523, 1e-15);
+        assert_close(normal.two_tailed(8.0), 1.24419211485436e-15, 1e-28);
+    }
+
+    #[test]
+    fn normal_cdf_test() {
+        let normal = Normal::new(1.0, 2.0);
+        assert_eq!(normal.cdf(1.0), 0.5);
+        assert_close(normal.cdf(4.92), 0.97500210485178, 1e-15);
+        assert_close(Normal::standard().sf(10.0), 7.61985302416053e-24, 1e-37);
+    }
+
+    #[test]
+    fn normal_quantile_test() {
+        let normal = Normal::standard();
+        assert_eq!(normal.quantile(0.5), 0.0);
+        assert_close(normal.quantile(0.975), 1.95996398454005, 1e-14);
+        assert_close(normal.quantile(0.01), -2.32634787404084, 1e-14);
+        assert_close(normal.quantile(1e-10), -6.36134090240406, 1e-12);
+        assert_close(normal.quantile(0.999), 3.09023230616781, 1e-13);
+        assert_eq!(normal.quantile(0.0), f64::NEG_INFINITY);
+        assert_close(
+            Normal::new(10.0, 2.0).quantile(0.975),
+            13.9199279690801,
+            1e-13,
+        );
+    }
+
+    #[test]
+    fn normal_pdf_test() {
+        let normal = Normal::standard();
+        assert_close(normal.pdf(0.0), 0.398942280401433, 1e-15);
+        assert_close(normal.pdf(-1.96), 0.0584409443334515, 1e-15);
+        assert_close(normal.cdf(1.96), 0.97500210485178, 1e-15);
+        // scipy.stats.norm(100, 15).pdf(130), φ(2) / 15
+        assert_close(
+            Normal::new(100.0, 15.0).pdf(130.0),
+            0.00359939776754587,
+            1e-17,
+        );
+        assert_eq!(normal.pdf(f64::INFINITY), 0.0);
+    }
+
+    #[test]
+    fn normal_round_trip_test() {
+        let normal = Normal::new(-3.0, 0.5);
+        for exponent in 1..=10 {
+            let p = 10f64.powi(-exponent);
+            for p in [p, 0.5 - p / 2.0, 1.0 - p] {
+                let x = normal.quantile(p);
+                let tail = if p > 0.5 { normal.sf(x) } else { normal.cdf(x) };
+                let p_tail = if p > 0.5 { 1.0 - p } else { p };
+                assert_close(tail, p_tail, 1e-13 * p_tail.max(1e-3));
+            }
+        }
+    }
+
+    #[test]
+    fn students_t_two_tailed_test() {
+        assert_eq!(StudentsT::new(4.0).two_tailed(0.0), 1.0);
+        assert_close(
+            StudentsT::new(5.0).two_tailed(2.0),
+            0.101939478829858,
+            1e-12,
+        );
+        assert_close(
+            StudentsT::new(10.0).two_tailed(-1.5),
+            0.16450732644544,
+            1e-12,
+        );
+        assert_close(
+            StudentsT::new(3.5).two_tailed(0.5),
+            0.646850439322551,
+            1e-12,
+        );
+        assert_close(
+            StudentsT::new(10.0).two_tailed(40.0),
+            2.28085774308575e-12,
+            1e-24,
+        );
+        assert_close(
+            StudentsT::new(30.0).two_tailed(1e3),
+            2.07200348311173e-69,
+            1e-80,
+        );
+    }
+
+    #[test]
+    fn students_t_quantile_test() {
+        // R: qt(c(0.975, 0.005, 0.9), c(7, 7, 30))
+        assert_close(StudentsT::new(7.0).quantile(0.975), 2.36462425159279, 1e-12);
+        assert_close(
+            StudentsT::new(7.0).quantile(0.005),
+            -3.49948329735049,
+            1e-11,
+        );
+        assert_close(StudentsT::new(30.0).quantile(0.9), 1.3104150253914, 1e-12);
+        assert_eq!(StudentsT::new(4.0).quantile(0.5), 0.0);
+        assert_close(StudentsT::new(7.0).cdf(2.36462425159279), 0.975, 1e-14);
+        assert_close(StudentsT::new(7.0).cdf(-3.49948329735049), 0.005, 1e-14);
+        let t = StudentsT::new(12.0).quantile(0.99);
+        assert_close(StudentsT::new(12.0).two_tailed(t), 0.02, 1e-14);
+    }
+
+    #[test]
+    fn students_t_pdf_test() {
+        // scipy.stats.t.pdf([1.3, -2.5], [4.5, 7.3])
+        assert_close(StudentsT::new(4.5).pdf(1.3), 0.157092678862801, 1e-15);
+        assert_close(StudentsT::new(7.3).pdf(-2.5), 0.0296022201730132, 1e-15);
+        // scipy.stats.t.sf([2.1, 25], [7.3, 4.5])
+        assert_close(StudentsT::new(7.3).sf(2.1), 0.0361233567124267, 1e-14);
+        assert_close(StudentsT::new(4.5).sf(25.0), 2.65112412098588e-6, 1e-18);
+        assert_eq!(StudentsT::new(3.0).quantile(0.0), f64::NEG_INFINITY);
+        assert_eq!(StudentsT::new(3.0).quantile(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn students_t_closed_form_test() {
+        let cauchy = StudentsT::new(1.0);
+        let two = StudentsT::new(2.0);
+        for t in [-30.0, -4.0, -1.0, -0.3, 0.0, 0.7, 2.5, 12.0] {
+            let pi = core::f64::consts::PI;
+            assert_close(cauchy.pdf(t), 1.0 / (pi * (1.0 + t * t)), 1e-15);
+            assert_close(cauchy.cdf(t), 0.5 + t.atan() / pi, 1e-15);
+            assert_close(two.pdf(t), (2.0 + t * t).powf(-1.5), 1e-15);
+            assert_close(two.cdf(t), 0.5 + t / (2.0 * (2.0 + t * t).sqrt()), 1e-15);
+        }
+        for p in [1e-6, 0.01, 0.2, 0.5, 0.75, 0.975, 0.999] {
+            let cauchy_quantile = -1.0 / (core::f64::consts::PI * p).tan();
+            assert_close(
+                cauchy.quantile(p),
+                cauchy_quantile,
+                1e-12 * cauchy_quantile.abs().max(1.0),
+            );
+            let two_quantile = (2.0 * p - 1.0) / (2.0 * p * (1.0 - p)).sqrt();
+            assert_close(
+                two.quantile(p),
+                two_quantile,
+                1e-12 * two_quantile.abs().max(1.0),
+            );
+        }
+    }
+
+    #[test]
+    fn students_t_large_df_test() {
+        // The t distribution differs from the normal by about x⁴ / (4 df)
+        // relative to the density.
+        let t = StudentsT::new(1e6);
+        let normal = Normal::standard();
+        assert_close(t.pdf(0.0), 0.398942180665875, 1e-13);
+        for x in [-4.0f64, -1.96, -0.5, 0.0, 1.0, 2.5, 6.0] {
+            let tolerance = (1.0 + x.powi(4)) / 1e6;
+            assert_close(t.pdf(x), normal.pdf(x), tolerance * normal.pdf(x));
+            assert_close(t.cdf(x), normal.cdf(x), tolerance * normal.cdf(x));
+            assert_close(t.sf(x), normal.sf(x), tolerance * normal.sf(x));
+        }
+        for p in [1e-8, 0.001, 0.1, 0.5, 0.9, 0.975] {
+            let z = normal.quantile(p);
+            assert_close(t.quantile(p), z, 1e-5 * z.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn noncentral_t_cdf_test() {
+        // R: pt(c(1.5, -0.5, 2), c(10, 4, 20), ncp = c(1, -2, -1))
+        assert_close(
+            NoncentralT::new(10.0, 1.0).cdf(1.5),
+            0.669516848215355,
+            1e-11,
+        );
+        assert_close(
+            NoncentralT::new(4.0, -2.0).cdf(-0.5),
+            0.934197981088116,
+            1e-11,
+        );
+        assert_close(
+            NoncentralT::new(20.0, -1.0).cdf(2.0),
+            0.997769294500427,
+            1e-11,
+        );
+        assert_close(
+            NoncentralT::new(10.0, 0.0).cdf(1.5),
+            StudentsT::new(10.0).cdf(1.5),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn chi_square_sf_test() {
+        assert_eq!(ChiSquare::new(3.0).sf(0.0), 1.0);
+        assert_close(ChiSquare::new(1.0).sf(0.5), 0.479500122186953, 1e-14);
+        assert_close(ChiSquare::new(3.0).sf(10.0), 0.0185661354630432, 1e-14);
+        assert_close(ChiSquare::new(250.0).sf(300.0), 0.0165182731578687, 1e-12);
+        assert_close(ChiSquare::new(2.0).sf(100.0), 1.92874984796392e-22, 1e-34);
+    }
+
+    #[test]
+    fn chi_square_quantile_test() {
+        assert_close(
+            ChiSquare::new(3.0).cdf(10.0),
+            1.0 - 0.0185661354630432,
+            1e-14,
+        );
+        assert_close(ChiSquare::new(1.0).quantile(0.95), 3.84145882069413, 1e-12);
+        assert_close(ChiSquare::new(9.0).quantile(0.025), 2.70038949998036, 1e-12);
+        assert_close(ChiSquare::new(9.0).quantile(0.975), 19.0227677986416, 1e-12);
+    }
+
+    #[test]
+    fn chi_square_quantiles_by_df_test() {
+        // scipy.stats.chi2.ppf([0.01, 0.5, 0.95, 0.999], df)
+        let expected = [
+            (
+                1.0,
+                [
+                    0.000157087857909702,
+                    0.454936423119573,
+                    3.84145882069412,
+                    10.8275661706627,
+                ],
+            ),
+            (
+                2.0,
+                [
+                    0.0201006717070029,
+                    1.38629436111989,
+                    5.99146454710798,
+                    13.8155105579643,
+                ],
+            ),
+            (
+                5.0,
+                [
+                    0.554298076728277,
+                    4.35146019109553,
+                    11.0704976935164,
+                    20.5150056524329,
+                ],
+            ),
+            (
+                30.0,
+                [
+                    14.9534565284554,
+                    29.3360315166616,
+                    43.7729718257422,
+                    59.7030643044299,
+                ],
+            ),
+            (
+                250.0,
+                [
+                    200.938623455286,
+                    249.333650306439,
+                    287.881500521831,
+                    324.832436568363,
+                ],
+            ),
+        ];
+        for (df, quantiles) in expected {
+            let chi_square = ChiSquare::new(df);
+            for (p, x) in [0.01, 0.5, 0.95, 0.999].into_iter().zip(quantiles) {
+                assert_close(chi_square.quantile(p), x, 1e-12 * x);
+                assert_close(chi_square.cdf(x), p, 1e-13);
+            }
+        }
+    }
+
+    #[test]
+    fn exponential_test() {
+        // R: dexp(0.7, 2.5), pexp(0.7, 2.5), qexp(0.9, 2.5), pexp(1e-12, 2.5),
+        // the last 2.5e-12 - 3.125e-24
+        let exponential = Exponential::new(2.5);
+        assert_close(exponential.pdf(0.7), 0.434434858626113, 1e-15);
+        assert_close(exponential.cdf(0.7), 0.826226056549555, 1e-15);
+        assert_close(exponential.sf(0.7), 0.173773943450445, 1e-15);
+        assert_close(exponential.quantile(0.9), 0.921034037197618, 1e-15);
+        assert_close(exponential.cdf(1e-12), 2.499999999996875e-12, 1e-26);
+        assert_eq!(exponential.pdf(-1.0), 0.0);
+        assert_eq!(exponential.cdf(-1.0), 0.0);
+        assert_eq!(exponential.sf(-1.0), 1.0);
+        assert_eq!(exponential.quantile(0.0), 0.0);
+        assert_eq!(exponential.quantile(1.0), f64::INFINITY);
+        for p in [0.01, 0.5, 0.999] {
+            assert_close(exponential.cdf(exponential.quantile(p)), p, 1e-15);
+        }
+    }
+
+    #[test]
+    fn chi_square_pdf_test() {
+        // scipy.stats.chi2.pdf([0.5, 3, 4.2, 25, 260, 2.5], [1, 2, 5, 30, 250, 3.7])
+        assert_close(ChiSquare::new(1.0).pdf(0.5), 0.439391289467722, 1e-15);
+        assert_close(ChiSquare::new(2.0).pdf(3.0), 0.111565080074215, 1e-15);
+        assert_close(ChiSquare::new(5.0).pdf(4.2), 0.140166534110895, 1e-15);
+        assert_close(ChiSquare::new(30.0).pdf(25.0), 0.0485982701182899, 1e-15);
+        assert_close(ChiSquare::new(250.0).pdf(260.0), 0.0155523921425694, 1e-14);
+        assert_close(ChiSquare::new(3.7).pdf(2.5), 0.183131400383263, 1e-15);
+        assert_eq!(ChiSquare::new(1.0).pdf(0.0), f64::INFINITY);
+        assert_eq!(ChiSquare::new(2.0).pdf(0.0), 0.5);
+        assert_eq!(ChiSquare::new(5.0).pdf(0.0), 0.0);
+        assert_eq!(ChiSquare::new(5.0).pdf(-1.0), 0.0);
+
+        // scipy.stats.chi2.cdf(2.5, 3.7), chi2.ppf(0.4027..., 3.7)
+        let fractional = ChiSquare::new(3.7);
+        assert_close(fractional.cdf(2.5), 0.402700322537442, 1e-14);
+        assert_close(fractional.quantile(0.402700322537442), 2.5, 1e-12);
+    }
+
+    #[test]
+    fn chi_square_upper_tail_test() {
+        // scipy.stats.chi2.sf([200, 1500, 60, 600], [5, 30, 1, 250]); every
+        // one of these is lost to rounding in 1 - cdf
+        let cases = [
+            (5.0, 200.0, 2.84062289864153e-41),
+            (30.0, 1500.0, 3.9605925288244e-297),
+            (1.0, 60.0, 9.48573757107385e-15),
+            (250.0, 600.0, 8.44755571184287e-31),
+        ];
+        for (df, x, expected) in cases {
+            let chi_square = ChiSquare::new(df);
+            assert_close(chi_square.sf(x), expected, 1e-12 * expected);
+        }
+        assert_eq!(1.0 - ChiSquare::new(5.0).cdf(200.0), 0.0);
+    }
+
+    #[test]
+    fn chi_square_quantile_fn_test() {
+        assert_close(chi_square_quantile(0.95, 1.0), 3.84145882069413, 1e-12);
+        assert!(chi_square_quantile(1.5, 1.0).is_nan());
+    }
+
+    #[test]
+    fn beta_test() {
+        // R: qbeta(0.025, 7, 24), qbeta(pbeta(0.3, 2.5, 4), 2.5, 4)
+        assert_close(
+            Beta::new(7.0, 24.0).quantile(0.025),
+            0.0993378649580198,
+            1e-13,
+        );
+        assert_close(Beta::new(2.5, 4.0).quantile(0.352197585906767), 0.3, 1e-13);
+    }
+
+    #[test]
+    fn fisher_f_sf_test() {
+        assert_eq!(FisherF::new(3.0, 10.0).sf(0.0), 1.0);
+        assert_close(FisherF::new(3.0, 10.0).sf(2.5), 0.119039562658278, 1e-13);
+        assert_close(FisherF::new(1.5, 7.25).sf(0.5), 0.575185837550625, 1e-13);
+        assert_close(
+            FisherF::new(2.0, 100.0).sf(30.0),
+            6.22301527786114e-11,
+            1e-22,
+        );
+    }
+
+    #[test]
+    fn fisher_f_quantile_test() {
+        assert_eq!(FisherF::new(3.0, 10.0).cdf(0.0), 0.0);
+        assert_close(
+            FisherF::new(3.0, 10.0).cdf(2.5),
+            1.0 - 0.119039562658278,
+            1e-13,
+        );
+        assert_close(
+            FisherF::new(3.0, 10.0).quantile(0.95),
+            3.70826481904684,
+            1e-12,
+        );
+        assert_close(
+            FisherF::new(5.0, 7.0).quantile(0.01),
+            0.095643341616909,
+            1e-13,
+        );
+        assert_close(FisherF::new(1.0, 1.0).quantile(0.5), 1.0, 1e-13);
+    }
+
+    #[test]
+    fn fisher_f_cdf_grid_test() {
+        // scipy.stats.f.cdf([0.2, 1, 3.5], df1, df2)
+        let expected = [
+            (
+                1.0,
+                3.0,
+                [0.314962357525707, 0.608997781044229, 0.841876331405933],
+            ),
+            (
+                1.0,
+                17.3,
+                [0.339730530124853, 0.668906765237349, 0.921617241680834],
+            ),
+            (
+                1.0,
+                200.0,
+                [0.344795210751582, 0.681481152090253, 0.937170612075516],
+            ),
+            (
+                4.5,
+                3.0,
+                [0.0662074093764867, 0.471271493785161, 0.834276440624664],
+            ),
+            (
+                4.5,
+                17.3,
+                [0.0519682221198597, 0.559068544707998, 0.974398613446889],
+            ),
+            (
+                4.5,
+                200.0,
+                [0.0482638849088652, 0.585798377898276, 0.993631963902409],
+            ),
+            (
+                12.0,
+                3.0,
+                [0.0177669976395528, 0.42622137926479, 0.834812281189614],
+            ),
+            (
+                12.0,
+                17.3,
+                [0.00351899382724017, 0.512899279504555, 0.990955908300921],
+            ),
+            (
+                12.0,
+                200.0,
+                [0.00164569462609475, 0.549655395369457, 0.99989700273827],
+            ),
+        ];
+        for (df1, df2, cdfs) in expected {
+            let f = FisherF::new(df1, df2);
+            for (x, p) in [0.2, 1.0, 3.5].into_iter().zip(cdfs) {
+                assert_close(f.cdf(x), p, 1e-13);
+                assert_close(f.sf(x), 1.0 - p, 1e-13);
+                assert_close(f.quantile(p), x, 1e-12 * x);
+            }
+        }
+    }
+
+    #[test]
+    fn fisher_f_pdf_test() {
+        // scipy.stats.f.pdf([0.5, 1.2, 2, 0.8], [1, 4.5, 12, 2], [3, 17.3, 200, 9])
+        assert_close(FisherF::new(1.0, 3.0).pdf(0.5), 0.381892310808331, 1e-14);
+        assert_close(FisherF::new(4.5, 17.3).pdf(1.2), 0.414399813413442, 1e-14);
+        assert_close(
+            FisherF::new(12.0, 200.0).pdf(2.0),
+            0.0874474681907923,
+            1e-14,
+        );
+        assert_close(FisherF::new(2.0, 9.0).pdf(0.8), 0.406585178475459, 1e-14);
+        assert_eq!(FisherF::new(1.0, 3.0).pdf(0.0), f64::INFINITY);
+        assert_eq!(FisherF::new(2.0, 3.0).pdf(0.0), 1.0);
+        assert_eq!(FisherF::new(4.0, 3.0).pdf(-1.0), 0.0);
+    }
+
+    #[test]
+    fn fisher_f_matches_squared_t_test() {
+        // If T ~ t(d) then T² ~ F(1, d), so the p-th quantile of F(1, d) is
+        // the square of the (1 + p) / 2 quantile of t(d).
+        for df in [1.0, 2.0, 3.7, 10.0, 45.5, 1000.0] {
+            let f = FisherF::new(1.0, df);
+            let t = StudentsT::new(df);
+            for p in [0.01, 0.25, 0.5, 0.9, 0.95, 0.999] {
+                let squared = t.quantile((1.0 + p) / 2.0).powi(2);
+                assert_close(f.quantile(p), squared, 1e-11 * squared);
+                assert_close(f.sf(squared), t.two_tailed(squared.sqrt()), 1e-14);
+            }
+        }
+    }
+
+    #[test]
+    fn kolmogorov_sf_test() {
+        assert_eq!(Kolmogorov::sf(0.0), 1.0);
+        assert_close(Kolmogorov::sf(0.1), 1.0, 1e-15);
+        assert_close(Kolmogorov::sf(0.5), 0.963945243664875, 1e-14);
+        assert_close(Kolmogorov::sf(1.0), 0.269999671677355, 1e-14);
+        assert_close(Kolmogorov::sf(2.0), 0.000670925255779695, 1e-16);
+        assert_close(Kolmogorov::sf(3.5), 4.57946969129111e-11, 1e-22);
+    }
+
+    #[test]
+    fn studentized_range_test() {
+        assert_eq!(StudentizedRange::new(3.0, 27.0).cdf(0.0), 0.0);
+        assert_close(
+            StudentizedRange::new(3.0, 27.0).cdf(3.5),
+            0.949509319400004,
+            1e-9,
+        );
+        assert_close(
+            StudentizedRange::new(4.0, 10.0).cdf(2.0),
+            0.481037324387624,
+            1e-9,
+        );
+        assert_close(
+            StudentizedRange::new(6.0, 5.0).cdf(5.0),
+            0.901425706602215,
+            1e-9,
+        );
+        assert_close(
+            StudentizedRange::new(2.0, 100.0).sf(3.0),
+            1.0 - 0.963633079386245,
+            1e-9,
+        );
+        // R: qtukey(0.95, 3, 27)
+        assert_close(
+            StudentizedRange::new(3.0, 27.0).quantile(0.95),
+            3.506426,
+            1e-6,
+        );
+    }
+}
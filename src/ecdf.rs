@@ -0,0 +1,119 @@
+use crate::StatsError;
+
+/// The empirical cumulative distribution function of a sample, the right
+/// continuous step function rising by 1 / n at each observation.
+pub struct Ecdf {
+    sorted: Vec<f64>,
+}
+
+impl Ecdf {
+    /// Sorts a copy of `data`, which must be non-empty and free of NaN.
+    pub fn new(data: &[f64]) -> Result<Self, StatsError> {
+        if data.is_empty() {
+            return Err(StatsError::EmptyInput);
+        }
+        if data.iter().any(|x| x.is_nan()) {
+            return Err(StatsError::InvalidParameter(
+                "empirical CDF data must not be NaN",
+            ));
+        }
+        let mut sorted = data.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        Ok(Ecdf { sorted })
+    }
+
+    /// The number of observations.
+    pub fn len(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// Always false, since an empty sample is rejected by `new`.
+    pub fn is_empty(&self) -> bool {
+        self.sorted.is_empty()
+    }
+
+    /// The observations in ascending order.
+    pub fn values(&self) -> &[f64] {
+        &self.sorted
+    }
+
+    /// The proportion of observations less than or equal to `x`, found by
+    /// binary search.
+    pub fn eval(&self, x: f64) -> f64 {
+        self.sorted.partition_point(|&value| value <= x) as f64 / self.len() as f64
+    }
+
+    /// The smallest observation x with `eval(x) >= p`, for p in [0, 1].
+    pub fn inverse(&self, p: f64) -> Result<f64, StatsError> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(StatsError::InvalidParameter(
+                "probability must be between 0 and 1",
+            ));
+        }
+        let rank = (p * self.len() as f64).ceil() as usize;
+        Ok(self.sorted[rank.max(1) - 1])
+    }
+
+    /// Each distinct observation with the value of the ECDF there, in
+    /// ascending order. Tied observations make a single, taller step.
+    pub fn steps(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        let n = self.len() as f64;
+        self.sorted
+            .chunk_by(|a, b| a == b)
+            .scan(0, move |seen, tied| {
+                *seen += tied.len();
+                Some((tied[0], *seen as f64 / n))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eval_test() {
+        let ecdf = Ecdf::new(&[3.0, 1.0, 2.0, 2.0]).unwrap();
+        assert_eq!(ecdf.len(), 4);
+        assert_eq!(ecdf.values(), [1.0, 2.0, 2.0, 3.0]);
+        assert_eq!(ecdf.eval(0.5), 0.0);
+        assert_eq!(ecdf.eval(1.0), 0.25);
+        assert_eq!(ecdf.eval(1.5), 0.25);
+        assert_eq!(ecdf.eval(2.0), 0.75);
+        assert_eq!(ecdf.eval(3.0), 1.0);
+        assert_eq!(ecdf.eval(f64::INFINITY), 1.0);
+    }
+
+    #[test]
+    fn inverse_test() {
+        let ecdf = Ecdf::new(&[3.0, 1.0, 2.0, 2.0]).unwrap();
+        assert_eq!(ecdf.inverse(0.0), Ok(1.0));
+        assert_eq!(ecdf.inverse(0.25), Ok(1.0));
+        assert_eq!(ecdf.inverse(0.26), Ok(2.0));
+        assert_eq!(ecdf.inverse(0.75), Ok(2.0));
+        assert_eq!(ecdf.inverse(1.0), Ok(3.0));
+        assert!(matches!(
+            ecdf.inverse(1.5),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn steps_test() {
+        let ecdf = Ecdf::new(&[2.0, 5.0, 2.0, 1.0, 5.0, 5.0, 4.0, 2.0]).unwrap();
+        let steps: Vec<(f64, f64)> = ecdf.steps().collect();
+        assert_eq!(steps, [(1.0, 0.125), (2.0, 0.5), (4.0, 0.625), (5.0, 1.0)]);
+        for (value, probability) in steps {
+            assert_eq!(ecdf.eval(value), probability);
+        }
+    }
+
+    #[test]
+    fn invalid_input_test() {
+        assert_eq!(Ecdf::new(&[]).err(), Some(StatsError::EmptyInput));
+        assert!(matches!(
+            Ecdf::new(&[1.0, f64::NAN]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
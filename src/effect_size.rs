@@ -0,0 +1,546 @@
+use crate::confidence::validate_confidence;
+use crate::distributions::NoncentralT;
+use crate::nonparametric::u_statistic;
+use crate::{
+    mean, sample_standard_deviation, ConfidenceInterval, PairedStatistics, SampleStatistics,
+    StatsError,
+};
+
+/// Noncentrality parameters are bisected until the bracket is this narrow.
+const NONCENTRALITY_TOLERANCE: f64 = 1e-10;
+
+/// Doublings of the bracket around the noncentrality before giving up.
+const MAX_BRACKET_EXPANSIONS: usize = 64;
+
+/// Cohen's conventional labels for the size of a standardized effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectMagnitude {
+    /// |d| < 0.2.
+    Negligible,
+    /// 0.2 <= |d| < 0.5.
+    Small,
+    /// 0.5 <= |d| < 0.8.
+    Medium,
+    /// |d| >= 0.8.
+    Large,
+}
+
+impl EffectMagnitude {
+    /// Classifies a standardized mean difference by its absolute value.
+    pub fn from_cohens_d(d: f64) -> Self {
+        let d = d.abs();
+        if d < 0.2 {
+            EffectMagnitude::Negligible
+        } else if d < 0.5 {
+            EffectMagnitude::Small
+        } else if d < 0.8 {
+            EffectMagnitude::Medium
+        } else {
+            EffectMagnitude::Large
+        }
+    }
+}
+
+/// Cohen's d for two independent samples, (x̄1 - x̄2) / s_pooled with the
+/// pooled standard deviation of the pooled t-test.
+pub fn cohens_d(samp_1: &SampleStatistics, samp_2: &SampleStatistics) -> f64 {
+    (samp_1.sample_mean - samp_2.sample_mean) / pooled_standard_deviation(samp_1, samp_2)
+}
+
+/// Cohen's d for one sample against a hypothesized mean, (x̄ - μ0) / s.
+pub fn cohens_d_one_sample(data: &[f64], mu0: f64) -> Result<f64, StatsError> {
+    if data.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: data.len(),
+        });
+    }
+    Ok((mean(data) - mu0) / sample_standard_deviation(data))
+}
+
+/// Cohen's d for paired observations: the mean of the differences
+/// `before[i] - after[i]` over their standard deviation, sometimes written d_z.
+pub fn cohens_d_paired(before: &[f64], after: &[f64]) -> Result<f64, StatsError> {
+    Ok(cohens_d_paired_from_stats(&PairedStatistics::from_arrays(
+        before, after,
+    )?))
+}
+
+/// The paired Cohen's d_z of the pairs summarized by `stats`,
+/// mean_difference / sd_difference.
+pub fn cohens_d_paired_from_stats(stats: &PairedStatistics) -> f64 {
+    stats.mean_difference / stats.sd_difference
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HedgesGResult {
+    /// The uncorrected Cohen's d.
+    pub d: f64,
+    /// The bias-corrected effect size, J * d.
+    pub g: f64,
+    /// The small-sample correction factor J.
+    pub correction: f64,
+}
+
+/// Hedges' g: Cohen's d scaled by Hedges' approximate correction
+/// J = 1 - 3 / (4 (n1 + n2) - 9), which removes most of the upward bias of d
+/// in small samples.
+pub fn hedges_g(samp_1: &SampleStatistics, samp_2: &SampleStatistics) -> HedgesGResult {
+    let d = cohens_d(samp_1, samp_2);
+    let correction = 1.0 - 3.0 / (4.0 * (samp_1.n + samp_2.n) as f64 - 9.0);
+    HedgesGResult {
+        d,
+        g: correction * d,
+        correction,
+    }
+}
+
+/// Confidence interval for the two-sample Cohen's d, found by inverting the
+/// noncentral t distribution of the pooled t statistic as R's `effectsize`
+/// package does.
+///
+/// Returns `StatsError::InsufficientData` unless both samples have two
+/// values, `StatsError::InvalidParameter` if the pooled standard deviation
+/// is zero or d is not finite, and `StatsError::NoConvergence` if the
+/// noncentral t cannot be inverted.
+pub fn cohens_d_confidence_interval(
+    samp_1: &SampleStatistics,
+    samp_2: &SampleStatistics,
+    confidence: f64,
+) -> Result<ConfidenceInterval, StatsError> {
+    for samp in [samp_1, samp_2] {
+        if samp.n < 2 {
+            return Err(StatsError::InsufficientData {
+                required: 2,
+                actual: samp.n,
+            });
+        }
+    }
+    validate_confidence(confidence)?;
+
+    if pooled_standard_deviation(samp_1, samp_2) == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "Cohen's d needs a nonzero pooled standard deviation",
+        ));
+    }
+    let d = cohens_d(samp_1, samp_2);
+    if !d.is_finite() {
+        return Err(StatsError::InvalidParameter("Cohen's d is not finite"));
+    }
+    let scale = (1.0 / samp_1.n as f64 + 1.0 / samp_2.n as f64).sqrt();
+    let t = d / scale;
+    let df = (samp_1.n + samp_2.n - 2) as f64;
+    let alpha = 1.0 - confidence;
+    Ok(ConfidenceInterval {
+        lower: noncentrality_for(t, df, 1.0 - alpha / 2.0)? * scale,
+        upper: noncentrality_for(t, df, alpha / 2.0)? * scale,
+        point_estimate: d,
+        confidence,
+    })
+}
+
+fn pooled_standard_deviation(samp_1: &SampleStatistics, samp_2: &SampleStatistics) -> f64 {
+    let n_1 = samp_1.n as f64;
+    let n_2 = samp_2.n as f64;
+    (((n_1 - 1.0) * samp_1.variance() + (n_2 - 1.0) * samp_2.variance()) / (n_1 + n_2 - 2.0)).sqrt()
+}
+
+/// The noncentrality at which an observed `t` sits at quantile `p` of the
+/// noncentral t with `df` degrees of freedom. P(T <= t) falls as the
+/// noncentrality grows, so the root is bracketed and bisected. A bracket
+/// still open after `MAX_BRACKET_EXPANSIONS` doublings, as when the CDF is
+/// NaN, returns `StatsError::NoConvergence`.
+fn noncentrality_for(t: f64, df: f64, p: f64) -> Result<f64, StatsError> {
+    let below = |ncp| NoncentralT::new(df, ncp).cdf(t) > p;
+    let expand = |inside: &dyn Fn(f64) -> bool, direction: f64| {
+        let mut width = 1.0;
+        for _ in 0..MAX_BRACKET_EXPANSIONS {
+            let end = t + direction * width;
+            if !inside(end) {
+                return Ok(end);
+            }
+            width *= 2.0;
+        }
+        Err(StatsError::NoConvergence {
+            iterations: MAX_BRACKET_EXPANSIONS,
+        })
+    };
+    let mut low = expand(&|ncp| !below(ncp), -1.0)?;
+    let mut high = expand(&below, 1.0)?;
+    while high - low > NONCENTRALITY_TOLERANCE {
+        let middle = 0.5 * (low + high);
+        if below(middle) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    Ok(0.5 * (low + high))
+}
+
+/// The common-language effect size, or probability of superiority, of
+/// `sample_1` over `sample_2`: P(X > Y) + P(X = Y) / 2 for X drawn from
+/// `sample_1` and Y from `sample_2`. This is the Mann-Whitney U divided by
+/// n1 n2, computed from midranks in O(n log n) rather than by comparing
+/// every pair; ties are counted exactly. Returns `StatsError::EmptyInput`
+/// if either sample is empty.
+pub fn common_language_effect_size(sample_1: &[f64], sample_2: &[f64]) -> Result<f64, StatsError> {
+    if sample_1.is_empty() || sample_2.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    let pooled: Vec<f64> = sample_1.iter().chain(sample_2).copied().collect();
+    let pairs = sample_1.len() as f64 * sample_2.len() as f64;
+    Ok(u_statistic(&pooled, sample_1.len()) / pairs)
+}
+
+/// The rank-biserial correlation 2 CL - 1 of `sample_1` against
+/// `sample_2`, between -1 (every value of `sample_1` is smaller) and 1.
+pub fn rank_biserial_correlation(sample_1: &[f64], sample_2: &[f64]) -> Result<f64, StatsError> {
+    Ok(2.0 * common_language_effect_size(sample_1, sample_2)? - 1.0)
+}
+
+/// Area under the ROC curve of a classifier that gives `scores_pos` to the
+/// positive cases and `scores_neg` to the negative ones. It is the
+/// common-language effect size of the positives over the negatives, with
+/// tied scores counting one half as in sklearn's `roc_auc_score`.
+pub fn roc_auc(scores_pos: &[f64], scores_neg: &[f64]) -> Result<f64, StatsError> {
+    common_language_effect_size(scores_pos, scores_neg)
+}
+
+/// Cohen's f, sqrt(η² / (1 - η²)), the effect size of an ANOVA: the
+/// standard deviation of the group means over the common within-group
+/// standard deviation. Returns `StatsError::InvalidParameter` unless
+/// 0 <= `eta_squared` < 1.
+pub fn cohens_f_from_eta_squared(eta_squared: f64) -> Result<f64, StatsError> {
+    if !(0.0..1.0).contains(&eta_squared) {
+        return Err(StatsError::InvalidParameter(
+            "eta squared must be in [0, 1)",
+        ));
+    }
+    Ok((eta_squared / (1.0 - eta_squared)).sqrt())
+}
+
+/// η² = F df1 / (F df1 + df2) recovered from a reported F statistic `f`
+/// with `df1` and `df2` degrees of freedom, as `effectsize::F_to_eta2`.
+/// For a one-way ANOVA this is exactly `AnovaResult::eta_squared`. Returns
+/// `StatsError::InvalidParameter` if `f` is negative or a degree of freedom
+/// is not positive.
+pub fn eta_squared_from_f(f: f64, df1: f64, df2: f64) -> Result<f64, StatsError> {
+    if !(0.0..f64::INFINITY).contains(&f) {
+        return Err(StatsError::InvalidParameter(
+            "F statistic must be finite and non-negative",
+        ));
+    }
+    if !(df1 > 0.0 && df2 > 0.0) {
+        return Err(StatsError::InvalidParameter(
+            "degrees of freedom must be positive",
+        ));
+    }
+    Ok(f * df1 / (f * df1 + df2))
+}
+
+/// The point-biserial correlation d / sqrt(d² + 4) matching Cohen's `d`
+/// for two groups of equal size, as `effectsize::d_to_r`.
+pub fn d_to_r(d: f64) -> f64 {
+    d / (d * d + 4.0).sqrt()
+}
+
+/// Cohen's d = 2 r / sqrt(1 - r²) for a point-biserial correlation `r`,
+/// the inverse of `d_to_r`. Returns `StatsError::InvalidParameter` unless
+/// -1 < `r` < 1.
+pub fn r_to_d(r: f64) -> Result<f64, StatsError> {
+    if r.is_nan() || r.abs() >= 1.0 {
+        return Err(StatsError::InvalidParameter(
+            "correlation must be strictly between -1 and 1",
+        ));
+    }
+    Ok(2.0 * r / (1.0 - r * r).sqrt())
+}
+
+/// Cohen's d = t sqrt(1 / n1 + 1 / n2) from the pooled two-sample t
+/// statistic of groups of sizes `n1` and `n2`, which gives back `cohens_d`
+/// exactly. Returns `StatsError::InsufficientData` if either group has
+/// fewer than two observations.
+pub fn d_from_t(t: f64, n1: usize, n2: usize) -> Result<f64, StatsError> {
+    for n in [n1, n2] {
+        if n < 2 {
+            return Err(StatsError::InsufficientData {
+                required: 2,
+                actual: n,
+            });
+        }
+    }
+    Ok(t * (1.0 / n1 as f64 + 1.0 / n2 as f64).sqrt())
+}
+
+/// Cohen's d = ln(OR) sqrt(3) / π for an odds ratio, treating the log odds
+/// ratio as a difference between logistic distributions (Chinn's
+/// conversion, `effectsize::oddsratio_to_d`). Returns
+/// `StatsError::InvalidParameter` unless `odds_ratio` is positive and
+/// finite.
+pub fn odds_ratio_to_d(odds_ratio: f64) -> Result<f64, StatsError> {
+    if !(odds_ratio > 0.0 && odds_ratio.is_finite()) {
+        return Err(StatsError::InvalidParameter(
+            "odds ratio must be positive and finite",
+        ));
+    }
+    Ok(odds_ratio.ln() * 3f64.sqrt() / core::f64::consts::PI)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, GetStatistics};
+
+    const SLEEP_1: [f64; 10] = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+    const SLEEP_2: [f64; 10] = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+
+    #[test]
+    fn cohens_d_test() {
+        // R: effectsize::cohens_d(extra ~ group, data = sleep)
+        let samp_1 = SampleStatistics::from_array(&SLEEP_1);
+        let samp_2 = SampleStatistics::from_array(&SLEEP_2);
+        assert_close(cohens_d(&samp_1, &samp_2), -0.83218108134954, 1e-13);
+
+        let interval = cohens_d_confidence_interval(&samp_1, &samp_2, 0.95).unwrap();
+        assert_close(interval.lower, -1.73881689910673, 1e-8);
+        assert_close(interval.upper, 0.0954503982114156, 1e-8);
+        assert_close(interval.point_estimate, -0.83218108134954, 1e-13);
+
+        let constant_1 = SampleStatistics::from_array(&[1.0, 1.0]);
+        let constant_2 = SampleStatistics::from_array(&[2.0, 2.0]);
+        assert!(matches!(
+            cohens_d_confidence_interval(&constant_1, &constant_2, 0.95),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn hedges_g_test() {
+        // R: effectsize::hedges_g(extra ~ group, data = sleep), approximate J
+        let samp_1 = SampleStatistics::from_array(&SLEEP_1);
+        let samp_2 = SampleStatistics::from_array(&SLEEP_2);
+        let result = hedges_g(&samp_1, &samp_2);
+        assert_close(result.d, -0.83218108134954, 1e-13);
+        assert_close(result.g, -0.797018500447447, 1e-13);
+        assert_close(result.correction, 68.0 / 71.0, 1e-15);
+        assert!(result.g.abs() < result.d.abs());
+
+        let large = |sample_mean| SampleStatistics {
+            sample_mean,
+            standard_deviation: 2.0,
+            n: 5000,
+        };
+        let result = hedges_g(&large(1.0), &large(0.0));
+        assert_close(result.d, 0.5, 1e-15);
+        assert_close(result.g, result.d, 1e-4);
+    }
+
+    #[test]
+    fn cohens_d_one_sample_and_paired_test() {
+        assert_close(
+            cohens_d_one_sample(&SLEEP_1, 0.5).unwrap(),
+            0.139742118727933,
+            1e-13,
+        );
+        assert_close(
+            cohens_d_paired(&SLEEP_1, &SLEEP_2).unwrap(),
+            -1.28455756259105,
+            1e-13,
+        );
+        let stats = PairedStatistics::from_arrays(&SLEEP_1, &SLEEP_2).unwrap();
+        assert_eq!(
+            cohens_d_paired_from_stats(&stats),
+            cohens_d_paired(&SLEEP_1, &SLEEP_2).unwrap()
+        );
+        assert_eq!(
+            cohens_d_paired(&SLEEP_1, &SLEEP_2[..4]),
+            Err(StatsError::MismatchedLengths { left: 10, right: 4 })
+        );
+        assert_eq!(
+            cohens_d_one_sample(&[1.0], 0.0),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn effect_magnitude_test() {
+        assert_eq!(
+            EffectMagnitude::from_cohens_d(0.1),
+            EffectMagnitude::Negligible
+        );
+        assert_eq!(EffectMagnitude::from_cohens_d(-0.2), EffectMagnitude::Small);
+        assert_eq!(
+            EffectMagnitude::from_cohens_d(0.65),
+            EffectMagnitude::Medium
+        );
+        assert_eq!(
+            EffectMagnitude::from_cohens_d(-0.83),
+            EffectMagnitude::Large
+        );
+    }
+
+    #[test]
+    fn common_language_effect_size_test() {
+        // R: wilcox.test(extra ~ group, data = sleep)$statistic / 100, and
+        // effectsize::rank_biserial(extra ~ group, data = sleep)
+        assert_close(
+            common_language_effect_size(&SLEEP_1, &SLEEP_2).unwrap(),
+            0.255,
+            1e-15,
+        );
+        assert_close(
+            rank_biserial_correlation(&SLEEP_1, &SLEEP_2).unwrap(),
+            -0.49,
+            1e-15,
+        );
+        assert_close(
+            common_language_effect_size(&SLEEP_2, &SLEEP_1).unwrap(),
+            0.745,
+            1e-15,
+        );
+
+        let result =
+            crate::mann_whitney_u(&SLEEP_1, &SLEEP_2, crate::Alternative::TwoSided).unwrap();
+        assert_close(result.common_language_effect_size, result.u / 100.0, 1e-15);
+        assert_close(result.rank_biserial(), -0.49, 1e-15);
+
+        assert_eq!(
+            common_language_effect_size(&[1.0, 2.0], &[1.0, 2.0]),
+            Ok(0.5)
+        );
+        assert_eq!(
+            common_language_effect_size(&[3.0, 4.0], &[1.0, 2.0]),
+            Ok(1.0)
+        );
+        assert_eq!(rank_biserial_correlation(&[1.0], &[3.0, 4.0]), Ok(-1.0));
+        assert_eq!(
+            common_language_effect_size(&[], &[1.0]),
+            Err(StatsError::EmptyInput)
+        );
+        assert_eq!(roc_auc(&[1.0], &[]), Err(StatsError::EmptyInput));
+    }
+
+    #[test]
+    fn roc_auc_test() {
+        // Scores rounded to one decimal, so most of them are tied.
+        const LABELS: [u8; 20] = [1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 0, 0, 0, 1, 0, 1, 1, 0, 0, 1];
+        const SCORES: [f64; 20] = [
+            0.9, 0.3, 0.7, 0.7, 0.5, 0.1, 0.3, 0.7, 0.9, 0.5, 0.3, 0.5, 0.1, 0.7, 0.3, 0.5, 0.9,
+            0.7, 0.1, 0.3,
+        ];
+        let (pos, neg): (Vec<_>, Vec<_>) = LABELS.iter().zip(SCORES).partition(|(&l, _)| l == 1);
+        let pos: Vec<f64> = pos.into_iter().map(|(_, s)| s).collect();
+        let neg: Vec<f64> = neg.into_iter().map(|(_, s)| s).collect();
+        // sklearn: roc_auc_score(LABELS, SCORES)
+        assert_close(roc_auc(&pos, &neg).unwrap(), 0.8, 1e-15);
+
+        // Agrees with counting every pair.
+        let mut rng = crate::rng::Rng::new(121);
+        let x: Vec<f64> = (0..37).map(|_| rng.below(6) as f64).collect();
+        let y: Vec<f64> = (0..23).map(|_| rng.below(6) as f64).collect();
+        let mut wins = 0.0;
+        for a in &x {
+            for b in &y {
+                wins += if a > b {
+                    1.0
+                } else if a == b {
+                    0.5
+                } else {
+                    0.0
+                };
+            }
+        }
+        assert_close(
+            common_language_effect_size(&x, &y).unwrap(),
+            wins / (37.0 * 23.0),
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn effect_size_conversion_test() {
+        // R: effectsize::d_to_r(0.5), r_to_d(0.3), oddsratio_to_d(c(2, 0.25))
+        assert_close(d_to_r(0.5), 0.242535625036333, 1e-15);
+        assert_close(r_to_d(0.3).unwrap(), 0.628970902033151, 1e-15);
+        assert_close(odds_ratio_to_d(2.0).unwrap(), 0.382152069422844, 1e-15);
+        assert_close(odds_ratio_to_d(0.25).unwrap(), -0.764304138845688, 1e-15);
+        assert_eq!(odds_ratio_to_d(1.0).unwrap(), 0.0);
+        assert_eq!(d_to_r(0.0), 0.0);
+
+        // Cohen's medium ANOVA effect, f = 0.25, is η² = 1 / 17
+        assert_close(cohens_f_from_eta_squared(1.0 / 17.0).unwrap(), 0.25, 1e-15);
+        assert_eq!(cohens_f_from_eta_squared(0.0).unwrap(), 0.0);
+
+        // R: effectsize::F_to_eta2(4.846088, 2, 27) and
+        // effectsize::cohens_f(aov(weight ~ group, data = PlantGrowth))
+        let eta_squared = eta_squared_from_f(4.84608786238013, 2.0, 27.0).unwrap();
+        assert_close(eta_squared, 0.26414829683212, 1e-12);
+        assert_close(
+            cohens_f_from_eta_squared(eta_squared).unwrap(),
+            0.599140610614418,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn effect_size_round_trip_test() {
+        for d in [-3.0, -0.8, -0.2, 0.0, 0.1, 0.5, 1.7, 4.0] {
+            assert_close(r_to_d(d_to_r(d)).unwrap(), d, 1e-13);
+        }
+        for r in [-0.95, -0.3, 0.0, 0.45, 0.99] {
+            assert_close(d_to_r(r_to_d(r).unwrap()), r, 1e-15);
+        }
+        for eta_squared in [0.0, 0.01, 0.3, 0.9] {
+            let f = cohens_f_from_eta_squared(eta_squared).unwrap();
+            assert_close(f * f / (1.0 + f * f), eta_squared, 1e-15);
+        }
+
+        // The pooled t statistic converts back to Cohen's d
+        let samp_1 = SampleStatistics::from_array(&SLEEP_1);
+        let samp_2 = SampleStatistics::from_array(&SLEEP_2);
+        let t = crate::two_samp_t_test_with(&samp_1, &samp_2, crate::TTestKind::EqualVariance).t;
+        assert_close(
+            d_from_t(t, 10, 10).unwrap(),
+            cohens_d(&samp_1, &samp_2),
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn effect_size_conversion_invalid_input_test() {
+        for eta_squared in [1.0, 1.5, -0.1, f64::NAN] {
+            assert!(matches!(
+                cohens_f_from_eta_squared(eta_squared),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        for r in [1.0, -1.0, 2.0, f64::NAN] {
+            assert!(matches!(r_to_d(r), Err(StatsError::InvalidParameter(_))));
+        }
+        for odds_ratio in [0.0, -1.0, f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                odds_ratio_to_d(odds_ratio),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        assert!(matches!(
+            eta_squared_from_f(-1.0, 2.0, 27.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            eta_squared_from_f(4.0, 0.0, 27.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            d_from_t(2.0, 1, 10).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::confidence::validate_confidence;
+use crate::distributions::Normal;
+use crate::{ConfidenceInterval, StatsError};
+
+/// Counts of a 2x2 exposure–outcome table. The fields are named so the
+/// orientation is explicit at construction:
+///
+/// |           | case                | non-case                |
+/// |-----------|---------------------|-------------------------|
+/// | exposed   | `exposed_cases`     | `exposed_non_cases`     |
+/// | unexposed | `unexposed_cases`   | `unexposed_non_cases`   |
+///
+/// Ratios compare the exposed group to the unexposed reference group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwoByTwoTable {
+    pub exposed_cases: u64,
+    pub exposed_non_cases: u64,
+    pub unexposed_cases: u64,
+    pub unexposed_non_cases: u64,
+}
+
+/// A ratio measure with its log-scale Wald interval.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatioEstimate {
+    pub estimate: f64,
+    pub confidence_interval: ConfidenceInterval,
+    /// Whether 0.5 was added to every cell because one of them was zero.
+    pub corrected: bool,
+}
+
+impl TwoByTwoTable {
+    /// Odds ratio (a d) / (b c) of the outcome among the exposed relative to
+    /// the unexposed, with the Woolf interval exp(ln OR ± z sqrt(1/a + 1/b +
+    /// 1/c + 1/d)), as R's `epitools::oddsratio.wald`.
+    pub fn odds_ratio(&self, confidence: f64) -> Result<RatioEstimate, StatsError> {
+        validate_confidence(confidence)?;
+        let (cells, corrected) = self.corrected_cells()?;
+        let [a, b, c, d] = cells;
+        let estimate = a * d / (b * c);
+        let standard_error = (1.0 / a + 1.0 / b + 1.0 / c + 1.0 / d).sqrt();
+        Ok(ratio_estimate(
+            estimate,
+            standard_error,
+            confidence,
+            corrected,
+        ))
+    }
+
+    /// Relative risk (a / (a + b)) / (c / (c + d)) of the outcome among the
+    /// exposed relative to the unexposed, with the log-scale Wald interval of
+    /// R's `epitools::riskratio.wald`.
+    pub fn relative_risk(&self, confidence: f64) -> Result<RatioEstimate, StatsError> {
+        validate_confidence(confidence)?;
+        let (cells, corrected) = self.corrected_cells()?;
+        let [a, b, c, d] = cells;
+        let estimate = (a / (a + b)) / (c / (c + d));
+        let standard_error = (1.0 / a - 1.0 / (a + b) + 1.0 / c - 1.0 / (c + d)).sqrt();
+        Ok(ratio_estimate(
+            estimate,
+            standard_error,
+            confidence,
+            corrected,
+        ))
+    }
+
+    /// The cells as [a, b, c, d], with the Haldane–Anscombe correction of
+    /// adding 0.5 to each when any is zero.
+    fn corrected_cells(&self) -> Result<([f64; 4], bool), StatsError> {
+        if self.exposed_cases + self.exposed_non_cases == 0
+            || self.unexposed_cases + self.unexposed_non_cases == 0
+        {
+            return Err(StatsError::InvalidParameter(
+                "both exposure groups must have observations",
+            ));
+        }
+        let cells = [
+            self.exposed_cases,
+            self.exposed_non_cases,
+            self.unexposed_cases,
+            self.unexposed_non_cases,
+        ];
+        let corrected = cells.contains(&0);
+        let offset = if corrected { 0.5 } else { 0.0 };
+        Ok((cells.map(|count| count as f64 + offset), corrected))
+    }
+}
+
+fn ratio_estimate(
+    estimate: f64,
+    standard_error: f64,
+    confidence: f64,
+    corrected: bool,
+) -> RatioEstimate {
+    let margin = Normal::standard().quantile(0.5 + confidence / 2.0) * standard_error;
+    RatioEstimate {
+        estimate,
+        confidence_interval: ConfidenceInterval {
+            lower: (estimate.ln() - margin).exp(),
+            upper: (estimate.ln() + margin).exp(),
+            point_estimate: estimate,
+            confidence,
+        },
+        corrected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const TABLE: TwoByTwoTable = TwoByTwoTable {
+        exposed_cases: 15,
+        exposed_non_cases: 85,
+        unexposed_cases: 5,
+        unexposed_non_cases: 95,
+    };
+
+    #[test]
+    fn odds_ratio_test() {
+        // R: epitools::oddsratio.wald(matrix(c(95, 85, 5, 15), 2))
+        let result = TABLE.odds_ratio(0.95).unwrap();
+        assert_close(result.estimate, 3.35294117647059, 1e-13);
+        assert_close(result.confidence_interval.lower, 1.16913423257348, 1e-12);
+        assert_close(result.confidence_interval.upper, 9.61584582817814, 1e-12);
+        assert!(!result.corrected);
+    }
+
+    #[test]
+    fn relative_risk_test() {
+        // R: epitools::riskratio.wald(matrix(c(95, 85, 5, 15), 2))
+        let result = TABLE.relative_risk(0.95).unwrap();
+        assert_close(result.estimate, 3.0, 1e-14);
+        assert_close(result.confidence_interval.lower, 1.13335859618979, 1e-12);
+        assert_close(result.confidence_interval.upper, 7.9409994597093, 1e-12);
+        assert!(!result.corrected);
+    }
+
+    #[test]
+    fn zero_cell_correction_test() {
+        let table = TwoByTwoTable {
+            exposed_cases: 0,
+            exposed_non_cases: 20,
+            unexposed_cases: 6,
+            unexposed_non_cases: 14,
+        };
+        let odds = table.odds_ratio(0.95).unwrap();
+        assert!(odds.corrected);
+        assert_close(odds.estimate, 0.0544090056285178, 1e-15);
+        assert_close(odds.confidence_interval.lower, 0.00283632945901124, 1e-15);
+        assert_close(odds.confidence_interval.upper, 1.04372215437768, 1e-12);
+
+        let risk = table.relative_risk(0.95).unwrap();
+        assert!(risk.corrected);
+        assert_close(risk.estimate, 0.0769230769230769, 1e-15);
+        assert_close(risk.confidence_interval.upper, 1.28040650648675, 1e-12);
+
+        let empty_group = TwoByTwoTable {
+            unexposed_cases: 0,
+            unexposed_non_cases: 0,
+            ..table
+        };
+        assert!(matches!(
+            empty_group.odds_ratio(0.95),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
@@ -0,0 +1,70 @@
+use core::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatsError {
+    /// The input contained no observations.
+    EmptyInput,
+    /// The input had fewer observations than the computation requires.
+    InsufficientData { required: usize, actual: usize },
+    /// One group of several had fewer observations than the computation
+    /// requires.
+    InsufficientGroupData {
+        group: usize,
+        required: usize,
+        actual: usize,
+    },
+    /// Two inputs that must be the same length were not.
+    MismatchedLengths { left: usize, right: usize },
+    /// A parameter was outside its valid range.
+    InvalidParameter(&'static str),
+    /// An iterative fit did not converge within its iteration limit.
+    NoConvergence { iterations: usize },
+    /// A row of a table did not have the total every row must share.
+    InconsistentRow {
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// The input contained a NaN at this index, under `NanPolicy::Error`.
+    NanInInput { index: usize },
+    /// A value that must be a probability was NaN or outside [0, 1].
+    InvalidProbability { index: usize },
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatsError::EmptyInput => write!(f, "input contains no observations"),
+            StatsError::InsufficientData { required, actual } => write!(
+                f,
+                "at least {required} observations are required, got {actual}"
+            ),
+            StatsError::InsufficientGroupData {
+                group,
+                required,
+                actual,
+            } => write!(
+                f,
+                "group {group} needs at least {required} observations, got {actual}"
+            ),
+            StatsError::MismatchedLengths { left, right } => {
+                write!(f, "input lengths differ: {left} and {right}")
+            }
+            StatsError::InvalidParameter(reason) => write!(f, "invalid parameter: {reason}"),
+            StatsError::NoConvergence { iterations } => {
+                write!(f, "failed to converge after {iterations} iterations")
+            }
+            StatsError::InconsistentRow {
+                row,
+                expected,
+                actual,
+            } => write!(f, "row {row} totals {actual}, expected {expected}"),
+            StatsError::NanInInput { index } => write!(f, "input contains NaN at index {index}"),
+            StatsError::InvalidProbability { index } => {
+                write!(f, "value at index {index} is not a probability in [0, 1]")
+            }
+        }
+    }
+}
+
+impl core::error::Error for StatsError {}
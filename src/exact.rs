@@ -0,0 +1,991 @@
+use std::fmt;
+
+use crate::combinatorics::{binomial_probability, hypergeometric_probability, ln_factorial};
+use crate::distributions::{Beta, Normal};
+use crate::format::{p_value, write_test_line};
+use crate::special::{
+    inverse_regularized_lower_incomplete_gamma, regularized_incomplete_beta,
+    regularized_lower_incomplete_gamma, regularized_upper_incomplete_gamma,
+};
+use crate::{Alternative, ConfidenceInterval, HypothesisTest, PValueMethod, StatsError};
+
+/// Tolerance used when comparing probabilities for the two-sided p-value, so
+/// outcomes as likely as the observed one are not lost to rounding.
+const RELATIVE_ERROR: f64 = 1.0 + 1e-7;
+
+/// Total probability of the outcomes as likely as the observed one, up to
+/// `RELATIVE_ERROR`, the observed one included. The two-sided mid-p value
+/// counts these outcomes at half weight.
+fn tied_probability(observed: f64, probabilities: impl IntoIterator<Item = f64>) -> f64 {
+    probabilities
+        .into_iter()
+        .filter(|&p| p <= observed * RELATIVE_ERROR && p * RELATIVE_ERROR >= observed)
+        .sum()
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinomialTestResult {
+    pub successes: usize,
+    pub n: usize,
+    /// Observed proportion of successes.
+    pub estimate: f64,
+    pub p_value: f64,
+    pub method: PValueMethod,
+}
+
+impl fmt::Display for BinomialTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} successes, {}",
+            self.successes,
+            self.n,
+            p_value(self.p_value)
+        )
+    }
+}
+
+impl HypothesisTest for BinomialTestResult {
+    fn statistic(&self) -> f64 {
+        self.successes as f64
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        match self.method {
+            PValueMethod::Exact => "exact binomial test",
+            PValueMethod::MidP => "mid-p binomial test",
+            PValueMethod::Asymptotic => "binomial test, normal approximation",
+        }
+    }
+}
+
+/// Exact binomial test of H0: p = p0. The two-sided p-value sums the
+/// probabilities of all outcomes no more likely than the observed one, as R's
+/// `binom.test` does.
+pub fn binomial_test(
+    successes: usize,
+    n: usize,
+    p0: f64,
+    alternative: Alternative,
+) -> Result<BinomialTestResult, StatsError> {
+    binomial_test_with(successes, n, p0, alternative, PValueMethod::Exact)
+}
+
+/// Binomial test with an explicit choice of p-value. The mid-p value counts
+/// the observed count at half its probability, and two-sided also every
+/// count as likely as it, so it is P(f(X) < f(x)) + P(f(X) = f(x)) / 2 for
+/// the binomial pmf f. The
+/// asymptotic p-value refers z = (x - n p0) / sqrt(n p0 (1 - p0)) to the
+/// standard normal, as R's `prop.test` with `correct = FALSE`.
+pub fn binomial_test_with(
+    successes: usize,
+    n: usize,
+    p0: f64,
+    alternative: Alternative,
+    method: PValueMethod,
+) -> Result<BinomialTestResult, StatsError> {
+    if n == 0 {
+        return Err(StatsError::EmptyInput);
+    }
+    if successes > n {
+        return Err(StatsError::InvalidParameter(
+            "successes must not exceed the number of trials",
+        ));
+    }
+    if !(p0 > 0.0 && p0 < 1.0) {
+        return Err(StatsError::InvalidParameter(
+            "p0 must be strictly between 0 and 1",
+        ));
+    }
+
+    let exact = || match alternative {
+        Alternative::Less => binomial_cdf(successes, n, p0),
+        Alternative::Greater => binomial_sf(successes, n, p0),
+        Alternative::TwoSided => binomial_two_sided(successes, n, p0),
+    };
+    let p_value = match method {
+        PValueMethod::Exact => exact(),
+        PValueMethod::MidP => {
+            let observed = binomial_probability(successes as u64, n as u64, p0);
+            let tied = match alternative {
+                Alternative::TwoSided => tied_probability(
+                    observed,
+                    (0..=n as u64).map(|i| binomial_probability(i, n as u64, p0)),
+                ),
+                Alternative::Less | Alternative::Greater => observed,
+            };
+            exact() - tied / 2.0
+        }
+        PValueMethod::Asymptotic => {
+            let expected = n as f64 * p0;
+            let z = (successes as f64 - expected) / (expected * (1.0 - p0)).sqrt();
+            Normal::standard().p_value(z, alternative)
+        }
+    };
+
+    Ok(BinomialTestResult {
+        successes,
+        n,
+        estimate: successes as f64 / n as f64,
+        p_value,
+        method,
+    })
+}
+
+fn binomial_two_sided(successes: usize, n: usize, p0: f64) -> f64 {
+    let expected = n as f64 * p0;
+    if successes as f64 == expected {
+        return 1.0;
+    }
+
+    let threshold = binomial_probability(successes as u64, n as u64, p0) * RELATIVE_ERROR;
+    let p_value = if (successes as f64) < expected {
+        let start = expected.ceil() as usize;
+        let count = (start..=n)
+            .filter(|&i| binomial_probability(i as u64, n as u64, p0) <= threshold)
+            .count();
+        binomial_cdf(successes, n, p0) + binomial_sf(n + 1 - count, n, p0)
+    } else {
+        let end = expected.floor() as usize;
+        let count = (0..=end)
+            .filter(|&i| binomial_probability(i as u64, n as u64, p0) <= threshold)
+            .count();
+        let lower = if count == 0 {
+            0.0
+        } else {
+            binomial_cdf(count - 1, n, p0)
+        };
+        lower + binomial_sf(successes, n, p0)
+    };
+
+    p_value.min(1.0)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FisherExactResult {
+    pub p_value: f64,
+    /// Sample odds ratio (a * d) / (b * c) for the table [[a, b], [c, d]].
+    pub odds_ratio: f64,
+    pub method: PValueMethod,
+}
+
+impl fmt::Display for FisherExactResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "odds ratio", &[], self.odds_ratio, self.p_value)
+    }
+}
+
+impl HypothesisTest for FisherExactResult {
+    fn statistic(&self) -> f64 {
+        self.odds_ratio
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        match self.method {
+            PValueMethod::Exact => "Fisher's exact test",
+            PValueMethod::MidP => "Fisher's exact test, mid-p",
+            PValueMethod::Asymptotic => "2x2 chi-square test",
+        }
+    }
+}
+
+/// Fisher's exact test for a 2x2 table [[a, b], [c, d]], conditioning on the
+/// row and column margins. `Alternative::Greater` tests whether the odds ratio
+/// exceeds one. The two-sided p-value sums the probabilities of all tables no
+/// more likely than the observed one, as R's `fisher.test` does.
+pub fn fishers_exact(
+    table: [[u64; 2]; 2],
+    alternative: Alternative,
+) -> Result<FisherExactResult, StatsError> {
+    fishers_exact_with(table, alternative, PValueMethod::Exact)
+}
+
+/// Fisher's test with an explicit choice of p-value. The mid-p value counts
+/// the observed table at half its probability, and two-sided also every
+/// table as likely as it, so it is P(f(X) < f(a)) + P(f(X) = f(a)) / 2 for
+/// the hypergeometric pmf f. The asymptotic
+/// p-value refers the signed square root of Pearson's uncorrected
+/// chi-square, (a d - b c) sqrt(n / (r1 r2 c1 c2)) for row and column totals
+/// r and c, to the standard normal; it is 1 when a margin is zero, as then
+/// no other table is possible.
+pub fn fishers_exact_with(
+    table: [[u64; 2]; 2],
+    alternative: Alternative,
+    method: PValueMethod,
+) -> Result<FisherExactResult, StatsError> {
+    let [[a, b], [c, d]] = table;
+    let n = a + b + c + d;
+    if n == 0 {
+        return Err(StatsError::EmptyInput);
+    }
+
+    let row_1 = a + b;
+    let col_1 = a + c;
+    let lowest = (row_1 + col_1).saturating_sub(n);
+    let highest = row_1.min(col_1);
+    let probability = |x: u64| hypergeometric_probability(x, row_1, col_1, n);
+
+    let exact = || match alternative {
+        Alternative::Less => (lowest..=a).map(probability).sum(),
+        Alternative::Greater => (a..=highest).map(probability).sum(),
+        Alternative::TwoSided => {
+            let threshold = probability(a) * RELATIVE_ERROR;
+            (lowest..=highest)
+                .map(probability)
+                .filter(|&p| p <= threshold)
+                .sum::<f64>()
+        }
+    };
+    let p_value = match method {
+        PValueMethod::Exact => exact(),
+        PValueMethod::MidP => {
+            let tied = match alternative {
+                Alternative::TwoSided => {
+                    tied_probability(probability(a), (lowest..=highest).map(probability))
+                }
+                Alternative::Less | Alternative::Greater => probability(a),
+            };
+            exact() - tied / 2.0
+        }
+        PValueMethod::Asymptotic => {
+            let margins = [row_1, c + d, col_1, b + d].map(|m| m as f64);
+            let product: f64 = margins.iter().product();
+            if product == 0.0 {
+                1.0
+            } else {
+                let z = (a as f64 * d as f64 - b as f64 * c as f64) * (n as f64 / product).sqrt();
+                Normal::standard().p_value(z, alternative)
+            }
+        }
+    };
+
+    let odds_ratio = (a as f64 * d as f64) / (b as f64 * c as f64);
+
+    Ok(FisherExactResult {
+        p_value: f64::min(p_value, 1.0),
+        odds_ratio,
+        method,
+    })
+}
+
+/// Confidence level of the interval `poisson_test` and `poisson_two_sample`
+/// report, as R's `poisson.test` does by default.
+const POISSON_TEST_CONFIDENCE: f64 = 0.95;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoissonTestResult {
+    pub events: u64,
+    pub exposure: f64,
+    /// Observed rate, events / exposure.
+    pub rate: f64,
+    pub p_value: f64,
+    /// Exact 95% interval for the rate from gamma quantiles, with a lower
+    /// bound of exactly zero when no events were observed.
+    pub confidence_interval: ConfidenceInterval,
+    /// How `p_value` was computed; the interval is always exact.
+    pub method: PValueMethod,
+}
+
+impl fmt::Display for PoissonTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} events, ", self.events)?;
+        write_test_line(f, "rate", &[], self.rate, self.p_value)
+    }
+}
+
+impl HypothesisTest for PoissonTestResult {
+    fn statistic(&self) -> f64 {
+        self.events as f64
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        match self.method {
+            PValueMethod::Exact => "exact Poisson test",
+            PValueMethod::MidP => "mid-p Poisson test",
+            PValueMethod::Asymptotic => "Poisson test, normal approximation",
+        }
+    }
+}
+
+/// Exact test of H0: λ = `rate0` for `events` counted over `exposure` units
+/// (time, area, items inspected), the events being Poisson with mean
+/// λ · exposure. The two-sided p-value sums the probabilities of all counts
+/// no more likely than the observed one, and the confidence interval comes
+/// from the gamma quantiles, both as R's `poisson.test` computes them.
+pub fn poisson_test(
+    events: u64,
+    exposure: f64,
+    rate0: f64,
+    alternative: Alternative,
+) -> Result<PoissonTestResult, StatsError> {
+    poisson_test_with(events, exposure, rate0, alternative, PValueMethod::Exact)
+}
+
+/// Poisson test with an explicit choice of p-value. The mid-p value counts
+/// the observed count, and two-sided every count as likely as it, at half
+/// its probability, and the
+/// asymptotic p-value refers z = (x - μ) / sqrt(μ), μ = `rate0` · exposure,
+/// to the standard normal.
+pub fn poisson_test_with(
+    events: u64,
+    exposure: f64,
+    rate0: f64,
+    alternative: Alternative,
+    method: PValueMethod,
+) -> Result<PoissonTestResult, StatsError> {
+    validate_exposure(exposure)?;
+    if !(rate0 > 0.0 && rate0.is_finite()) {
+        return Err(StatsError::InvalidParameter(
+            "rate0 must be positive and finite",
+        ));
+    }
+
+    let mean = rate0 * exposure;
+    let x = events as f64;
+    let exact = || match alternative {
+        Alternative::Less => poisson_cdf(events, mean),
+        Alternative::Greater => poisson_sf(events, mean),
+        Alternative::TwoSided => poisson_two_sided(events, mean),
+    };
+    let p_value = match method {
+        PValueMethod::Exact => exact(),
+        PValueMethod::MidP => {
+            let observed = poisson_pmf(events, mean);
+            let tied = match alternative {
+                // Past the mean the pmf only falls, so the search can stop
+                // once it drops below the observed probability
+                Alternative::TwoSided => tied_probability(
+                    observed,
+                    (0..)
+                        .take_while(|&i| {
+                            i as f64 <= mean || poisson_pmf(i, mean) * RELATIVE_ERROR >= observed
+                        })
+                        .map(|i| poisson_pmf(i, mean)),
+                ),
+                Alternative::Less | Alternative::Greater => observed,
+            };
+            exact() - tied / 2.0
+        }
+        PValueMethod::Asymptotic => {
+            Normal::standard().p_value((x - mean) / mean.sqrt(), alternative)
+        }
+    };
+
+    let confidence = POISSON_TEST_CONFIDENCE;
+    let lower_bound = |alpha: f64| {
+        if events == 0 {
+            0.0
+        } else {
+            inverse_regularized_lower_incomplete_gamma(x, alpha)
+        }
+    };
+    let upper_bound = |alpha: f64| inverse_regularized_lower_incomplete_gamma(x + 1.0, 1.0 - alpha);
+    let (lower, upper) = match alternative {
+        Alternative::TwoSided => {
+            let alpha = (1.0 - confidence) / 2.0;
+            (lower_bound(alpha), upper_bound(alpha))
+        }
+        Alternative::Less => (0.0, upper_bound(1.0 - confidence)),
+        Alternative::Greater => (lower_bound(1.0 - confidence), f64::INFINITY),
+    };
+
+    let rate = x / exposure;
+    Ok(PoissonTestResult {
+        events,
+        exposure,
+        rate,
+        p_value: p_value.min(1.0),
+        confidence_interval: ConfidenceInterval {
+            lower: lower / exposure,
+            upper: upper / exposure,
+            point_estimate: rate,
+            confidence,
+        },
+        method,
+    })
+}
+
+fn poisson_two_sided(events: u64, mean: f64) -> f64 {
+    let x = events as f64;
+    if x == mean {
+        return 1.0;
+    }
+
+    let threshold = poisson_pmf(events, mean) * RELATIVE_ERROR;
+    if x < mean {
+        // Count the outcomes above the mean as unlikely as the observed one,
+        // searching far enough out that the pmf has fallen below it.
+        let mut end = (2.0 * mean - x).ceil() as u64;
+        while poisson_pmf(end, mean) > threshold {
+            end *= 2;
+        }
+        let count = (mean.ceil() as u64..=end)
+            .filter(|&i| poisson_pmf(i, mean) <= threshold)
+            .count() as u64;
+        poisson_cdf(events, mean) + poisson_sf(end - count + 1, mean)
+    } else {
+        let count = (0..=mean.floor() as u64)
+            .filter(|&i| poisson_pmf(i, mean) <= threshold)
+            .count() as u64;
+        let lower = if count == 0 {
+            0.0
+        } else {
+            poisson_cdf(count - 1, mean)
+        };
+        lower + poisson_sf(events, mean)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoissonTwoSampleResult {
+    pub events_1: u64,
+    pub events_2: u64,
+    /// (events_1 / exposure_1) / (events_2 / exposure_2); infinite when the
+    /// second sample has no events.
+    pub rate_ratio: f64,
+    pub p_value: f64,
+    /// Exact 95% interval for the rate ratio from the Clopper-Pearson
+    /// interval of the conditional binomial proportion.
+    pub confidence_interval: ConfidenceInterval,
+}
+
+impl fmt::Display for PoissonTwoSampleResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} and {} events, ", self.events_1, self.events_2)?;
+        write_test_line(f, "rate ratio", &[], self.rate_ratio, self.p_value)
+    }
+}
+
+impl HypothesisTest for PoissonTwoSampleResult {
+    fn statistic(&self) -> f64 {
+        self.events_1 as f64
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "exact Poisson rate ratio test"
+    }
+}
+
+/// Exact comparison of two Poisson rates, H0: λ1 = λ2. Given the total
+/// number of events, the count in the first sample is binomial with
+/// proportion exposure_1 / (exposure_1 + exposure_2) under H0, so this is
+/// `binomial_test` on that count, with the Clopper-Pearson interval for the
+/// proportion mapped to the rate ratio, as R's `poisson.test` does for two
+/// samples. `Alternative::Greater` tests whether the first rate is higher.
+pub fn poisson_two_sample(
+    events_1: u64,
+    exposure_1: f64,
+    events_2: u64,
+    exposure_2: f64,
+    alternative: Alternative,
+) -> Result<PoissonTwoSampleResult, StatsError> {
+    validate_exposure(exposure_1)?;
+    validate_exposure(exposure_2)?;
+    let total = events_1 + events_2;
+    if total == 0 {
+        return Err(StatsError::EmptyInput);
+    }
+
+    let p0 = exposure_1 / (exposure_1 + exposure_2);
+    let binomial = binomial_test(events_1 as usize, total as usize, p0, alternative)?;
+
+    let confidence = POISSON_TEST_CONFIDENCE;
+    let (x, n) = (events_1 as f64, total as f64);
+    let lower_bound = |alpha: f64| {
+        if events_1 == 0 {
+            0.0
+        } else {
+            Beta::new(x, n - x + 1.0).quantile(alpha)
+        }
+    };
+    let upper_bound = |alpha: f64| {
+        if events_1 == total {
+            1.0
+        } else {
+            Beta::new(x + 1.0, n - x).quantile(1.0 - alpha)
+        }
+    };
+    let (lower, upper) = match alternative {
+        Alternative::TwoSided => {
+            let alpha = (1.0 - confidence) / 2.0;
+            (lower_bound(alpha), upper_bound(alpha))
+        }
+        Alternative::Less => (0.0, upper_bound(1.0 - confidence)),
+        Alternative::Greater => (lower_bound(1.0 - confidence), 1.0),
+    };
+    // A proportion q of the events in the first sample is a rate ratio of
+    // q / (1 - q) · exposure_2 / exposure_1.
+    let to_ratio = |q: f64| {
+        if q == 1.0 {
+            f64::INFINITY
+        } else {
+            q / (1.0 - q) * exposure_2 / exposure_1
+        }
+    };
+
+    let rate_ratio = (x / exposure_1) / (events_2 as f64 / exposure_2);
+    Ok(PoissonTwoSampleResult {
+        events_1,
+        events_2,
+        rate_ratio,
+        p_value: binomial.p_value,
+        confidence_interval: ConfidenceInterval {
+            lower: to_ratio(lower),
+            upper: to_ratio(upper),
+            point_estimate: rate_ratio,
+            confidence,
+        },
+    })
+}
+
+fn validate_exposure(exposure: f64) -> Result<(), StatsError> {
+    if exposure > 0.0 && exposure.is_finite() {
+        Ok(())
+    } else {
+        Err(StatsError::InvalidParameter(
+            "exposure must be positive and finite",
+        ))
+    }
+}
+
+/// P(X = k) for X ~ Poisson(mean), evaluated in log space.
+fn poisson_pmf(k: u64, mean: f64) -> f64 {
+    (k as f64 * mean.ln() - mean - ln_factorial(k)).exp()
+}
+
+/// P(X <= k) for X ~ Poisson(mean).
+fn poisson_cdf(k: u64, mean: f64) -> f64 {
+    regularized_upper_incomplete_gamma(k as f64 + 1.0, mean)
+}
+
+/// P(X >= k) for X ~ Poisson(mean).
+fn poisson_sf(k: u64, mean: f64) -> f64 {
+    if k == 0 {
+        return 1.0;
+    }
+    regularized_lower_incomplete_gamma(k as f64, mean)
+}
+
+/// P(X <= k) for X ~ Binomial(n, p).
+pub(crate) fn binomial_cdf(k: usize, n: usize, p: f64) -> f64 {
+    if k >= n {
+        return 1.0;
+    }
+    regularized_incomplete_beta((n - k) as f64, k as f64 + 1.0, 1.0 - p)
+}
+
+/// P(X >= k) for X ~ Binomial(n, p).
+pub(crate) fn binomial_sf(k: usize, n: usize, p: f64) -> f64 {
+    if k == 0 {
+        return 1.0;
+    }
+    if k > n {
+        return 0.0;
+    }
+    regularized_incomplete_beta(k as f64, (n - k) as f64 + 1.0, p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn binomial_test_two_sided_test() {
+        // R: binom.test(7, 20, 0.5)
+        let result = binomial_test(7, 20, 0.5, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.263175964355469, 1e-12);
+        assert_close(result.estimate, 0.35, 1e-15);
+
+        // R: binom.test(682, 925, p = 0.75)
+        let result = binomial_test(682, 925, 0.75, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.382491559574852, 1e-10);
+
+        let result = binomial_test(3, 30, 0.25, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.0590429666036563, 1e-12);
+
+        let result = binomial_test(10, 20, 0.5, Alternative::TwoSided).unwrap();
+        assert_eq!(result.p_value, 1.0);
+    }
+
+    #[test]
+    fn binomial_test_one_sided_test() {
+        let result = binomial_test(7, 20, 0.5, Alternative::Less).unwrap();
+        assert_close(result.p_value, 0.131587982177734, 1e-12);
+
+        let result = binomial_test(7, 20, 0.5, Alternative::Greater).unwrap();
+        assert_close(result.p_value, 0.942340850830078, 1e-12);
+
+        let result = binomial_test(10, 30, 0.25, Alternative::Greater).unwrap();
+        assert_close(result.p_value, 0.196593363050489, 1e-12);
+    }
+
+    #[test]
+    fn binomial_test_large_n_test() {
+        let result = binomial_test(60, 10_000, 0.005, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.155818289947252, 1e-8);
+    }
+
+    #[test]
+    fn binomial_test_invalid_input_test() {
+        assert!(matches!(
+            binomial_test(3, 0, 0.5, Alternative::TwoSided),
+            Err(StatsError::EmptyInput)
+        ));
+        assert!(matches!(
+            binomial_test(21, 20, 0.5, Alternative::TwoSided),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            binomial_test(3, 20, 1.5, Alternative::TwoSided),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn fishers_exact_tea_tasting_test() {
+        // R: fisher.test(matrix(c(3, 1, 1, 3), nrow = 2))
+        let result = fishers_exact([[3, 1], [1, 3]], Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.485714285714286, 1e-12);
+        assert_close(result.odds_ratio, 9.0, 1e-12);
+
+        let result = fishers_exact([[3, 1], [1, 3]], Alternative::Greater).unwrap();
+        assert_close(result.p_value, 0.242857142857143, 1e-12);
+
+        let result = fishers_exact([[3, 1], [1, 3]], Alternative::Less).unwrap();
+        assert_close(result.p_value, 0.985714285714286, 1e-12);
+    }
+
+    #[test]
+    fn fishers_exact_zero_cell_test() {
+        let result = fishers_exact([[10, 0], [3, 8]], Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.00103199174406605, 1e-14);
+        assert_eq!(result.odds_ratio, f64::INFINITY);
+
+        let result = fishers_exact([[2, 7], [8, 2]], Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.0230141375652212, 1e-13);
+    }
+
+    #[test]
+    fn fishers_exact_large_counts_test() {
+        let result = fishers_exact([[1200, 800], [900, 1100]], Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 2.4272522820494e-21, 1e-28);
+        assert_eq!(
+            fishers_exact([[0, 0], [0, 0]], Alternative::TwoSided).err(),
+            Some(StatsError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn fishers_exact_mid_p_test() {
+        // mpmath: P(f(X) < f(a)) + P(f(X) = f(a)) / 2 two-sided, the
+        // one-sided exact p-values less half of f(a), for the
+        // hypergeometric pmf f. The symmetric first table ties a = 3 with
+        // a = 1, so both count half
+        let cases = [
+            (
+                [[3, 1], [1, 3]],
+                0.2571428571428571,
+                0.12857142857142856,
+                0.8714285714285714,
+            ),
+            (
+                [[2, 7], [8, 2]],
+                0.014245816103401242,
+                0.9902465955097534,
+                0.009753404490246595,
+            ),
+            (
+                [[10, 0], [3, 8]],
+                0.0006265664160401002,
+                0.00040542532802594723,
+                0.9995945746719741,
+            ),
+        ];
+        for (table, two_sided, greater, less) in cases {
+            for (alternative, expected) in [
+                (Alternative::TwoSided, two_sided),
+                (Alternative::Greater, greater),
+                (Alternative::Less, less),
+            ] {
+                let mid_p = fishers_exact_with(table, alternative, PValueMethod::MidP).unwrap();
+                assert_close(mid_p.p_value, expected, 1e-12);
+                assert_eq!(mid_p.method, PValueMethod::MidP);
+                let exact = fishers_exact(table, alternative).unwrap();
+                assert_eq!(exact.method, PValueMethod::Exact);
+                assert!(mid_p.p_value < exact.p_value);
+                assert_eq!(mid_p.odds_ratio, exact.odds_ratio);
+            }
+        }
+
+        // Pearson's uncorrected chi-square of 2 on one degree of freedom
+        let result = fishers_exact_with(
+            [[3, 1], [1, 3]],
+            Alternative::TwoSided,
+            PValueMethod::Asymptotic,
+        )
+        .unwrap();
+        assert_close(result.p_value, 0.15729920705028513, 1e-12);
+        assert_eq!(result.test_name(), "2x2 chi-square test");
+        let empty_row = [[0, 0], [4, 5]];
+        let result = fishers_exact_with(empty_row, Alternative::Less, PValueMethod::Asymptotic);
+        assert_eq!(result.unwrap().p_value, 1.0);
+    }
+
+    #[test]
+    fn binomial_test_mid_p_test() {
+        // mpmath: P(f(X) < f(x)) + P(f(X) = f(x)) / 2 two-sided, the
+        // one-sided exact p-values less half of f(x). With p0 = 0.5, 13
+        // successes are as likely as 7 and count half too
+        let cases = [
+            (
+                7,
+                20,
+                0.5,
+                [0.18924713134765625, 0.09462356567382812, 0.9053764343261719],
+            ),
+            (
+                3,
+                30,
+                0.25,
+                [0.04561623908346634, 0.02402259820361087, 0.9759774017963891],
+            ),
+        ];
+        let alternatives = [
+            Alternative::TwoSided,
+            Alternative::Less,
+            Alternative::Greater,
+        ];
+        for (successes, n, p0, expected) in cases {
+            for (alternative, expected) in alternatives.into_iter().zip(expected) {
+                let mid_p =
+                    binomial_test_with(successes, n, p0, alternative, PValueMethod::MidP).unwrap();
+                assert_close(mid_p.p_value, expected, 1e-12);
+                let exact = binomial_test(successes, n, p0, alternative).unwrap();
+                assert!(mid_p.p_value < exact.p_value);
+            }
+        }
+
+        // R: prop.test(7, 20, correct = FALSE)
+        let result =
+            binomial_test_with(7, 20, 0.5, Alternative::TwoSided, PValueMethod::Asymptotic)
+                .unwrap();
+        assert_close(result.p_value, 0.17971249487899985, 1e-12);
+        assert_eq!(result.method, PValueMethod::Asymptotic);
+        let default = PValueMethod::default();
+        let result = binomial_test_with(7, 20, 0.5, Alternative::TwoSided, default).unwrap();
+        assert_eq!(result.method, PValueMethod::Exact);
+        assert_eq!(
+            result.p_value,
+            binomial_test(7, 20, 0.5, Alternative::TwoSided)
+                .unwrap()
+                .p_value
+        );
+    }
+
+    #[test]
+    fn poisson_test_two_sided_test() {
+        // R: poisson.test(137, 24.19893)
+        let result = poisson_test(137, 24.19893, 1.0, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 2.8452272641144834e-56, 1e-68);
+        assert_close(result.rate, 5.661407343217241, 1e-14);
+        assert_close(result.confidence_interval.lower, 4.753124804848751, 1e-12);
+        assert_close(result.confidence_interval.upper, 6.692709334176074, 1e-12);
+        assert_eq!(result.confidence_interval.confidence, 0.95);
+
+        // R: poisson.test(137, 24.19893, r = 5)
+        let result = poisson_test(137, 24.19893, 5.0, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.14561131663335382, 1e-12);
+
+        // R: poisson.test(3, 2, r = 3), below the expected count of 6
+        let result = poisson_test(3, 2.0, 3.0, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.30396638879208655, 1e-12);
+        assert_close(result.confidence_interval.lower, 0.3093360614478006, 1e-12);
+        assert_close(result.confidence_interval.upper, 4.383636534871163, 1e-12);
+
+        // R: poisson.test(9, 2, r = 3), above it
+        let result = poisson_test(9, 2.0, 3.0, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.21473131043209765, 1e-12);
+        assert_close(result.confidence_interval.lower, 2.057686548689166, 1e-12);
+        assert_close(result.confidence_interval.upper, 8.542401725709585, 1e-12);
+
+        // R: poisson.test(12, 1, r = 7)
+        let result = poisson_test(12, 1.0, 7.0, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.08298578703208039, 1e-12);
+
+        // The observed count equals the expected one.
+        let result = poisson_test(6, 2.0, 3.0, Alternative::TwoSided).unwrap();
+        assert_eq!(result.p_value, 1.0);
+    }
+
+    #[test]
+    fn poisson_test_zero_events_test() {
+        // R: poisson.test(0, 10, r = 0.2)
+        let result = poisson_test(0, 10.0, 0.2, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.27821182273806564, 1e-12);
+        assert_eq!(result.rate, 0.0);
+        assert_eq!(result.confidence_interval.lower, 0.0);
+        assert_close(result.confidence_interval.upper, 0.36888794541139363, 1e-12);
+
+        // R: poisson.test(0, 10, r = 0.2, alternative = "less")
+        let result = poisson_test(0, 10.0, 0.2, Alternative::Less).unwrap();
+        assert_close(result.p_value, (-2.0_f64).exp(), 1e-15);
+        assert_eq!(result.confidence_interval.lower, 0.0);
+        assert_close(result.confidence_interval.upper, 0.2995732273553991, 1e-12);
+
+        // R: poisson.test(0, 10, r = 0.2, alternative = "greater")
+        let result = poisson_test(0, 10.0, 0.2, Alternative::Greater).unwrap();
+        assert_eq!(result.p_value, 1.0);
+        assert_eq!(result.confidence_interval.lower, 0.0);
+        assert_eq!(result.confidence_interval.upper, f64::INFINITY);
+    }
+
+    #[test]
+    fn poisson_test_one_sided_test() {
+        // R: poisson.test(4, 2.5, r = 3.1, alternative = "less")
+        let result = poisson_test(4, 2.5, 3.1, Alternative::Less).unwrap();
+        assert_close(result.p_value, 0.11486811277078364, 1e-12);
+        assert_eq!(result.confidence_interval.lower, 0.0);
+        assert_close(result.confidence_interval.upper, 3.6614076106550294, 1e-12);
+
+        // R: poisson.test(4, 2.5, r = 0.7, alternative = "greater")
+        let result = poisson_test(4, 2.5, 0.7, Alternative::Greater).unwrap();
+        assert_close(result.p_value, 0.10081034990616024, 1e-12);
+        assert_close(result.confidence_interval.lower, 0.5465273586999324, 1e-12);
+        assert_eq!(result.confidence_interval.upper, f64::INFINITY);
+    }
+
+    #[test]
+    fn poisson_test_mid_p_test() {
+        // mpmath: the exact p-value less half of P(X = 2) for X ~ Poisson(6),
+        // which no other count ties
+        let alternatives = [
+            Alternative::TwoSided,
+            Alternative::Less,
+            Alternative::Greater,
+        ];
+        let expected = [
+            0.12358405182153756,
+            0.039660034826661736,
+            0.9603399651733383,
+        ];
+        for (alternative, expected) in alternatives.into_iter().zip(expected) {
+            let mid_p = poisson_test_with(2, 4.0, 1.5, alternative, PValueMethod::MidP).unwrap();
+            assert_close(mid_p.p_value, expected, 1e-12);
+            let exact = poisson_test(2, 4.0, 1.5, alternative).unwrap();
+            assert!(mid_p.p_value < exact.p_value);
+            assert_eq!(mid_p.confidence_interval, exact.confidence_interval);
+        }
+
+        // z = (2 - 6) / sqrt(6)
+        let result =
+            poisson_test_with(2, 4.0, 1.5, Alternative::TwoSided, PValueMethod::Asymptotic)
+                .unwrap();
+        assert_close(result.p_value, 0.10247043485974942, 1e-12);
+        assert_eq!(result.test_name(), "Poisson test, normal approximation");
+    }
+
+    #[test]
+    fn poisson_two_sample_test() {
+        // R: poisson.test(c(11, 6 + 8 + 7), c(800, 1083 + 1050 + 878))
+        let result = poisson_two_sample(11, 800.0, 21, 3011.0, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.07966863303332947, 1e-12);
+        assert_close(result.rate_ratio, 1.971488095238095, 1e-14);
+        assert_close(result.confidence_interval.lower, 0.8584264033916397, 1e-10);
+        assert_close(result.confidence_interval.upper, 4.277265943654074, 1e-10);
+
+        // The same with alternative = "less" and "greater"
+        let result = poisson_two_sample(11, 800.0, 21, 3011.0, Alternative::Less).unwrap();
+        assert_close(result.p_value, 0.9759318232273121, 1e-12);
+        assert_eq!(result.confidence_interval.lower, 0.0);
+        assert_close(result.confidence_interval.upper, 3.827386407850879, 1e-10);
+        let result = poisson_two_sample(11, 800.0, 21, 3011.0, Alternative::Greater).unwrap();
+        assert_close(result.p_value, 0.05600865978769966, 1e-12);
+        assert_close(result.confidence_interval.lower, 0.977584347496745, 1e-10);
+        assert_eq!(result.confidence_interval.upper, f64::INFINITY);
+
+        // R: poisson.test(c(20, 35), c(100.5, 150.25))
+        let result = poisson_two_sample(20, 100.5, 35, 150.25, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.6801535437030988, 1e-12);
+        assert_close(result.rate_ratio, 0.8542999289267946, 1e-14);
+        assert_close(result.confidence_interval.lower, 0.46730638616090315, 1e-10);
+        assert_close(result.confidence_interval.upper, 1.5213794724180762, 1e-10);
+    }
+
+    #[test]
+    fn poisson_two_sample_zero_events_test() {
+        // R: poisson.test(c(0, 5), c(10, 12))
+        let result = poisson_two_sample(0, 10.0, 5, 12.0, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.0676866334266785, 1e-12);
+        assert_eq!(result.rate_ratio, 0.0);
+        assert_eq!(result.confidence_interval.lower, 0.0);
+        assert_close(result.confidence_interval.upper, 1.3095349262190558, 1e-10);
+
+        // R: poisson.test(c(6, 0), c(10, 12))
+        let result = poisson_two_sample(6, 10.0, 0, 12.0, Alternative::TwoSided).unwrap();
+        assert_close(result.p_value, 0.008819905157090272, 1e-12);
+        assert_eq!(result.rate_ratio, f64::INFINITY);
+        assert_close(result.confidence_interval.lower, 1.412909670869013, 1e-10);
+        assert_eq!(result.confidence_interval.upper, f64::INFINITY);
+
+        assert_eq!(
+            poisson_two_sample(0, 10.0, 0, 12.0, Alternative::TwoSided).err(),
+            Some(StatsError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn poisson_invalid_input_test() {
+        for exposure in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(poisson_test(3, exposure, 1.0, Alternative::TwoSided).is_err());
+            assert!(poisson_two_sample(3, exposure, 2, 1.0, Alternative::TwoSided).is_err());
+            assert!(poisson_two_sample(3, 1.0, 2, exposure, Alternative::TwoSided).is_err());
+        }
+        for rate0 in [0.0, -2.0, f64::NAN] {
+            assert!(poisson_test(3, 1.0, rate0, Alternative::TwoSided).is_err());
+        }
+    }
+
+    #[test]
+    fn poisson_display_test() {
+        let result = poisson_test(137, 24.19893, 1.0, Alternative::TwoSided).unwrap();
+        assert_eq!(result.to_string(), "137 events, rate = 5.66, p < 0.001");
+        let result = poisson_two_sample(11, 800.0, 21, 3011.0, Alternative::TwoSided).unwrap();
+        assert_eq!(
+            result.to_string(),
+            "11 and 21 events, rate ratio = 1.97, p = 0.0797"
+        );
+    }
+}
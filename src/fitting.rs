@@ -0,0 +1,243 @@
+use crate::distributions::{Exponential, Normal};
+use crate::{ks_test, mean, KsTestResult, StatsError};
+
+/// A fitted parameter with its asymptotic standard error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FittedParameter {
+    pub estimate: f64,
+    pub standard_error: f64,
+}
+
+/// How well a fitted distribution describes the data it was fitted to.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoodnessOfFit {
+    /// Kolmogorov-Smirnov test against the fitted CDF. The parameters came
+    /// from the same data, so its p-value is conservative (too large); the
+    /// statistic is still a fair measure of the largest misfit.
+    pub ks: KsTestResult,
+    pub log_likelihood: f64,
+    /// Akaike's information criterion 2 k - 2 log L; lower is better when
+    /// comparing fits of the same data.
+    pub aic: f64,
+}
+
+impl GoodnessOfFit {
+    fn new(
+        data: &[f64],
+        parameters: usize,
+        log_pdf: impl Fn(f64) -> f64,
+        cdf: impl Fn(f64) -> f64,
+    ) -> Result<Self, StatsError> {
+        let log_likelihood: f64 = data.iter().map(|&x| log_pdf(x)).sum();
+        Ok(GoodnessOfFit {
+            ks: ks_test(data, cdf)?,
+            log_likelihood,
+            aic: 2.0 * parameters as f64 - 2.0 * log_likelihood,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NormalFit {
+    pub mean: FittedParameter,
+    /// The maximum likelihood standard deviation, with divisor n.
+    pub sd: FittedParameter,
+    pub goodness_of_fit: GoodnessOfFit,
+}
+
+impl NormalFit {
+    /// The fitted distribution.
+    pub fn distribution(&self) -> Normal {
+        Normal::new(self.mean.estimate, self.sd.estimate)
+    }
+}
+
+/// Fits a normal distribution to `data` by maximum likelihood, which for
+/// the normal is also the method of moments: the mean, and the standard
+/// deviation with divisor n. The standard errors are sd / sqrt(n) and
+/// sd / sqrt(2 n), as R's `MASS::fitdistr`.
+///
+/// Returns `StatsError::InsufficientData` for fewer than two values,
+/// `StatsError::NanInInput` if `data` contains NaN and
+/// `StatsError::InvalidParameter` if every value is the same.
+pub fn fit_normal(data: &[f64]) -> Result<NormalFit, StatsError> {
+    if data.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: data.len(),
+        });
+    }
+    if let Some(index) = data.iter().position(|x| x.is_nan()) {
+        return Err(StatsError::NanInInput { index });
+    }
+
+    let n = data.len() as f64;
+    let center = mean(data);
+    let sd = (data.iter().map(|x| (x - center).powi(2)).sum::<f64>() / n).sqrt();
+    if sd == 0.0 {
+        return Err(StatsError::InvalidParameter("all values are equal"));
+    }
+    let normal = Normal::new(center, sd);
+    let log_normalizer = (sd * (2.0 * core::f64::consts::PI).sqrt()).ln();
+    Ok(NormalFit {
+        mean: FittedParameter {
+            estimate: center,
+            standard_error: sd / n.sqrt(),
+        },
+        sd: FittedParameter {
+            estimate: sd,
+            standard_error: sd / (2.0 * n).sqrt(),
+        },
+        goodness_of_fit: GoodnessOfFit::new(
+            data,
+            2,
+            |x| -log_normalizer - (x - center).powi(2) / (2.0 * sd * sd),
+            |x| normal.cdf(x),
+        )?,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExponentialFit {
+    pub rate: FittedParameter,
+    pub goodness_of_fit: GoodnessOfFit,
+}
+
+impl ExponentialFit {
+    /// The fitted distribution.
+    pub fn distribution(&self) -> Exponential {
+        Exponential::new(self.rate.estimate)
+    }
+}
+
+/// Fits an exponential distribution to `data` by maximum likelihood, which
+/// is also the method of moments: the rate 1 / x̄, with standard error
+/// rate / sqrt(n) as R's `MASS::fitdistr`.
+///
+/// Returns `StatsError::EmptyInput` for no data, `StatsError::NanInInput`
+/// if it contains NaN and `StatsError::InvalidParameter` if any value is
+/// zero or negative.
+pub fn fit_exponential(data: &[f64]) -> Result<ExponentialFit, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if let Some(index) = data.iter().position(|x| x.is_nan()) {
+        return Err(StatsError::NanInInput { index });
+    }
+    if data.iter().any(|&x| x <= 0.0) {
+        return Err(StatsError::InvalidParameter(
+            "exponential fit requires positive data",
+        ));
+    }
+
+    let rate = 1.0 / mean(data);
+    let exponential = Exponential::new(rate);
+    Ok(ExponentialFit {
+        rate: FittedParameter {
+            estimate: rate,
+            standard_error: rate / (data.len() as f64).sqrt(),
+        },
+        goodness_of_fit: GoodnessOfFit::new(
+            data,
+            1,
+            |x| rate.ln() - rate * x,
+            |x| exponential.cdf(x),
+        )?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+    use crate::rng::Rng;
+
+    const WAITS: [f64; 15] = [
+        2.31, 0.47, 1.88, 3.92, 0.95, 1.12, 5.61, 0.28, 2.74, 1.46, 0.83, 4.05, 1.67, 0.61, 2.19,
+    ];
+
+    #[test]
+    fn fit_normal_test() {
+        // R: MASS::fitdistr(waits, "normal"), AIC() and
+        // ks.test(waits, "pnorm", mean, sd)$statistic
+        let fit = fit_normal(&WAITS).unwrap();
+        assert_close(fit.mean.estimate, 2.006, 1e-14);
+        assert_close(fit.sd.estimate, 1.4717871222881838, 1e-14);
+        assert_close(fit.mean.standard_error, 0.3800138009189783, 1e-14);
+        assert_close(fit.sd.standard_error, 0.26871033557428425, 1e-14);
+        let gof = &fit.goodness_of_fit;
+        assert_close(gof.log_likelihood, -27.081238875636295, 1e-12);
+        assert_close(gof.aic, 58.16247775127259, 1e-12);
+        assert_close(gof.ks.d, 0.1515133606594552, 1e-14);
+        assert_eq!(
+            fit.distribution(),
+            Normal::new(fit.mean.estimate, fit.sd.estimate)
+        );
+    }
+
+    #[test]
+    fn fit_exponential_test() {
+        // R: MASS::fitdistr(waits, "exponential"), AIC() and
+        // ks.test(waits, "pexp", rate)$statistic
+        let fit = fit_exponential(&WAITS).unwrap();
+        assert_close(fit.rate.estimate, 0.4985044865403789, 1e-14);
+        assert_close(fit.rate.standard_error, 0.12871330495870445, 1e-14);
+        let gof = &fit.goodness_of_fit;
+        assert_close(gof.log_likelihood, -25.442140343096156, 1e-12);
+        assert_close(gof.aic, 52.88428068619231, 1e-12);
+        assert_close(gof.ks.d, 0.14220660297280197, 1e-14);
+
+        // The right-skewed waits are better described as exponential
+        assert!(gof.aic < fit_normal(&WAITS).unwrap().goodness_of_fit.aic);
+    }
+
+    #[test]
+    fn fit_recovers_parameters_test() {
+        let mut rng = Rng::new(132);
+        let normal = Normal::new(10.0, 2.0);
+        let data: Vec<f64> = (0..5000).map(|_| normal.quantile(rng.next_f64())).collect();
+        let fit = fit_normal(&data).unwrap();
+        assert!((fit.mean.estimate - 10.0).abs() < 3.0 * fit.mean.standard_error);
+        assert!((fit.sd.estimate - 2.0).abs() < 3.0 * fit.sd.standard_error);
+        assert!(fit.goodness_of_fit.ks.p_value > 0.05);
+
+        let exponential = Exponential::new(0.25);
+        let data: Vec<f64> = (0..5000)
+            .map(|_| exponential.quantile(rng.next_f64()))
+            .collect();
+        let fit = fit_exponential(&data).unwrap();
+        assert!((fit.rate.estimate - 0.25).abs() < 3.0 * fit.rate.standard_error);
+        assert!(fit.goodness_of_fit.ks.p_value > 0.05);
+        // A normal fit to the same data misses the skew
+        let normal_fit = fit_normal(&data).unwrap();
+        assert!(normal_fit.goodness_of_fit.ks.p_value < 1e-6);
+        assert!(fit.goodness_of_fit.aic < normal_fit.goodness_of_fit.aic);
+    }
+
+    #[test]
+    fn fit_invalid_input_test() {
+        assert_eq!(
+            fit_normal(&[1.0]).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert!(matches!(
+            fit_normal(&[3.0, 3.0, 3.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            fit_normal(&[1.0, f64::NAN]).err(),
+            Some(StatsError::NanInInput { index: 1 })
+        );
+        assert_eq!(fit_exponential(&[]).err(), Some(StatsError::EmptyInput));
+        for bad in [0.0, -1.5] {
+            assert!(matches!(
+                fit_exponential(&[1.0, bad, 2.0]),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,18 @@
+/// A floating-point sample value. The core statistics accept slices of any
+/// `Float` and accumulate in f64, so f32 data need not be converted first but
+/// loses no accuracy in the sums.
+pub trait Float: Copy {
+    fn to_f64(self) -> f64;
+}
+
+impl Float for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+impl Float for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
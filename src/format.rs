@@ -0,0 +1,89 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use crate::math::F64Ext;
+
+/// `x` rounded to `digits` significant digits, without an exponent and with
+/// trailing zeros dropped, as R's `signif` prints: 2.3114 gives "2.31",
+/// 23.44 gives "23.4" and 9.0 gives "9".
+pub(crate) fn significant(x: f64, digits: usize) -> String {
+    if !x.is_finite() {
+        return format!("{x}");
+    }
+    if x == 0.0 {
+        return "0".to_string();
+    }
+    let magnitude = x.abs().log10().floor() as i32;
+    let decimals = (digits as i32 - 1 - magnitude).max(0) as usize;
+    let mut formatted = format!("{x:.decimals$}");
+    if formatted.contains('.') {
+        formatted = formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string();
+    }
+    if formatted == "-0" {
+        formatted.remove(0);
+    }
+    formatted
+}
+
+/// A p-value as reported: "p = 0.0301", or "p < 0.001" below that.
+pub(crate) fn p_value(p: f64) -> String {
+    if p < 0.001 {
+        "p < 0.001".to_string()
+    } else {
+        format!("p = {}", significant(p, 3))
+    }
+}
+
+/// Writes "name(df, ...) = value, p = ...", the compact line the result
+/// structs display, leaving out the parentheses when there are no degrees of
+/// freedom.
+pub(crate) fn write_test_line(
+    f: &mut fmt::Formatter,
+    name: &str,
+    df: &[f64],
+    value: f64,
+    p: f64,
+) -> fmt::Result {
+    write!(f, "{name}")?;
+    if !df.is_empty() {
+        let df: Vec<String> = df.iter().map(|&df| significant(df, 3)).collect();
+        write!(f, "({})", df.join(", "))?;
+    }
+    write!(f, " = {}, {}", significant(value, 3), p_value(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn significant_test() {
+        assert_eq!(significant(2.3114, 3), "2.31");
+        assert_eq!(significant(23.44, 3), "23.4");
+        assert_eq!(significant(9.0, 3), "9");
+        assert_eq!(significant(0.030149, 3), "0.0301");
+        assert_eq!(significant(-1.8608, 3), "-1.86");
+        assert_eq!(significant(1234.56, 3), "1235");
+        assert_eq!(significant(9.9996, 3), "10");
+        assert_eq!(significant(2.5, 3), "2.5");
+        assert_eq!(significant(-0.0001, 1), "-0.0001");
+        assert_eq!(significant(0.0, 3), "0");
+        assert_eq!(significant(f64::NAN, 3), "NaN");
+        assert_eq!(significant(f64::NEG_INFINITY, 3), "-inf");
+    }
+
+    #[test]
+    fn p_value_test() {
+        assert_eq!(p_value(0.030149), "p = 0.0301");
+        assert_eq!(p_value(0.001), "p = 0.001");
+        assert_eq!(p_value(0.00099), "p < 0.001");
+        assert_eq!(p_value(1.0), "p = 1");
+        assert_eq!(p_value(f64::NAN), "p = NaN");
+    }
+}
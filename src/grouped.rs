@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::hypothesis::validate_alpha;
+use crate::{
+    adjust_p_values, one_way_anova, two_samp_t_test_with, AnovaResult, CorrectionMethod,
+    GetStatistics, SampleStatistics, StatsError, TTestKind, TTestResult,
+};
+
+/// Statistics of each group of `(label, value)` pairs, the same as calling
+/// `SampleStatistics::from_array` on each group's values in input order.
+/// A group with a single value is kept, with a NaN standard deviation.
+pub fn grouped_statistics<K: Eq + Hash>(
+    pairs: impl IntoIterator<Item = (K, f64)>,
+) -> HashMap<K, SampleStatistics> {
+    partition(pairs)
+        .into_iter()
+        .map(|(label, values)| (label, SampleStatistics::from_array(&values)))
+        .collect()
+}
+
+/// `one_way_anova` on labeled data.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupedAnovaResult<K> {
+    pub result: AnovaResult,
+    /// The groups compared, in order of first appearance.
+    pub groups: Vec<K>,
+    /// Groups left out for having a single observation, in order of first
+    /// appearance.
+    pub excluded: Vec<K>,
+}
+
+/// One-way ANOVA across the groups of `(label, value)` pairs. Groups with a
+/// single observation cannot contribute a variance, so they are left out
+/// and listed in `excluded` rather than failing the test. Returns
+/// `StatsError::InsufficientData` if fewer than two groups remain.
+pub fn grouped_anova<K: Eq + Hash>(
+    pairs: impl IntoIterator<Item = (K, f64)>,
+) -> Result<GroupedAnovaResult<K>, StatsError> {
+    let (groups, values, excluded) = testable_groups(pairs);
+    let slices: Vec<&[f64]> = values.iter().map(|v| v.as_slice()).collect();
+    Ok(GroupedAnovaResult {
+        result: one_way_anova(&slices)?,
+        groups,
+        excluded,
+    })
+}
+
+/// A two-sample t-test between two labeled groups.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupPairTTest<K> {
+    pub group_1: K,
+    pub group_2: K,
+    /// The test of `group_1` against `group_2`, so its mean difference is
+    /// x̄1 - x̄2.
+    pub result: TTestResult,
+    /// `result.p_value` corrected over every pair of groups.
+    pub adjusted_p_value: f64,
+    /// Whether `adjusted_p_value` is below the chosen alpha.
+    pub significant: bool,
+}
+
+/// Pairwise t-tests between labeled groups.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupedPairwiseTTests<K> {
+    /// Every pair of groups, ordered by the first appearance of each.
+    pub comparisons: Vec<GroupPairTTest<K>>,
+    /// Groups left out for having a single observation, in order of first
+    /// appearance.
+    pub excluded: Vec<K>,
+}
+
+/// Two-sample t-tests of `kind` between every pair of groups of
+/// `(label, value)` pairs, with the p-values corrected for the k (k - 1) / 2
+/// comparisons by `method` and compared to `alpha`. Groups with a single
+/// observation are left out and listed in `excluded`. Returns
+/// `StatsError::InsufficientData` if fewer than two groups remain, and
+/// `StatsError::InvalidParameter` if two groups are both constant, which
+/// leaves their test without a standard error.
+pub fn grouped_pairwise_t_tests<K: Eq + Hash + Clone>(
+    pairs: impl IntoIterator<Item = (K, f64)>,
+    kind: TTestKind,
+    alpha: f64,
+    method: CorrectionMethod,
+) -> Result<GroupedPairwiseTTests<K>, StatsError> {
+    validate_alpha(alpha)?;
+    let (groups, values, excluded) = testable_groups(pairs);
+    if groups.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: groups.len(),
+        });
+    }
+
+    let statistics: Vec<SampleStatistics> = values
+        .iter()
+        .map(|v| SampleStatistics::from_array(v))
+        .collect();
+    let mut indices = Vec::new();
+    let mut results = Vec::new();
+    for i in 0..groups.len() {
+        for j in i + 1..groups.len() {
+            if statistics[i].standard_deviation == 0.0 && statistics[j].standard_deviation == 0.0 {
+                return Err(StatsError::InvalidParameter(
+                    "two groups have zero variance",
+                ));
+            }
+            indices.push((i, j));
+            results.push(two_samp_t_test_with(&statistics[i], &statistics[j], kind));
+        }
+    }
+    let p_values: Vec<f64> = results.iter().map(|r| r.p_value).collect();
+    let adjusted = adjust_p_values(&p_values, method)?;
+    let comparisons = indices
+        .into_iter()
+        .zip(results)
+        .zip(adjusted)
+        .map(|(((i, j), result), adjusted_p_value)| GroupPairTTest {
+            group_1: groups[i].clone(),
+            group_2: groups[j].clone(),
+            result,
+            adjusted_p_value,
+            significant: adjusted_p_value < alpha,
+        })
+        .collect();
+
+    Ok(GroupedPairwiseTTests {
+        comparisons,
+        excluded,
+    })
+}
+
+/// Splits `pairs` into groups, ordered by the first appearance of each label,
+/// keeping each group's values in input order.
+fn partition<K: Eq + Hash>(pairs: impl IntoIterator<Item = (K, f64)>) -> Vec<(K, Vec<f64>)> {
+    let mut groups: HashMap<K, (usize, Vec<f64>)> = HashMap::new();
+    for (label, value) in pairs {
+        let next = groups.len();
+        groups
+            .entry(label)
+            .or_insert((next, Vec::new()))
+            .1
+            .push(value);
+    }
+    let mut groups: Vec<(usize, K, Vec<f64>)> = groups
+        .into_iter()
+        .map(|(label, (first, values))| (first, label, values))
+        .collect();
+    groups.sort_unstable_by_key(|&(first, _, _)| first);
+    groups
+        .into_iter()
+        .map(|(_, label, values)| (label, values))
+        .collect()
+}
+
+/// The labels and values of the groups with at least two observations, and
+/// the labels of the rest.
+fn testable_groups<K: Eq + Hash>(
+    pairs: impl IntoIterator<Item = (K, f64)>,
+) -> (Vec<K>, Vec<Vec<f64>>, Vec<K>) {
+    let mut labels = Vec::new();
+    let mut values = Vec::new();
+    let mut excluded = Vec::new();
+    for (label, group) in partition(pairs) {
+        if group.len() < 2 {
+            excluded.push(label);
+        } else {
+            labels.push(label);
+            values.push(group);
+        }
+    }
+    (labels, values, excluded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const CTRL: [f64; 10] = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+    const TRT1: [f64; 10] = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+    const TRT2: [f64; 10] = [6.31, 5.12, 5.54, 5.50, 5.37, 5.29, 4.92, 6.15, 5.80, 5.26];
+
+    /// PlantGrowth as (group, weight) pairs, interleaved so that no group
+    /// arrives contiguously.
+    fn plant_growth() -> Vec<(&'static str, f64)> {
+        (0..10)
+            .flat_map(|i| [("trt1", TRT1[i]), ("ctrl", CTRL[i]), ("trt2", TRT2[i])])
+            .collect()
+    }
+
+    #[test]
+    fn grouped_statistics_test() {
+        let statistics = grouped_statistics(plant_growth());
+        assert_eq!(statistics.len(), 3);
+        for (label, values) in [("ctrl", CTRL), ("trt1", TRT1), ("trt2", TRT2)] {
+            let expected = SampleStatistics::from_array(&values);
+            let actual = &statistics[label];
+            assert_eq!(actual.n, expected.n);
+            assert_eq!(actual.sample_mean, expected.sample_mean);
+            assert_eq!(actual.standard_deviation, expected.standard_deviation);
+        }
+
+        let statistics = grouped_statistics([(1, 2.0), (2, 5.0), (1, 4.0)]);
+        assert_eq!(statistics[&1].sample_mean, 3.0);
+        assert_eq!(statistics[&2].n, 1);
+        assert!(statistics[&2].standard_deviation.is_nan());
+        assert!(grouped_statistics::<u8>([]).is_empty());
+    }
+
+    #[test]
+    fn grouped_anova_test() {
+        let mut pairs = plant_growth();
+        pairs.push(("trt3", 5.0));
+        let grouped = grouped_anova(pairs).unwrap();
+        assert_eq!(grouped.groups, ["trt1", "ctrl", "trt2"]);
+        assert_eq!(grouped.excluded, ["trt3"]);
+
+        // R: summary(aov(weight ~ group, data = PlantGrowth))
+        assert_close(grouped.result.f, 4.84608786238013, 1e-10);
+        assert_close(grouped.result.p_value, 0.0159099583256229, 1e-10);
+        let direct = one_way_anova(&[&TRT1, &CTRL, &TRT2]).unwrap();
+        assert_eq!(grouped.result.f, direct.f);
+
+        let error = grouped_anova([("a", 1.0), ("a", 2.0), ("b", 3.0)]).err();
+        assert_eq!(
+            error,
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn grouped_pairwise_t_tests_test() {
+        let mut pairs = plant_growth();
+        pairs.insert(0, ("single", 9.0));
+        let grouped = grouped_pairwise_t_tests(
+            pairs,
+            TTestKind::UnequalVariance,
+            0.05,
+            CorrectionMethod::Holm,
+        )
+        .unwrap();
+        assert_eq!(grouped.excluded, ["single"]);
+        let labels: Vec<(&str, &str)> = grouped
+            .comparisons
+            .iter()
+            .map(|c| (c.group_1, c.group_2))
+            .collect();
+        assert_eq!(
+            labels,
+            [("trt1", "ctrl"), ("trt1", "trt2"), ("ctrl", "trt2")]
+        );
+
+        // R: pairwise.t.test(weight, group, pool.sd = FALSE,
+        //                    p.adjust.method = "holm")
+        let adjusted: Vec<f64> = grouped
+            .comparisons
+            .iter()
+            .map(|c| c.adjusted_p_value)
+            .collect();
+        assert_close(adjusted[0], 0.250382508587548, 1e-12);
+        assert_close(adjusted[1], 0.0278952141518096, 1e-12);
+        assert_close(adjusted[2], 0.0957985112039384, 1e-12);
+        let significant: Vec<bool> = grouped.comparisons.iter().map(|c| c.significant).collect();
+        assert_eq!(significant, [false, true, false]);
+
+        let direct = crate::welch_t_test(
+            &SampleStatistics::from_array(&TRT1),
+            &SampleStatistics::from_array(&CTRL),
+        );
+        assert_eq!(grouped.comparisons[0].result, direct);
+    }
+
+    #[test]
+    fn grouped_pairwise_invalid_input_test() {
+        assert_eq!(
+            grouped_pairwise_t_tests(
+                [("a", 1.0), ("a", 2.0), ("b", 3.0)],
+                TTestKind::EqualVariance,
+                0.05,
+                CorrectionMethod::Bonferroni,
+            )
+            .err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert!(matches!(
+            grouped_pairwise_t_tests(
+                plant_growth(),
+                TTestKind::EqualVariance,
+                1.5,
+                CorrectionMethod::Bonferroni,
+            ),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        // One constant group still has a standard error against the others
+        let one_constant = [("a", 2.0), ("a", 2.0), ("b", 3.0), ("b", 4.5)];
+        let result = grouped_pairwise_t_tests(
+            one_constant,
+            TTestKind::UnequalVariance,
+            0.05,
+            CorrectionMethod::Holm,
+        )
+        .unwrap();
+        assert!(!result.comparisons[0].result.p_value.is_nan());
+        let two_constant = [
+            ("a", 2.0),
+            ("a", 2.0),
+            ("b", 3.0),
+            ("b", 3.0),
+            ("c", 1.0),
+            ("c", 5.0),
+        ];
+        for kind in [TTestKind::EqualVariance, TTestKind::UnequalVariance] {
+            assert_eq!(
+                grouped_pairwise_t_tests(two_constant, kind, 0.05, CorrectionMethod::Holm).err(),
+                Some(StatsError::InvalidParameter(
+                    "two groups have zero variance"
+                ))
+            );
+        }
+    }
+}
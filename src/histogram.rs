@@ -0,0 +1,476 @@
+use crate::descriptive::sorted_quantile;
+use crate::{population_standard_deviation, QuantileMethod, StatsError};
+
+/// How `Histogram::new` chooses its bins. The automatic rules give a bin
+/// width, and the range of the data is split into the fewest equal bins of
+/// at most that width, as numpy's `histogram_bin_edges` does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinSpec {
+    /// This many equal-width bins spanning the data.
+    Count(usize),
+    /// Explicit, strictly increasing bin edges. Values outside them are not
+    /// counted.
+    Edges(Vec<f64>),
+    /// Width range / (log2(n) + 1), suited to roughly normal data.
+    Sturges,
+    /// Width (24 sqrt(π) / n)^(1/3) σ, optimal for normal data.
+    Scott,
+    /// Width 2 IQR / n^(1/3), robust to outliers.
+    FreedmanDiaconis,
+}
+
+/// Counts of data in contiguous bins. Each bin includes its left edge and
+/// excludes its right, except the last, which includes both.
+pub struct Histogram {
+    edges: Vec<f64>,
+    counts: Vec<usize>,
+    total: usize,
+}
+
+impl Histogram {
+    pub fn new(data: &[f64], bins: BinSpec) -> Result<Self, StatsError> {
+        if data.is_empty() {
+            return Err(StatsError::EmptyInput);
+        }
+        if data.iter().any(|x| x.is_nan()) {
+            return Err(StatsError::InvalidParameter(
+                "histogram data must not be NaN",
+            ));
+        }
+
+        let edges = match bins {
+            BinSpec::Edges(edges) => {
+                if edges.len() < 2 {
+                    return Err(StatsError::InvalidParameter(
+                        "a histogram needs at least two edges",
+                    ));
+                }
+                if edges.iter().any(|e| !e.is_finite()) || edges.windows(2).any(|w| w[0] >= w[1]) {
+                    return Err(StatsError::InvalidParameter(
+                        "histogram edges must be finite and strictly increasing",
+                    ));
+                }
+                edges
+            }
+            rule => {
+                if data.iter().any(|x| x.is_infinite()) {
+                    return Err(StatsError::InvalidParameter(
+                        "histogram data must be finite to choose bins",
+                    ));
+                }
+                let mut sorted = data.to_vec();
+                sorted.sort_by(f64::total_cmp);
+                automatic_edges(&sorted, rule)?
+            }
+        };
+
+        let mut histogram = Histogram {
+            counts: vec![0; edges.len() - 1],
+            edges,
+            total: 0,
+        };
+        for &x in data {
+            if let Some(bin) = histogram.find_bin(x) {
+                histogram.counts[bin] += 1;
+                histogram.total += 1;
+            }
+        }
+        Ok(histogram)
+    }
+
+    /// The bin edges, one more than the number of bins.
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// The number of values counted, which excludes values outside explicit
+    /// edges.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Counts divided by the total and the bin width, so that the histogram
+    /// integrates to one, as numpy's `density=True`.
+    pub fn densities(&self) -> Vec<f64> {
+        self.counts
+            .iter()
+            .zip(self.edges.windows(2))
+            .map(|(&count, edge)| count as f64 / (self.total as f64 * (edge[1] - edge[0])))
+            .collect()
+    }
+
+    /// The index of the bin holding `value`, or `None` outside the edges.
+    pub fn find_bin(&self, value: f64) -> Option<usize> {
+        let last = self.edges.len() - 1;
+        if value == self.edges[last] {
+            return Some(last - 1);
+        }
+        match self.edges.partition_point(|&edge| edge <= value) {
+            0 => None,
+            above if above > last => None,
+            above => Some(above - 1),
+        }
+    }
+
+    /// Observed counts and the counts expected under a distribution with
+    /// cumulative distribution function `cdf`, ready for `chi_square_gof`.
+    /// The tails below the first edge and above the last are added to the
+    /// outer bins, so the expected counts sum to the total.
+    pub fn observed_expected(&self, cdf: impl Fn(f64) -> f64) -> (Vec<f64>, Vec<f64>) {
+        let last = self.edges.len() - 1;
+        let probabilities: Vec<f64> = self
+            .edges
+            .iter()
+            .enumerate()
+            .map(|(i, &edge)| match i {
+                0 => 0.0,
+                i if i == last => 1.0,
+                _ => cdf(edge),
+            })
+            .collect();
+        let observed = self.counts.iter().map(|&count| count as f64).collect();
+        let expected = probabilities
+            .windows(2)
+            .map(|p| self.total as f64 * (p[1] - p[0]))
+            .collect();
+        (observed, expected)
+    }
+}
+
+/// What `qcut` does when heavy ties make neighbouring quantile edges equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateEdges {
+    /// Return `StatsError::InvalidParameter`, as pandas'
+    /// `duplicates="raise"`.
+    Error,
+    /// Merge the empty bins away, leaving fewer bins than requested, as
+    /// pandas' `duplicates="drop"`.
+    Drop,
+}
+
+/// Equal-frequency bins learned by `qcut`. Each bin excludes its left edge
+/// and includes its right, except the first, which includes both, as in
+/// pandas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QcutResult {
+    edges: Vec<f64>,
+    counts: Vec<usize>,
+    bins: Vec<usize>,
+    extend_edges: bool,
+}
+
+impl QcutResult {
+    /// The quantile edges, one more than the number of bins.
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Observations in each bin.
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// The bin of each observation, in input order.
+    pub fn bins(&self) -> &[usize] {
+        &self.bins
+    }
+
+    /// With `extend` set, `apply` puts values below the first edge in the
+    /// first bin and values above the last in the last bin, instead of
+    /// returning `None` for them.
+    pub fn extend_edges(self, extend: bool) -> Self {
+        QcutResult {
+            extend_edges: extend,
+            ..self
+        }
+    }
+
+    /// The bin a new observation falls in under the learned edges. `None`
+    /// for NaN, and for values outside the edges unless `extend_edges` is
+    /// set.
+    pub fn apply(&self, value: f64) -> Option<usize> {
+        let last = self.edges.len() - 1;
+        if value.is_nan() {
+            return None;
+        }
+        if !self.extend_edges && (value < self.edges[0] || value > self.edges[last]) {
+            return None;
+        }
+        // edges[above] is the first edge at or above value, so value lies in
+        // (edges[above - 1], edges[above]]; the minimum and anything below
+        // it go in the first bin, anything above the last edge in the last
+        let above = self.edges.partition_point(|&edge| edge < value);
+        Some(above.clamp(1, last) - 1)
+    }
+}
+
+/// Splits `data` into `bins` bins holding about equal numbers of
+/// observations, as pandas' `qcut`: the edges are the linearly
+/// interpolated quantiles at 0, 1 / bins, ..., 1, and each observation is
+/// assigned the bin it falls in. Heavily tied data can give equal edges,
+/// which `duplicates` either rejects or merges.
+///
+/// Returns `StatsError::EmptyInput` for no data, `StatsError::NanInInput`
+/// if it contains NaN, and `StatsError::InvalidParameter` if `bins` is 0,
+/// the data are infinite or all equal, or edges repeat under
+/// `DuplicateEdges::Error`.
+pub fn qcut(
+    data: &[f64],
+    bins: usize,
+    duplicates: DuplicateEdges,
+) -> Result<QcutResult, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if bins == 0 {
+        return Err(StatsError::InvalidParameter("qcut needs at least one bin"));
+    }
+    if let Some(index) = data.iter().position(|x| x.is_nan()) {
+        return Err(StatsError::NanInInput { index });
+    }
+    if data.iter().any(|x| x.is_infinite()) {
+        return Err(StatsError::InvalidParameter("qcut data must be finite"));
+    }
+
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mut edges: Vec<f64> = (0..=bins)
+        .map(|i| sorted_quantile(&sorted, i as f64 / bins as f64, QuantileMethod::Linear))
+        .collect();
+    if edges.windows(2).any(|w| w[0] == w[1]) {
+        if duplicates == DuplicateEdges::Error {
+            return Err(StatsError::InvalidParameter(
+                "quantile bin edges are not unique; merge them with DuplicateEdges::Drop",
+            ));
+        }
+        edges.dedup();
+    }
+    if edges.len() < 2 {
+        return Err(StatsError::InvalidParameter("qcut data are all equal"));
+    }
+
+    let mut result = QcutResult {
+        counts: vec![0; edges.len() - 1],
+        edges,
+        bins: Vec::with_capacity(data.len()),
+        extend_edges: false,
+    };
+    for &x in data {
+        let bin = result.apply(x).expect("observations lie within the edges");
+        result.counts[bin] += 1;
+        result.bins.push(bin);
+    }
+    Ok(result)
+}
+
+fn automatic_edges(sorted: &[f64], bins: BinSpec) -> Result<Vec<f64>, StatsError> {
+    let n = sorted.len() as f64;
+    let (mut low, mut high) = (sorted[0], sorted[sorted.len() - 1]);
+    let range = high - low;
+    let width = match bins {
+        BinSpec::Count(0) => {
+            return Err(StatsError::InvalidParameter(
+                "a histogram needs at least one bin",
+            ))
+        }
+        BinSpec::Count(_) | BinSpec::Edges(_) => 0.0,
+        BinSpec::Sturges => range / (n.log2() + 1.0),
+        BinSpec::Scott => {
+            (24.0 * std::f64::consts::PI.sqrt() / n).cbrt() * population_standard_deviation(sorted)
+        }
+        BinSpec::FreedmanDiaconis => {
+            let iqr = sorted_quantile(sorted, 0.75, QuantileMethod::Linear)
+                - sorted_quantile(sorted, 0.25, QuantileMethod::Linear);
+            2.0 * iqr / n.cbrt()
+        }
+    };
+    let count = match bins {
+        BinSpec::Count(count) => count,
+        // A zero width, from constant data or a zero IQR, gives one bin
+        _ if width > 0.0 => (range / width).ceil().max(1.0) as usize,
+        _ => 1,
+    };
+    if range == 0.0 {
+        low -= 0.5;
+        high += 0.5;
+    }
+
+    let step = (high - low) / count as f64;
+    let mut edges: Vec<f64> = (0..count).map(|i| low + i as f64 * step).collect();
+    edges.push(high);
+    Ok(edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+    use crate::distributions::Normal;
+
+    const DATA: [f64; 20] = [
+        2.3, 4.1, 1.7, 5.6, 3.3, 2.9, 4.8, 3.0, 6.2, 1.1, 3.7, 2.5, 4.4, 3.9, 7.5, 2.2, 3.4, 5.1,
+        2.8, 3.6,
+    ];
+
+    #[test]
+    fn fixed_count_test() {
+        // numpy.histogram(DATA, bins=4)
+        let histogram = Histogram::new(&DATA, BinSpec::Count(4)).unwrap();
+        let edges = [1.1, 2.7, 4.3, 5.9, 7.5];
+        for (actual, expected) in histogram.edges().iter().zip(edges) {
+            assert_close(*actual, expected, 1e-14);
+        }
+        assert_eq!(histogram.counts(), [5, 9, 4, 2]);
+        assert_eq!(histogram.total(), 20);
+        let densities = histogram.densities();
+        let integral: f64 = densities.iter().map(|d| d * 1.6).sum();
+        assert_close(integral, 1.0, 1e-14);
+        assert_close(densities[1], 9.0 / 32.0, 1e-14);
+    }
+
+    #[test]
+    fn edge_convention_test() {
+        let histogram = Histogram::new(
+            &[0.0, 1.0, 1.0, 2.0, 3.0, -1.0],
+            BinSpec::Edges(vec![0.0, 1.0, 2.0, 3.0]),
+        )
+        .unwrap();
+        // Interior edges go right, the last edge is inclusive, -1 is dropped
+        assert_eq!(histogram.counts(), [1, 2, 2]);
+        assert_eq!(histogram.total(), 5);
+        assert_eq!(histogram.find_bin(1.0), Some(1));
+        assert_eq!(histogram.find_bin(0.5), Some(0));
+        assert_eq!(histogram.find_bin(3.0), Some(2));
+        assert_eq!(histogram.find_bin(3.5), None);
+        assert_eq!(histogram.find_bin(-0.5), None);
+    }
+
+    #[test]
+    fn automatic_rules_test() {
+        // numpy.histogram_bin_edges(DATA, bins=rule): widths 6.4 / 5.32 for
+        // Sturges, 0.984 σ for Scott and 2 (4.5 - 2.725) / 20^(1/3) for
+        // Freedman–Diaconis
+        let sturges = Histogram::new(&DATA, BinSpec::Sturges).unwrap();
+        assert_eq!(sturges.counts().len(), 6);
+        let scott = Histogram::new(&DATA, BinSpec::Scott).unwrap();
+        assert_eq!(scott.counts().len(), 4);
+        let fd = Histogram::new(&DATA, BinSpec::FreedmanDiaconis).unwrap();
+        assert_eq!(fd.counts().len(), 5);
+        for histogram in [sturges, scott, fd] {
+            assert_eq!(histogram.counts().iter().sum::<usize>(), 20);
+            assert_eq!(histogram.edges()[0], 1.1);
+            assert_eq!(*histogram.edges().last().unwrap(), 7.5);
+        }
+
+        let constant = Histogram::new(&[2.0; 5], BinSpec::FreedmanDiaconis).unwrap();
+        assert_eq!(constant.edges(), [1.5, 2.5]);
+        assert_eq!(constant.counts(), [5]);
+    }
+
+    #[test]
+    fn observed_expected_test() {
+        let histogram = Histogram::new(&DATA, BinSpec::Edges(vec![1.0, 3.0, 4.0, 8.0])).unwrap();
+        let normal = Normal::new(3.5, 1.5);
+        let (observed, expected) = histogram.observed_expected(|x| normal.cdf(x));
+        assert_eq!(observed, [7.0, 6.0, 7.0]);
+        assert_close(expected.iter().sum(), 20.0, 1e-12);
+        assert_close(
+            expected[1],
+            20.0 * (normal.cdf(4.0) - normal.cdf(3.0)),
+            1e-14,
+        );
+        assert!(crate::chi_square_gof(&observed, &expected).is_ok());
+    }
+
+    #[test]
+    fn invalid_input_test() {
+        assert!(Histogram::new(&[], BinSpec::Sturges).is_err());
+        for bins in [
+            BinSpec::Count(0),
+            BinSpec::Edges(vec![1.0]),
+            BinSpec::Edges(vec![0.0, 2.0, 1.0]),
+        ] {
+            assert!(matches!(
+                Histogram::new(&DATA, bins),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        assert!(matches!(
+            Histogram::new(&[1.0, f64::NAN], BinSpec::Count(2)),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn qcut_test() {
+        // pandas: pd.qcut(DATA, 4, retbins=True)
+        let result = qcut(&DATA, 4, DuplicateEdges::Error).unwrap();
+        let edges = [1.1, 2.725, 3.5, 4.5, 7.5];
+        for (actual, expected) in result.edges().iter().zip(edges) {
+            assert_close(*actual, expected, 1e-14);
+        }
+        assert_eq!(result.counts(), [5, 5, 5, 5]);
+        assert_eq!(
+            result.bins(),
+            [0, 2, 0, 3, 1, 1, 3, 1, 3, 0, 2, 0, 2, 2, 3, 0, 1, 3, 1, 2]
+        );
+
+        // Right edges are inclusive and the first bin takes the minimum
+        assert_eq!(result.apply(1.1), Some(0));
+        assert_eq!(result.apply(3.5), Some(1));
+        assert_eq!(result.apply(3.5000001), Some(2));
+        assert_eq!(result.apply(7.5), Some(3));
+        assert_eq!(result.apply(0.0), None);
+        assert_eq!(result.apply(9.0), None);
+        assert_eq!(result.apply(f64::NAN), None);
+
+        let result = result.extend_edges(true);
+        assert_eq!(result.apply(0.0), Some(0));
+        assert_eq!(result.apply(9.0), Some(3));
+        assert_eq!(result.apply(f64::NAN), None);
+    }
+
+    #[test]
+    fn qcut_duplicate_edges_test() {
+        // pandas: pd.qcut(data, 4, duplicates="drop") gives edges
+        // [1, 1.5, 3.25, 5] and codes [0, 0, 0, 0, 1, 1, 2, 2], in input
+        // order below; duplicates="raise" fails on the repeated edge 1
+        let data = [4.0, 1.0, 2.0, 1.0, 5.0, 1.0, 3.0, 1.0];
+        assert!(matches!(
+            qcut(&data, 4, DuplicateEdges::Error),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        let result = qcut(&data, 4, DuplicateEdges::Drop).unwrap();
+        assert_eq!(result.edges(), [1.0, 1.5, 3.25, 5.0]);
+        assert_eq!(result.counts(), [4, 2, 2]);
+        assert_eq!(result.bins(), [2, 0, 1, 0, 2, 0, 1, 0]);
+    }
+
+    #[test]
+    fn qcut_invalid_input_test() {
+        assert_eq!(
+            qcut(&[], 4, DuplicateEdges::Drop),
+            Err(StatsError::EmptyInput)
+        );
+        assert_eq!(
+            qcut(&[1.0, f64::NAN], 2, DuplicateEdges::Drop),
+            Err(StatsError::NanInInput { index: 1 })
+        );
+        for (data, bins) in [
+            (&DATA[..], 0),
+            (&[3.0, 3.0, 3.0][..], 2),
+            (&[1.0, f64::INFINITY][..], 2),
+        ] {
+            assert!(matches!(
+                qcut(data, bins, DuplicateEdges::Drop),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        let single = qcut(&DATA, 1, DuplicateEdges::Error).unwrap();
+        assert_eq!(single.counts(), [20]);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::StatsError;
+
+/// The alternative hypothesis of a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alternative {
+    /// The parameter differs from its null value in either direction.
+    #[default]
+    TwoSided,
+    /// The parameter is less than its null value.
+    Less,
+    /// The parameter is greater than its null value.
+    Greater,
+}
+
+/// How a test of small counts turns the distribution of its statistic into
+/// a p-value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PValueMethod {
+    /// The probability of outcomes at least as extreme as the observed one,
+    /// which is conservative: below level alpha less than alpha of the time.
+    #[default]
+    Exact,
+    /// The exact p-value with the observed outcome counted at half its
+    /// probability, and for two-sided tests every outcome as likely as it
+    /// too, which comes closer to the nominal level at the cost of occasionally
+    /// exceeding it.
+    MidP,
+    /// A normal or chi-square approximation, without continuity correction
+    /// unless the test says otherwise.
+    Asymptotic,
+}
+
+/// Checks that a significance level lies strictly between 0 and 1.
+pub(crate) fn validate_alpha(alpha: f64) -> Result<(), StatsError> {
+    if alpha > 0.0 && alpha < 1.0 {
+        Ok(())
+    } else {
+        Err(StatsError::InvalidParameter(
+            "alpha must be between 0 and 1",
+        ))
+    }
+}
+
+/// The common shape of a hypothesis test result: a test statistic and the
+/// p-value computed from it.
+///
+/// Results from different tests can be collected as `&dyn HypothesisTest`,
+/// for example to adjust their p-values together. Tests whose reference
+/// distribution has two degrees of freedom, such as F-tests, report `None`
+/// from [`degrees_of_freedom`](HypothesisTest::degrees_of_freedom); both are
+/// available on the result struct itself.
+pub trait HypothesisTest {
+    /// The test statistic.
+    fn statistic(&self) -> f64;
+
+    /// The p-value of the test.
+    fn p_value(&self) -> f64;
+
+    /// The degrees of freedom of the reference distribution, if it has
+    /// exactly one.
+    fn degrees_of_freedom(&self) -> Option<f64>;
+
+    /// A short human-readable name of the test.
+    fn test_name(&self) -> &'static str;
+
+    /// Whether the null hypothesis is rejected at significance level `alpha`.
+    fn is_significant(&self, alpha: f64) -> bool {
+        self.p_value() < alpha
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        binomial_test, mcnemar_test, one_way_anova, tost_equivalence, welch_t_test, GetStatistics,
+        SampleStatistics,
+    };
+
+    fn bonferroni_rejections(tests: &[&dyn HypothesisTest], alpha: f64) -> usize {
+        let alpha = alpha / tests.len() as f64;
+        tests
+            .iter()
+            .filter(|test| test.is_significant(alpha))
+            .count()
+    }
+
+    #[test]
+    fn heterogeneous_results_test() {
+        let x = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+        let y = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+        let samp_x = SampleStatistics::from_array(&x);
+        let samp_y = SampleStatistics::from_array(&y);
+
+        let t = welch_t_test(&samp_x, &samp_y);
+        let tost = tost_equivalence(&samp_x, &samp_y, -2.0, 2.0, 0.05).unwrap();
+        let (t_lower, p_lower) = (tost.t_lower, tost.p_lower);
+        let anova = one_way_anova(&[&x, &y]).unwrap();
+        let mcnemar = mcnemar_test([[794, 150], [86, 570]], false).unwrap();
+        let binomial = binomial_test(7, 20, 0.5, Alternative::TwoSided).unwrap();
+
+        let results: Vec<Box<dyn HypothesisTest>> = vec![
+            Box::new(t),
+            Box::new(tost),
+            Box::new(anova),
+            Box::new(mcnemar),
+            Box::new(binomial),
+        ];
+
+        let names: Vec<_> = results.iter().map(|result| result.test_name()).collect();
+        assert_eq!(
+            names,
+            [
+                "t-test",
+                "TOST equivalence test",
+                "one-way ANOVA",
+                "McNemar's test",
+                "exact binomial test"
+            ]
+        );
+
+        assert_eq!(results[0].statistic(), t.t);
+        assert_eq!(results[0].p_value(), t.p_value);
+        assert_eq!(results[0].degrees_of_freedom(), Some(t.df));
+        // The binding one-sided test is the lower one here.
+        assert_eq!(results[1].statistic(), t_lower);
+        assert_eq!(results[1].p_value(), p_lower);
+        // F-tests have two degrees of freedom.
+        assert_eq!(results[2].degrees_of_freedom(), None);
+        assert_eq!(results[3].degrees_of_freedom(), Some(1.0));
+        assert_eq!(results[4].statistic(), 7.0);
+        assert_eq!(results[4].degrees_of_freedom(), None);
+
+        for result in &results {
+            assert_eq!(result.is_significant(0.05), result.p_value() < 0.05);
+        }
+
+        let borrowed: Vec<&dyn HypothesisTest> = results.iter().map(|result| &**result).collect();
+        assert_eq!(bonferroni_rejections(&borrowed, 0.05), 1);
+        assert_eq!(bonferroni_rejections(&borrowed, 0.5), 3);
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt;
+
+use crate::distributions::{Kolmogorov, Normal};
+use crate::format::write_test_line;
+use crate::{Ecdf, HypothesisTest, StatsError};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KsTestResult {
+    /// The largest absolute difference between the empirical and reference
+    /// CDFs.
+    pub d: f64,
+    /// Two-sided p-value from the asymptotic Kolmogorov distribution.
+    pub p_value: f64,
+}
+
+impl fmt::Display for KsTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "D", &[], self.d, self.p_value)
+    }
+}
+
+impl HypothesisTest for KsTestResult {
+    fn statistic(&self) -> f64 {
+        self.d
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Kolmogorov-Smirnov test"
+    }
+}
+
+/// One-sample Kolmogorov-Smirnov test that `data` was drawn from the
+/// continuous distribution with the given CDF.
+pub fn ks_test<F: Fn(f64) -> f64>(data: &[f64], cdf: F) -> Result<KsTestResult, StatsError> {
+    let ecdf = Ecdf::new(data)?;
+    let n = ecdf.len() as f64;
+    // The gap is largest at a step, either just above it or just below
+    let mut below = 0.0;
+    let mut d: f64 = 0.0;
+    for (x, above) in ecdf.steps() {
+        let f = cdf(x);
+        d = d.max(above - f).max(f - below);
+        below = above;
+    }
+
+    Ok(KsTestResult {
+        d,
+        p_value: Kolmogorov::sf(n.sqrt() * d),
+    })
+}
+
+/// Kolmogorov-Smirnov test against a normal distribution with a known mean
+/// and standard deviation. Estimating them from the same data makes this
+/// test conservative; use a dedicated normality test in that case.
+pub fn ks_test_normal(data: &[f64], mean: f64, sd: f64) -> Result<KsTestResult, StatsError> {
+    if sd.is_nan() || sd <= 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "standard deviation must be positive",
+        ));
+    }
+    let normal = Normal::new(mean, sd);
+    ks_test(data, |x| normal.cdf(x))
+}
+
+/// Two-sample Kolmogorov-Smirnov test that both samples come from the same
+/// continuous distribution. The p-value uses the asymptotic Kolmogorov
+/// distribution with the effective sample size n1 n2 / (n1 + n2).
+pub fn ks_2samp(sample_1: &[f64], sample_2: &[f64]) -> Result<KsTestResult, StatsError> {
+    let ecdf_1 = Ecdf::new(sample_1)?;
+    let ecdf_2 = Ecdf::new(sample_2)?;
+    let n1 = ecdf_1.len() as f64;
+    let n2 = ecdf_2.len() as f64;
+
+    // Both ECDFs are evaluated past every copy of each value, so ties across
+    // the samples do not count as a gap
+    let d = ecdf_1
+        .steps()
+        .chain(ecdf_2.steps())
+        .map(|(value, _)| (ecdf_1.eval(value) - ecdf_2.eval(value)).abs())
+        .fold(0.0, f64::max);
+
+    let effective_n = n1 * n2 / (n1 + n2);
+    Ok(KsTestResult {
+        d,
+        p_value: Kolmogorov::sf(effective_n.sqrt() * d),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const Z: [f64; 20] = [
+        0.31, -1.42, 0.77, 1.95, -0.33, 0.12, -0.88, 2.41, 0.54, -0.07, 1.23, -1.71, 0.66, 0.98,
+        -0.25, 1.62, 0.05, -0.61, 1.37, 0.89,
+    ];
+
+    #[test]
+    fn ks_test_uniform_test() {
+        // scipy.stats.kstest(u, "uniform", method="asymp")
+        let u = [
+            0.12, 0.85, 0.33, 0.47, 0.91, 0.05, 0.64, 0.58, 0.29, 0.77, 0.41, 0.96, 0.18, 0.69,
+            0.52,
+        ];
+        let result = ks_test(&u, |x| x.clamp(0.0, 1.0)).unwrap();
+        assert_close(result.d, 0.09, 1e-12);
+        assert_close(result.p_value, 0.999720093503485, 1e-12);
+    }
+
+    #[test]
+    fn ks_test_normal_test() {
+        // scipy.stats.kstest(z, "norm", method="asymp")
+        let result = ks_test_normal(&Z, 0.0, 1.0).unwrap();
+        assert_close(result.d, 0.205401483784302, 1e-12);
+        assert_close(result.p_value, 0.367588813257227, 1e-12);
+
+        // scipy.stats.kstest(z, "norm", args=(0.5, 2), method="asymp")
+        let result = ks_test_normal(&Z, 0.5, 2.0).unwrap();
+        assert_close(result.d, 0.187739718849027, 1e-12);
+        assert_close(result.p_value, 0.481256867375054, 1e-12);
+    }
+
+    #[test]
+    fn ks_2samp_test() {
+        // R: ks.test(x, y, exact = FALSE)
+        let shifted = [
+            1.82, 2.95, 1.12, 3.41, 2.07, 0.66, 2.73, 1.58, 3.09, 2.24, 1.35, 2.88, 0.98, 2.51,
+            1.77, 3.62, 2.19,
+        ];
+        let result = ks_2samp(&Z, &shifted).unwrap();
+        assert_close(result.d, 0.641176470588235, 1e-12);
+        assert_close(result.p_value, 0.00104646473337006, 1e-12);
+
+        let same = [
+            -0.52, 0.44, 1.08, -1.26, 0.17, 0.93, -0.38, 1.51, -0.84, 0.29, 0.71, -0.15, 1.24,
+            -0.97, 0.58,
+        ];
+        let result = ks_2samp(&Z, &same).unwrap();
+        assert_close(result.d, 0.15, 1e-12);
+        assert_close(result.p_value, 0.990487271204685, 1e-12);
+    }
+
+    #[test]
+    fn ks_2samp_ties_test() {
+        let result = ks_2samp(
+            &[1.0, 2.0, 2.0, 3.0, 3.0, 4.0],
+            &[2.0, 3.0, 3.0, 3.0, 5.0, 6.0, 6.0],
+        )
+        .unwrap();
+        assert_close(result.d, 0.428571428571429, 1e-12);
+        assert_close(result.p_value, 0.593079549945346, 1e-12);
+    }
+
+    #[test]
+    fn ks_test_invalid_input_test() {
+        assert_eq!(ks_test(&[], |x| x).err(), Some(StatsError::EmptyInput));
+        assert!(matches!(
+            ks_test_normal(&Z, 0.0, 0.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
@@ -1,12 +1,220 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+mod agreement;
+#[cfg(feature = "std")]
+mod anova;
+#[cfg(feature = "std")]
+mod chi_square;
+pub mod combinatorics;
+mod confidence;
+#[cfg(feature = "std")]
+mod correlation;
+#[cfg(feature = "std")]
+mod describe;
+#[cfg(feature = "std")]
+mod descriptive;
+pub mod distributions;
+#[cfg(feature = "std")]
+mod ecdf;
+#[cfg(feature = "std")]
+mod effect_size;
+#[cfg(feature = "std")]
+mod epidemiology;
+mod error;
+#[cfg(feature = "std")]
+mod exact;
+#[cfg(feature = "std")]
+mod fitting;
+mod float;
+#[cfg(feature = "alloc")]
+mod format;
+#[cfg(feature = "std")]
+mod grouped;
+#[cfg(feature = "std")]
+mod histogram;
+#[cfg(feature = "std")]
+mod hodges_lehmann;
+mod hypothesis;
+#[cfg(feature = "std")]
+mod kolmogorov_smirnov;
+#[cfg(feature = "std")]
+mod l_moments;
+#[cfg(feature = "std")]
+mod linalg;
+#[cfg(feature = "std")]
+mod logistic;
+#[cfg(not(feature = "std"))]
+mod math;
+#[cfg(feature = "std")]
+mod meta_analysis;
+#[cfg(feature = "std")]
+mod multiple_testing;
+#[cfg(feature = "std")]
+mod nan_policy;
+#[cfg(feature = "ndarray")]
+mod ndarray_interop;
+#[cfg(feature = "std")]
+mod nonparametric;
+#[cfg(feature = "std")]
+mod normality;
+#[cfg(feature = "std")]
+mod outliers;
+#[cfg(feature = "std")]
+mod paired_proportions;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "std")]
+mod power;
+#[cfg(feature = "std")]
+mod probability_plot;
+#[cfg(feature = "alloc")]
+mod rank;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+mod regression;
+#[cfg(feature = "std")]
+mod reliability;
+#[cfg(feature = "std")]
+mod resampling;
+#[cfg(feature = "std")]
+mod rng;
+mod running_statistics;
+#[cfg(feature = "alloc")]
+mod selection;
+#[cfg(all(test, feature = "serde"))]
+mod serde_round_trip;
+#[cfg(feature = "std")]
+pub mod simulate;
+pub mod special;
+#[cfg(feature = "std")]
+mod summary;
+mod summation;
+mod t_test;
+#[cfg(feature = "std")]
+mod time_series;
+#[cfg(feature = "std")]
+mod tolerance;
+#[cfg(feature = "std")]
+mod trend;
+#[cfg(feature = "std")]
+mod variance;
+#[cfg(feature = "std")]
+mod z_test;
+
+#[cfg(feature = "std")]
+pub use agreement::*;
+#[cfg(feature = "std")]
+pub use anova::*;
+#[cfg(feature = "std")]
+pub use chi_square::*;
+pub use confidence::{
+    mean_confidence_interval, prediction_interval, proportion_confidence_interval,
+    ConfidenceInterval, ProportionCiMethod,
+};
+#[cfg(feature = "std")]
+pub use correlation::*;
+#[cfg(feature = "std")]
+pub use describe::*;
+#[cfg(feature = "std")]
+pub use descriptive::*;
+pub use distributions::chi_square_quantile;
+#[cfg(feature = "std")]
+pub use ecdf::*;
+#[cfg(feature = "std")]
+pub use effect_size::*;
+#[cfg(feature = "std")]
+pub use epidemiology::*;
+pub use error::StatsError;
+#[cfg(feature = "std")]
+pub use exact::*;
+#[cfg(feature = "std")]
+pub use fitting::*;
+pub use float::Float;
+#[cfg(feature = "std")]
+pub use grouped::*;
+#[cfg(feature = "std")]
+pub use histogram::*;
+#[cfg(feature = "std")]
+pub use hodges_lehmann::*;
+pub use hypothesis::{Alternative, HypothesisTest, PValueMethod};
+#[cfg(feature = "std")]
+pub use kolmogorov_smirnov::*;
+#[cfg(feature = "std")]
+pub use l_moments::*;
+#[cfg(feature = "std")]
+pub use logistic::*;
+#[cfg(feature = "std")]
+pub use meta_analysis::*;
+#[cfg(feature = "std")]
+pub use multiple_testing::*;
+#[cfg(feature = "std")]
+pub use nan_policy::*;
+#[cfg(feature = "ndarray")]
+pub use ndarray_interop::*;
+#[cfg(feature = "std")]
+pub use nonparametric::*;
+#[cfg(feature = "std")]
+pub use normality::*;
+#[cfg(feature = "std")]
+pub use outliers::*;
+#[cfg(feature = "std")]
+pub use paired_proportions::*;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+#[cfg(feature = "std")]
+pub use power::*;
+#[cfg(feature = "std")]
+pub use probability_plot::*;
+#[cfg(feature = "alloc")]
+pub use rank::{rank, tie_term, RankMethod};
+#[cfg(feature = "std")]
+pub use reader::ReadError;
+#[cfg(feature = "std")]
+pub use regression::*;
+#[cfg(feature = "std")]
+pub use reliability::*;
+#[cfg(feature = "std")]
+pub use resampling::*;
+pub use running_statistics::*;
+#[cfg(feature = "alloc")]
+pub use selection::*;
+#[cfg(feature = "std")]
+pub use summary::*;
+pub use t_test::*;
+#[cfg(feature = "std")]
+pub use time_series::*;
+#[cfg(feature = "std")]
+pub use tolerance::*;
+#[cfg(feature = "std")]
+pub use trend::*;
+#[cfg(feature = "std")]
+pub use variance::*;
+#[cfg(feature = "std")]
+pub use z_test::*;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use math::F64Ext;
+use summation::{two_product, two_sum, CompensatedSum};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SampleStatistics {
     pub sample_mean: f64,
-    pub standard_error: f64,
+    pub standard_deviation: f64,
     pub n: usize,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PopulationStatistics {
     pub population_mean: f64,
-    pub standard_error: f64,
+    pub standard_deviation: f64,
     pub n: usize,
 }
 
@@ -14,122 +222,559 @@ pub trait GetStatistics {
     fn from_array(array: &[f64]) -> Self;
 }
 
-impl GetStatistics for SampleStatistics {
-    fn from_array(array: &[f64]) -> Self {
-        let n = array.len();
-        let sample_mean = mean(&array);
-        let standard_error = sample_standard_deviation(&array);
+impl SampleStatistics {
+    /// Statistics from a reported mean, sample standard deviation and sample
+    /// size, for running the t-tests and effect sizes against published
+    /// results without the raw data.
+    pub fn from_summary(
+        sample_mean: f64,
+        standard_deviation: f64,
+        n: usize,
+    ) -> Result<Self, StatsError> {
+        validate_summary(sample_mean, standard_deviation, n)?;
+        Ok(SampleStatistics {
+            sample_mean,
+            standard_deviation,
+            n,
+        })
+    }
+
+    /// `from_array`, with an error for fewer than two values, where the
+    /// standard deviation and every t-test built on it would be NaN.
+    pub fn try_from_array(array: &[f64]) -> Result<Self, StatsError> {
+        require_sample(array)?;
+        Ok(SampleStatistics::from_array(array))
+    }
+
+    /// Summarizes weighted observations for the t-tests: the weighted mean,
+    /// the standard deviation from the reliability-weighted variance, and as
+    /// `n` Kish's effective sample size (Σ w)² / Σ w² rounded to the nearest
+    /// integer, so that unequal weights cost degrees of freedom.
+    #[cfg(feature = "std")]
+    pub fn from_weighted(values: &[f64], weights: &[f64]) -> Result<Self, StatsError> {
+        let standard_deviation =
+            weighted_variance(values, weights, WeightKind::Reliability)?.sqrt();
+        Ok(SampleStatistics {
+            sample_mean: weighted_mean(values, weights)?,
+            standard_deviation,
+            n: descriptive::effective_sample_size(weights).round() as usize,
+        })
+    }
+
+    /// Sample variance s², from the stored standard deviation.
+    pub fn variance(&self) -> f64 {
+        self.standard_deviation.powi(2)
+    }
+
+    /// Standard error of the mean, s / sqrt(n).
+    pub fn standard_error(&self) -> f64 {
+        standard_error_from_sd(self.standard_deviation, self.n)
+    }
+
+    /// Standard error of the mean under its usual abbreviation; the same as
+    /// `standard_error`.
+    pub fn sem(&self) -> f64 {
+        self.standard_error()
+    }
+
+    /// Coefficient of variation s / x̄; see `coefficient_of_variation`.
+    #[cfg(feature = "std")]
+    pub fn coefficient_of_variation(&self) -> Result<f64, StatsError> {
+        descriptive::variation_ratio(self.standard_deviation, self.sample_mean)
+    }
 
+    /// The statistics of the combined data of `self` and `other`, pooled
+    /// from the means and standard deviations alone with the parallel
+    /// variance formula of Chan et al. A value with n = 0 leaves the other
+    /// unchanged, and two of them merge to a standard deviation of 0.
+    pub fn merge(&self, other: &SampleStatistics) -> SampleStatistics {
+        let squared_deviations = |s: &SampleStatistics| match s.n {
+            0 | 1 => 0.0,
+            n => s.variance() * (n - 1) as f64,
+        };
+        let (n, sample_mean, m2) = merge_moments(
+            (self.n, self.sample_mean, squared_deviations(self)),
+            (other.n, other.sample_mean, squared_deviations(other)),
+        );
+        // Two empty groups would give sqrt(0 / -1) = -0.0
+        let standard_deviation = match n {
+            0 => 0.0,
+            n => (m2 / (n as f64 - 1.0)).sqrt(),
+        };
         SampleStatistics {
             sample_mean,
-            standard_error,
+            standard_deviation,
+            n,
+        }
+    }
+}
+
+impl PopulationStatistics {
+    /// `from_array`, with an error for an empty slice.
+    pub fn try_from_array(array: &[f64]) -> Result<Self, StatsError> {
+        try_mean(array)?;
+        Ok(PopulationStatistics::from_array(array))
+    }
+
+    /// Statistics from a known mean, standard deviation and size; see
+    /// `SampleStatistics::from_summary`.
+    pub fn from_summary(
+        population_mean: f64,
+        standard_deviation: f64,
+        n: usize,
+    ) -> Result<Self, StatsError> {
+        validate_summary(population_mean, standard_deviation, n)?;
+        Ok(PopulationStatistics {
+            population_mean,
+            standard_deviation,
+            n,
+        })
+    }
+
+    /// Standard error of the mean, sigma / sqrt(n).
+    pub fn standard_error(&self) -> f64 {
+        standard_error_from_sd(self.standard_deviation, self.n)
+    }
+
+    /// The statistics of the combined populations; see
+    /// `SampleStatistics::merge`.
+    pub fn merge(&self, other: &PopulationStatistics) -> PopulationStatistics {
+        let squared_deviations = |s: &PopulationStatistics| match s.n {
+            0 => 0.0,
+            n => s.standard_deviation.powi(2) * n as f64,
+        };
+        let (n, population_mean, m2) = merge_moments(
+            (self.n, self.population_mean, squared_deviations(self)),
+            (other.n, other.population_mean, squared_deviations(other)),
+        );
+        // Two empty populations would give sqrt(0 / 0) = NaN
+        let standard_deviation = match n {
+            0 => 0.0,
+            n => (m2 / n as f64).sqrt(),
+        };
+        PopulationStatistics {
+            population_mean,
+            standard_deviation,
             n,
         }
     }
 }
 
+fn require_sample(array: &[f64]) -> Result<(), StatsError> {
+    if array.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: array.len(),
+        });
+    }
+    Ok(())
+}
+
+fn validate_summary(mean: f64, standard_deviation: f64, n: usize) -> Result<(), StatsError> {
+    if n == 0 {
+        return Err(StatsError::EmptyInput);
+    }
+    if !mean.is_finite() {
+        return Err(StatsError::InvalidParameter("mean must be finite"));
+    }
+    if !(standard_deviation >= 0.0 && standard_deviation.is_finite()) {
+        return Err(StatsError::InvalidParameter(
+            "standard deviation must be non-negative and finite",
+        ));
+    }
+    Ok(())
+}
+
+impl GetStatistics for SampleStatistics {
+    fn from_array(array: &[f64]) -> Self {
+        SampleStatistics::from_array_of(array)
+    }
+}
+
 impl GetStatistics for PopulationStatistics {
     fn from_array(array: &[f64]) -> Self {
-        let n = array.len();
-        let population_mean = mean(&array);
-        let standard_error = population_standard_deviation(&array);
+        PopulationStatistics::from_array_of(array)
+    }
+}
+
+impl SampleStatistics {
+    /// `from_array` for f32 or f64 values, accumulated in f64.
+    pub fn from_array_of<T: Float>(array: &[T]) -> Self {
+        let (n, sample_mean, m2) = single_pass_moments(array.iter().copied());
+        SampleStatistics {
+            sample_mean,
+            standard_deviation: (m2 / (n as f64 - 1.0)).sqrt(),
+            n,
+        }
+    }
+}
 
+impl PopulationStatistics {
+    /// `from_array` for f32 or f64 values, accumulated in f64.
+    pub fn from_array_of<T: Float>(array: &[T]) -> Self {
+        let (n, population_mean, m2) = single_pass_moments(array.iter().copied());
+        // Two empty populations would give sqrt(0 / 0) = NaN
+        let standard_deviation = match n {
+            0 => 0.0,
+            n => (m2 / n as f64).sqrt(),
+        };
         PopulationStatistics {
             population_mean,
-            standard_error,
+            standard_deviation,
             n,
         }
     }
 }
 
+/// An owned set of observations treated as a whole population, with the
+/// divisor-n variance.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Population {
-    pub population: [f64],
+    values: Vec<f64>,
 }
 
+#[cfg(feature = "alloc")]
+impl Population {
+    pub fn new(values: Vec<f64>) -> Self {
+        Population { values }
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// NaN when empty.
+    pub fn mean(&self) -> f64 {
+        mean(&self.values)
+    }
+
+    /// `population_variance`, with divisor n.
+    pub fn variance(&self) -> f64 {
+        population_variance(&self.values)
+    }
+
+    pub fn standard_deviation(&self) -> f64 {
+        population_standard_deviation(&self.values)
+    }
+
+    pub fn statistics(&self) -> PopulationStatistics {
+        PopulationStatistics::from_array(&self.values)
+    }
+
+    /// The observations, under the name of the old public field.
+    #[deprecated(note = "use `values`")]
+    pub fn population(&self) -> &[f64] {
+        &self.values
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Vec<f64>> for Population {
+    fn from(values: Vec<f64>) -> Self {
+        Population::new(values)
+    }
+}
+
+/// An owned sample of observations from a larger population, with the
+/// divisor n - 1 variance.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Sample {
-    pub sample: [f64],
+    values: Vec<f64>,
 }
 
-trait StandDev {
+#[cfg(feature = "alloc")]
+impl Sample {
+    pub fn new(values: Vec<f64>) -> Self {
+        Sample { values }
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// NaN when empty.
+    pub fn mean(&self) -> f64 {
+        mean(&self.values)
+    }
+
+    /// `sample_variance`, with divisor n - 1; NaN for fewer than two values.
+    pub fn variance(&self) -> f64 {
+        sample_variance(&self.values)
+    }
+
+    pub fn standard_deviation(&self) -> f64 {
+        sample_standard_deviation(&self.values)
+    }
+
+    pub fn statistics(&self) -> SampleStatistics {
+        SampleStatistics::from_array(&self.values)
+    }
+
+    /// The observations, under the name of the old public field.
+    #[deprecated(note = "use `values`")]
+    pub fn sample(&self) -> &[f64] {
+        &self.values
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Vec<f64>> for Sample {
+    fn from(values: Vec<f64>) -> Self {
+        Sample::new(values)
+    }
+}
+
+/// The standard deviation of a slice with the divisor of the implementing
+/// type, from before `Sample` and `Population` held their data.
+#[cfg(feature = "alloc")]
+#[deprecated(note = "use `Sample::standard_deviation` or `sample_standard_deviation`")]
+pub trait StandDev {
     fn standard_deviation(array: &[f64]) -> f64;
 }
 
+#[cfg(feature = "alloc")]
+#[allow(deprecated)]
 impl StandDev for Population {
     fn standard_deviation(array: &[f64]) -> f64 {
-        let n = array.len();
-        let p_mean = mean(&array);
-
-        let mut sum = 0.0;
-        for xi in array.into_iter() {
-            sum += f64::powf(xi - p_mean, 2.0)
-        }
-
-        sum = sum / (n as f64);
-        sum.sqrt()
+        population_standard_deviation(array)
     }
 }
 
+#[cfg(feature = "alloc")]
+#[allow(deprecated)]
 impl StandDev for Sample {
     fn standard_deviation(array: &[f64]) -> f64 {
-        let n = array.len();
-        let s_mean = mean(&array);
+        sample_standard_deviation(array)
+    }
+}
 
-        let mut sum = 0.0;
-        for xi in array.into_iter() {
-            sum += f64::powf(xi - s_mean, 2.0)
-        }
+/// The arithmetic mean; NaN for an empty slice. See `try_mean` for a
+/// checked version.
+pub fn mean(list: &[f64]) -> f64 {
+    mean_of(list)
+}
+
+/// `mean` for f32 or f64 values, accumulated in f64.
+pub fn mean_of<T: Float>(list: &[T]) -> f64 {
+    mean_iter(list.iter().copied())
+}
 
-        sum = sum / (n as f64 - 1.0);
-        sum.sqrt()
+/// The arithmetic mean of values produced lazily, in a single pass; NaN if
+/// there are none. The sum is compensated, so a long run of values with a
+/// large common offset does not lose precision.
+pub fn mean_iter<T: Float>(values: impl IntoIterator<Item = T>) -> f64 {
+    let mut sum = CompensatedSum::default();
+    let mut count = 0usize;
+    for x in values {
+        sum.add(x.to_f64());
+        count += 1;
     }
+    sum.total() / count as f64
 }
 
-pub fn mean(list: &[f64]) -> f64 {
-    let sum: f64 = Iterator::sum(list.iter());
-    f64::from(sum) / (list.len() as f64)
+/// Sample variance, with divisor n - 1; NaN for fewer than two values.
+pub fn sample_variance(array: &[f64]) -> f64 {
+    sample_variance_of(array)
+}
+
+/// `sample_variance` for f32 or f64 values, accumulated in f64.
+pub fn sample_variance_of<T: Float>(array: &[T]) -> f64 {
+    if array.len() < 2 {
+        return f64::NAN;
+    }
+    squared_deviations(array) / (array.len() as f64 - 1.0)
 }
 
+/// Population variance, with divisor n; NaN for an empty slice and zero for
+/// a single value.
+pub fn population_variance(array: &[f64]) -> f64 {
+    population_variance_of(array)
+}
+
+/// `population_variance` for f32 or f64 values, accumulated in f64.
+pub fn population_variance_of<T: Float>(array: &[T]) -> f64 {
+    squared_deviations(array) / array.len() as f64
+}
+
+/// The square root of `sample_variance`; NaN for fewer than two values.
 pub fn sample_standard_deviation(array: &[f64]) -> f64 {
-    let n = array.len();
-    let s_mean = mean(&array);
+    sample_standard_deviation_of(array)
+}
+
+/// `sample_standard_deviation` for f32 or f64 values.
+pub fn sample_standard_deviation_of<T: Float>(array: &[T]) -> f64 {
+    sample_variance_of(array).sqrt()
+}
 
-    let mut sum = 0.0;
-    for xi in array.into_iter() {
-        sum += f64::powf(xi - s_mean, 2.0) as f64;
+/// The square root of `population_variance`; NaN for an empty slice.
+pub fn population_standard_deviation(array: &[f64]) -> f64 {
+    population_standard_deviation_of(array)
+}
+
+/// `population_standard_deviation` for f32 or f64 values.
+pub fn population_standard_deviation_of<T: Float>(array: &[T]) -> f64 {
+    population_variance_of(array).sqrt()
+}
+
+/// `sample_standard_deviation` about a mean the caller already has, which
+/// saves the pass that computes it. `None` computes the mean as usual.
+pub fn sample_standard_deviation_given_mean(array: &[f64], mean: Option<f64>) -> f64 {
+    if array.len() < 2 {
+        return f64::NAN;
     }
+    (squared_deviations_given_mean(array, mean) / (array.len() as f64 - 1.0)).sqrt()
+}
 
-    sum = sum / (n as f64 - 1.0);
-    sum.sqrt()
+/// `population_standard_deviation` about a mean the caller already has; see
+/// `sample_standard_deviation_given_mean`.
+pub fn population_standard_deviation_given_mean(array: &[f64], mean: Option<f64>) -> f64 {
+    (squared_deviations_given_mean(array, mean) / array.len() as f64).sqrt()
 }
 
-pub fn population_standard_deviation(array: &[f64]) -> f64 {
-    let n = array.len();
-    let p_mean = mean(&array);
+/// `mean`, with an error instead of NaN for an empty slice.
+pub fn try_mean(list: &[f64]) -> Result<f64, StatsError> {
+    if list.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    Ok(mean(list))
+}
+
+/// `sample_variance`, with an error instead of NaN for fewer than two values.
+pub fn try_sample_variance(array: &[f64]) -> Result<f64, StatsError> {
+    require_sample(array)?;
+    Ok(sample_variance(array))
+}
+
+/// `population_variance`, with an error instead of NaN for an empty slice.
+pub fn try_population_variance(array: &[f64]) -> Result<f64, StatsError> {
+    try_mean(array)?;
+    Ok(population_variance(array))
+}
+
+/// `sample_standard_deviation`, with an error instead of NaN for fewer than
+/// two values.
+pub fn try_sample_standard_deviation(array: &[f64]) -> Result<f64, StatsError> {
+    Ok(try_sample_variance(array)?.sqrt())
+}
+
+/// `population_standard_deviation`, with an error instead of NaN for an
+/// empty slice.
+pub fn try_population_standard_deviation(array: &[f64]) -> Result<f64, StatsError> {
+    Ok(try_population_variance(array)?.sqrt())
+}
+
+/// Standard error of the mean, s / sqrt(n) with the sample standard
+/// deviation, as scipy's `sem`.
+pub fn standard_error_of_mean(array: &[f64]) -> f64 {
+    standard_error_from_sd(sample_standard_deviation(array), array.len())
+}
+
+/// Standard error of the mean from a summary standard deviation and sample
+/// size, for when the raw data is not available.
+pub fn standard_error_from_sd(standard_deviation: f64, n: usize) -> f64 {
+    standard_deviation / (n as f64).sqrt()
+}
 
-    let mut sum = 0.0;
-    for xi in array.into_iter() {
-        sum += f64::powf(xi - p_mean, 2.0) as f64;
+/// Combines (n, mean, sum of squared deviations) of two groups into those of
+/// their union. An empty group is skipped so its mean, which may be NaN, does
+/// not reach the result.
+pub(crate) fn merge_moments(a: (usize, f64, f64), b: (usize, f64, f64)) -> (usize, f64, f64) {
+    let ((n_a, mean_a, m2_a), (n_b, mean_b, m2_b)) = (a, b);
+    if n_a == 0 {
+        return b;
+    }
+    if n_b == 0 {
+        return a;
     }
+    let n = n_a + n_b;
+    let delta = mean_b - mean_a;
+    let mean = mean_a + delta * n_b as f64 / n as f64;
+    let m2 = m2_a + m2_b + delta * delta * (n_a as f64 * n_b as f64) / n as f64;
+    (n, mean, m2)
+}
+
+fn squared_deviations<T: Float>(array: &[T]) -> f64 {
+    squared_deviations_iter(array.iter().copied())
+}
 
-    sum = sum / (n as f64);
-    sum.sqrt()
+fn squared_deviations_given_mean(array: &[f64], mean: Option<f64>) -> f64 {
+    match mean {
+        Some(mean) => squared_deviations_about(array.iter().copied(), mean),
+        None => squared_deviations(array),
+    }
 }
 
-pub struct TTestResult {
-    pub t: f64,
-    pub p_value: f64,
+/// (n, mean, sum of squared deviations) in one pass, from sums of the
+/// values and of their deviations from a pivot, the first value. Shifting
+/// by a value inside the data keeps Σd² - (Σd)² / n from cancelling the way
+/// raw sums of squares do when the values share a large offset, and the
+/// rounding error of every deviation, square and addition is carried in a
+/// low part, so what cancellation remains exposes errors far below an ulp.
+/// Without the low parts a pivot far from the mean, as when the first value
+/// is an outlier, still costs up to about n ulps, and `from_array` would
+/// no longer agree with the two-pass standard deviation it replaced. The
+/// mean is taken from the plain sum, so it matches `mean`.
+pub(crate) fn single_pass_moments<T: Float>(values: impl Iterator<Item = T>) -> (usize, f64, f64) {
+    let mut values = values.map(|x| x.to_f64()).peekable();
+    let pivot = match values.peek() {
+        Some(x) if x.is_finite() => *x,
+        _ => 0.0,
+    };
+    let mut n = 0;
+    let mut sum = CompensatedSum::default();
+    let (mut deviations, mut deviations_low) = (0.0, 0.0);
+    let (mut squares, mut squares_low) = (0.0, 0.0);
+    for x in values {
+        n += 1;
+        sum.add(x);
+        let (d, d_low) = two_sum(x, -pivot);
+        let (total, error) = two_sum(deviations, d);
+        deviations = total;
+        deviations_low += error + d_low;
+        let (square, square_low) = two_product(d, d);
+        let (total, error) = two_sum(squares, square);
+        squares = total;
+        squares_low += error + square_low + 2.0 * d * d_low;
+    }
+    let count = n as f64;
+    let (deviations, deviations_low) = two_sum(deviations, deviations_low);
+    let (square, square_low) = two_product(deviations, deviations);
+    let square_low = square_low + 2.0 * deviations * deviations_low;
+    let correction = square / count;
+    let (product, product_low) = two_product(correction, count);
+    let correction_low = ((square - product) - product_low + square_low) / count;
+    let (m2, m2_low) = two_sum(squares, -correction);
+    let m2 = m2 + (m2_low + squares_low - correction_low);
+    // Rounding can leave a tiny negative value when every value is equal
+    let m2 = if m2 < 0.0 { 0.0 } else { m2 };
+    (n, sum.total() / count, m2)
 }
 
-pub fn two_samp_t_test(samp_1: SampleStatistics, samp_2: SampleStatistics) -> TTestResult {
-    let mean_delta = samp_1.sample_mean - samp_2.sample_mean;
-    let stand =
-        (samp_1.standard_error / samp_1.n as f64) + (samp_2.standard_error / samp_2.n as f64);
-    let t = mean_delta / stand.sqrt();
+/// Sum of squared deviations from the mean, in two compensated passes over
+/// `values`.
+pub(crate) fn squared_deviations_iter<T: Float>(values: impl Iterator<Item = T> + Clone) -> f64 {
+    let values_mean = mean_iter(values.clone());
+    squared_deviations_about(values, values_mean)
+}
 
-    // TODO: use t cdf for p_value
-    let p_value: f64 = 0.05;
+/// Sum of squared deviations from `mean`, compensated.
+fn squared_deviations_about<T: Float>(values: impl Iterator<Item = T>, mean: f64) -> f64 {
+    let mut sum = CompensatedSum::default();
+    for xi in values {
+        sum.add(f64::powf(xi.to_f64() - mean, 2.0));
+    }
+    sum.total()
+}
 
-    return TTestResult { t, p_value };
+#[cfg(test)]
+#[track_caller]
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "expected {expected}, got {actual} (tolerance {tolerance})"
+    );
 }
 
 #[cfg(test)]
@@ -142,11 +787,47 @@ mod tests {
         assert!(mean(&[1.0, 3.0]) == 2.0);
     }
 
+    #[test]
+    fn mean_iter_test() {
+        let data = [3.4, 6.7, 2.3, 1.1];
+        assert_eq!(mean_iter(data), mean(&data));
+        assert_eq!(mean_iter((1..=4).map(|i| i as f64)), 2.5);
+        assert!(mean_iter(std::iter::empty::<f64>()).is_nan());
+    }
+
+    #[test]
+    fn compensated_summation_test() {
+        // 1e8 plus offsets k / 1024 for k = 0..1024, each exact in f64 and
+        // each appearing equally often, visited in a scrambled order. The
+        // running sum reaches 1e15, where a naive sum drops bits on every
+        // addition.
+        let n: usize = 1024 * 10_000;
+        let data: Vec<f64> = (0..n)
+            .map(|i| 1e8 + ((i * 337 + i / 1024) % 1024) as f64 / 1024.0)
+            .collect();
+        let expected_mean = 1e8 + 1023.0 / 2048.0;
+        let expected_variance = (1024.0 * 1024.0 - 1.0) / (12.0 * 1024.0 * 1024.0);
+        assert_close(mean(&data), expected_mean, 1e-7);
+        assert_close(population_variance(&data), expected_variance, 1e-10);
+        assert_close(
+            sample_variance(&data),
+            expected_variance * n as f64 / (n - 1) as f64,
+            1e-10,
+        );
+        let samp = SampleStatistics::from_array(&data);
+        assert_close(samp.sample_mean, expected_mean, 1e-7);
+        assert_close(
+            samp.standard_deviation,
+            (expected_variance * n as f64 / (n - 1) as f64).sqrt(),
+            1e-10,
+        );
+    }
+
     #[test]
     fn sample_standard_deviation_test() {
         assert_eq!(
             sample_standard_deviation(&[1.0, 2.0, 3.0, 5.5, 7.7]),
-            2.73001831495688
+            2.7300183149568795
         );
 
         assert_eq!(sample_standard_deviation(&[1.0, 2.0, 3.0]), 1.0);
@@ -165,11 +846,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn variance_test() {
+        assert_eq!(sample_variance(&[1.0, 2.0, 3.0]), 1.0);
+        assert_eq!(population_variance(&[1.0, 2.0, 3.0, 4.0]), 1.25);
+        assert_close(sample_variance(&[1.0, 2.0, 3.0, 5.5, 7.7]), 7.453, 1e-14);
+        assert_close(
+            SampleStatistics::from_array(&[1.0, 2.0, 3.0, 5.5, 7.7]).variance(),
+            7.453,
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn f32_input_test() {
+        let data_64 = [3.4, 6.7, 2.3, 1.1, 9.8, 4.4, 5.0, 7.25];
+        let data_32 = data_64.map(|x| x as f32);
+        let close = |actual: f64, expected: f64| {
+            assert_close(actual, expected, f32::EPSILON as f64 * expected.abs());
+        };
+        close(mean_of(&data_32), mean(&data_64));
+        close(sample_variance_of(&data_32), sample_variance(&data_64));
+        close(
+            population_variance_of(&data_32),
+            population_variance(&data_64),
+        );
+        close(
+            sample_standard_deviation_of(&data_32),
+            sample_standard_deviation(&data_64),
+        );
+        close(
+            population_standard_deviation_of(&data_32),
+            population_standard_deviation(&data_64),
+        );
+        let (samp_32, samp_64) = (
+            SampleStatistics::from_array_of(&data_32),
+            SampleStatistics::from_array(&data_64),
+        );
+        assert_eq!(samp_32.n, 8);
+        close(samp_32.sample_mean, samp_64.sample_mean);
+        close(samp_32.standard_deviation, samp_64.standard_deviation);
+        let pop = PopulationStatistics::from_array_of(&data_32);
+        close(
+            pop.standard_deviation,
+            population_standard_deviation(&data_64),
+        );
+
+        // Accumulating in f64 avoids the f32 sum stalling at 2^24
+        let ones = vec![1.0f32; 20_000_000];
+        assert_eq!(mean_of(&ones), 1.0);
+    }
+
     #[test]
     fn pop_stats_from_array_test() {
         let pop = PopulationStatistics::from_array(&[1.0, 5.5, 7.7, 8.9]);
         assert_eq!(pop.n, 4);
-        assert_eq!(pop.standard_error, 3.0144443932506038);
+        assert_eq!(pop.standard_deviation, 3.0144443932506038);
         assert_eq!(pop.population_mean, 5.775);
     }
 
@@ -177,7 +909,332 @@ mod tests {
     fn samp_stats_from_array_test() {
         let samp = SampleStatistics::from_array(&[1.0, 5.5, 7.7, 8.9]);
         assert_eq!(samp.n, 4);
-        assert_eq!(samp.standard_error, 3.4807805638007885);
+        assert_eq!(samp.standard_deviation, 3.4807805638007885);
         assert_eq!(samp.sample_mean, 5.775);
     }
+
+    #[test]
+    fn single_pass_matches_two_pass_test() {
+        // from_array used to be mean() followed by the two-pass standard
+        // deviation functions, which remain the reference here
+        let mut rng = crate::rng::Rng::new(111);
+        let uniform: Vec<f64> = (0..10_000).map(|_| rng.next_f64()).collect();
+        let offset: Vec<f64> = uniform.iter().map(|x| 1e9 + x).collect();
+        // A pivot far from the mean is the single-pass worst case
+        let mut outlier_first = uniform.clone();
+        outlier_first[0] = 1e4;
+        // With the offset it is the two-pass reference that is about 4e-15
+        // out, from deviations taken about the rounded mean; the single
+        // pass matches the exact value computed in rationals
+        for (data, tolerance) in [(&uniform, 1e-15), (&offset, 1e-14), (&outlier_first, 1e-15)] {
+            let samp = SampleStatistics::from_array(data);
+            let expected_sd = sample_standard_deviation(data);
+            assert_eq!(samp.sample_mean, mean(data));
+            assert_close(
+                samp.standard_deviation,
+                expected_sd,
+                tolerance * expected_sd,
+            );
+            let pop = PopulationStatistics::from_array(data);
+            let expected_sd = population_standard_deviation(data);
+            assert_close(pop.standard_deviation, expected_sd, tolerance * expected_sd);
+        }
+        let constant = [0.1; 7];
+        assert_eq!(
+            SampleStatistics::from_array(&constant).standard_deviation,
+            0.0
+        );
+    }
+
+    #[test]
+    fn compensated_single_pass_test() {
+        // The plain shifted pass, Σd² - (Σd)² / n about the first value,
+        // with no error terms
+        let plain = |data: &[f64]| {
+            let (mut deviations, mut squares) = (0.0, 0.0);
+            for &x in data {
+                deviations += x - data[0];
+                squares += (x - data[0]) * (x - data[0]);
+            }
+            squares - deviations * deviations / data.len() as f64
+        };
+        let mut rng = crate::rng::Rng::new(111);
+        let mut data: Vec<f64> = (0..10_000).map(|_| rng.next_f64()).collect();
+        // A first value far out makes Σd² and (Σd)² / n large and nearly
+        // equal; the two-pass sum about the mean is accurate here
+        data[0] = 1e4;
+        let expected = squared_deviations(&data);
+        let plain_error = (plain(&data) - expected).abs() / expected;
+        assert!(plain_error > 1e-12, "{plain_error}");
+        let (_, _, m2) = single_pass_moments(data.iter().copied());
+        assert_close(m2, expected, 1e-15 * expected);
+    }
+
+    #[test]
+    fn standard_deviation_given_mean_test() {
+        let data = [1.0, 2.0, 3.0, 5.5, 7.7];
+        let data_mean = mean(&data);
+        assert_eq!(
+            sample_standard_deviation_given_mean(&data, Some(data_mean)),
+            sample_standard_deviation(&data)
+        );
+        assert_eq!(
+            sample_standard_deviation_given_mean(&data, None),
+            sample_standard_deviation(&data)
+        );
+        assert_eq!(
+            population_standard_deviation_given_mean(&data, Some(data_mean)),
+            population_standard_deviation(&data)
+        );
+        // About another point the squares are larger: Σ(x - 0)² / 4
+        assert_close(
+            sample_standard_deviation_given_mean(&data, Some(0.0)),
+            (103.54f64 / 4.0).sqrt(),
+            1e-14,
+        );
+        assert!(sample_standard_deviation_given_mean(&[1.0], Some(1.0)).is_nan());
+    }
+
+    #[test]
+    fn owned_sample_test() {
+        let sample = Sample::new(vec![1.0, 5.5, 7.7, 8.9]);
+        assert_eq!(sample.values(), &[1.0, 5.5, 7.7, 8.9]);
+        assert_eq!(sample.mean(), 5.775);
+        assert_eq!(sample.variance(), sample_variance(sample.values()));
+        assert_eq!(sample.standard_deviation(), 3.4807805638007885);
+        let statistics = sample.statistics();
+        assert_eq!(statistics.n, 4);
+        assert_eq!(statistics.standard_deviation, 3.4807805638007885);
+        let other = Sample::from(vec![2.0, 3.5, 4.1]);
+        assert!(welch_t_test(&sample.statistics(), &other.statistics()).p_value < 0.5);
+        assert!(Sample::default().mean().is_nan());
+        assert!(Sample::new(vec![3.0]).variance().is_nan());
+    }
+
+    #[test]
+    fn owned_population_test() {
+        let population = Population::new(vec![1.0, 5.5, 7.7, 8.9]);
+        assert_eq!(population.mean(), 5.775);
+        assert_eq!(
+            population.variance(),
+            population_variance(population.values())
+        );
+        assert_eq!(population.standard_deviation(), 3.0144443932506038);
+        let statistics = population.statistics();
+        assert_eq!(statistics.n, 4);
+        assert_eq!(statistics.standard_deviation, 3.0144443932506038);
+        assert_eq!(Population::from(vec![4.0]).variance(), 0.0);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_sample_population_test() {
+        let data = [1.0, 5.5, 7.7, 8.9];
+        let sample = Sample::new(data.to_vec());
+        let population = Population::new(data.to_vec());
+        assert_eq!(sample.sample(), &data);
+        assert_eq!(population.population(), &data);
+        assert_eq!(
+            <Sample as StandDev>::standard_deviation(&data),
+            sample.standard_deviation()
+        );
+        assert_eq!(
+            <Population as StandDev>::standard_deviation(&data),
+            population.standard_deviation()
+        );
+    }
+
+    #[test]
+    fn samp_stats_from_weighted_test() {
+        let values = [3.2, 4.8, 5.1, 2.9, 6.4, 5.5];
+        let samp =
+            SampleStatistics::from_weighted(&values, &[1.0, 2.5, 0.5, 3.0, 1.5, 2.0]).unwrap();
+        assert_close(samp.sample_mean, 4.48095238095238, 1e-14);
+        assert_close(samp.standard_deviation, 1.43608595047193, 1e-14);
+        // Effective sample size 4.846
+        assert_eq!(samp.n, 5);
+
+        let unweighted = SampleStatistics::from_weighted(&values, &[2.0; 6]).unwrap();
+        let samp = SampleStatistics::from_array(&values);
+        assert_close(unweighted.sample_mean, samp.sample_mean, 1e-14);
+        assert_close(
+            unweighted.standard_deviation,
+            samp.standard_deviation,
+            1e-14,
+        );
+        assert_eq!(unweighted.n, 6);
+
+        assert!(SampleStatistics::from_weighted(&values, &[1.0]).is_err());
+    }
+
+    #[test]
+    fn degenerate_input_test() {
+        assert!(mean(&[]).is_nan());
+        assert!(sample_variance(&[]).is_nan());
+        assert!(sample_variance(&[4.0]).is_nan());
+        assert!(sample_standard_deviation(&[4.0]).is_nan());
+        assert!(population_variance(&[]).is_nan());
+        assert_eq!(population_variance(&[4.0]), 0.0);
+        assert_eq!(population_standard_deviation(&[4.0]), 0.0);
+
+        assert_eq!(try_mean(&[]), Err(StatsError::EmptyInput));
+        assert_eq!(try_mean(&[4.0]), Ok(4.0));
+        assert_eq!(try_population_variance(&[]), Err(StatsError::EmptyInput));
+        assert_eq!(try_population_standard_deviation(&[4.0]), Ok(0.0));
+        for data in [&[][..], &[4.0]] {
+            let error = Some(StatsError::InsufficientData {
+                required: 2,
+                actual: data.len(),
+            });
+            assert_eq!(try_sample_variance(data).err(), error);
+            assert_eq!(try_sample_standard_deviation(data).err(), error);
+            assert_eq!(SampleStatistics::try_from_array(data).err(), error);
+        }
+        assert_eq!(try_sample_variance(&[1.0, 2.0, 3.0]), Ok(1.0));
+        assert_eq!(try_sample_standard_deviation(&[1.0, 2.0, 3.0]), Ok(1.0));
+
+        let samp = SampleStatistics::try_from_array(&[1.0, 5.5, 7.7, 8.9]).unwrap();
+        assert_eq!(samp.standard_deviation, 3.4807805638007885);
+        assert!(PopulationStatistics::try_from_array(&[]).is_err());
+        let pop = PopulationStatistics::try_from_array(&[4.0]).unwrap();
+        assert_eq!((pop.population_mean, pop.standard_deviation), (4.0, 0.0));
+    }
+
+    #[test]
+    fn from_summary_test() {
+        let data_1 = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+        let data_2 = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+        let summary = |data: &[f64]| {
+            SampleStatistics::from_summary(mean(data), sample_standard_deviation(data), data.len())
+                .unwrap()
+        };
+        let (summary_1, summary_2) = (summary(&data_1), summary(&data_2));
+        let (samp_1, samp_2) = (
+            SampleStatistics::from_array(&data_1),
+            SampleStatistics::from_array(&data_2),
+        );
+
+        for kind in [TTestKind::EqualVariance, TTestKind::UnequalVariance] {
+            let expected = two_samp_t_test_with(&samp_1, &samp_2, kind);
+            let actual = two_samp_t_test_with(&summary_1, &summary_2, kind);
+            assert_eq!(actual.t, expected.t);
+            assert_eq!(actual.df, expected.df);
+            assert_eq!(actual.p_value, expected.p_value);
+        }
+        assert_eq!(cohens_d(&summary_1, &summary_2), cohens_d(&samp_1, &samp_2));
+        assert_eq!(
+            hedges_g(&summary_1, &summary_2).g,
+            hedges_g(&samp_1, &samp_2).g
+        );
+        assert_eq!(
+            summary_1.mean_confidence_interval(0.95),
+            samp_1.mean_confidence_interval(0.95)
+        );
+
+        let pop = PopulationStatistics::from_summary(5.775, 3.0144443932506038, 4).unwrap();
+        let expected = PopulationStatistics::from_array(&[1.0, 5.5, 7.7, 8.9]);
+        assert_eq!(pop.standard_error(), expected.standard_error());
+    }
+
+    #[test]
+    fn from_summary_invalid_test() {
+        assert_eq!(
+            SampleStatistics::from_summary(1.0, 1.0, 0).err(),
+            Some(StatsError::EmptyInput)
+        );
+        for (mean, sd) in [(1.0, -0.5), (1.0, f64::NAN), (f64::INFINITY, 1.0)] {
+            assert!(matches!(
+                SampleStatistics::from_summary(mean, sd, 10),
+                Err(StatsError::InvalidParameter(_))
+            ));
+            assert!(matches!(
+                PopulationStatistics::from_summary(mean, sd, 10),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        assert!(SampleStatistics::from_summary(2.0, 0.0, 1).is_ok());
+    }
+
+    #[test]
+    fn merge_test() {
+        let mut rng = rng::Rng::new(93);
+        let data: Vec<f64> = (0..200).map(|_| 50.0 + 10.0 * rng.next_f64()).collect();
+        let samp = SampleStatistics::from_array(&data);
+        let pop = PopulationStatistics::from_array(&data);
+        for _ in 0..50 {
+            let split = 1 + rng.below(data.len() - 1);
+            let (a, b) = data.split_at(split);
+            let merged = SampleStatistics::from_array(a).merge(&SampleStatistics::from_array(b));
+            assert_eq!(merged.n, 200);
+            assert_close(merged.sample_mean, samp.sample_mean, 1e-12);
+            assert_close(merged.standard_deviation, samp.standard_deviation, 1e-12);
+
+            let merged =
+                PopulationStatistics::from_array(a).merge(&PopulationStatistics::from_array(b));
+            assert_eq!(merged.n, 200);
+            assert_close(merged.population_mean, pop.population_mean, 1e-12);
+            assert_close(merged.standard_deviation, pop.standard_deviation, 1e-12);
+        }
+
+        // Singletons have a NaN sample standard deviation but no spread
+        let pair =
+            SampleStatistics::from_array(&[1.0]).merge(&SampleStatistics::from_array(&[3.0]));
+        assert_eq!(pair.sample_mean, 2.0);
+        assert_eq!(pair.standard_deviation, core::f64::consts::SQRT_2);
+
+        let empty = SampleStatistics::from_array(&[]);
+        let merged = empty.merge(&samp);
+        assert_eq!(merged.n, samp.n);
+        assert_eq!(merged.sample_mean, samp.sample_mean);
+        assert_eq!(merged.standard_deviation, samp.standard_deviation);
+        let merged = samp.merge(&empty);
+        assert_eq!(merged.sample_mean, samp.sample_mean);
+        assert_eq!(merged.standard_deviation, samp.standard_deviation);
+        let both_empty = empty.merge(&empty);
+        assert_eq!(both_empty.n, 0);
+        assert_eq!(both_empty.standard_deviation.to_bits(), 0.0f64.to_bits());
+        let merged = PopulationStatistics::from_array(&[]).merge(&pop);
+        assert_eq!(merged.population_mean, pop.population_mean);
+        assert_eq!(merged.standard_deviation, pop.standard_deviation);
+        let empty = PopulationStatistics::from_array(&[]);
+        let both_empty = empty.merge(&empty);
+        assert_eq!(both_empty.n, 0);
+        assert_eq!(both_empty.standard_deviation.to_bits(), 0.0f64.to_bits());
+    }
+
+    #[test]
+    fn standard_error_test() {
+        let samp = SampleStatistics::from_array(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_close(
+            samp.standard_error(),
+            core::f64::consts::FRAC_1_SQRT_2,
+            1e-15,
+        );
+
+        let pop = PopulationStatistics::from_array(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_close(pop.standard_error(), 0.6324555320336759, 1e-15);
+    }
+
+    #[test]
+    fn standard_error_of_mean_test() {
+        let data = [1.0, 2.0, 3.0, 5.5, 7.7];
+        // scipy.stats.sem(data)
+        assert_close(standard_error_of_mean(&data), 1.22090130641260, 1e-14);
+        assert_close(
+            standard_error_from_sd(sample_standard_deviation(&data), 5),
+            standard_error_of_mean(&data),
+            1e-15,
+        );
+        assert_eq!(
+            SampleStatistics::from_array(&data).sem(),
+            standard_error_of_mean(&data)
+        );
+
+        // mean ± t SEM is the t interval for the mean
+        let interval = mean_confidence_interval(&data, 0.95).unwrap();
+        let t = distributions::StudentsT::new(4.0).quantile(0.975);
+        let margin = t * standard_error_of_mean(&data);
+        assert_close(interval.lower, mean(&data) - margin, 1e-14);
+        assert_close(interval.upper, mean(&data) + margin, 1e-14);
+    }
 }
@@ -0,0 +1,226 @@
+use crate::StatsError;
+
+/// Columns whose Householder diagonal falls below this fraction of their
+/// original norm are treated as linearly dependent, as in R's `lm`.
+const RANK_TOLERANCE: f64 = 1e-7;
+
+/// Mirrored entries of a symmetric matrix may differ by this fraction of the
+/// larger, allowing for rounding wherever the matrix was computed.
+const SYMMETRY_TOLERANCE: f64 = 1e-12;
+
+pub(crate) struct LeastSquares {
+    pub(crate) coefficients: Vec<f64>,
+    /// (X'X)^-1, which scaled by the residual variance gives the covariance
+    /// of the coefficients.
+    pub(crate) unscaled_covariance: Vec<Vec<f64>>,
+}
+
+/// Solves min ||X b - y|| by Householder QR, with X given column by column.
+///
+/// Returns `InvalidParameter` when the columns are linearly dependent instead
+/// of producing meaningless coefficients.
+pub(crate) fn least_squares(
+    mut columns: Vec<Vec<f64>>,
+    y: &[f64],
+) -> Result<LeastSquares, StatsError> {
+    let p = columns.len();
+    let mut qty = y.to_vec();
+
+    for k in 0..p {
+        let original_norm = norm(&columns[k]);
+        let norm = norm(&columns[k][k..]);
+        if norm <= RANK_TOLERANCE * original_norm || original_norm == 0.0 {
+            return Err(StatsError::InvalidParameter(
+                "design matrix is rank deficient",
+            ));
+        }
+
+        let alpha = if columns[k][k] > 0.0 { -norm } else { norm };
+        let mut v = columns[k][k..].to_vec();
+        v[0] -= alpha;
+        let v_norm_sq: f64 = v.iter().map(|x| x * x).sum();
+
+        let reflect = |target: &mut [f64]| {
+            let s: f64 = v.iter().zip(target.iter()).map(|(a, b)| a * b).sum();
+            let scale = 2.0 * s / v_norm_sq;
+            for (t, vi) in target.iter_mut().zip(&v) {
+                *t -= scale * vi;
+            }
+        };
+        for column in columns.iter_mut().skip(k + 1) {
+            reflect(&mut column[k..]);
+        }
+        reflect(&mut qty[k..]);
+
+        columns[k][k] = alpha;
+        columns[k][k + 1..].iter_mut().for_each(|x| *x = 0.0);
+    }
+
+    // columns[j][i] now holds R[i][j] for i <= j
+    let mut coefficients = vec![0.0; p];
+    for i in (0..p).rev() {
+        let mut sum = qty[i];
+        for j in i + 1..p {
+            sum -= columns[j][i] * coefficients[j];
+        }
+        coefficients[i] = sum / columns[i][i];
+    }
+
+    // R^-1 is upper triangular; (X'X)^-1 = R^-1 R^-T
+    let mut r_inverse = vec![vec![0.0; p]; p];
+    for i in (0..p).rev() {
+        let mut row = vec![0.0; p];
+        row[i] = 1.0 / columns[i][i];
+        for (j, entry) in row.iter_mut().enumerate().skip(i + 1) {
+            let sum: f64 = (i + 1..=j).map(|m| columns[m][i] * r_inverse[m][j]).sum();
+            *entry = -sum / columns[i][i];
+        }
+        r_inverse[i] = row;
+    }
+    let mut unscaled_covariance = vec![vec![0.0; p]; p];
+    for i in 0..p {
+        for j in 0..p {
+            let start = i.max(j);
+            unscaled_covariance[i][j] = (start..p).map(|m| r_inverse[i][m] * r_inverse[j][m]).sum();
+        }
+    }
+
+    Ok(LeastSquares {
+        coefficients,
+        unscaled_covariance,
+    })
+}
+
+/// The lower triangular L with L L' = `matrix`, for a symmetric matrix
+/// given by rows. Only the lower triangle is read by the factorization, so
+/// the matrix is checked for symmetry first.
+///
+/// Returns `InvalidParameter` when the matrix is not symmetric within
+/// `SYMMETRY_TOLERANCE`, or is singular or not positive definite, judged
+/// like `least_squares` by a pivot falling below `RANK_TOLERANCE` of the
+/// scale of its diagonal entry.
+pub(crate) fn cholesky(matrix: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, StatsError> {
+    let p = matrix.len();
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, &a) in row[..i].iter().enumerate() {
+            let b = matrix[j][i];
+            if (a - b).abs() > SYMMETRY_TOLERANCE * a.abs().max(b.abs()) {
+                return Err(StatsError::InvalidParameter(
+                    "covariance matrix is not symmetric",
+                ));
+            }
+        }
+    }
+    let mut lower = vec![vec![0.0; p]; p];
+    for j in 0..p {
+        let pivot = matrix[j][j] - lower[j][..j].iter().map(|l| l * l).sum::<f64>();
+        if matrix[j][j] <= 0.0 || pivot <= RANK_TOLERANCE * RANK_TOLERANCE * matrix[j][j] {
+            return Err(StatsError::InvalidParameter(
+                "covariance matrix is singular or not positive definite",
+            ));
+        }
+        lower[j][j] = pivot.sqrt();
+        for i in j + 1..p {
+            let dot: f64 = (0..j).map(|k| lower[i][k] * lower[j][k]).sum();
+            lower[i][j] = (matrix[i][j] - dot) / lower[j][j];
+        }
+    }
+    Ok(lower)
+}
+
+/// Solves L z = b for lower triangular L by forward substitution.
+pub(crate) fn forward_substitute(lower: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let mut z = Vec::with_capacity(b.len());
+    for (i, row) in lower.iter().enumerate() {
+        let dot: f64 = row[..i].iter().zip(&z).map(|(l, z)| l * z).sum();
+        z.push((b[i] - dot) / row[i]);
+    }
+    z
+}
+
+fn norm(values: &[f64]) -> f64 {
+    values.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn least_squares_test() {
+        // Exact fit of y = 1 + 2a - b
+        let ones = vec![1.0; 4];
+        let a = vec![0.0, 1.0, 2.0, 3.0];
+        let b = vec![1.0, 0.0, 4.0, 2.0];
+        let y: Vec<f64> = a.iter().zip(&b).map(|(a, b)| 1.0 + 2.0 * a - b).collect();
+        let fit = least_squares(vec![ones, a, b], &y).unwrap();
+        assert_close(fit.coefficients[0], 1.0, 1e-14);
+        assert_close(fit.coefficients[1], 2.0, 1e-14);
+        assert_close(fit.coefficients[2], -1.0, 1e-14);
+
+        // Intercept only: (X'X)^-1 = 1 / n
+        let fit = least_squares(vec![vec![1.0; 5]], &[1.0, 2.0, 3.0, 4.0, 10.0]).unwrap();
+        assert_close(fit.coefficients[0], 4.0, 1e-14);
+        assert_close(fit.unscaled_covariance[0][0], 0.2, 1e-15);
+    }
+
+    #[test]
+    fn least_squares_rank_deficient_test() {
+        let a = vec![1.0, 2.0, 3.0, 4.0];
+        let b = vec![2.0, 4.0, 6.0, 8.0];
+        assert!(matches!(
+            least_squares(vec![a, b], &[1.0, 2.0, 2.0, 3.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn cholesky_test() {
+        let matrix = vec![
+            vec![4.0, 2.0, -2.0],
+            vec![2.0, 10.0, 2.0],
+            vec![-2.0, 2.0, 5.0],
+        ];
+        let lower = cholesky(&matrix).unwrap();
+        let expected = [
+            [2.0, 0.0, 0.0],
+            [1.0, 3.0, 0.0],
+            [-1.0, 1.0, 1.7320508075688772],
+        ];
+        for (row, expected) in lower.iter().zip(expected) {
+            for (l, e) in row.iter().zip(expected) {
+                assert_close(*l, e, 1e-15);
+            }
+        }
+        // L z = b for b = L [1, 2, 3]
+        let b: Vec<f64> = lower
+            .iter()
+            .map(|row| row.iter().zip([1.0, 2.0, 3.0]).map(|(l, z)| l * z).sum())
+            .collect();
+        let z = forward_substitute(&lower, &b);
+        for (z, expected) in z.iter().zip([1.0, 2.0, 3.0]) {
+            assert_close(*z, expected, 1e-14);
+        }
+
+        assert_eq!(
+            cholesky(&[vec![4.0, 2.0], vec![1.0, 3.0]]),
+            Err(StatsError::InvalidParameter(
+                "covariance matrix is not symmetric"
+            ))
+        );
+        // Rounding in the last place is tolerated
+        assert!(cholesky(&[vec![4.0, 2.0], vec![2.0000000000000004, 3.0]]).is_ok());
+
+        for singular in [
+            vec![vec![1.0, 2.0], vec![2.0, 4.0]],
+            vec![vec![1.0, 2.0], vec![2.0, 1.0]],
+            vec![vec![0.0, 0.0], vec![0.0, 1.0]],
+        ] {
+            assert!(matches!(
+                cholesky(&singular),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+    }
+}
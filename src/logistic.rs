@@ -0,0 +1,213 @@
+use crate::distributions::Normal;
+use crate::StatsError;
+
+/// Newton-Raphson iterations allowed before giving up, as in R's `glm`.
+const LOGISTIC_MAX_ITERATIONS: usize = 25;
+
+/// Largest relative change in a coefficient still counted as converged.
+const LOGISTIC_TOLERANCE: f64 = 1e-10;
+
+/// An estimated logistic regression coefficient with its Wald test of zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogisticCoefficient {
+    pub estimate: f64,
+    pub standard_error: f64,
+    /// Wald statistic estimate / standard_error.
+    pub z: f64,
+    /// Two-sided p-value from the standard normal.
+    pub p_value: f64,
+}
+
+impl LogisticCoefficient {
+    fn new(estimate: f64, standard_error: f64) -> Self {
+        let z = estimate / standard_error;
+        LogisticCoefficient {
+            estimate,
+            standard_error,
+            z,
+            p_value: Normal::standard().two_tailed(z),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogisticRegressionResult {
+    pub intercept: LogisticCoefficient,
+    pub slope: LogisticCoefficient,
+    /// Maximized log-likelihood; the residual deviance is -2 times this.
+    pub log_likelihood: f64,
+    /// Newton-Raphson iterations used.
+    pub iterations: usize,
+}
+
+impl LogisticRegressionResult {
+    /// Fitted probability that the outcome is `true` at `x`.
+    pub fn predict_proba(&self, x: f64) -> f64 {
+        logistic(self.intercept.estimate + self.slope.estimate * x)
+    }
+}
+
+/// Logistic regression of a binary outcome on one predictor, fitted by
+/// Newton-Raphson (equivalently iteratively reweighted least squares), as
+/// R's `glm(y ~ x, family = binomial)`.
+///
+/// Under perfect or quasi-complete separation the maximum likelihood
+/// estimate does not exist and the slope grows without bound; this is
+/// reported as `NoConvergence` once the iteration limit is reached.
+pub fn logistic_regression(x: &[f64], y: &[bool]) -> Result<LogisticRegressionResult, StatsError> {
+    if x.len() != y.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: x.len(),
+            right: y.len(),
+        });
+    }
+    if x.len() < 3 {
+        return Err(StatsError::InsufficientData {
+            required: 3,
+            actual: x.len(),
+        });
+    }
+    if x.iter().all(|&v| v == x[0]) {
+        return Err(StatsError::InvalidParameter(
+            "regression is undefined when x is constant",
+        ));
+    }
+
+    let mut beta = [0.0, 0.0];
+    for iteration in 1..=LOGISTIC_MAX_ITERATIONS {
+        let information = fisher_information(x, beta);
+        let mut gradient = [0.0, 0.0];
+        for (&xi, &yi) in x.iter().zip(y) {
+            let residual = f64::from(u8::from(yi)) - logistic(beta[0] + beta[1] * xi);
+            gradient[0] += residual;
+            gradient[1] += residual * xi;
+        }
+
+        // Weights underflow to zero once the fitted probabilities saturate
+        let Some(inverse) = invert(information) else {
+            return Err(StatsError::NoConvergence {
+                iterations: iteration,
+            });
+        };
+        let step = [
+            inverse[0][0] * gradient[0] + inverse[0][1] * gradient[1],
+            inverse[1][0] * gradient[0] + inverse[1][1] * gradient[1],
+        ];
+        beta[0] += step[0];
+        beta[1] += step[1];
+
+        let converged = step
+            .iter()
+            .zip(beta)
+            .all(|(s, b)| s.abs() <= LOGISTIC_TOLERANCE * (b.abs() + 1.0));
+        if converged {
+            let Some(covariance) = invert(fisher_information(x, beta)) else {
+                return Err(StatsError::NoConvergence {
+                    iterations: iteration,
+                });
+            };
+            let log_likelihood = x
+                .iter()
+                .zip(y)
+                .map(|(&xi, &yi)| {
+                    let eta = beta[0] + beta[1] * xi;
+                    // log p = -log(1 + e^-eta), log(1 - p) = -log(1 + e^eta)
+                    let signed = if yi { -eta } else { eta };
+                    -signed.exp().ln_1p()
+                })
+                .sum();
+            return Ok(LogisticRegressionResult {
+                intercept: LogisticCoefficient::new(beta[0], covariance[0][0].sqrt()),
+                slope: LogisticCoefficient::new(beta[1], covariance[1][1].sqrt()),
+                log_likelihood,
+                iterations: iteration,
+            });
+        }
+    }
+
+    Err(StatsError::NoConvergence {
+        iterations: LOGISTIC_MAX_ITERATIONS,
+    })
+}
+
+fn logistic(eta: f64) -> f64 {
+    1.0 / (1.0 + (-eta).exp())
+}
+
+/// X'WX for the design [1, x] with weights p(1 - p).
+fn fisher_information(x: &[f64], beta: [f64; 2]) -> [[f64; 2]; 2] {
+    let mut information = [[0.0; 2]; 2];
+    for &xi in x {
+        let p = logistic(beta[0] + beta[1] * xi);
+        let w = p * (1.0 - p);
+        information[0][0] += w;
+        information[0][1] += w * xi;
+        information[1][1] += w * xi * xi;
+    }
+    information[1][0] = information[0][1];
+    information
+}
+
+fn invert(m: [[f64; 2]; 2]) -> Option<[[f64; 2]; 2]> {
+    let det = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+    if det.is_nan() || det <= 0.0 {
+        return None;
+    }
+    Some([
+        [m[1][1] / det, -m[0][1] / det],
+        [-m[1][0] / det, m[0][0] / det],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn logistic_regression_test() {
+        // R: glm(pass ~ hours, family = binomial)
+        let hours = [
+            0.5, 0.75, 1.0, 1.25, 1.5, 1.75, 1.75, 2.0, 2.25, 2.5, 2.75, 3.0, 3.25, 3.5, 4.0, 4.25,
+            4.5, 4.75, 5.0, 5.5,
+        ];
+        let pass = [0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1].map(|v| v == 1);
+        let fit = logistic_regression(&hours, &pass).unwrap();
+        assert_close(fit.intercept.estimate, -4.07771343108763, 1e-10);
+        assert_close(fit.intercept.standard_error, 1.76099431415647, 1e-10);
+        assert_close(fit.intercept.z, -2.31557444468007, 1e-10);
+        assert_close(fit.intercept.p_value, 0.0205815155124586, 1e-11);
+        assert_close(fit.slope.estimate, 1.50464542837333, 1e-10);
+        assert_close(fit.slope.standard_error, 0.628720845945386, 1e-10);
+        assert_close(fit.slope.z, 2.39318520783393, 1e-10);
+        assert_close(fit.slope.p_value, 0.0167028073403679, 1e-11);
+        assert_close(fit.log_likelihood, -8.02987846434467, 1e-11);
+        assert_close(fit.predict_proba(3.0), 0.607358645366086, 1e-11);
+        assert!(fit.iterations <= LOGISTIC_MAX_ITERATIONS);
+    }
+
+    #[test]
+    fn logistic_regression_separation_test() {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let y = [false, false, false, true, true, true];
+        assert_eq!(
+            logistic_regression(&x, &y).err(),
+            Some(StatsError::NoConvergence {
+                iterations: LOGISTIC_MAX_ITERATIONS
+            })
+        );
+    }
+
+    #[test]
+    fn logistic_regression_invalid_input_test() {
+        assert_eq!(
+            logistic_regression(&[1.0, 2.0, 3.0], &[true, false]).err(),
+            Some(StatsError::MismatchedLengths { left: 3, right: 2 })
+        );
+        assert!(matches!(
+            logistic_regression(&[2.0, 2.0, 2.0], &[true, false, true]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
@@ -0,0 +1,61 @@
+/// The f64 methods the core uses that live in std rather than core, backed
+/// by libm so the same code compiles with `no_std`. With std the inherent
+/// methods take precedence and this module is not compiled.
+pub(crate) trait F64Ext {
+    fn sqrt(self) -> f64;
+    fn exp(self) -> f64;
+    fn exp_m1(self) -> f64;
+    fn ln(self) -> f64;
+    fn ln_1p(self) -> f64;
+    fn powf(self, n: f64) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn sin(self) -> f64;
+    #[cfg(feature = "alloc")]
+    fn log10(self) -> f64;
+    #[cfg(feature = "alloc")]
+    fn floor(self) -> f64;
+}
+
+impl F64Ext for f64 {
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn exp_m1(self) -> f64 {
+        libm::expm1(self)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn ln_1p(self) -> f64 {
+        libm::log1p(self)
+    }
+
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn log10(self) -> f64 {
+        libm::log10(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+}
@@ -0,0 +1,312 @@
+use std::fmt;
+
+use crate::confidence::validate_confidence;
+use crate::distributions::{ChiSquare, Normal};
+use crate::format::write_test_line;
+use crate::{ConfidenceInterval, HypothesisTest, StatsError};
+
+/// How a meta-analysis weights its studies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MetaAnalysisModel {
+    /// Every study estimates one common effect; weights 1 / SE².
+    FixedEffect,
+    /// The true effects vary between studies with variance τ², estimated by
+    /// the DerSimonian-Laird method; weights 1 / (SE² + τ²).
+    RandomEffects,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetaAnalysisResult {
+    /// The inverse-variance weighted mean of the study estimates.
+    pub estimate: f64,
+    pub standard_error: f64,
+    /// estimate / standard_error, tested against the standard normal.
+    pub z: f64,
+    pub p_value: f64,
+    pub confidence_interval: ConfidenceInterval,
+    /// Cochran's Q, the weighted squared deviations of the studies from the
+    /// fixed-effect estimate.
+    pub q: f64,
+    /// Degrees of freedom of Q, one fewer than the number of studies.
+    pub q_df: f64,
+    /// P(χ²(q_df) >= Q), small when the studies disagree more than their
+    /// standard errors allow; 1 for a single study.
+    pub q_p_value: f64,
+    /// I², the percentage of the variation across studies due to
+    /// heterogeneity rather than chance: max(0, (Q - df) / Q) 100.
+    pub i_squared: f64,
+    /// The between-study variance τ², 0 for the fixed-effect model.
+    pub tau_squared: f64,
+    pub model: MetaAnalysisModel,
+}
+
+impl fmt::Display for MetaAnalysisResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "z", &[], self.z, self.p_value)
+    }
+}
+
+impl HypothesisTest for MetaAnalysisResult {
+    fn statistic(&self) -> f64 {
+        self.z
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        match self.model {
+            MetaAnalysisModel::FixedEffect => "fixed-effect meta-analysis",
+            MetaAnalysisModel::RandomEffects => "random-effects meta-analysis",
+        }
+    }
+}
+
+/// Fixed-effect inverse-variance meta-analysis of per-study effect
+/// `estimates` with their `standard_errors`, as R's
+/// `metafor::rma(yi, sei = sei, method = "FE")`: the pooled estimate, a
+/// two-sided z test that it is zero, a `confidence` interval, and Cochran's
+/// Q with I² for heterogeneity. For study means against a reference value
+/// μ0 the estimates are x̄ - μ0 with standard errors s / sqrt(n).
+///
+/// Returns `StatsError::EmptyInput` for no studies,
+/// `StatsError::MismatchedLengths` if the inputs differ in length,
+/// `StatsError::NanInInput` for a NaN estimate and
+/// `StatsError::InvalidParameter` for a standard error that is not
+/// positive and finite or an invalid `confidence`.
+pub fn fixed_effect_meta(
+    estimates: &[f64],
+    standard_errors: &[f64],
+    confidence: f64,
+) -> Result<MetaAnalysisResult, StatsError> {
+    meta_analysis(
+        estimates,
+        standard_errors,
+        confidence,
+        MetaAnalysisModel::FixedEffect,
+    )
+}
+
+/// Random-effects meta-analysis with the DerSimonian-Laird estimate of the
+/// between-study variance, τ² = max(0, (Q - df) / (Σw - Σw² / Σw)), as
+/// `metafor::rma(yi, sei = sei, method = "DL")`. When Q does not exceed its
+/// degrees of freedom τ² is 0 and this is the fixed-effect analysis. Fails
+/// as `fixed_effect_meta` does.
+pub fn random_effects_meta(
+    estimates: &[f64],
+    standard_errors: &[f64],
+    confidence: f64,
+) -> Result<MetaAnalysisResult, StatsError> {
+    meta_analysis(
+        estimates,
+        standard_errors,
+        confidence,
+        MetaAnalysisModel::RandomEffects,
+    )
+}
+
+fn meta_analysis(
+    estimates: &[f64],
+    standard_errors: &[f64],
+    confidence: f64,
+    model: MetaAnalysisModel,
+) -> Result<MetaAnalysisResult, StatsError> {
+    if estimates.len() != standard_errors.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: estimates.len(),
+            right: standard_errors.len(),
+        });
+    }
+    if estimates.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if let Some(index) = estimates.iter().position(|x| x.is_nan()) {
+        return Err(StatsError::NanInInput { index });
+    }
+    if !standard_errors.iter().all(|&se| se > 0.0 && se.is_finite()) {
+        return Err(StatsError::InvalidParameter(
+            "standard errors must be positive and finite",
+        ));
+    }
+    validate_confidence(confidence)?;
+
+    let variances: Vec<f64> = standard_errors.iter().map(|se| se * se).collect();
+    let weighted_mean = |weights: &[f64]| {
+        let total: f64 = weights.iter().sum();
+        let sum: f64 = weights.iter().zip(estimates).map(|(w, y)| w * y).sum();
+        (sum / total, total)
+    };
+
+    let weights: Vec<f64> = variances.iter().map(|v| 1.0 / v).collect();
+    let (fixed_estimate, weight_total) = weighted_mean(&weights);
+    let q: f64 = weights
+        .iter()
+        .zip(estimates)
+        .map(|(w, y)| w * (y - fixed_estimate).powi(2))
+        .sum();
+    let q_df = (estimates.len() - 1) as f64;
+    let q_p_value = if q_df == 0.0 {
+        1.0
+    } else {
+        ChiSquare::new(q_df).sf(q)
+    };
+    let i_squared = if q > 0.0 {
+        ((q - q_df) / q).max(0.0) * 100.0
+    } else {
+        0.0
+    };
+
+    let tau_squared = match model {
+        MetaAnalysisModel::FixedEffect => 0.0,
+        MetaAnalysisModel::RandomEffects => {
+            let square_total: f64 = weights.iter().map(|w| w * w).sum();
+            let scale = weight_total - square_total / weight_total;
+            if scale > 0.0 {
+                ((q - q_df) / scale).max(0.0)
+            } else {
+                0.0
+            }
+        }
+    };
+    let (estimate, weight_total) = if tau_squared > 0.0 {
+        let weights: Vec<f64> = variances.iter().map(|v| 1.0 / (v + tau_squared)).collect();
+        weighted_mean(&weights)
+    } else {
+        (fixed_estimate, weight_total)
+    };
+
+    let standard_error = 1.0 / weight_total.sqrt();
+    let z = estimate / standard_error;
+    let normal = Normal::standard();
+    let margin = normal.quantile(0.5 + confidence / 2.0) * standard_error;
+    Ok(MetaAnalysisResult {
+        estimate,
+        standard_error,
+        z,
+        p_value: normal.two_tailed(z),
+        confidence_interval: ConfidenceInterval {
+            lower: estimate - margin,
+            upper: estimate + margin,
+            point_estimate: estimate,
+            confidence,
+        },
+        q,
+        q_df,
+        q_p_value,
+        i_squared,
+        tau_squared,
+        model,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const ESTIMATES: [f64; 3] = [0.52, 0.31, 0.77];
+    const STANDARD_ERRORS: [f64; 3] = [0.18, 0.12, 0.25];
+    const HETEROGENEOUS: [f64; 3] = [0.1, 0.9, 0.4];
+    const HETEROGENEOUS_ERRORS: [f64; 3] = [0.1, 0.15, 0.12];
+
+    #[test]
+    fn fixed_effect_meta_test() {
+        // R: metafor::rma(yi, sei = sei, method = "FE")
+        let result = fixed_effect_meta(&ESTIMATES, &STANDARD_ERRORS, 0.95).unwrap();
+        assert_close(result.estimate, 0.4290064748965078, 1e-15);
+        assert_close(result.standard_error, 0.09272439482469304, 1e-15);
+        assert_close(result.z, 4.626684010260705, 1e-13);
+        assert_close(result.p_value, 3.715667499668161e-06, 1e-17);
+        assert_close(result.confidence_interval.lower, 0.24727000055183723, 1e-14);
+        assert_close(result.confidence_interval.upper, 0.6107429492411784, 1e-14);
+        assert_close(result.q, 3.099485192654708, 1e-13);
+        assert_eq!(result.q_df, 2.0);
+        assert_close(result.q_p_value, 0.21230261426674105, 1e-13);
+        assert_close(result.i_squared, 35.47315519558908, 1e-11);
+        assert_eq!(result.tau_squared, 0.0);
+        assert_eq!(result.test_name(), "fixed-effect meta-analysis");
+        assert_eq!(result.to_string(), "z = 4.63, p < 0.001");
+
+        let result = fixed_effect_meta(&HETEROGENEOUS, &HETEROGENEOUS_ERRORS, 0.95).unwrap();
+        assert_close(result.estimate, 0.36363636363636365, 1e-15);
+        assert_close(result.q, 19.828282828282827, 1e-12);
+        assert_close(result.q_p_value, 4.947013448849583e-05, 1e-16);
+        assert_close(result.i_squared, 89.91339786041773, 1e-11);
+    }
+
+    #[test]
+    fn random_effects_meta_test() {
+        // R: metafor::rma(yi, sei = sei, method = "DL")
+        let result = random_effects_meta(&HETEROGENEOUS, &HETEROGENEOUS_ERRORS, 0.95).unwrap();
+        assert_close(result.tau_squared, 0.13171641791044775, 1e-14);
+        assert_close(result.estimate, 0.4552031175594537, 1e-14);
+        assert_close(result.standard_error, 0.22148735277229015, 1e-14);
+        assert_close(result.p_value, 0.03985868414357268, 1e-13);
+        assert_close(
+            result.confidence_interval.lower,
+            0.021095883094647282,
+            1e-13,
+        );
+        assert_close(result.confidence_interval.upper, 0.8893103520242601, 1e-13);
+        assert_close(result.i_squared, 89.91339786041773, 1e-11);
+        assert_eq!(result.model, MetaAnalysisModel::RandomEffects);
+
+        let result = random_effects_meta(&ESTIMATES, &STANDARD_ERRORS, 0.95).unwrap();
+        assert_close(result.tau_squared, 0.017058417200365966, 1e-14);
+        assert_close(result.estimate, 0.4652875214447651, 1e-14);
+        assert_close(result.standard_error, 0.12444090218310856, 1e-14);
+
+        // Studies that agree within their errors give τ² = 0, the fixed
+        // effect
+        let estimates = [0.40, 0.42, 0.41];
+        let fixed = fixed_effect_meta(&estimates, &STANDARD_ERRORS, 0.9).unwrap();
+        let random = random_effects_meta(&estimates, &STANDARD_ERRORS, 0.9).unwrap();
+        assert_eq!(random.tau_squared, 0.0);
+        assert_eq!(random.i_squared, 0.0);
+        assert_eq!(random.estimate, fixed.estimate);
+        assert_eq!(random.standard_error, fixed.standard_error);
+    }
+
+    #[test]
+    fn single_study_test() {
+        let result = fixed_effect_meta(&[0.3], &[0.1], 0.95).unwrap();
+        assert_close(result.estimate, 0.3, 1e-15);
+        assert_close(result.standard_error, 0.1, 1e-15);
+        assert_eq!((result.q, result.q_df, result.q_p_value), (0.0, 0.0, 1.0));
+        assert_eq!(result.i_squared, 0.0);
+        let result = random_effects_meta(&[0.3], &[0.1], 0.95).unwrap();
+        assert_eq!(result.tau_squared, 0.0);
+    }
+
+    #[test]
+    fn meta_invalid_input_test() {
+        assert_eq!(
+            fixed_effect_meta(&[], &[], 0.95).err(),
+            Some(StatsError::EmptyInput)
+        );
+        assert_eq!(
+            fixed_effect_meta(&[0.1, 0.2], &[0.1], 0.95).err(),
+            Some(StatsError::MismatchedLengths { left: 2, right: 1 })
+        );
+        assert_eq!(
+            random_effects_meta(&[0.1, f64::NAN], &[0.1, 0.1], 0.95).err(),
+            Some(StatsError::NanInInput { index: 1 })
+        );
+        for se in [0.0, -0.1, f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                fixed_effect_meta(&[0.1, 0.2], &[0.1, se], 0.95),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        assert!(matches!(
+            fixed_effect_meta(&[0.1], &[0.1], 1.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
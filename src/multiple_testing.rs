@@ -0,0 +1,264 @@
+use crate::hypothesis::validate_alpha;
+use crate::summation::CompensatedSum;
+use crate::StatsError;
+
+/// How `adjust_p_values` corrects for testing several hypotheses at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorrectionMethod {
+    /// Multiply every p-value by the number of tests. Controls the
+    /// family-wise error rate under any dependence.
+    Bonferroni,
+    /// Holm's step-down procedure: uniformly more powerful than Bonferroni
+    /// with the same guarantee.
+    Holm,
+    /// Hochberg's step-up procedure. Controls the family-wise error rate
+    /// for independent or positively dependent tests.
+    Hochberg,
+    /// Benjamini-Hochberg: controls the false discovery rate for independent
+    /// or positively dependent tests.
+    BenjaminiHochberg,
+    /// Benjamini-Yekutieli: controls the false discovery rate under any
+    /// dependence, at the cost of power.
+    BenjaminiYekutieli,
+}
+
+/// Adjusted p-values for `p_values`, in the input order, as R's `p.adjust`
+/// computes them. Each adjusted value is at most 1 and at least its raw
+/// p-value, and tied p-values get equal adjusted values.
+///
+/// Returns `StatsError::InvalidProbability` with the index of the first
+/// value that is NaN or outside [0, 1]. An empty input gives an empty
+/// output.
+pub fn adjust_p_values(p_values: &[f64], method: CorrectionMethod) -> Result<Vec<f64>, StatsError> {
+    if let Some(index) = p_values.iter().position(|p| !(0.0..=1.0).contains(p)) {
+        return Err(StatsError::InvalidProbability { index });
+    }
+    let n = p_values.len() as f64;
+
+    if method == CorrectionMethod::Bonferroni {
+        return Ok(p_values.iter().map(|p| (n * p).min(1.0)).collect());
+    }
+
+    // Ranks in ascending order of p; the sort is stable, as R's `order` is.
+    let mut order: Vec<usize> = (0..p_values.len()).collect();
+    order.sort_by(|&a, &b| p_values[a].total_cmp(&p_values[b]));
+
+    let mut adjusted = vec![0.0; p_values.len()];
+    if method == CorrectionMethod::Holm {
+        // Step down from the smallest p-value, never letting the adjusted
+        // values decrease.
+        let mut running_max = 0.0_f64;
+        for (k, &index) in order.iter().enumerate() {
+            let i = (k + 1) as f64;
+            running_max = running_max.max((n - i + 1.0) * p_values[index]);
+            adjusted[index] = running_max.min(1.0);
+        }
+        return Ok(adjusted);
+    }
+
+    let harmonic = if method == CorrectionMethod::BenjaminiYekutieli {
+        let mut sum = CompensatedSum::default();
+        for k in 1..=p_values.len() {
+            sum.add(1.0 / k as f64);
+        }
+        sum.total()
+    } else {
+        1.0
+    };
+
+    // Step up from the largest p-value, never letting the adjusted values
+    // increase. The products are grouped as in `p.adjust` so the results
+    // agree to the last bit.
+    let mut running_min = f64::INFINITY;
+    for (k, &index) in order.iter().enumerate().rev() {
+        let i = (k + 1) as f64;
+        let p = p_values[index];
+        let value = match method {
+            CorrectionMethod::Hochberg => (n + 1.0 - i) * p,
+            CorrectionMethod::BenjaminiHochberg => n / i * p,
+            _ => harmonic * n / i * p,
+        };
+        running_min = running_min.min(value);
+        adjusted[index] = running_min.min(1.0);
+    }
+    Ok(adjusted)
+}
+
+/// Whether each hypothesis is rejected at level `alpha` after correcting
+/// `p_values` with `method`: its adjusted p-value is below `alpha`.
+pub fn significant_after_correction(
+    p_values: &[f64],
+    alpha: f64,
+    method: CorrectionMethod,
+) -> Result<Vec<bool>, StatsError> {
+    validate_alpha(alpha)?;
+    Ok(adjust_p_values(p_values, method)?
+        .into_iter()
+        .map(|p| p < alpha)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const METHODS: [CorrectionMethod; 5] = [
+        CorrectionMethod::Bonferroni,
+        CorrectionMethod::Holm,
+        CorrectionMethod::Hochberg,
+        CorrectionMethod::BenjaminiHochberg,
+        CorrectionMethod::BenjaminiYekutieli,
+    ];
+
+    #[test]
+    fn adjust_sorted_test() {
+        // R: p.adjust(c(0.01, 0.02, 0.03, 0.04, 0.05), method)
+        let p = [0.01, 0.02, 0.03, 0.04, 0.05];
+        assert_eq!(
+            adjust_p_values(&p, CorrectionMethod::Bonferroni).unwrap(),
+            [0.05, 0.1, 0.15, 0.2, 0.25]
+        );
+        assert_eq!(
+            adjust_p_values(&p, CorrectionMethod::Holm).unwrap(),
+            [0.05, 0.08, 0.09, 0.09, 0.09]
+        );
+        assert_eq!(
+            adjust_p_values(&p, CorrectionMethod::Hochberg).unwrap(),
+            [0.05; 5]
+        );
+        assert_eq!(
+            adjust_p_values(&p, CorrectionMethod::BenjaminiHochberg).unwrap(),
+            [0.05; 5]
+        );
+        let by = adjust_p_values(&p, CorrectionMethod::BenjaminiYekutieli).unwrap();
+        for (actual, expected) in by.iter().zip([
+            0.11416666666666665,
+            0.11416666666666665,
+            0.11416666666666665,
+            0.11416666666666667,
+            0.11416666666666667,
+        ]) {
+            assert_close(*actual, expected, 1e-16);
+        }
+    }
+
+    #[test]
+    fn adjust_unsorted_with_ties_test() {
+        // R: p <- c(0.04, 0.001, 0.03, 0.04, 0.2, 0.012, 0.5, 0.03)
+        let p = [0.04, 0.001, 0.03, 0.04, 0.2, 0.012, 0.5, 0.03];
+        // R: p.adjust(p, "bonferroni")
+        assert_eq!(
+            adjust_p_values(&p, CorrectionMethod::Bonferroni).unwrap(),
+            [0.32, 0.008, 0.24, 0.32, 1.0, 0.096, 1.0, 0.24]
+        );
+        // R: p.adjust(p, "holm")
+        assert_eq!(
+            adjust_p_values(&p, CorrectionMethod::Holm).unwrap(),
+            [0.18, 0.008, 0.18, 0.18, 0.4, 0.084, 0.5, 0.18]
+        );
+        // R: p.adjust(p, "hochberg")
+        assert_eq!(
+            adjust_p_values(&p, CorrectionMethod::Hochberg).unwrap(),
+            [0.12, 0.008, 0.12, 0.12, 0.4, 0.084, 0.5, 0.12]
+        );
+        // R: p.adjust(p, "BH")
+        let bh = 0.05333333333333333;
+        assert_eq!(
+            adjust_p_values(&p, CorrectionMethod::BenjaminiHochberg).unwrap(),
+            [bh, 0.008, bh, bh, 0.22857142857142856, 0.048, 0.5, bh]
+        );
+        // R: p.adjust(p, "BY")
+        let by = adjust_p_values(&p, CorrectionMethod::BenjaminiYekutieli).unwrap();
+        let expected = [
+            0.14495238095238094,
+            0.021742857142857144,
+            0.14495238095238094,
+            0.14495238095238094,
+            0.6212244897959184,
+            0.13045714285714285,
+            1.0,
+            0.14495238095238094,
+        ];
+        for (actual, expected) in by.iter().zip(expected) {
+            assert_close(*actual, expected, 1e-16);
+        }
+    }
+
+    #[test]
+    fn adjusted_bounds_test() {
+        let mut rng = crate::rng::Rng::new(115);
+        let p: Vec<f64> = (0..200).map(|_| rng.next_f64().powi(3)).collect();
+        for method in METHODS {
+            let adjusted = adjust_p_values(&p, method).unwrap();
+            assert_eq!(adjusted.len(), p.len());
+            for (raw, adj) in p.iter().zip(&adjusted) {
+                assert!(raw <= adj && *adj <= 1.0, "{method:?}: {raw} -> {adj}");
+            }
+            // Adjustment preserves the order of the raw p-values.
+            for i in 0..p.len() {
+                for j in 0..p.len() {
+                    if p[i] < p[j] {
+                        assert!(adjusted[i] <= adjusted[j], "{method:?}");
+                    }
+                }
+            }
+        }
+        // The FDR corrections are never more conservative than Holm's, and
+        // Holm's never more than Bonferroni's.
+        let holm = adjust_p_values(&p, CorrectionMethod::Holm).unwrap();
+        let bonferroni = adjust_p_values(&p, CorrectionMethod::Bonferroni).unwrap();
+        let hochberg = adjust_p_values(&p, CorrectionMethod::Hochberg).unwrap();
+        let bh = adjust_p_values(&p, CorrectionMethod::BenjaminiHochberg).unwrap();
+        for i in 0..p.len() {
+            assert!(holm[i] <= bonferroni[i]);
+            assert!(hochberg[i] <= holm[i]);
+            assert!(bh[i] <= hochberg[i]);
+        }
+    }
+
+    #[test]
+    fn single_and_empty_input_test() {
+        for method in METHODS {
+            assert_eq!(adjust_p_values(&[0.3], method).unwrap(), [0.3]);
+            assert_eq!(adjust_p_values(&[], method).unwrap(), Vec::<f64>::new());
+        }
+    }
+
+    #[test]
+    fn invalid_p_value_test() {
+        for method in METHODS {
+            assert_eq!(
+                adjust_p_values(&[0.1, 0.2, f64::NAN], method),
+                Err(StatsError::InvalidProbability { index: 2 })
+            );
+            assert_eq!(
+                adjust_p_values(&[0.1, 1.5, -0.1], method),
+                Err(StatsError::InvalidProbability { index: 1 })
+            );
+        }
+        assert_eq!(
+            adjust_p_values(&[0.0, 1.0], CorrectionMethod::Holm).unwrap(),
+            [0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn significant_after_correction_test() {
+        let p = [0.04, 0.001, 0.03, 0.04, 0.2, 0.012, 0.5, 0.03];
+        assert_eq!(
+            significant_after_correction(&p, 0.05, CorrectionMethod::Bonferroni).unwrap(),
+            [false, true, false, false, false, false, false, false]
+        );
+        assert_eq!(
+            significant_after_correction(&p, 0.05, CorrectionMethod::BenjaminiHochberg).unwrap(),
+            [false, true, false, false, false, true, false, false]
+        );
+        assert_eq!(
+            significant_after_correction(&p, 1.5, CorrectionMethod::Holm),
+            Err(StatsError::InvalidParameter(
+                "alpha must be between 0 and 1"
+            ))
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::borrow::Cow;
+
+use crate::{
+    mean, population_standard_deviation, rank, sample_standard_deviation, GetStatistics,
+    PopulationStatistics, RankMethod, SampleStatistics, StatsError,
+};
+
+/// What the `_with` functions do when the data contains NaN. Infinities are
+/// not NaN and are kept under every policy, so an infinite value gives an
+/// infinite mean and a NaN standard deviation, as in the unchecked functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NanPolicy {
+    /// Compute as usual, so any NaN makes the result NaN.
+    Propagate,
+    /// Drop NaN values and compute on the rest.
+    Ignore,
+    /// Return `StatsError::NanInInput` with the index of the first NaN.
+    Error,
+}
+
+impl NanPolicy {
+    /// `data` with the policy applied, borrowed unless values were dropped.
+    pub(crate) fn apply(self, data: &[f64]) -> Result<Cow<'_, [f64]>, StatsError> {
+        match self {
+            NanPolicy::Propagate => Ok(Cow::Borrowed(data)),
+            NanPolicy::Ignore if data.iter().any(|x| x.is_nan()) => Ok(Cow::Owned(
+                data.iter().copied().filter(|x| !x.is_nan()).collect(),
+            )),
+            NanPolicy::Ignore => Ok(Cow::Borrowed(data)),
+            NanPolicy::Error => match data.iter().position(|x| x.is_nan()) {
+                Some(index) => Err(StatsError::NanInInput { index }),
+                None => Ok(Cow::Borrowed(data)),
+            },
+        }
+    }
+}
+
+/// `mean` under a NaN policy.
+pub fn mean_with(data: &[f64], policy: NanPolicy) -> Result<f64, StatsError> {
+    Ok(mean(&policy.apply(data)?))
+}
+
+/// `sample_standard_deviation` under a NaN policy.
+pub fn sample_standard_deviation_with(data: &[f64], policy: NanPolicy) -> Result<f64, StatsError> {
+    Ok(sample_standard_deviation(&policy.apply(data)?))
+}
+
+/// `population_standard_deviation` under a NaN policy.
+pub fn population_standard_deviation_with(
+    data: &[f64],
+    policy: NanPolicy,
+) -> Result<f64, StatsError> {
+    Ok(population_standard_deviation(&policy.apply(data)?))
+}
+
+/// `rank` under a NaN policy. With `NanPolicy::Ignore` the other values are
+/// ranked among themselves and NaN positions stay NaN in the output, as
+/// scipy's `rankdata(..., nan_policy="omit")` does.
+pub fn rank_with(
+    data: &[f64],
+    method: RankMethod,
+    policy: NanPolicy,
+) -> Result<Vec<f64>, StatsError> {
+    let kept = policy.apply(data)?;
+    if policy != NanPolicy::Ignore {
+        return Ok(rank(&kept, method));
+    }
+    let mut kept_ranks = rank(&kept, method).into_iter();
+    Ok(data
+        .iter()
+        .map(|x| {
+            if x.is_nan() {
+                f64::NAN
+            } else {
+                kept_ranks.next().unwrap()
+            }
+        })
+        .collect())
+}
+
+impl SampleStatistics {
+    /// `from_array` under a NaN policy. With `NanPolicy::Ignore`, `n` is the
+    /// number of values that were not NaN, so the t-tests use the effective
+    /// sample size.
+    pub fn from_array_with(array: &[f64], policy: NanPolicy) -> Result<Self, StatsError> {
+        Ok(SampleStatistics::from_array(&policy.apply(array)?))
+    }
+}
+
+impl PopulationStatistics {
+    /// `from_array` under a NaN policy; see `SampleStatistics::from_array_with`.
+    pub fn from_array_with(array: &[f64], policy: NanPolicy) -> Result<Self, StatsError> {
+        Ok(PopulationStatistics::from_array(&policy.apply(array)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, pooled_t_test};
+
+    const WITH_NAN: [f64; 6] = [1.0, 5.5, f64::NAN, 7.7, f64::NAN, 8.9];
+    const CLEAN: [f64; 4] = [1.0, 5.5, 7.7, 8.9];
+
+    #[test]
+    fn propagate_test() {
+        assert!(mean_with(&WITH_NAN, NanPolicy::Propagate).unwrap().is_nan());
+        assert!(
+            sample_standard_deviation_with(&WITH_NAN, NanPolicy::Propagate)
+                .unwrap()
+                .is_nan()
+        );
+        let samp = SampleStatistics::from_array_with(&WITH_NAN, NanPolicy::Propagate).unwrap();
+        assert_eq!(samp.n, 6);
+        let other = SampleStatistics::from_array(&[2.0, 3.5, 4.1]);
+        assert!(pooled_t_test(&samp, &other).p_value.is_nan());
+    }
+
+    #[test]
+    fn ignore_test() {
+        assert_eq!(mean_with(&WITH_NAN, NanPolicy::Ignore), Ok(5.775));
+        assert_eq!(
+            sample_standard_deviation_with(&WITH_NAN, NanPolicy::Ignore),
+            Ok(3.4807805638007885)
+        );
+        assert_eq!(
+            population_standard_deviation_with(&WITH_NAN, NanPolicy::Ignore),
+            Ok(3.0144443932506038)
+        );
+        let samp = SampleStatistics::from_array_with(&WITH_NAN, NanPolicy::Ignore).unwrap();
+        assert_eq!(samp.n, 4);
+        let pop = PopulationStatistics::from_array_with(&WITH_NAN, NanPolicy::Ignore).unwrap();
+        assert_eq!(pop.n, 4);
+
+        let other = SampleStatistics::from_array(&[2.0, 3.5, 4.1]);
+        let result = pooled_t_test(&samp, &other);
+        let expected = pooled_t_test(&SampleStatistics::from_array(&CLEAN), &other);
+        assert_close(result.t, expected.t, 1e-15);
+        assert_eq!(result.df, 5.0);
+        assert_close(result.p_value, expected.p_value, 1e-15);
+    }
+
+    #[test]
+    fn error_test() {
+        let error = Err(StatsError::NanInInput { index: 2 });
+        assert_eq!(mean_with(&WITH_NAN, NanPolicy::Error), error);
+        assert_eq!(
+            sample_standard_deviation_with(&WITH_NAN, NanPolicy::Error),
+            error
+        );
+        assert!(SampleStatistics::from_array_with(&WITH_NAN, NanPolicy::Error).is_err());
+        assert_eq!(mean_with(&CLEAN, NanPolicy::Error), Ok(5.775));
+        let samp = SampleStatistics::from_array_with(&CLEAN, NanPolicy::Error).unwrap();
+        let other = SampleStatistics::from_array(&[2.0, 3.5, 4.1]);
+        assert!(pooled_t_test(&samp, &other).p_value < 0.5);
+    }
+
+    #[test]
+    fn rank_with_test() {
+        // scipy.stats.rankdata([1, 5.5, nan, 7.7, nan, 8.9], nan_policy="omit")
+        let ranks = rank_with(&WITH_NAN, RankMethod::Average, NanPolicy::Ignore).unwrap();
+        assert_eq!(ranks[0], 1.0);
+        assert_eq!(ranks[1], 2.0);
+        assert!(ranks[2].is_nan());
+        assert_eq!(ranks[3], 3.0);
+        assert!(ranks[4].is_nan());
+        assert_eq!(ranks[5], 4.0);
+
+        let ranks = rank_with(&WITH_NAN, RankMethod::Min, NanPolicy::Propagate).unwrap();
+        assert!(ranks.iter().all(|r| r.is_nan()));
+        assert_eq!(
+            rank_with(&WITH_NAN, RankMethod::Dense, NanPolicy::Error),
+            Err(StatsError::NanInInput { index: 2 })
+        );
+        assert_eq!(
+            rank_with(&CLEAN, RankMethod::Ordinal, NanPolicy::Error),
+            Ok(vec![1.0, 2.0, 3.0, 4.0])
+        );
+    }
+
+    #[test]
+    fn infinity_test() {
+        let data = [1.0, f64::INFINITY, 3.0];
+        for policy in [NanPolicy::Propagate, NanPolicy::Ignore, NanPolicy::Error] {
+            assert_eq!(mean_with(&data, policy), Ok(f64::INFINITY));
+            assert!(sample_standard_deviation_with(&data, policy)
+                .unwrap()
+                .is_nan());
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use ndarray::{ArrayView1, ArrayView2, Axis};
+
+use crate::{
+    mean_iter, single_pass_moments, squared_deviations_iter, Float, PopulationStatistics,
+    SampleStatistics,
+};
+
+impl SampleStatistics {
+    /// `from_array` for a one-dimensional view. The view is read through
+    /// its iterator, so strided and transposed views work without being
+    /// copied into a contiguous slice.
+    pub fn from_array_view<T: Float>(view: ArrayView1<T>) -> Self {
+        let (n, sample_mean, m2) = single_pass_moments(view.iter().copied());
+        SampleStatistics {
+            sample_mean,
+            standard_deviation: (m2 / (n as f64 - 1.0)).sqrt(),
+            n,
+        }
+    }
+}
+
+impl PopulationStatistics {
+    /// `from_array` for a one-dimensional view; see
+    /// `SampleStatistics::from_array_view`.
+    pub fn from_array_view<T: Float>(view: ArrayView1<T>) -> Self {
+        let (n, population_mean, m2) = single_pass_moments(view.iter().copied());
+        PopulationStatistics {
+            population_mean,
+            standard_deviation: (m2 / n as f64).sqrt(),
+            n,
+        }
+    }
+}
+
+/// Sample statistics of each lane along `axis`, following ndarray's axis
+/// convention: `Axis(0)` summarizes each column and `Axis(1)` each row.
+pub fn sample_statistics_axis<T: Float>(view: ArrayView2<T>, axis: Axis) -> Vec<SampleStatistics> {
+    view.lanes(axis)
+        .into_iter()
+        .map(SampleStatistics::from_array_view)
+        .collect()
+}
+
+/// The mean of each lane along `axis`; see `sample_statistics_axis`.
+pub fn mean_axis<T: Float>(view: ArrayView2<T>, axis: Axis) -> Vec<f64> {
+    view.lanes(axis)
+        .into_iter()
+        .map(|lane| mean_iter(lane.iter().copied()))
+        .collect()
+}
+
+/// The sample standard deviation of each lane along `axis`.
+pub fn sample_standard_deviation_axis<T: Float>(view: ArrayView2<T>, axis: Axis) -> Vec<f64> {
+    view.lanes(axis)
+        .into_iter()
+        .map(|lane| sample_standard_deviation_of(lane.iter().copied(), lane.len()))
+        .collect()
+}
+
+/// The population standard deviation of each lane along `axis`.
+pub fn population_standard_deviation_axis<T: Float>(view: ArrayView2<T>, axis: Axis) -> Vec<f64> {
+    view.lanes(axis)
+        .into_iter()
+        .map(|lane| (squared_deviations_iter(lane.iter().copied()) / lane.len() as f64).sqrt())
+        .collect()
+}
+
+/// Matches `sample_standard_deviation`, including NaN below two values.
+fn sample_standard_deviation_of<T: Float>(
+    values: impl Iterator<Item = T> + Clone,
+    n: usize,
+) -> f64 {
+    if n < 2 {
+        return f64::NAN;
+    }
+    (squared_deviations_iter(values) / (n as f64 - 1.0)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{population_standard_deviation, sample_standard_deviation, GetStatistics};
+    use ndarray::{array, s};
+
+    #[test]
+    fn from_array_view_test() {
+        let data = [1.0, 5.5, 7.7, 8.9];
+        let view = ArrayView1::from(&data);
+        let samp = SampleStatistics::from_array_view(view);
+        let expected = SampleStatistics::from_array(&data);
+        assert_eq!(samp.n, expected.n);
+        assert_eq!(samp.sample_mean, expected.sample_mean);
+        assert_eq!(samp.standard_deviation, expected.standard_deviation);
+        let pop = PopulationStatistics::from_array_view(view);
+        assert_eq!(pop.standard_deviation, 3.0144443932506038);
+    }
+
+    #[test]
+    fn strided_view_test() {
+        let data = array![1.0, -3.0, 5.5, -3.0, 7.7, -3.0, 8.9, -3.0];
+        let every_other = data.slice(s![..;2]);
+        assert!(every_other.as_slice().is_none());
+        let samp = SampleStatistics::from_array_view(every_other);
+        assert_eq!(samp.n, 4);
+        assert_eq!(samp.sample_mean, 5.775);
+        assert_eq!(samp.standard_deviation, 3.4807805638007885);
+    }
+
+    #[test]
+    fn axis_test() {
+        let matrix = array![[1.0, 2.0, 4.0], [3.0, 2.0, 8.0], [5.0, 2.0, 9.0]];
+        let columns: Vec<Vec<f64>> = (0..3).map(|j| matrix.column(j).to_vec()).collect();
+
+        let stats = sample_statistics_axis(matrix.view(), Axis(0));
+        assert_eq!(stats.len(), 3);
+        for (samp, column) in stats.iter().zip(&columns) {
+            assert_eq!(samp.n, 3);
+            assert_eq!(samp.standard_deviation, sample_standard_deviation(column));
+        }
+        assert_eq!(mean_axis(matrix.view(), Axis(0)), [3.0, 2.0, 7.0]);
+        assert_eq!(
+            sample_standard_deviation_axis(matrix.view(), Axis(0))[0],
+            2.0
+        );
+        assert_eq!(
+            population_standard_deviation_axis(matrix.view(), Axis(1))[1],
+            population_standard_deviation(&[3.0, 2.0, 8.0])
+        );
+
+        // Rows of the transpose are the columns of the original
+        let transposed = matrix.t();
+        assert_eq!(
+            mean_axis(transposed, Axis(1)),
+            mean_axis(matrix.view(), Axis(0))
+        );
+        assert_eq!(mean_axis(matrix.view(), Axis(1))[2], 16.0 / 3.0);
+    }
+}
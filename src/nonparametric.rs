@@ -0,0 +1,1775 @@
+use std::fmt;
+
+use crate::confidence::validate_confidence;
+use crate::distributions::{ChiSquare, Normal, StudentsT};
+use crate::exact::binomial_cdf;
+use crate::format::{p_value, write_test_line};
+use crate::rank::{midranks, tie_term};
+use crate::{
+    adjust_p_values, binomial_test, median, Alternative, ConfidenceInterval, ContingencyTable,
+    CorrectionMethod, HypothesisTest, StatsError,
+};
+
+/// Largest sample size for which rank tests use the exact null distribution
+/// when the method is chosen automatically.
+pub(crate) const EXACT_MAX_SAMPLE_SIZE: usize = 20;
+
+/// Largest sample size for which the Ansari-Bradley test uses the exact null
+/// distribution when the method is chosen automatically, as in scipy.
+const ANSARI_EXACT_MAX_SAMPLE_SIZE: usize = 54;
+
+/// How a rank test computed its p-value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RankTestMethod {
+    /// The exact permutation distribution of the statistic, which requires
+    /// data without ties.
+    Exact,
+    /// The normal approximation with tie and continuity corrections.
+    NormalApproximation,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KruskalWallisResult {
+    pub h: f64,
+    pub df: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for KruskalWallisResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "H", &[self.df], self.h, self.p_value)
+    }
+}
+
+impl HypothesisTest for KruskalWallisResult {
+    fn statistic(&self) -> f64 {
+        self.h
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Kruskal-Wallis test"
+    }
+}
+
+/// Kruskal-Wallis H test that two or more independent groups come from the
+/// same distribution. Observations are ranked across the pooled data with
+/// average ranks for ties, and H is divided by the usual tie correction.
+pub fn kruskal_wallis(groups: &[&[f64]]) -> Result<KruskalWallisResult, StatsError> {
+    if groups.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: groups.len(),
+        });
+    }
+    if groups.iter().any(|g| g.is_empty()) {
+        return Err(StatsError::EmptyInput);
+    }
+
+    let pooled: Vec<f64> = groups.iter().flat_map(|g| g.iter().copied()).collect();
+    let n = pooled.len() as f64;
+    let correction = 1.0 - tie_term(&pooled) / (n * n * n - n);
+    if correction <= 0.0 {
+        return Err(StatsError::InvalidParameter("all observations are tied"));
+    }
+
+    let ranks = midranks(&pooled);
+    let mut offset = 0;
+    let mut weighted_sum = 0.0;
+    for group in groups {
+        let rank_sum: f64 = ranks[offset..offset + group.len()].iter().sum();
+        weighted_sum += rank_sum * rank_sum / group.len() as f64;
+        offset += group.len();
+    }
+
+    let h = (12.0 / (n * (n + 1.0)) * weighted_sum - 3.0 * (n + 1.0)) / correction;
+    let df = (groups.len() - 1) as f64;
+    let p_value = ChiSquare::new(df).sf(h);
+
+    Ok(KruskalWallisResult { h, df, p_value })
+}
+
+/// One pairwise comparison from `conover_iman`, between the groups at
+/// indices `group_1` and `group_2` of the input.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConoverImanComparison {
+    pub group_1: usize,
+    pub group_2: usize,
+    /// Mean rank of `group_1` minus mean rank of `group_2`, over its
+    /// standard error.
+    pub t: f64,
+    pub df: f64,
+    /// Two-sided p-value against Student's t.
+    pub p_value: f64,
+    /// `p_value` corrected over every pair of groups.
+    pub adjusted_p_value: f64,
+}
+
+/// The Conover-Iman post-hoc test after a Kruskal-Wallis test: every pair
+/// of groups is compared on its mean pooled rank, with a t statistic on
+/// N - k degrees of freedom whose variance
+/// S² (N - 1 - H) / (N - k) (1 / n_i + 1 / n_j) uses the tie-corrected H of
+/// `kruskal_wallis` and the variance S² of the midranks, so ties are
+/// accounted for as in the omnibus test. The p-values are two-sided, as
+/// R's `conover.test` with `altp = TRUE`, and corrected for the
+/// k (k - 1) / 2 pairs by `correction`.
+///
+/// Fails as `kruskal_wallis` does, and with `StatsError::InsufficientData`
+/// unless there are more observations than groups, which the N - k degrees
+/// of freedom need.
+pub fn conover_iman(
+    groups: &[&[f64]],
+    correction: CorrectionMethod,
+) -> Result<Vec<ConoverImanComparison>, StatsError> {
+    let h = kruskal_wallis(groups)?.h;
+    let pooled: Vec<f64> = groups.iter().flat_map(|g| g.iter().copied()).collect();
+    if pooled.len() <= groups.len() {
+        return Err(StatsError::InsufficientData {
+            required: groups.len() + 1,
+            actual: pooled.len(),
+        });
+    }
+    let n = pooled.len() as f64;
+    let k = groups.len() as f64;
+    let ranks = midranks(&pooled);
+
+    let rank_square_sum: f64 = ranks.iter().map(|r| r * r).sum();
+    let rank_variance = (rank_square_sum - n * (n + 1.0) * (n + 1.0) / 4.0) / (n - 1.0);
+    let scale = rank_variance * (n - 1.0 - h) / (n - k);
+    let mut offset = 0;
+    let mean_ranks: Vec<f64> = groups
+        .iter()
+        .map(|group| {
+            let rank_sum: f64 = ranks[offset..offset + group.len()].iter().sum();
+            offset += group.len();
+            rank_sum / group.len() as f64
+        })
+        .collect();
+
+    let df = n - k;
+    let t_distribution = StudentsT::new(df);
+    let mut comparisons = Vec::new();
+    for i in 0..groups.len() {
+        for j in i + 1..groups.len() {
+            let standard_error =
+                (scale * (1.0 / groups[i].len() as f64 + 1.0 / groups[j].len() as f64)).sqrt();
+            let t = (mean_ranks[i] - mean_ranks[j]) / standard_error;
+            comparisons.push(ConoverImanComparison {
+                group_1: i,
+                group_2: j,
+                t,
+                df,
+                p_value: t_distribution.two_tailed(t),
+                adjusted_p_value: 0.0,
+            });
+        }
+    }
+
+    let p_values: Vec<f64> = comparisons.iter().map(|c| c.p_value).collect();
+    for (comparison, adjusted) in comparisons
+        .iter_mut()
+        .zip(adjust_p_values(&p_values, correction)?)
+    {
+        comparison.adjusted_p_value = adjusted;
+    }
+    Ok(comparisons)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FriedmanResult {
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+    /// Kendall's coefficient of concordance, statistic / (n (k - 1)).
+    pub kendalls_w: f64,
+}
+
+impl fmt::Display for FriedmanResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "χ²", &[self.df], self.statistic, self.p_value)
+    }
+}
+
+impl HypothesisTest for FriedmanResult {
+    fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Friedman test"
+    }
+}
+
+/// Friedman rank test for k treatments measured on the same n blocks, where
+/// each row of `data` is one block. Values are ranked within each row with
+/// average ranks for ties, and the statistic is corrected for those ties.
+pub fn friedman_test(data: &[Vec<f64>]) -> Result<FriedmanResult, StatsError> {
+    if data.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: data.len(),
+        });
+    }
+    let k = data[0].len();
+    if k < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: k,
+        });
+    }
+    if let Some(row) = data.iter().find(|row| row.len() != k) {
+        return Err(StatsError::MismatchedLengths {
+            left: k,
+            right: row.len(),
+        });
+    }
+
+    let mut rank_sums = vec![0.0; k];
+    let mut ties = 0.0;
+    for row in data {
+        for (sum, rank) in rank_sums.iter_mut().zip(midranks(row)) {
+            *sum += rank;
+        }
+        ties += tie_term(row);
+    }
+
+    let n = data.len() as f64;
+    let k = k as f64;
+    let correction = 1.0 - ties / (n * (k * k * k - k));
+    if correction <= 0.0 {
+        return Err(StatsError::InvalidParameter("every block is entirely tied"));
+    }
+    let squares: f64 = rank_sums.iter().map(|r| r * r).sum();
+    let statistic = (12.0 / (n * k * (k + 1.0)) * squares - 3.0 * n * (k + 1.0)) / correction;
+    let df = k - 1.0;
+
+    Ok(FriedmanResult {
+        statistic,
+        df,
+        p_value: ChiSquare::new(df).sf(statistic),
+        kendalls_w: statistic / (n * df),
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MannWhitneyResult {
+    /// U statistic for `sample_1`, the number of pairs in which its value
+    /// exceeds the one from `sample_2`, counting ties as one half.
+    pub u: f64,
+    /// The continuity-corrected z statistic, when the normal approximation
+    /// was used.
+    pub z: Option<f64>,
+    pub p_value: f64,
+    pub method: RankTestMethod,
+    /// U / (n1 n2), the probability that an observation from `sample_1`
+    /// exceeds one from `sample_2`, counting ties as one half.
+    pub common_language_effect_size: f64,
+}
+
+impl MannWhitneyResult {
+    /// The rank-biserial correlation, 2 CL - 1.
+    pub fn rank_biserial(&self) -> f64 {
+        2.0 * self.common_language_effect_size - 1.0
+    }
+}
+
+impl fmt::Display for MannWhitneyResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "U", &[], self.u, self.p_value)
+    }
+}
+
+impl HypothesisTest for MannWhitneyResult {
+    fn statistic(&self) -> f64 {
+        self.u
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Mann-Whitney U test"
+    }
+}
+
+/// Mann-Whitney U (Wilcoxon rank-sum) test. `Alternative::Greater` tests
+/// whether `sample_1` tends to be larger than `sample_2`. Uses the exact
+/// distribution of U when both samples have at most 20 observations and
+/// there are no ties, and the normal approximation otherwise.
+pub fn mann_whitney_u(
+    sample_1: &[f64],
+    sample_2: &[f64],
+    alternative: Alternative,
+) -> Result<MannWhitneyResult, StatsError> {
+    let has_ties = {
+        let pooled: Vec<f64> = sample_1.iter().chain(sample_2).copied().collect();
+        tie_term(&pooled) > 0.0
+    };
+    let method = if sample_1.len() <= EXACT_MAX_SAMPLE_SIZE
+        && sample_2.len() <= EXACT_MAX_SAMPLE_SIZE
+        && !has_ties
+    {
+        RankTestMethod::Exact
+    } else {
+        RankTestMethod::NormalApproximation
+    };
+    mann_whitney_u_with(sample_1, sample_2, alternative, method)
+}
+
+/// Mann-Whitney U test with an explicit choice of method. The exact method
+/// rejects samples containing ties.
+pub fn mann_whitney_u_with(
+    sample_1: &[f64],
+    sample_2: &[f64],
+    alternative: Alternative,
+    method: RankTestMethod,
+) -> Result<MannWhitneyResult, StatsError> {
+    if sample_1.is_empty() || sample_2.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+
+    let pooled: Vec<f64> = sample_1.iter().chain(sample_2).copied().collect();
+    let n1 = sample_1.len() as f64;
+    let n2 = sample_2.len() as f64;
+    let n = n1 + n2;
+    let u = u_statistic(&pooled, sample_1.len());
+    let common_language_effect_size = u / (n1 * n2);
+    let ties = tie_term(&pooled);
+
+    match method {
+        RankTestMethod::Exact => {
+            if ties > 0.0 {
+                return Err(StatsError::InvalidParameter(
+                    "the exact Mann-Whitney test requires data without ties",
+                ));
+            }
+            let counts = u_distribution(sample_1.len(), sample_2.len());
+            let total: f64 = counts.iter().sum();
+            // P(U >= u), with u an integer when there are no ties
+            let upper = |u: f64| counts[u as usize..].iter().sum::<f64>() / total;
+            let p_value = match alternative {
+                Alternative::Greater => upper(u),
+                Alternative::Less => upper(n1 * n2 - u),
+                Alternative::TwoSided => (2.0 * upper(u.max(n1 * n2 - u))).min(1.0),
+            };
+            Ok(MannWhitneyResult {
+                u,
+                z: None,
+                p_value,
+                method,
+                common_language_effect_size,
+            })
+        }
+        RankTestMethod::NormalApproximation => {
+            let variance = n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)));
+            if variance <= 0.0 {
+                return Err(StatsError::InvalidParameter("all observations are tied"));
+            }
+            let deviation = u - n1 * n2 / 2.0;
+            let z = (deviation - continuity_correction(deviation, alternative)) / variance.sqrt();
+            Ok(MannWhitneyResult {
+                u,
+                z: Some(z),
+                p_value: normal_p_value(z, alternative),
+                method,
+                common_language_effect_size,
+            })
+        }
+    }
+}
+
+/// The Mann-Whitney U of the first `n1` values of `pooled` against the
+/// rest: their midrank sum less its minimum, n1 (n1 + 1) / 2. Each tie
+/// between the samples counts one half.
+pub(crate) fn u_statistic(pooled: &[f64], n1: usize) -> f64 {
+    let rank_sum: f64 = midranks(pooled)[..n1].iter().sum();
+    rank_sum - (n1 * (n1 + 1)) as f64 / 2.0
+}
+
+/// Which of two samples a dispersion test found more spread out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoreDispersed {
+    First,
+    Second,
+    /// The statistic equals its null mean.
+    Neither,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnsariBradleyResult {
+    /// Sum of the scores min(r, N + 1 - r) of `sample_1`, where r are the
+    /// midranks in the pooled sample of size N.
+    pub ab: f64,
+    /// The z statistic, positive when `sample_1` is more dispersed, when the
+    /// normal approximation was used.
+    pub z: Option<f64>,
+    pub p_value: f64,
+    pub method: RankTestMethod,
+    /// The sample whose observations lie further from the pooled center:
+    /// the first when AB is below its null mean, since its extreme values
+    /// get small scores.
+    pub more_dispersed: MoreDispersed,
+}
+
+impl fmt::Display for AnsariBradleyResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "AB", &[], self.ab, self.p_value)
+    }
+}
+
+impl HypothesisTest for AnsariBradleyResult {
+    fn statistic(&self) -> f64 {
+        self.ab
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Ansari-Bradley test"
+    }
+}
+
+/// Ansari-Bradley test that two samples with a common median have equal
+/// dispersions, without assuming normality as `var_f_test` does.
+/// `Alternative::Greater` tests whether `sample_1` is more dispersed, i.e.
+/// the ratio of its scale to that of `sample_2` exceeds 1. As in scipy's
+/// `ansari`, the exact distribution of AB is used when both samples have
+/// fewer than 55 observations and there are no ties, and the normal
+/// approximation with a tie-corrected variance otherwise.
+pub fn ansari_bradley(
+    sample_1: &[f64],
+    sample_2: &[f64],
+    alternative: Alternative,
+) -> Result<AnsariBradleyResult, StatsError> {
+    let has_ties = {
+        let pooled: Vec<f64> = sample_1.iter().chain(sample_2).copied().collect();
+        tie_term(&pooled) > 0.0
+    };
+    let method = if sample_1.len() <= ANSARI_EXACT_MAX_SAMPLE_SIZE
+        && sample_2.len() <= ANSARI_EXACT_MAX_SAMPLE_SIZE
+        && !has_ties
+    {
+        RankTestMethod::Exact
+    } else {
+        RankTestMethod::NormalApproximation
+    };
+    ansari_bradley_with(sample_1, sample_2, alternative, method)
+}
+
+/// Ansari-Bradley test with an explicit choice of method. The exact method
+/// rejects samples containing ties. No continuity correction is applied to
+/// the normal approximation, matching scipy and R.
+pub fn ansari_bradley_with(
+    sample_1: &[f64],
+    sample_2: &[f64],
+    alternative: Alternative,
+    method: RankTestMethod,
+) -> Result<AnsariBradleyResult, StatsError> {
+    if sample_1.is_empty() || sample_2.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+
+    let pooled: Vec<f64> = sample_1.iter().chain(sample_2).copied().collect();
+    // The tie-corrected variance stays positive here, but there is no
+    // dispersion to compare.
+    if pooled.iter().all(|&value| value == pooled[0]) {
+        return Err(StatsError::InvalidParameter("all observations are tied"));
+    }
+    let n1 = sample_1.len() as f64;
+    let n2 = sample_2.len() as f64;
+    let n = n1 + n2;
+    let scores: Vec<f64> = midranks(&pooled)
+        .into_iter()
+        .map(|r| r.min(n + 1.0 - r))
+        .collect();
+    let ab: f64 = scores[..sample_1.len()].iter().sum();
+    let even = pooled.len().is_multiple_of(2);
+    let null_mean = if even {
+        n1 * (n + 2.0) / 4.0
+    } else {
+        n1 * (n + 1.0) * (n + 1.0) / (4.0 * n)
+    };
+    let more_dispersed = if ab < null_mean {
+        MoreDispersed::First
+    } else if ab > null_mean {
+        MoreDispersed::Second
+    } else {
+        MoreDispersed::Neither
+    };
+    let ties = tie_term(&pooled);
+
+    match method {
+        RankTestMethod::Exact => {
+            if ties > 0.0 {
+                return Err(StatsError::InvalidParameter(
+                    "the exact Ansari-Bradley test requires data without ties",
+                ));
+            }
+            let counts = ab_distribution(sample_1.len(), sample_2.len());
+            let total: f64 = counts.iter().sum();
+            // AB is an integer when there are no ties
+            let lower = counts[..=ab as usize].iter().sum::<f64>() / total;
+            let upper = counts[ab as usize..].iter().sum::<f64>() / total;
+            let p_value = match alternative {
+                Alternative::Greater => lower,
+                Alternative::Less => upper,
+                Alternative::TwoSided => (2.0 * lower.min(upper)).min(1.0),
+            };
+            Ok(AnsariBradleyResult {
+                ab,
+                z: None,
+                p_value,
+                method,
+                more_dispersed,
+            })
+        }
+        RankTestMethod::NormalApproximation => {
+            let variance = if ties > 0.0 {
+                let squares: f64 = scores.iter().map(|a| a * a).sum();
+                if even {
+                    n1 * n2 * (16.0 * squares - n * (n + 2.0).powi(2)) / (16.0 * n * (n - 1.0))
+                } else {
+                    n1 * n2 * (16.0 * n * squares - (n + 1.0).powi(4)) / (16.0 * n * n * (n - 1.0))
+                }
+            } else if even {
+                n1 * n2 * (n + 2.0) * (n - 2.0) / (48.0 * (n - 1.0))
+            } else {
+                n1 * n2 * (n + 1.0) * (3.0 + n * n) / (48.0 * n * n)
+            };
+            if variance <= 0.0 {
+                return Err(StatsError::InvalidParameter(
+                    "the normal approximation needs at least three observations",
+                ));
+            }
+            let z = (null_mean - ab) / variance.sqrt();
+            Ok(AnsariBradleyResult {
+                ab,
+                z: Some(z),
+                p_value: normal_p_value(z, alternative),
+                method,
+                more_dispersed,
+            })
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WilcoxonSignedRankResult {
+    /// Sum of the ranks of the positive differences, R's V.
+    pub w: f64,
+    /// Number of non-zero differences that were ranked.
+    pub n: usize,
+    /// The continuity-corrected z statistic, when the normal approximation
+    /// was used.
+    pub z: Option<f64>,
+    pub p_value: f64,
+    pub method: RankTestMethod,
+}
+
+impl fmt::Display for WilcoxonSignedRankResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "W", &[], self.w, self.p_value)
+    }
+}
+
+impl HypothesisTest for WilcoxonSignedRankResult {
+    fn statistic(&self) -> f64 {
+        self.w
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Wilcoxon signed-rank test"
+    }
+}
+
+/// Wilcoxon signed-rank test on the paired differences `x - y`.
+/// `Alternative::Greater` tests whether the differences tend to be positive.
+/// Zero differences are dropped before ranking. Uses the exact distribution
+/// of W for at most 20 differences without ties, and the normal
+/// approximation with tie and continuity corrections otherwise.
+pub fn wilcoxon_signed_rank(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+) -> Result<WilcoxonSignedRankResult, StatsError> {
+    if x.len() != y.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: x.len(),
+            right: y.len(),
+        });
+    }
+    let differences: Vec<f64> = x.iter().zip(y).map(|(a, b)| a - b).collect();
+    signed_rank_test(&differences, alternative)
+}
+
+/// One-sample Wilcoxon signed-rank test that the data are symmetric about
+/// the median `m0`.
+pub fn wilcoxon_signed_rank_one_sample(
+    data: &[f64],
+    m0: f64,
+    alternative: Alternative,
+) -> Result<WilcoxonSignedRankResult, StatsError> {
+    let differences: Vec<f64> = data.iter().map(|x| x - m0).collect();
+    signed_rank_test(&differences, alternative)
+}
+
+fn signed_rank_test(
+    differences: &[f64],
+    alternative: Alternative,
+) -> Result<WilcoxonSignedRankResult, StatsError> {
+    let differences: Vec<f64> = differences.iter().copied().filter(|&d| d != 0.0).collect();
+    if differences.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+
+    let magnitudes: Vec<f64> = differences.iter().map(|d| d.abs()).collect();
+    let w: f64 = midranks(&magnitudes)
+        .iter()
+        .zip(&differences)
+        .filter(|(_, &d)| d > 0.0)
+        .map(|(rank, _)| rank)
+        .sum();
+    let ties = tie_term(&magnitudes);
+    let n = differences.len();
+    let n_f = n as f64;
+    let mean = n_f * (n_f + 1.0) / 4.0;
+
+    if n <= EXACT_MAX_SAMPLE_SIZE && ties == 0.0 {
+        let counts = signed_rank_distribution(n);
+        let total: f64 = counts.iter().sum();
+        let lower = |w: f64| counts[..=w as usize].iter().sum::<f64>() / total;
+        let upper = |w: f64| counts[w as usize..].iter().sum::<f64>() / total;
+        let p_value = match alternative {
+            Alternative::Greater => upper(w),
+            Alternative::Less => lower(w),
+            Alternative::TwoSided if w > mean => (2.0 * upper(w)).min(1.0),
+            Alternative::TwoSided => (2.0 * lower(w)).min(1.0),
+        };
+        return Ok(WilcoxonSignedRankResult {
+            w,
+            n,
+            z: None,
+            p_value,
+            method: RankTestMethod::Exact,
+        });
+    }
+
+    let variance = n_f * (n_f + 1.0) * (2.0 * n_f + 1.0) / 24.0 - ties / 48.0;
+    let deviation = w - mean;
+    let z = (deviation - continuity_correction(deviation, alternative)) / variance.sqrt();
+    Ok(WilcoxonSignedRankResult {
+        w,
+        n,
+        z: Some(z),
+        p_value: normal_p_value(z, alternative),
+        method: RankTestMethod::NormalApproximation,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignTestResult {
+    /// Number of values above the hypothesized median.
+    pub above: usize,
+    /// Number of values below the hypothesized median.
+    pub below: usize,
+    pub p_value: f64,
+}
+
+impl fmt::Display for SignTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} above, {} below, {}",
+            self.above,
+            self.below,
+            p_value(self.p_value)
+        )
+    }
+}
+
+impl HypothesisTest for SignTestResult {
+    fn statistic(&self) -> f64 {
+        self.above as f64
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "sign test"
+    }
+}
+
+/// Sign test that the median of `data` equals `m0`, using an exact binomial
+/// test with p = 0.5 on the counts above and below it. Values equal to `m0`
+/// are discarded. `Alternative::Greater` tests whether the median exceeds
+/// `m0`.
+pub fn sign_test(
+    data: &[f64],
+    m0: f64,
+    alternative: Alternative,
+) -> Result<SignTestResult, StatsError> {
+    let above = data.iter().filter(|&&x| x > m0).count();
+    let below = data.iter().filter(|&&x| x < m0).count();
+    let p_value = binomial_test(above, above + below, 0.5, alternative)?.p_value;
+
+    Ok(SignTestResult {
+        above,
+        below,
+        p_value,
+    })
+}
+
+/// Paired sign test on the differences `x - y`, testing that their median
+/// is zero.
+pub fn paired_sign_test(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+) -> Result<SignTestResult, StatsError> {
+    if x.len() != y.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: x.len(),
+            right: y.len(),
+        });
+    }
+    let differences: Vec<f64> = x.iter().zip(y).map(|(a, b)| a - b).collect();
+    sign_test(&differences, 0.0, alternative)
+}
+
+/// One-sample test that the median of `data` equals `m0`. This is
+/// `sign_test` under the name people look for; `median_confidence_interval`
+/// gives the matching interval. For comparing the medians of several
+/// groups, see `mood_median_test`.
+pub fn one_sample_median_test(
+    data: &[f64],
+    m0: f64,
+    alternative: Alternative,
+) -> Result<SignTestResult, StatsError> {
+    sign_test(data, m0, alternative)
+}
+
+/// A distribution-free confidence interval for a median, bounded by two
+/// order statistics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MedianConfidenceInterval {
+    /// The interval around the sample median, at the requested confidence.
+    pub interval: ConfidenceInterval,
+    /// The probability that the interval covers the median of a continuous
+    /// population, which is at least the requested confidence.
+    pub achieved_confidence: f64,
+    /// 1-based rank of the order statistic at the lower bound, or 0 when
+    /// the bound is -inf.
+    pub lower_rank: usize,
+    /// 1-based rank of the order statistic at the upper bound, or n + 1
+    /// when the bound is inf.
+    pub upper_rank: usize,
+}
+
+/// Exact interval for the population median between the order statistics
+/// of ranks l and u, the (1 - confidence) / 2 and (1 + confidence) / 2
+/// quantiles of Binomial(n, 1/2) (the latter plus one), as R's
+/// `DescTools::MedianCI(method = "exact")`. Order statistics can only
+/// reach a few coverage levels, so the interval covers with probability
+/// P(l <= B < u), reported as `achieved_confidence`, rather than exactly
+/// `confidence`. If the sample is too small for even the extreme values to
+/// reach the requested level, the interval is unbounded.
+///
+/// Returns `StatsError::NanInInput` if `data` contains NaN.
+pub fn median_confidence_interval(
+    data: &[f64],
+    confidence: f64,
+) -> Result<MedianConfidenceInterval, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if let Some(index) = data.iter().position(|x| x.is_nan()) {
+        return Err(StatsError::NanInInput { index });
+    }
+    validate_confidence(confidence)?;
+
+    let n = data.len();
+    let alpha = 1.0 - confidence;
+    let lower_rank = binomial_half_quantile(alpha / 2.0, n);
+    let upper_rank = binomial_half_quantile(1.0 - alpha / 2.0, n) + 1;
+    let below = |rank: usize| match rank {
+        0 => 0.0,
+        _ => binomial_cdf(rank - 1, n, 0.5),
+    };
+    let achieved_confidence = below(upper_rank) - below(lower_rank);
+
+    let mut sorted = data.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+    let lower = match lower_rank {
+        0 => f64::NEG_INFINITY,
+        rank => sorted[rank - 1],
+    };
+    let upper = sorted.get(upper_rank - 1).copied().unwrap_or(f64::INFINITY);
+
+    Ok(MedianConfidenceInterval {
+        interval: ConfidenceInterval {
+            lower,
+            upper,
+            point_estimate: median(data)?,
+            confidence,
+        },
+        achieved_confidence,
+        lower_rank,
+        upper_rank,
+    })
+}
+
+/// The smallest k with P(B <= k) >= p for B ~ Binomial(n, 1/2), by
+/// bisection. As in R's `qbinom`, p is first reduced by a relative 64 ulps
+/// so that a cdf value equal to p up to rounding counts as reaching it.
+fn binomial_half_quantile(p: f64, n: usize) -> usize {
+    let target = p * (1.0 - 64.0 * f64::EPSILON);
+    let (mut low, mut high) = (0, n);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if binomial_cdf(middle, n, 0.5) >= target {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    low
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoodMedianResult {
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+    /// Median of the pooled observations.
+    pub grand_median: f64,
+    /// Number of observations in each group above the grand median.
+    pub above: Vec<usize>,
+    /// Number of observations in each group at or below the grand median.
+    pub below: Vec<usize>,
+}
+
+impl fmt::Display for MoodMedianResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "χ²", &[self.df], self.statistic, self.p_value)
+    }
+}
+
+impl HypothesisTest for MoodMedianResult {
+    fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Mood's median test"
+    }
+}
+
+/// Mood's median test that two or more groups share a common median. Each
+/// group's observations are counted above and at-or-below the grand median,
+/// so values equal to the median count as below, and the resulting 2 x k
+/// table gets a chi-square test of independence. With two groups Yates'
+/// continuity correction is applied, as scipy's `median_test` does.
+pub fn mood_median_test(groups: &[&[f64]]) -> Result<MoodMedianResult, StatsError> {
+    if groups.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: groups.len(),
+        });
+    }
+    if groups.iter().any(|g| g.is_empty()) {
+        return Err(StatsError::EmptyInput);
+    }
+
+    let pooled: Vec<f64> = groups.iter().flat_map(|g| g.iter().copied()).collect();
+    let grand_median = median(&pooled)?;
+    let above: Vec<usize> = groups
+        .iter()
+        .map(|g| g.iter().filter(|&&x| x > grand_median).count())
+        .collect();
+    let below: Vec<usize> = groups
+        .iter()
+        .zip(&above)
+        .map(|(g, above)| g.len() - above)
+        .collect();
+
+    let table = ContingencyTable::new(vec![
+        above.iter().map(|&c| c as f64).collect(),
+        below.iter().map(|&c| c as f64).collect(),
+    ])?;
+    let test = if groups.len() == 2 {
+        table.yates_chi_square_test()?
+    } else {
+        table.chi_square_test()
+    };
+
+    Ok(MoodMedianResult {
+        statistic: test.statistic,
+        df: test.df,
+        p_value: test.p_value,
+        grand_median,
+        above,
+        below,
+    })
+}
+
+/// Below this many observations the normal approximation to the runs
+/// distribution is unreliable.
+const RUNS_MIN_SAMPLE_SIZE: usize = 10;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunsTestResult {
+    pub runs: usize,
+    /// Number of observations above the cutoff, or `true` values.
+    pub n_above: usize,
+    /// Number of observations below the cutoff, or `false` values.
+    pub n_below: usize,
+    pub z: f64,
+    /// Two-sided p-value from the normal approximation.
+    pub p_value: f64,
+    /// Set when there are fewer than 10 observations, where the normal
+    /// approximation should not be trusted.
+    pub small_sample: bool,
+}
+
+impl fmt::Display for RunsTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} runs, ", self.runs)?;
+        write_test_line(f, "z", &[], self.z, self.p_value)
+    }
+}
+
+impl HypothesisTest for RunsTestResult {
+    fn statistic(&self) -> f64 {
+        self.z
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "runs test"
+    }
+}
+
+/// Wald-Wolfowitz runs test for randomness, dichotomizing `data` around its
+/// median. Values equal to the median are dropped.
+pub fn runs_test(data: &[f64]) -> Result<RunsTestResult, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    runs_test_with_cutoff(data, median(data)?)
+}
+
+/// Runs test dichotomizing `data` around `cutoff`. Values equal to the
+/// cutoff are dropped.
+pub fn runs_test_with_cutoff(data: &[f64], cutoff: f64) -> Result<RunsTestResult, StatsError> {
+    let above: Vec<bool> = data
+        .iter()
+        .filter(|&&x| x != cutoff)
+        .map(|&x| x > cutoff)
+        .collect();
+    runs_test_binary(&above)
+}
+
+/// Runs test on an already dichotomized sequence.
+pub fn runs_test_binary(sequence: &[bool]) -> Result<RunsTestResult, StatsError> {
+    if sequence.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    let n_above = sequence.iter().filter(|&&b| b).count();
+    let n_below = sequence.len() - n_above;
+    if n_above == 0 || n_below == 0 {
+        return Err(StatsError::InvalidParameter(
+            "the runs test needs observations on both sides of the cutoff",
+        ));
+    }
+
+    let runs = 1 + sequence.windows(2).filter(|w| w[0] != w[1]).count();
+    let n = sequence.len() as f64;
+    let product = 2.0 * n_above as f64 * n_below as f64;
+    let expected = product / n + 1.0;
+    let variance = product * (product - n) / (n * n * (n - 1.0));
+    let z = (runs as f64 - expected) / variance.sqrt();
+
+    Ok(RunsTestResult {
+        runs,
+        n_above,
+        n_below,
+        z,
+        p_value: Normal::standard().two_tailed(z),
+        small_sample: sequence.len() < RUNS_MIN_SAMPLE_SIZE,
+    })
+}
+
+/// Number of sign assignments giving each value of the signed-rank
+/// statistic for `n` untied differences, the coefficients of the product
+/// (1 + q)(1 + q^2)...(1 + q^n).
+pub(crate) fn signed_rank_distribution(n: usize) -> Vec<f64> {
+    let mut counts = vec![0.0; n * (n + 1) / 2 + 1];
+    counts[0] = 1.0;
+    for i in 1..=n {
+        for j in (i..counts.len()).rev() {
+            counts[j] += counts[j - i];
+        }
+    }
+    counts
+}
+
+/// Number of arrangements giving each value of U for samples of size `m`
+/// and `n`, the coefficients of the Gaussian binomial coefficient
+/// [m + n choose m] as a polynomial in q.
+pub(crate) fn u_distribution(m: usize, n: usize) -> Vec<f64> {
+    let mut counts = vec![0.0; m * n + 1];
+    counts[0] = 1.0;
+    for i in 1..=m {
+        // Multiply by (1 - q^(n + i)), then divide by (1 - q^i)
+        for j in (n + i..counts.len()).rev() {
+            counts[j] -= counts[j - n - i];
+        }
+        for j in i..counts.len() {
+            counts[j] += counts[j - i];
+        }
+    }
+    counts
+}
+
+/// Number of ways each total of the scores min(i, N + 1 - i), i = 1..N,
+/// of `m` out of N = m + n untied observations can arise, indexed by the
+/// total.
+fn ab_distribution(m: usize, n: usize) -> Vec<f64> {
+    let size = m + n;
+    let mut scores: Vec<usize> = (1..=size).map(|i| i.min(size + 1 - i)).collect();
+    scores.sort_unstable_by(|a, b| b.cmp(a));
+    let width = scores[..m].iter().sum::<usize>() + 1;
+    // counts[k * width + s]: ways to choose k of the scores seen so far
+    // with total s. Updating k in decreasing order reads each score once.
+    let mut counts = vec![0.0; (m + 1) * width];
+    counts[0] = 1.0;
+    for (seen, &score) in scores.iter().enumerate() {
+        for k in (1..=m.min(seen + 1)).rev() {
+            for s in score..width {
+                counts[k * width + s] += counts[(k - 1) * width + s - score];
+            }
+        }
+    }
+    counts.split_off(m * width)
+}
+
+/// The continuity correction that moves a statistic's deviation from its
+/// null mean half a unit towards zero, in the direction the alternative
+/// tests.
+pub(crate) fn continuity_correction(deviation: f64, alternative: Alternative) -> f64 {
+    match alternative {
+        Alternative::TwoSided if deviation == 0.0 => 0.0,
+        Alternative::TwoSided => 0.5 * deviation.signum(),
+        Alternative::Greater => 0.5,
+        Alternative::Less => -0.5,
+    }
+}
+
+fn normal_p_value(z: f64, alternative: Alternative) -> f64 {
+    Normal::standard().p_value(z, alternative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+    use crate::combinatorics::binomial_coefficient;
+
+    #[test]
+    fn kruskal_wallis_test() {
+        // R: kruskal.test(weight ~ group, data = PlantGrowth)
+        let ctrl = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+        let trt1 = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+        let trt2 = [6.31, 5.12, 5.54, 5.50, 5.37, 5.29, 4.92, 6.15, 5.80, 5.26];
+        let result = kruskal_wallis(&[&ctrl, &trt1, &trt2]).unwrap();
+        assert_close(result.h, 7.9882287494437, 1e-10);
+        assert_eq!(result.df, 2.0);
+        assert_close(result.p_value, 0.0184237557314721, 1e-12);
+    }
+
+    #[test]
+    fn kruskal_wallis_heavy_ties_test() {
+        // scipy.stats.kruskal
+        let result = kruskal_wallis(&[
+            &[1.0, 2.0, 2.0, 3.0, 3.0, 3.0],
+            &[2.0, 3.0, 3.0, 4.0, 4.0],
+            &[3.0, 4.0, 4.0, 5.0, 5.0, 5.0, 5.0],
+        ])
+        .unwrap();
+        assert_close(result.h, 10.3261878597593, 1e-10);
+        assert_close(result.p_value, 0.00572396272204643, 1e-12);
+    }
+
+    #[test]
+    fn conover_iman_test() {
+        // R: conover.test(weight, group, method = "bonferroni", altp = TRUE)
+        // on PlantGrowth
+        let ctrl = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+        let trt1 = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+        let trt2 = [6.31, 5.12, 5.54, 5.50, 5.37, 5.29, 4.92, 6.15, 5.80, 5.26];
+        let groups: [&[f64]; 3] = [&ctrl, &trt1, &trt2];
+        let comparisons = conover_iman(&groups, CorrectionMethod::Bonferroni).unwrap();
+        let pairs: Vec<(usize, usize)> =
+            comparisons.iter().map(|c| (c.group_1, c.group_2)).collect();
+        assert_eq!(pairs, [(0, 1), (0, 2), (1, 2)]);
+        let expected = [
+            (1.26702647993515, 0.215966242818074, 0.647898728454222),
+            (-1.9149377480838, 0.0661505595424723, 0.198451678627417),
+            (-3.18196422801895, 0.00366115439287371, 0.0109834631786211),
+        ];
+        for (comparison, (t, p, adjusted)) in comparisons.iter().zip(expected) {
+            assert_eq!(comparison.df, 27.0);
+            assert_close(comparison.t, t, 1e-12);
+            assert_close(comparison.p_value, p, 1e-12);
+            assert_close(comparison.adjusted_p_value, adjusted, 1e-12);
+        }
+
+        // method = "bh"
+        let comparisons = conover_iman(&groups, CorrectionMethod::BenjaminiHochberg).unwrap();
+        assert_close(comparisons[1].adjusted_p_value, 0.0992258393137084, 1e-12);
+        assert_close(comparisons[2].adjusted_p_value, 0.0109834631786211, 1e-12);
+    }
+
+    #[test]
+    fn conover_iman_ties_test() {
+        // R: conover.test(x, g, method = "bh", altp = TRUE)
+        let groups: [&[f64]; 3] = [
+            &[1.0, 2.0, 2.0, 3.0, 3.0, 3.0],
+            &[2.0, 3.0, 3.0, 4.0, 4.0],
+            &[3.0, 4.0, 4.0, 5.0, 5.0, 5.0, 5.0],
+        ];
+        let comparisons = conover_iman(&groups, CorrectionMethod::BenjaminiHochberg).unwrap();
+        let expected = [
+            (-1.76271747976293, 0.0983048850623763, 0.0983048850623763),
+            (-4.76845211579405, 0.000248828580622033, 0.0007464857418661),
+            (-2.70783087733018, 0.0162022931416651, 0.0243034397124976),
+        ];
+        for (comparison, (t, p, adjusted)) in comparisons.iter().zip(expected) {
+            assert_eq!(comparison.df, 15.0);
+            assert_close(comparison.t, t, 1e-12);
+            assert_close(comparison.p_value, p, 1e-12);
+            assert_close(comparison.adjusted_p_value, adjusted, 1e-12);
+        }
+
+        assert_eq!(
+            conover_iman(&[&[1.0, 2.0]], CorrectionMethod::Holm).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert!(matches!(
+            conover_iman(&[&[1.0, 1.0], &[1.0]], CorrectionMethod::Holm),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            conover_iman(&[&[1.0], &[2.0], &[3.0]], CorrectionMethod::Holm).err(),
+            Some(StatsError::InsufficientData {
+                required: 4,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn kruskal_wallis_invalid_input_test() {
+        assert_eq!(
+            kruskal_wallis(&[&[1.0, 2.0], &[]]).err(),
+            Some(StatsError::EmptyInput)
+        );
+        assert!(matches!(
+            kruskal_wallis(&[&[1.0, 1.0], &[1.0]]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn friedman_test_test() {
+        // R: friedman.test(RoundingTimes), from Hollander & Wolfe
+        let times = [
+            [5.40, 5.50, 5.55],
+            [5.85, 5.70, 5.75],
+            [5.20, 5.60, 5.50],
+            [5.55, 5.50, 5.40],
+            [5.90, 5.85, 5.70],
+            [5.45, 5.55, 5.60],
+            [5.40, 5.40, 5.35],
+            [5.45, 5.50, 5.35],
+            [5.25, 5.15, 5.00],
+            [5.85, 5.80, 5.70],
+            [5.25, 5.20, 5.10],
+            [5.65, 5.55, 5.45],
+            [5.60, 5.35, 5.45],
+            [5.05, 5.00, 4.95],
+            [5.50, 5.50, 5.40],
+            [5.45, 5.55, 5.50],
+            [5.55, 5.55, 5.35],
+            [5.45, 5.50, 5.55],
+            [5.50, 5.45, 5.25],
+            [5.65, 5.60, 5.40],
+            [5.70, 5.65, 5.55],
+            [6.30, 6.30, 6.25],
+        ];
+        let data: Vec<Vec<f64>> = times.iter().map(|row| row.to_vec()).collect();
+        let result = friedman_test(&data).unwrap();
+        assert_close(result.statistic, 11.1428571428571, 1e-10);
+        assert_eq!(result.df, 2.0);
+        assert_close(result.p_value, 0.00380504077551136, 1e-12);
+        assert_close(result.kendalls_w, 0.253246753246753, 1e-12);
+    }
+
+    #[test]
+    fn friedman_test_without_ties_test() {
+        let data = vec![
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![2.0, 1.0, 3.0, 4.0],
+            vec![1.0, 3.0, 2.0, 4.0],
+            vec![1.0, 2.0, 4.0, 3.0],
+            vec![2.0, 1.0, 4.0, 3.0],
+        ];
+        let result = friedman_test(&data).unwrap();
+        assert_close(result.statistic, 10.2, 1e-12);
+        assert_close(result.p_value, 0.0169403735225339, 1e-12);
+        assert_close(result.kendalls_w, 0.68, 1e-12);
+    }
+
+    #[test]
+    fn friedman_test_ragged_rows_test() {
+        let data = vec![vec![1.0, 2.0, 3.0], vec![2.0, 1.0]];
+        assert_eq!(
+            friedman_test(&data).err(),
+            Some(StatsError::MismatchedLengths { left: 3, right: 2 })
+        );
+    }
+
+    const X: [f64; 9] = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+    const Y: [f64; 9] = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.07, 3.15, 1.28];
+
+    #[test]
+    fn mann_whitney_u_exact_test() {
+        // scipy.stats.mannwhitneyu(x, y, method="exact")
+        let expected = [
+            (Alternative::TwoSided, 0.135911147675854),
+            (Alternative::Greater, 0.0679555738379268),
+            (Alternative::Less, 0.943253805018511),
+        ];
+        for (alternative, p_value) in expected {
+            let result = mann_whitney_u(&X, &Y, alternative).unwrap();
+            assert_eq!(result.method, RankTestMethod::Exact);
+            assert_eq!(result.u, 58.0);
+            assert_eq!(result.z, None);
+            assert_close(result.p_value, p_value, 1e-14);
+        }
+    }
+
+    #[test]
+    fn mann_whitney_u_asymptotic_test() {
+        // scipy.stats.mannwhitneyu(x, y, method="asymptotic")
+        let expected = [
+            (Alternative::TwoSided, 1.50113667134038, 0.133320215589627),
+            (Alternative::Greater, 1.50113667134038, 0.0666601077948137),
+            (Alternative::Less, 1.58943882847805, 0.944019322868126),
+        ];
+        for (alternative, z, p_value) in expected {
+            let result =
+                mann_whitney_u_with(&X, &Y, alternative, RankTestMethod::NormalApproximation)
+                    .unwrap();
+            assert_close(result.z.unwrap(), z, 1e-12);
+            assert_close(result.p_value, p_value, 1e-12);
+        }
+    }
+
+    #[test]
+    fn mann_whitney_u_ties_test() {
+        let x = [
+            1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 5.0, 5.0, 6.0, 7.0, 7.0, 8.0, 9.0, 9.0, 10.0, 11.0,
+            12.0, 12.0, 13.0, 14.0, 15.0, 15.0,
+        ];
+        let y = [
+            3.0, 4.0, 4.0, 5.0, 6.0, 6.0, 7.0, 8.0, 8.0, 9.0, 10.0, 11.0, 11.0, 12.0, 13.0, 14.0,
+            14.0, 15.0, 16.0, 17.0, 18.0, 18.0, 19.0, 20.0, 21.0,
+        ];
+        let result = mann_whitney_u(&x, &y, Alternative::TwoSided).unwrap();
+        assert_eq!(result.method, RankTestMethod::NormalApproximation);
+        assert_eq!(result.u, 170.5);
+        assert_close(result.common_language_effect_size, 170.5 / 575.0, 1e-15);
+        assert_close(result.z.unwrap(), -2.40780865021197, 1e-12);
+        assert_close(result.p_value, 0.0160485900859503, 1e-12);
+
+        let result = mann_whitney_u(&x, &y, Alternative::Less).unwrap();
+        assert_close(result.p_value, 0.00802429504297517, 1e-12);
+
+        assert!(matches!(
+            mann_whitney_u_with(&x, &y, Alternative::TwoSided, RankTestMethod::Exact),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn u_distribution_test() {
+        // Sizes 2 and 2: U takes 0, 1, 2, 3, 4 in 1, 1, 2, 1, 1 ways
+        assert_eq!(u_distribution(2, 2), vec![1.0, 1.0, 2.0, 1.0, 1.0]);
+        let counts = u_distribution(20, 20);
+        assert_eq!(counts.iter().sum::<f64>(), 137_846_528_820.0);
+    }
+
+    #[test]
+    fn ansari_bradley_exact_test() {
+        // Equal medians, the first sample about four times as spread out.
+        let x = [-3.1, -1.7, -0.4, 0.2, 0.9, 1.8, 2.6, 3.5, -2.3];
+        let y = [-0.8, -0.3, -0.1, 0.05, 0.15, 0.35, 0.6, 1.1, -0.55, 0.45];
+        // scipy.stats.ansari(x, y, alternative), whose exact p-values are
+        // 586 / 46189, 293 / 46189 and 92041 / 92378
+        let expected = [
+            (Alternative::TwoSided, 586.0 / 46189.0),
+            (Alternative::Greater, 293.0 / 46189.0),
+            (Alternative::Less, 92041.0 / 92378.0),
+        ];
+        for (alternative, p_value) in expected {
+            let result = ansari_bradley(&x, &y, alternative).unwrap();
+            assert_eq!(result.method, RankTestMethod::Exact);
+            assert_eq!(result.ab, 32.0);
+            assert_eq!(result.z, None);
+            assert_eq!(result.more_dispersed, MoreDispersed::First);
+            assert_close(result.p_value, p_value, 1e-15);
+        }
+        let result = ansari_bradley(&y, &x, Alternative::Greater).unwrap();
+        assert_eq!(result.more_dispersed, MoreDispersed::Second);
+        assert!(result.p_value > 0.99);
+
+        let result = ansari_bradley(
+            &[1.0, 2.0, 3.0],
+            &[4.0, 5.0, 6.0, 7.0],
+            Alternative::TwoSided,
+        )
+        .unwrap();
+        assert_close(result.p_value, 6.0 / 7.0, 1e-15);
+    }
+
+    #[test]
+    fn ansari_bradley_normal_test() {
+        // R: ansari.test(ramsay, vanEden), which has ties
+        let ramsay = [
+            111.0, 107.0, 100.0, 99.0, 102.0, 106.0, 109.0, 108.0, 104.0, 99.0, 101.0, 96.0, 97.0,
+            102.0, 107.0, 113.0, 116.0, 113.0, 110.0, 98.0,
+        ];
+        let van_eden = [
+            107.0, 108.0, 106.0, 98.0, 105.0, 103.0, 110.0, 105.0, 104.0, 100.0, 96.0, 108.0,
+            103.0, 104.0, 114.0, 114.0, 113.0, 108.0, 106.0, 99.0,
+        ];
+        // scipy.stats.ansari(ramsay, van_eden, alternative)
+        let expected = [
+            (Alternative::TwoSided, 0.181458199728671),
+            (Alternative::Greater, 0.0907290998643354),
+            (Alternative::Less, 0.909270900135665),
+        ];
+        for (alternative, p_value) in expected {
+            let result = ansari_bradley(&ramsay, &van_eden, alternative).unwrap();
+            assert_eq!(result.method, RankTestMethod::NormalApproximation);
+            assert_eq!(result.ab, 185.5);
+            assert_close(result.z.unwrap(), 1.3362786880982, 1e-12);
+            assert_close(result.p_value, p_value, 1e-12);
+        }
+        assert!(matches!(
+            ansari_bradley_with(
+                &ramsay,
+                &van_eden,
+                Alternative::TwoSided,
+                RankTestMethod::Exact
+            ),
+            Err(StatsError::InvalidParameter(_))
+        ));
+
+        // Too large for the exact distribution, without ties.
+        let x: Vec<f64> = (0..60).map(|i| 1.5 * (2 * i - 59) as f64).collect();
+        let y: Vec<f64> = (0..70).map(|j| 1.05 * (2 * j - 69) as f64).collect();
+        let result = ansari_bradley(&x, &y, Alternative::Greater).unwrap();
+        assert_eq!(result.method, RankTestMethod::NormalApproximation);
+        assert_eq!(result.ab, 1786.0);
+        assert_eq!(result.more_dispersed, MoreDispersed::First);
+        // scipy.stats.ansari(x, y, alternative='greater')
+        assert_close(result.z.unwrap(), 1.81217697524563, 1e-12);
+        assert_close(result.p_value, 0.0349794277687924, 1e-12);
+
+        // mpmath: the untied normal approximation for the exact test's data
+        let x = [-3.1, -1.7, -0.4, 0.2, 0.9, 1.8, 2.6, 3.5, -2.3];
+        let y = [-0.8, -0.3, -0.1, 0.05, 0.15, 0.35, 0.6, 1.1, -0.55, 0.45];
+        let result = ansari_bradley_with(
+            &x,
+            &y,
+            Alternative::TwoSided,
+            RankTestMethod::NormalApproximation,
+        )
+        .unwrap();
+        assert_close(result.z.unwrap(), 2.49928927626373, 1e-12);
+        assert_close(result.p_value, 0.0124442683561006, 1e-12);
+    }
+
+    #[test]
+    fn ansari_bradley_invalid_test() {
+        assert!(matches!(
+            ansari_bradley(&[], &[1.0, 2.0], Alternative::TwoSided),
+            Err(StatsError::EmptyInput)
+        ));
+        assert!(matches!(
+            ansari_bradley(&[3.0, 3.0], &[3.0, 3.0, 3.0], Alternative::TwoSided),
+            Err(StatsError::InvalidParameter("all observations are tied"))
+        ));
+        assert!(matches!(
+            ansari_bradley_with(
+                &[1.0],
+                &[2.0],
+                Alternative::TwoSided,
+                RankTestMethod::NormalApproximation
+            ),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        let result = ansari_bradley(&[1.0], &[2.0], Alternative::TwoSided).unwrap();
+        assert_eq!(result.p_value, 1.0);
+        assert_eq!(result.more_dispersed, MoreDispersed::Neither);
+    }
+
+    #[test]
+    fn ab_distribution_test() {
+        // Sizes 2 and 2: the scores are 1, 2, 2, 1, so AB takes 2, 3 and 4
+        // in 1, 4 and 1 ways
+        assert_eq!(ab_distribution(2, 2), vec![0.0, 0.0, 1.0, 4.0, 1.0]);
+        let counts = ab_distribution(20, 20);
+        assert_eq!(counts.iter().sum::<f64>(), 137_846_528_820.0);
+        let counts = ab_distribution(54, 54);
+        let ways = binomial_coefficient(108, 54).unwrap().unwrap() as f64;
+        assert_close(counts.iter().sum::<f64>() / ways, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn wilcoxon_signed_rank_exact_test() {
+        // R: wilcox.test(x, y, paired = TRUE), from Hollander & Wolfe
+        let x = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+        let y = [0.878, 0.647, 0.598, 2.0, 1.06, 1.29, 1.06, 3.15, 1.29];
+        let expected = [
+            (Alternative::TwoSided, 0.0390625),
+            (Alternative::Greater, 0.01953125),
+            (Alternative::Less, 0.986328125),
+        ];
+        for (alternative, p_value) in expected {
+            let result = wilcoxon_signed_rank(&x, &y, alternative).unwrap();
+            assert_eq!(result.method, RankTestMethod::Exact);
+            assert_eq!(result.w, 40.0);
+            assert_eq!(result.n, 9);
+            assert_eq!(result.z, None);
+            assert_close(result.p_value, p_value, 1e-15);
+        }
+    }
+
+    #[test]
+    fn wilcoxon_signed_rank_normal_test() {
+        // R: wilcox.test(d, mu = 1)
+        let d = [
+            1.5, -0.3, 2.2, 4.1, 0.8, -1.1, 3.3, 2.7, 0.4, 1.9, 5.2, -0.6, 2.4, 3.8, 1.2, 0.9, 2.0,
+            -1.7, 3.1, 2.6, 1.4, 4.4, 0.2, 2.9,
+        ];
+        let result = wilcoxon_signed_rank_one_sample(&d, 1.0, Alternative::TwoSided).unwrap();
+        assert_eq!(result.method, RankTestMethod::NormalApproximation);
+        assert_eq!(result.w, 221.5);
+        assert_eq!(result.n, 24);
+        assert_close(result.z.unwrap(), 2.0288819955037, 1e-12);
+        assert_close(result.p_value, 0.0424703110188696, 1e-12);
+
+        // R: with(sleep, wilcox.test(extra[group == 1], extra[group == 2], paired = TRUE))
+        let group_1 = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+        let group_2 = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+        let result = wilcoxon_signed_rank(&group_1, &group_2, Alternative::TwoSided).unwrap();
+        assert_eq!(result.w, 0.0);
+        assert_eq!(result.n, 9);
+        assert_close(result.z.unwrap(), -2.60862433360651, 1e-12);
+        assert_close(result.p_value, 0.00909069801592506, 1e-12);
+    }
+
+    #[test]
+    fn wilcoxon_signed_rank_drops_zeros_test() {
+        let result =
+            wilcoxon_signed_rank_one_sample(&[3.0, 5.0, 1.0, 3.0, 6.0], 3.0, Alternative::Greater)
+                .unwrap();
+        // Differences 2, -2, 3 after dropping the zeros; ranks 1.5, 1.5, 3
+        assert_eq!(result.n, 3);
+        assert_eq!(result.w, 4.5);
+        assert_eq!(result.method, RankTestMethod::NormalApproximation);
+
+        assert_eq!(
+            wilcoxon_signed_rank(&[1.0, 2.0], &[1.0, 2.0], Alternative::TwoSided).err(),
+            Some(StatsError::EmptyInput)
+        );
+        assert_eq!(
+            wilcoxon_signed_rank(&[1.0, 2.0], &[1.0], Alternative::TwoSided).err(),
+            Some(StatsError::MismatchedLengths { left: 2, right: 1 })
+        );
+    }
+
+    #[test]
+    fn signed_rank_distribution_test() {
+        // n = 3: W takes 0..=6 in 1, 1, 1, 2, 1, 1, 1 ways
+        assert_eq!(
+            signed_rank_distribution(3),
+            vec![1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 1.0]
+        );
+        assert_eq!(
+            signed_rank_distribution(20).iter().sum::<f64>(),
+            1_048_576.0
+        );
+    }
+
+    #[test]
+    fn sign_test_test() {
+        let data = [7.1, 8.4, 6.2, 9.0, 7.7, 5.9, 8.8, 7.0, 9.5, 8.1, 7.0, 6.8];
+        // Two values are tied with the median and dropped: 7 above, 3 below
+        let result = sign_test(&data, 7.0, Alternative::TwoSided).unwrap();
+        assert_eq!(result.above, 7);
+        assert_eq!(result.below, 3);
+        // R: binom.test(7, 10)
+        assert_close(result.p_value, 0.34375, 1e-15);
+
+        let result = sign_test(&data, 7.0, Alternative::Greater).unwrap();
+        assert_close(result.p_value, 0.171875, 1e-15);
+        let result = sign_test(&data, 7.0, Alternative::Less).unwrap();
+        assert_close(result.p_value, 0.9453125, 1e-15);
+    }
+
+    #[test]
+    fn paired_sign_test_test() {
+        let group_1 = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+        let group_2 = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+        let result = paired_sign_test(&group_1, &group_2, Alternative::TwoSided).unwrap();
+        assert_eq!(result.above, 0);
+        assert_eq!(result.below, 9);
+        assert_close(result.p_value, 0.00390625, 1e-15);
+
+        assert_eq!(
+            sign_test(&[1.0, 1.0], 1.0, Alternative::TwoSided).err(),
+            Some(StatsError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn one_sample_median_test_test() {
+        let data = [7.1, 8.4, 6.2, 9.0, 7.7, 5.9, 8.8, 7.0, 9.5, 8.1, 7.0, 6.8];
+        for alternative in [
+            Alternative::TwoSided,
+            Alternative::Greater,
+            Alternative::Less,
+        ] {
+            let result = one_sample_median_test(&data, 7.0, alternative).unwrap();
+            let sign = sign_test(&data, 7.0, alternative).unwrap();
+            assert_eq!((result.above, result.below), (sign.above, sign.below));
+            assert_eq!(result.p_value, sign.p_value);
+        }
+    }
+
+    #[test]
+    fn median_confidence_interval_test() {
+        let x = [
+            2.3, 4.1, 1.7, 5.6, 3.3, 2.9, 4.8, 3.9, 2.2, 6.1, 3.0, 4.4, 3.6, 2.7, 5.0,
+        ];
+        // R: DescTools::MedianCI(x, conf.level, method = "exact"), whose
+        // conf.level attribute is the achieved coverage
+        let expected = [
+            (0.95, 2.7, 4.8, 4, 12, 0.96484375),
+            (0.9, 2.7, 4.8, 4, 12, 0.96484375),
+            (0.99, 2.3, 5.0, 3, 13, 0.99261474609375),
+            (0.5, 3.0, 4.1, 6, 10, 0.6982421875),
+        ];
+        for (confidence, lower, upper, lower_rank, upper_rank, achieved) in expected {
+            let result = median_confidence_interval(&x, confidence).unwrap();
+            assert_eq!(result.interval.lower, lower);
+            assert_eq!(result.interval.upper, upper);
+            assert_eq!(result.interval.point_estimate, 3.6);
+            assert_eq!(result.interval.confidence, confidence);
+            assert_eq!(
+                (result.lower_rank, result.upper_rank),
+                (lower_rank, upper_rank)
+            );
+            assert_close(result.achieved_confidence, achieved, 1e-14);
+            assert!(result.achieved_confidence >= confidence);
+        }
+
+        // Discrete data with ties
+        let y = [
+            12.0, 15.0, 9.0, 20.0, 11.0, 14.0, 15.0, 13.0, 18.0, 10.0, 16.0, 12.0, 17.0, 14.0,
+            19.0, 11.0, 13.0, 15.0, 22.0, 8.0,
+        ];
+        let result = median_confidence_interval(&y, 0.95).unwrap();
+        assert_eq!((result.interval.lower, result.interval.upper), (12.0, 16.0));
+        assert_eq!(result.interval.point_estimate, 14.0);
+        assert_close(result.achieved_confidence, 0.9586105346679688, 1e-14);
+        let result = median_confidence_interval(&y, 0.8).unwrap();
+        assert_eq!((result.interval.lower, result.interval.upper), (12.0, 15.0));
+        assert_close(result.achieved_confidence, 0.8846817016601562, 1e-14);
+    }
+
+    #[test]
+    fn median_confidence_interval_small_sample_test() {
+        // With five values even the extremes cover with probability 15/16.
+        let result = median_confidence_interval(&[3.0, 1.0, 2.0, 5.0, 4.0], 0.95).unwrap();
+        assert_eq!(result.interval.lower, f64::NEG_INFINITY);
+        assert_eq!(result.interval.upper, f64::INFINITY);
+        assert_eq!((result.lower_rank, result.upper_rank), (0, 6));
+        assert_eq!(result.achieved_confidence, 1.0);
+
+        let result = median_confidence_interval(&[3.0, 1.0, 2.0, 5.0, 4.0, 7.0], 0.95).unwrap();
+        assert_eq!((result.interval.lower, result.interval.upper), (1.0, 7.0));
+        assert_close(result.achieved_confidence, 0.96875, 1e-15);
+
+        // P(B <= 0) = 1/4 exactly for n = 2, so the lower quantile at
+        // confidence 1/2 is 0 and the interval is unbounded below.
+        let result = median_confidence_interval(&[1.0, 2.0], 0.5).unwrap();
+        assert_eq!((result.lower_rank, result.upper_rank), (0, 2));
+        assert_close(result.achieved_confidence, 0.75, 1e-15);
+
+        assert_eq!(
+            median_confidence_interval(&[], 0.95),
+            Err(StatsError::EmptyInput)
+        );
+        assert_eq!(
+            median_confidence_interval(&[1.0, f64::NAN], 0.95),
+            Err(StatsError::NanInInput { index: 1 })
+        );
+        assert!(median_confidence_interval(&[1.0, 2.0], 1.5).is_err());
+    }
+
+    #[test]
+    fn mood_median_test_test() {
+        // scipy.stats.median_test, from its documentation
+        let g1 = [
+            10.0, 14.0, 14.0, 18.0, 20.0, 22.0, 24.0, 25.0, 31.0, 31.0, 32.0, 39.0, 43.0, 43.0,
+            48.0, 49.0,
+        ];
+        let g2 = [
+            28.0, 30.0, 31.0, 33.0, 34.0, 35.0, 36.0, 40.0, 44.0, 55.0, 57.0, 61.0, 91.0, 92.0,
+            99.0,
+        ];
+        let g3 = [
+            0.0, 3.0, 9.0, 22.0, 23.0, 25.0, 25.0, 33.0, 34.0, 34.0, 40.0, 45.0, 46.0, 48.0, 62.0,
+            67.0, 84.0,
+        ];
+        let result = mood_median_test(&[&g1, &g2, &g3]).unwrap();
+        assert_eq!(result.grand_median, 34.0);
+        // The two 34s in g3 equal the median and count as below
+        assert_eq!(result.above, vec![5, 10, 7]);
+        assert_eq!(result.below, vec![11, 5, 10]);
+        assert_close(result.statistic, 4.14150555327026, 1e-12);
+        assert_eq!(result.df, 2.0);
+        assert_close(result.p_value, 0.126090827740932, 1e-12);
+    }
+
+    #[test]
+    fn mood_median_test_two_groups_test() {
+        let result = mood_median_test(&[
+            &[1.2, 3.4, 2.2, 5.1, 4.4, 2.9],
+            &[3.3, 6.1, 5.5, 4.8, 7.2, 6.6, 5.9],
+        ])
+        .unwrap();
+        assert_eq!(result.grand_median, 4.8);
+        assert_close(result.statistic, 2.00637755102041, 1e-12);
+        assert_close(result.p_value, 0.156638944510679, 1e-12);
+    }
+
+    #[test]
+    fn runs_test_test() {
+        let residuals = [
+            2.1, -0.4, 1.3, 0.8, -1.2, -0.7, -0.3, 1.9, 2.2, 0.5, -1.1, 0.9, -0.2, -0.8, 1.4, 1.1,
+            0.6, -0.9, -1.5, 0.3,
+        ];
+        let result = runs_test(&residuals).unwrap();
+        assert_eq!(result.runs, 10);
+        assert_eq!(result.n_above, 10);
+        assert_eq!(result.n_below, 10);
+        assert_close(result.z, -0.459468291736341, 1e-12);
+        assert_close(result.p_value, 0.645897917026563, 1e-12);
+        assert!(!result.small_sample);
+    }
+
+    #[test]
+    fn runs_test_binary_test() {
+        let sequence = [
+            true, true, true, true, false, false, false, false, true, true, true, true, false,
+            false, false, false, true, true, true, false, false, false,
+        ];
+        let result = runs_test_binary(&sequence).unwrap();
+        assert_eq!(result.runs, 6);
+        assert_close(result.z, -2.62158869251591, 1e-12);
+        assert_close(result.p_value, 0.00875209859892434, 1e-12);
+
+        let result = runs_test_binary(&[true, false, true, false]).unwrap();
+        assert!(result.small_sample);
+    }
+
+    #[test]
+    fn runs_test_degenerate_test() {
+        assert!(matches!(
+            runs_test_binary(&[true, true, true]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        // Everything equal to the cutoff is dropped, leaving nothing
+        assert_eq!(
+            runs_test(&[2.0, 2.0, 2.0]).err(),
+            Some(StatsError::EmptyInput)
+        );
+    }
+}
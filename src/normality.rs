@@ -0,0 +1,596 @@
+use std::fmt;
+
+use crate::descriptive::central_moments;
+use crate::distributions::{ChiSquare, Normal};
+use crate::format::write_test_line;
+use crate::{mean, sample_standard_deviation, HypothesisTest, StatsError};
+
+/// Largest sample for which Royston's approximation is valid.
+const SHAPIRO_WILK_MAX_N: usize = 5000;
+
+// Polynomial coefficients from Royston (1995), AS R94, lowest order first
+const SW_C1: [f64; 6] = [0.0, 0.221157, -0.147981, -2.07119, 4.434685, -2.706056];
+const SW_C2: [f64; 6] = [0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633];
+const SW_C3: [f64; 4] = [0.544, -0.39978, 0.025054, -6.714e-4];
+const SW_C4: [f64; 4] = [1.3822, -0.77857, 0.062767, -0.0020322];
+const SW_C5: [f64; 4] = [-1.5861, -0.31082, -0.083751, 0.0038915];
+const SW_C6: [f64; 3] = [-0.4803, -0.082676, 0.0030302];
+const SW_G: [f64; 2] = [-2.273, 0.459];
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShapiroWilkResult {
+    pub w: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for ShapiroWilkResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "W", &[], self.w, self.p_value)
+    }
+}
+
+impl HypothesisTest for ShapiroWilkResult {
+    fn statistic(&self) -> f64 {
+        self.w
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Shapiro-Wilk test"
+    }
+}
+
+/// Shapiro-Wilk test of normality for 3 <= n <= 5000, using Royston's
+/// approximation to the coefficients and to the distribution of W (AS R94),
+/// as R's `shapiro.test` does.
+pub fn shapiro_wilk(data: &[f64]) -> Result<ShapiroWilkResult, StatsError> {
+    let n = data.len();
+    if n < 3 {
+        return Err(StatsError::InsufficientData {
+            required: 3,
+            actual: n,
+        });
+    }
+    if n > SHAPIRO_WILK_MAX_N {
+        return Err(StatsError::InvalidParameter(
+            "Shapiro-Wilk requires at most 5000 observations",
+        ));
+    }
+
+    let mut x = data.to_vec();
+    x.sort_by(f64::total_cmp);
+    if x[n - 1] - x[0] <= 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "all observations are identical",
+        ));
+    }
+
+    let coefficients = shapiro_wilk_coefficients(n);
+    let coefficient_mean = mean(&coefficients);
+    let x_mean = mean(&x);
+    let mut ss_a = 0.0;
+    let mut ss_x = 0.0;
+    let mut cross = 0.0;
+    for (a, x) in coefficients.iter().zip(&x) {
+        let a = a - coefficient_mean;
+        let x = x - x_mean;
+        ss_a += a * a;
+        ss_x += x * x;
+        cross += a * x;
+    }
+    // 1 - W, computed directly to avoid cancellation when W is close to one
+    let root = (ss_a * ss_x).sqrt();
+    let w1 = (root - cross) * (root + cross) / (ss_a * ss_x);
+    let w = 1.0 - w1;
+
+    Ok(ShapiroWilkResult {
+        w,
+        p_value: shapiro_wilk_p_value(w, w1, n),
+    })
+}
+
+/// The Shapiro-Wilk weights for the ordered sample, antisymmetric about the
+/// middle observation.
+fn shapiro_wilk_coefficients(n: usize) -> Vec<f64> {
+    let half = n / 2;
+    let mut a = vec![0.0; half];
+    if n == 3 {
+        a[0] = std::f64::consts::FRAC_1_SQRT_2;
+    } else {
+        let normal = Normal::standard();
+        let an = n as f64;
+        for (i, a) in a.iter_mut().enumerate() {
+            *a = normal.quantile((i as f64 + 1.0 - 0.375) / (an + 0.25));
+        }
+        let sum_squares = 2.0 * a.iter().map(|m| m * m).sum::<f64>();
+        let root = sum_squares.sqrt();
+        let rsn = 1.0 / an.sqrt();
+
+        let a1 = polynomial(&SW_C1, rsn) - a[0] / root;
+        let (first_scaled, factor) = if n > 5 {
+            let a2 = -a[1] / root + polynomial(&SW_C2, rsn);
+            let factor = ((sum_squares - 2.0 * a[0] * a[0] - 2.0 * a[1] * a[1])
+                / (1.0 - 2.0 * a1 * a1 - 2.0 * a2 * a2))
+                .sqrt();
+            a[1] = a2;
+            (2, factor)
+        } else {
+            let factor = ((sum_squares - 2.0 * a[0] * a[0]) / (1.0 - 2.0 * a1 * a1)).sqrt();
+            (1, factor)
+        };
+        a[0] = a1;
+        for a in &mut a[first_scaled..] {
+            *a /= -factor;
+        }
+    }
+
+    let mut coefficients = vec![0.0; n];
+    for (i, &a) in a.iter().enumerate() {
+        coefficients[i] = -a;
+        coefficients[n - 1 - i] = a;
+    }
+    coefficients
+}
+
+fn shapiro_wilk_p_value(w: f64, w1: f64, n: usize) -> f64 {
+    if n == 3 {
+        // Exact: (6 / pi) (asin(sqrt(W)) - pi / 3)
+        let pi = std::f64::consts::PI;
+        return (6.0 / pi * (w.sqrt().asin() - pi / 3.0)).max(0.0);
+    }
+
+    let an = n as f64;
+    let mut y = w1.ln();
+    let (m, s) = if n <= 11 {
+        let gamma = polynomial(&SW_G, an);
+        if y >= gamma {
+            return 1e-99;
+        }
+        y = -(gamma - y).ln();
+        (polynomial(&SW_C3, an), polynomial(&SW_C4, an).exp())
+    } else {
+        let ln_n = an.ln();
+        (polynomial(&SW_C5, ln_n), polynomial(&SW_C6, ln_n).exp())
+    };
+
+    1.0 - Normal::new(m, s).cdf(y)
+}
+
+/// Significance levels, in percent, of `AndersonDarlingResult::critical_values`.
+pub const ANDERSON_DARLING_SIGNIFICANCE_LEVELS: [f64; 5] = [15.0, 10.0, 5.0, 2.5, 1.0];
+
+/// Asymptotic critical values of A² for the normal case with both
+/// parameters estimated, matching `ANDERSON_DARLING_SIGNIFICANCE_LEVELS`.
+const ANDERSON_DARLING_CRITICAL_VALUES: [f64; 5] = [0.576, 0.656, 0.787, 0.918, 1.092];
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AndersonDarlingResult {
+    /// The A² statistic.
+    pub a_squared: f64,
+    /// A² with the small-sample correction (1 + 0.75 / n + 2.25 / n²).
+    pub a_squared_adjusted: f64,
+    /// p-value from D'Agostino and Stephens' approximation for the adjusted
+    /// statistic.
+    pub p_value: f64,
+    /// Critical values of `a_squared` at the significance levels in
+    /// `ANDERSON_DARLING_SIGNIFICANCE_LEVELS`, adjusted for sample size as
+    /// scipy's `anderson` does.
+    pub critical_values: [f64; 5],
+}
+
+impl fmt::Display for AndersonDarlingResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "A²", &[], self.a_squared, self.p_value)
+    }
+}
+
+impl HypothesisTest for AndersonDarlingResult {
+    fn statistic(&self) -> f64 {
+        self.a_squared
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Anderson-Darling test"
+    }
+}
+
+impl AndersonDarlingResult {
+    /// Whether normality is rejected at significance level `alpha`.
+    pub fn rejects_normality(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+}
+
+/// Anderson-Darling test of normality with the mean and standard deviation
+/// estimated from the data.
+pub fn anderson_darling(data: &[f64]) -> Result<AndersonDarlingResult, StatsError> {
+    let n = data.len();
+    if n < 3 {
+        return Err(StatsError::InsufficientData {
+            required: 3,
+            actual: n,
+        });
+    }
+    let sd = sample_standard_deviation(data);
+    if sd == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "all observations are identical",
+        ));
+    }
+
+    let normal = Normal::new(mean(data), sd);
+    let mut x = data.to_vec();
+    x.sort_by(f64::total_cmp);
+    let an = n as f64;
+    let sum: f64 = (0..n)
+        .map(|i| {
+            let lower = normal.cdf(x[i]).ln();
+            let upper = normal.sf(x[n - 1 - i]).ln();
+            (2 * i + 1) as f64 * (lower + upper)
+        })
+        .sum();
+    let a_squared = -an - sum / an;
+    let adjusted = a_squared * (1.0 + 0.75 / an + 2.25 / (an * an));
+
+    let p_value = if adjusted >= 0.6 {
+        (1.2937 - 5.709 * adjusted + 0.0186 * adjusted * adjusted).exp()
+    } else if adjusted > 0.34 {
+        (0.9177 - 4.279 * adjusted - 1.38 * adjusted * adjusted).exp()
+    } else if adjusted > 0.2 {
+        1.0 - (-8.318 + 42.796 * adjusted - 59.938 * adjusted * adjusted).exp()
+    } else {
+        1.0 - (-13.436 + 101.14 * adjusted - 223.73 * adjusted * adjusted).exp()
+    };
+
+    let scale = 1.0 + 4.0 / an - 25.0 / (an * an);
+    let critical_values =
+        ANDERSON_DARLING_CRITICAL_VALUES.map(|c| (c / scale * 1000.0).round() / 1000.0);
+
+    Ok(AndersonDarlingResult {
+        a_squared,
+        a_squared_adjusted: adjusted,
+        p_value,
+        critical_values,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JarqueBeraResult {
+    pub statistic: f64,
+    /// Sample skewness, as returned by `skewness`.
+    pub skewness: f64,
+    /// Sample excess kurtosis, as returned by `kurtosis`.
+    pub kurtosis: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for JarqueBeraResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "JB", &[], self.statistic, self.p_value)
+    }
+}
+
+impl HypothesisTest for JarqueBeraResult {
+    fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(2.0)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Jarque-Bera test"
+    }
+}
+
+/// Jarque-Bera test of normality, JB = n / 6 (S² + K² / 4) for sample
+/// skewness S and excess kurtosis K, compared against chi-square with two
+/// degrees of freedom. The approximation needs a fairly large sample.
+pub fn jarque_bera(data: &[f64]) -> Result<JarqueBeraResult, StatsError> {
+    if data.len() < 3 {
+        return Err(StatsError::InsufficientData {
+            required: 3,
+            actual: data.len(),
+        });
+    }
+    let [m2, m3, m4] = central_moments(data);
+    if m2 == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "all observations are identical",
+        ));
+    }
+
+    let skewness = m3 / m2.powf(1.5);
+    let kurtosis = m4 / (m2 * m2) - 3.0;
+    let statistic = data.len() as f64 / 6.0 * (skewness * skewness + kurtosis * kurtosis / 4.0);
+
+    Ok(JarqueBeraResult {
+        statistic,
+        skewness,
+        kurtosis,
+        p_value: ChiSquare::new(2.0).sf(statistic),
+    })
+}
+
+/// The kurtosis transform is undefined below this sample size.
+const DAGOSTINO_MIN_N: usize = 8;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DAgostinoResult {
+    /// K² = z_skewness² + z_kurtosis².
+    pub k_squared: f64,
+    /// D'Agostino's normalizing transform of the sample skewness.
+    pub z_skewness: f64,
+    /// Anscombe and Glynn's normalizing transform of the sample kurtosis.
+    pub z_kurtosis: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for DAgostinoResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "K²", &[], self.k_squared, self.p_value)
+    }
+}
+
+impl HypothesisTest for DAgostinoResult {
+    fn statistic(&self) -> f64 {
+        self.k_squared
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(2.0)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "D'Agostino-Pearson test"
+    }
+}
+
+/// D'Agostino-Pearson K² omnibus test of normality, combining transformed
+/// skewness and kurtosis statistics and comparing K² against chi-square with
+/// two degrees of freedom, as scipy's `normaltest` does. Needs at least 8
+/// observations.
+pub fn dagostino_k2(data: &[f64]) -> Result<DAgostinoResult, StatsError> {
+    if data.len() < DAGOSTINO_MIN_N {
+        return Err(StatsError::InsufficientData {
+            required: DAGOSTINO_MIN_N,
+            actual: data.len(),
+        });
+    }
+    let [m2, m3, m4] = central_moments(data);
+    if m2 == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "all observations are identical",
+        ));
+    }
+
+    let n = data.len() as f64;
+    let z_skewness = skewness_z(m3 / m2.powf(1.5), n);
+    let z_kurtosis = kurtosis_z(m4 / (m2 * m2), n);
+    let k_squared = z_skewness * z_skewness + z_kurtosis * z_kurtosis;
+
+    Ok(DAgostinoResult {
+        k_squared,
+        z_skewness,
+        z_kurtosis,
+        p_value: ChiSquare::new(2.0).sf(k_squared),
+    })
+}
+
+/// D'Agostino (1970) transform of the sample skewness to a standard normal
+/// deviate.
+fn skewness_z(skewness: f64, n: f64) -> f64 {
+    let y = skewness * ((n + 1.0) * (n + 3.0) / (6.0 * (n - 2.0))).sqrt();
+    let beta2 = 3.0 * (n * n + 27.0 * n - 70.0) * (n + 1.0) * (n + 3.0)
+        / ((n - 2.0) * (n + 5.0) * (n + 7.0) * (n + 9.0));
+    let w2 = -1.0 + (2.0 * (beta2 - 1.0)).sqrt();
+    let delta = 1.0 / (0.5 * w2.ln()).sqrt();
+    let alpha = (2.0 / (w2 - 1.0)).sqrt();
+    delta * (y / alpha).asinh()
+}
+
+/// Anscombe and Glynn (1983) transform of the sample kurtosis b2 = m4 / m2²
+/// to a standard normal deviate.
+fn kurtosis_z(b2: f64, n: f64) -> f64 {
+    let expected = 3.0 * (n - 1.0) / (n + 1.0);
+    let variance =
+        24.0 * n * (n - 2.0) * (n - 3.0) / ((n + 1.0) * (n + 1.0) * (n + 3.0) * (n + 5.0));
+    let x = (b2 - expected) / variance.sqrt();
+    let root_beta1 = 6.0 * (n * n - 5.0 * n + 2.0) / ((n + 7.0) * (n + 9.0))
+        * (6.0 * (n + 3.0) * (n + 5.0) / (n * (n - 2.0) * (n - 3.0))).sqrt();
+    let a = 6.0
+        + 8.0 / root_beta1 * (2.0 / root_beta1 + (1.0 + 4.0 / (root_beta1 * root_beta1)).sqrt());
+    let denominator = 1.0 + x * (2.0 / (a - 4.0)).sqrt();
+    let term = ((1.0 - 2.0 / a) / denominator).cbrt();
+    (1.0 - 2.0 / (9.0 * a) - term) / (2.0 / (9.0 * a)).sqrt()
+}
+
+/// Evaluates c[0] + c[1] x + c[2] x^2 + ...
+fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn shapiro_wilk_test() {
+        // R: shapiro.test(mtcars$mpg)
+        let mpg = [
+            21.0, 21.0, 22.8, 21.4, 18.7, 18.1, 14.3, 24.4, 22.8, 19.2, 17.8, 16.4, 17.3, 15.2,
+            10.4, 10.4, 14.7, 32.4, 30.4, 33.9, 21.5, 15.5, 15.2, 13.3, 19.2, 27.3, 26.0, 30.4,
+            15.8, 19.7, 15.0, 21.4,
+        ];
+        let result = shapiro_wilk(&mpg).unwrap();
+        assert_close(result.w, 0.947564726479274, 1e-9);
+        assert_close(result.p_value, 0.122881358539441, 1e-8);
+
+        // R: shapiro.test(PlantGrowth$weight)
+        let weight = [
+            4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14, 4.81, 4.17, 4.41, 3.59,
+            5.87, 3.83, 6.03, 4.89, 4.32, 4.69, 6.31, 5.12, 5.54, 5.50, 5.37, 5.29, 4.92, 6.15,
+            5.80, 5.26,
+        ];
+        let result = shapiro_wilk(&weight).unwrap();
+        assert_close(result.w, 0.982683016098587, 1e-9);
+        assert_close(result.p_value, 0.89150736822739, 1e-8);
+    }
+
+    #[test]
+    fn shapiro_wilk_skewed_test() {
+        let skewed = [
+            0.2, 0.3, 0.3, 0.4, 0.5, 0.6, 0.8, 0.9, 1.1, 1.4, 1.8, 2.3, 3.1, 4.4, 6.2, 9.5, 14.8,
+            23.1,
+        ];
+        let result = shapiro_wilk(&skewed).unwrap();
+        assert_close(result.w, 0.659285358326067, 1e-9);
+        assert_close(result.p_value, 2.78646527118072e-5, 1e-12);
+    }
+
+    #[test]
+    fn shapiro_wilk_small_samples_test() {
+        let result = shapiro_wilk(&[1.2, 3.4, 2.2]).unwrap();
+        assert_close(result.w, 0.997252747252747, 1e-12);
+        assert_close(result.p_value, 0.899850280037225, 1e-10);
+
+        let result = shapiro_wilk(&[4.1, 5.3, 2.8, 6.0, 4.7]).unwrap();
+        assert_close(result.w, 0.98173574300881, 1e-9);
+        assert_close(result.p_value, 0.943688070474558, 1e-8);
+
+        let result = shapiro_wilk(&[2.3, 1.8, 3.1, 2.9, 2.2, 2.6, 3.4, 1.9, 2.7, 2.5]).unwrap();
+        assert_close(result.w, 0.980325571785745, 1e-9);
+        assert_close(result.p_value, 0.966886533841059, 1e-8);
+    }
+
+    #[test]
+    fn anderson_darling_test() {
+        // R: nortest::ad.test(mtcars$mpg); critical values from scipy.stats.anderson
+        let mpg = [
+            21.0, 21.0, 22.8, 21.4, 18.7, 18.1, 14.3, 24.4, 22.8, 19.2, 17.8, 16.4, 17.3, 15.2,
+            10.4, 10.4, 14.7, 32.4, 30.4, 33.9, 21.5, 15.5, 15.2, 13.3, 19.2, 27.3, 26.0, 30.4,
+            15.8, 19.7, 15.0, 21.4,
+        ];
+        let result = anderson_darling(&mpg).unwrap();
+        assert_close(result.a_squared, 0.579680713977765, 1e-12);
+        assert_close(result.a_squared_adjusted, 0.594540693217917, 1e-12);
+        assert_close(result.p_value, 0.120737061441899, 1e-12);
+        assert_eq!(result.critical_values, [0.523, 0.596, 0.715, 0.834, 0.992]);
+        assert!(!result.rejects_normality(0.05));
+    }
+
+    #[test]
+    fn anderson_darling_skewed_test() {
+        let skewed = [
+            0.2, 0.3, 0.3, 0.4, 0.5, 0.6, 0.8, 0.9, 1.1, 1.4, 1.8, 2.3, 3.1, 4.4, 6.2, 9.5, 14.8,
+            23.1,
+        ];
+        let result = anderson_darling(&skewed).unwrap();
+        assert_close(result.a_squared, 2.4211916823661, 1e-12);
+        assert_close(result.p_value, 2.08462893289506e-6, 1e-16);
+        assert_eq!(result.critical_values, [0.503, 0.573, 0.687, 0.802, 0.954]);
+        assert!(result.rejects_normality(0.05));
+        assert!(result.a_squared > result.critical_values[4]);
+    }
+
+    #[test]
+    fn jarque_bera_test() {
+        // statsmodels.stats.stattools.jarque_bera
+        let normal = [
+            0.31, -1.42, 0.77, 1.95, -0.33, 0.12, -0.88, 2.41, 0.54, -0.07, 1.23, -1.71, 0.66,
+            0.98, -0.25, 1.62, 0.05, -0.61, 1.37, 0.89,
+        ];
+        let result = jarque_bera(&normal).unwrap();
+        assert_close(result.skewness, -0.112483352470701, 1e-13);
+        assert_close(result.kurtosis, -0.529477058222109, 1e-13);
+        assert_close(result.statistic, 0.275796644596442, 1e-13);
+        assert_close(result.p_value, 0.871187267561814, 1e-13);
+
+        let exponential = [
+            0.05, 0.11, 0.13, 0.21, 0.26, 0.33, 0.38, 0.41, 0.52, 0.57, 0.66, 0.74, 0.81, 0.95,
+            1.02, 1.13, 1.31, 1.46, 1.62, 1.88, 2.09, 2.37, 2.71, 3.15, 3.88, 4.62, 5.94, 7.83,
+        ];
+        let result = jarque_bera(&exponential).unwrap();
+        assert_close(result.statistic, 22.9017053905902, 1e-11);
+        assert_close(result.p_value, 1.06403977173934e-5, 1e-16);
+    }
+
+    #[test]
+    fn dagostino_k2_test() {
+        // scipy.stats.normaltest, skewtest and kurtosistest
+        let normal = [
+            0.31, -1.42, 0.77, 1.95, -0.33, 0.12, -0.88, 2.41, 0.54, -0.07, 1.23, -1.71, 0.66,
+            0.98, -0.25, 1.62, 0.05, -0.61, 1.37, 0.89,
+        ];
+        let result = dagostino_k2(&normal).unwrap();
+        assert_close(result.z_skewness, -0.252532292322767, 1e-12);
+        assert_close(result.z_kurtosis, -0.14618528624993, 1e-12);
+        assert_close(result.k_squared, 0.0851426965817657, 1e-12);
+        assert_close(result.p_value, 0.958322088438557, 1e-12);
+
+        let exponential = [
+            0.05, 0.11, 0.13, 0.21, 0.26, 0.33, 0.38, 0.41, 0.52, 0.57, 0.66, 0.74, 0.81, 0.95,
+            1.02, 1.13, 1.31, 1.46, 1.62, 1.88, 2.09, 2.37, 2.71, 3.15, 3.88, 4.62, 5.94, 7.83,
+        ];
+        let result = dagostino_k2(&exponential).unwrap();
+        assert_close(result.z_skewness, 3.55378844819652, 1e-12);
+        assert_close(result.z_kurtosis, 2.54088433518834, 1e-12);
+        assert_close(result.p_value, 7.17191493740789e-5, 1e-16);
+    }
+
+    #[test]
+    fn dagostino_k2_small_sample_test() {
+        let result = dagostino_k2(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]).unwrap();
+        assert_eq!(result.z_skewness, 0.0);
+        assert_close(result.z_kurtosis, -0.823819896246139, 1e-12);
+        assert_close(result.p_value, 0.71224052349034, 1e-12);
+
+        assert_eq!(
+            dagostino_k2(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]).err(),
+            Some(StatsError::InsufficientData {
+                required: 8,
+                actual: 7
+            })
+        );
+    }
+
+    #[test]
+    fn shapiro_wilk_invalid_input_test() {
+        assert_eq!(
+            shapiro_wilk(&[1.0, 2.0]).err(),
+            Some(StatsError::InsufficientData {
+                required: 3,
+                actual: 2
+            })
+        );
+        assert!(matches!(
+            shapiro_wilk(&[2.0, 2.0, 2.0, 2.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
@@ -0,0 +1,684 @@
+use std::fmt;
+
+use crate::distributions::{ChiSquare, StudentsT};
+use crate::format::write_test_line;
+use crate::hypothesis::validate_alpha;
+use crate::linalg::{cholesky, forward_substitute};
+use crate::{covariance_matrix, mean, GetStatistics, HypothesisTest, SampleStatistics, StatsError};
+
+/// Significance levels with tabulated Dixon critical values.
+const DIXON_ALPHAS: [f64; 3] = [0.10, 0.05, 0.01];
+
+/// Two-sided critical values of Dixon's ratio for n = 3 to 30, by column of
+/// `DIXON_ALPHAS`, using the ratio `DixonRatio::for_sample_size` picks. The
+/// r10 rows, n = 3 to 7, are the two-tailed values of Rorabacher (1991),
+/// Anal. Chem. 63, 139, Table I. Dixon's (1951, 1953) tables for r11, r21
+/// and r22 are one-tailed and lack the α/2 = 0.025 column a two-sided 5%
+/// test needs, so the rows from n = 8 are quantiles of the larger of the
+/// two end ratios over 1.6e7 simulated normal samples per n. Their 10%
+/// column sits just below Dixon's one-tailed 5% values, e.g. 0.544 against
+/// 0.554 for r11 at n = 8, as a two-sided test of either end must.
+const DIXON_CRITICAL_VALUES: [[f64; 3]; 28] = [
+    [0.941, 0.970, 0.994],
+    [0.765, 0.829, 0.926],
+    [0.642, 0.710, 0.821],
+    [0.560, 0.625, 0.740],
+    [0.507, 0.568, 0.680],
+    [0.544, 0.608, 0.719],
+    [0.503, 0.564, 0.672],
+    [0.470, 0.530, 0.635],
+    [0.572, 0.621, 0.707],
+    [0.543, 0.590, 0.676],
+    [0.519, 0.565, 0.649],
+    [0.539, 0.586, 0.670],
+    [0.518, 0.565, 0.648],
+    [0.500, 0.546, 0.627],
+    [0.484, 0.529, 0.610],
+    [0.470, 0.514, 0.594],
+    [0.457, 0.501, 0.580],
+    [0.446, 0.489, 0.567],
+    [0.435, 0.478, 0.555],
+    [0.426, 0.468, 0.544],
+    [0.417, 0.459, 0.535],
+    [0.410, 0.451, 0.526],
+    [0.402, 0.443, 0.517],
+    [0.396, 0.436, 0.510],
+    [0.389, 0.429, 0.502],
+    [0.383, 0.423, 0.495],
+    [0.378, 0.417, 0.489],
+    [0.373, 0.412, 0.483],
+];
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrubbsResult {
+    /// G = max |x - x̄| / s.
+    pub statistic: f64,
+    /// The value of G beyond which the suspect is rejected at `alpha`.
+    pub critical_value: f64,
+    pub p_value: f64,
+    /// Index in the input of the value furthest from the mean.
+    pub outlier_index: usize,
+    /// Whether the suspect is an outlier, p_value < alpha.
+    pub is_outlier: bool,
+}
+
+impl fmt::Display for GrubbsResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "G", &[], self.statistic, self.p_value)
+    }
+}
+
+impl HypothesisTest for GrubbsResult {
+    fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Grubbs' test"
+    }
+}
+
+/// Grubbs' test of whether the value furthest from the mean is an outlier
+/// from an otherwise normal sample, as R's `outliers::grubbs.test` with its
+/// defaults. The p-value is the Bonferroni bound n P(T > t) on the t
+/// statistic with n - 2 degrees of freedom that G transforms to.
+pub fn grubbs_test(data: &[f64], alpha: f64) -> Result<GrubbsResult, StatsError> {
+    if data.len() < 3 {
+        return Err(StatsError::InsufficientData {
+            required: 3,
+            actual: data.len(),
+        });
+    }
+    validate_alpha(alpha)?;
+
+    let samp = SampleStatistics::from_array(data);
+    if samp.standard_deviation == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "Grubbs' test needs data that is not constant",
+        ));
+    }
+    let (outlier_index, deviation) = data
+        .iter()
+        .map(|x| (x - samp.sample_mean).abs())
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .expect("data is not empty");
+    let statistic = deviation / samp.standard_deviation;
+
+    let n = data.len() as f64;
+    let distribution = StudentsT::new(n - 2.0);
+    let g2 = statistic * statistic;
+    let t = (g2 * n * (n - 2.0) / ((n - 1.0).powi(2) - g2 * n)).sqrt();
+    // G is bounded by (n - 1) / sqrt(n), where t is infinite
+    let p_value = if t.is_nan() {
+        0.0
+    } else {
+        (n * (1.0 - distribution.cdf(t))).min(1.0)
+    };
+    let t_critical = distribution.quantile(1.0 - alpha / n);
+    let critical_value =
+        (n - 1.0) / n.sqrt() * (t_critical.powi(2) / (n - 2.0 + t_critical.powi(2))).sqrt();
+
+    Ok(GrubbsResult {
+        statistic,
+        critical_value,
+        p_value,
+        outlier_index,
+        is_outlier: p_value < alpha,
+    })
+}
+
+/// Applies `grubbs_test` repeatedly, removing each detected outlier and
+/// testing the rest, until a test fails to reject, `max_outliers` have been
+/// removed, fewer than three values remain or the rest are all equal.
+/// Returns the indices into `data` of the removed values in the order they
+/// were removed.
+pub fn grubbs_test_iterative(
+    data: &[f64],
+    alpha: f64,
+    max_outliers: usize,
+) -> Result<Vec<usize>, StatsError> {
+    if data.len() < 3 {
+        return Err(StatsError::InsufficientData {
+            required: 3,
+            actual: data.len(),
+        });
+    }
+    validate_alpha(alpha)?;
+
+    let mut remaining: Vec<usize> = (0..data.len()).collect();
+    let mut removed = Vec::new();
+    while removed.len() < max_outliers && remaining.len() >= 3 {
+        let values: Vec<f64> = remaining.iter().map(|&i| data[i]).collect();
+        // Nothing stands out from constant data, but only the first test
+        // reports it as an error
+        if !removed.is_empty() && values.iter().all(|&x| x == values[0]) {
+            break;
+        }
+        let result = grubbs_test(&values, alpha)?;
+        if !result.is_outlier {
+            break;
+        }
+        removed.push(remaining.remove(result.outlier_index));
+    }
+    Ok(removed)
+}
+
+/// Dixon's gap-to-range ratios. rij compares the gap from the suspect to
+/// its i-th neighbour with the range that leaves out j values at the far end,
+/// so the larger ratios resist a second outlier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DixonRatio {
+    /// r10 = (x2 - x1) / (xn - x1), for 3 to 7 values.
+    R10,
+    /// r11 = (x2 - x1) / (x(n-1) - x1), for 8 to 10 values.
+    R11,
+    /// r21 = (x3 - x1) / (x(n-1) - x1), for 11 to 13 values.
+    R21,
+    /// r22 = (x3 - x1) / (x(n-2) - x1), for 14 to 30 values.
+    R22,
+}
+
+impl DixonRatio {
+    /// The ratio Dixon recommends for a sample of `n` values.
+    pub fn for_sample_size(n: usize) -> Self {
+        match n {
+            0..=7 => DixonRatio::R10,
+            8..=10 => DixonRatio::R11,
+            11..=13 => DixonRatio::R21,
+            _ => DixonRatio::R22,
+        }
+    }
+
+    /// The neighbour the gap is measured to and the number of values left
+    /// out at the far end of the range.
+    fn offsets(self) -> (usize, usize) {
+        match self {
+            DixonRatio::R10 => (1, 0),
+            DixonRatio::R11 => (1, 1),
+            DixonRatio::R21 => (2, 1),
+            DixonRatio::R22 => (2, 2),
+        }
+    }
+}
+
+/// Which end of the sorted sample a suspected outlier sits at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutlierEnd {
+    Min,
+    Max,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DixonResult {
+    /// The Dixon ratio at the suspect end.
+    pub statistic: f64,
+    pub ratio: DixonRatio,
+    pub critical_value: f64,
+    pub suspect: OutlierEnd,
+    /// Index in the input of the suspect; the first one if it is tied.
+    pub outlier_index: usize,
+    /// Whether the suspect is rejected, statistic > critical_value.
+    pub is_outlier: bool,
+}
+
+/// Dixon's Q test of whether the smallest or largest value is an outlier
+/// from an otherwise normal sample of 3 to 30 values. The ratio is chosen by
+/// sample size, computed at both ends, and the larger compared with the
+/// tabulated two-sided critical value. An end whose range is zero, as when
+/// every value but one is equal, has a ratio of zero rather than 0 / 0.
+/// `alpha` must be 0.10, 0.05 or 0.01.
+///
+/// Returns `StatsError::NanInInput` with the index of the first NaN in
+/// `data`.
+pub fn dixon_q_test(data: &[f64], alpha: f64) -> Result<DixonResult, StatsError> {
+    if data.len() < 3 {
+        return Err(StatsError::InsufficientData {
+            required: 3,
+            actual: data.len(),
+        });
+    }
+    if data.len() > DIXON_CRITICAL_VALUES.len() + 2 {
+        return Err(StatsError::InvalidParameter(
+            "Dixon's Q test is tabulated for at most 30 values",
+        ));
+    }
+    let Some(column) = DIXON_ALPHAS.iter().position(|&a| a == alpha) else {
+        return Err(StatsError::InvalidParameter(
+            "Dixon's Q test is tabulated for alpha 0.10, 0.05 and 0.01",
+        ));
+    };
+    if let Some(index) = data.iter().position(|x| x.is_nan()) {
+        return Err(StatsError::NanInInput { index });
+    }
+
+    let n = data.len();
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    if sorted[0] == sorted[n - 1] {
+        return Err(StatsError::InvalidParameter(
+            "Dixon's Q test needs data that is not constant",
+        ));
+    }
+    let ratio = DixonRatio::for_sample_size(n);
+    let (gap, skipped) = ratio.offsets();
+    let gap_ratio = |gap: f64, range: f64| if range == 0.0 { 0.0 } else { gap / range };
+    let low = gap_ratio(sorted[gap] - sorted[0], sorted[n - 1 - skipped] - sorted[0]);
+    let high = gap_ratio(
+        sorted[n - 1] - sorted[n - 1 - gap],
+        sorted[n - 1] - sorted[skipped],
+    );
+    let (statistic, suspect, value) = if low >= high {
+        (low, OutlierEnd::Min, sorted[0])
+    } else {
+        (high, OutlierEnd::Max, sorted[n - 1])
+    };
+    let critical_value = DIXON_CRITICAL_VALUES[n - 3][column];
+
+    Ok(DixonResult {
+        statistic,
+        ratio,
+        critical_value,
+        suspect,
+        outlier_index: data
+            .iter()
+            .position(|&x| x == value)
+            .expect("the suspect comes from data"),
+        is_outlier: statistic > critical_value,
+    })
+}
+
+/// The Mahalanobis distance sqrt((x - μ)' Σ^-1 (x - μ)) of `x` from
+/// `mean` under `covariance`, given by rows. It is computed through the
+/// Cholesky factor of Σ rather than its inverse.
+///
+/// Returns `StatsError::EmptyInput` for no variables,
+/// `StatsError::MismatchedLengths` if `mean` or a row of `covariance`
+/// does not match `x` in length, `StatsError::NanInInput` if `x` contains
+/// NaN and `StatsError::InvalidParameter` if `mean` or `covariance`
+/// contains NaN, or `covariance` is not square and symmetric, or is
+/// singular or not positive definite.
+pub fn mahalanobis_distance(
+    x: &[f64],
+    mean: &[f64],
+    covariance: &[Vec<f64>],
+) -> Result<f64, StatsError> {
+    if x.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if let Some(length) = [mean.len()]
+        .into_iter()
+        .chain(covariance.iter().map(|row| row.len()))
+        .find(|&length| length != x.len())
+    {
+        return Err(StatsError::MismatchedLengths {
+            left: x.len(),
+            right: length,
+        });
+    }
+    if covariance.len() != x.len() {
+        return Err(StatsError::InvalidParameter(
+            "covariance must be a square matrix with a row per variable",
+        ));
+    }
+    if let Some(index) = x.iter().position(|v| v.is_nan()) {
+        return Err(StatsError::NanInInput { index });
+    }
+    if mean
+        .iter()
+        .chain(covariance.iter().flatten())
+        .any(|v| v.is_nan())
+    {
+        return Err(StatsError::InvalidParameter(
+            "mean and covariance must not contain NaN",
+        ));
+    }
+    let lower = cholesky(covariance)?;
+    Ok(whitened_distance(&lower, x, mean))
+}
+
+/// Rows flagged by `mahalanobis_outliers`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MahalanobisOutliers {
+    /// Mahalanobis distance of each row from the centroid, in input order.
+    pub distances: Vec<f64>,
+    /// The column means the distances are measured from.
+    pub centroid: Vec<f64>,
+    /// Rows whose squared distance exceeds this, the 1 - alpha quantile of
+    /// χ² with one degree of freedom per column, are flagged.
+    pub threshold: f64,
+    /// Indices of the flagged rows, ascending.
+    pub outliers: Vec<usize>,
+}
+
+/// Flags multivariate outliers among `rows`, each holding one value per
+/// variable: the distance of every row from the centroid is measured with
+/// the sample covariance, and rows whose squared distance exceeds the
+/// chi-square quantile for `alpha` are flagged. A point can sit well inside
+/// the range of every variable and still be far from the others jointly,
+/// off the direction along which the variables move together.
+///
+/// Returns `StatsError::EmptyInput` for no rows or an empty first row,
+/// `StatsError::MismatchedLengths` if the rows differ in length,
+/// `StatsError::NanInInput` with the index of the first row containing
+/// NaN, `StatsError::InsufficientData` for a single row, and
+/// `StatsError::InvalidParameter` for an invalid `alpha` or a singular
+/// sample covariance, as with fewer rows than variables or a constant or
+/// collinear column.
+pub fn mahalanobis_outliers(
+    rows: &[Vec<f64>],
+    alpha: f64,
+) -> Result<MahalanobisOutliers, StatsError> {
+    validate_alpha(alpha)?;
+    let width = rows.first().map_or(0, |row| row.len());
+    if width == 0 {
+        return Err(StatsError::EmptyInput);
+    }
+    if let Some(row) = rows.iter().find(|row| row.len() != width) {
+        return Err(StatsError::MismatchedLengths {
+            left: width,
+            right: row.len(),
+        });
+    }
+    if let Some(index) = rows.iter().position(|row| row.iter().any(|v| v.is_nan())) {
+        return Err(StatsError::NanInInput { index });
+    }
+
+    let columns: Vec<Vec<f64>> = (0..width)
+        .map(|j| rows.iter().map(|row| row[j]).collect())
+        .collect();
+    let columns: Vec<&[f64]> = columns.iter().map(|c| c.as_slice()).collect();
+    let covariance = covariance_matrix(&columns)?;
+    let lower = cholesky(&covariance)?;
+    let centroid: Vec<f64> = columns.iter().map(|column| mean(column)).collect();
+
+    let distances: Vec<f64> = rows
+        .iter()
+        .map(|row| whitened_distance(&lower, row, &centroid))
+        .collect();
+    let threshold = ChiSquare::new(width as f64).quantile(1.0 - alpha);
+    let outliers = (0..rows.len())
+        .filter(|&i| distances[i] * distances[i] > threshold)
+        .collect();
+    Ok(MahalanobisOutliers {
+        distances,
+        centroid,
+        threshold,
+        outliers,
+    })
+}
+
+/// ||L^-1 (x - μ)||, the Mahalanobis distance for Σ = L L'.
+fn whitened_distance(lower: &[Vec<f64>], x: &[f64], mean: &[f64]) -> f64 {
+    let deviation: Vec<f64> = x.iter().zip(mean).map(|(a, b)| a - b).collect();
+    forward_substitute(lower, &deviation)
+        .iter()
+        .map(|z| z * z)
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const PLANTED: [f64; 10] = [10.1, 9.8, 10.3, 10.0, 9.9, 10.2, 14.5, 10.1, 9.7, 10.0];
+    const CLEAN: [f64; 8] = [10.1, 9.8, 10.3, 10.0, 9.9, 10.2, 10.1, 9.7];
+
+    #[test]
+    fn grubbs_test_test() {
+        // R: outliers::grubbs.test(x)
+        let result = grubbs_test(&PLANTED, 0.05).unwrap();
+        assert_eq!(result.outlier_index, 6);
+        assert_close(result.statistic, 2.82364865907419, 1e-13);
+        assert_close(result.critical_value, 2.17606839419422, 1e-10);
+        assert_close(result.p_value, 8.316832498708e-8, 1e-15);
+        assert!(result.is_outlier);
+
+        let result = grubbs_test(&CLEAN, 0.05).unwrap();
+        assert_eq!(result.outlier_index, 7);
+        assert_close(result.statistic, 1.53864363724166, 1e-13);
+        assert_close(result.critical_value, 2.03165200154994, 1e-10);
+        assert_close(result.p_value, 0.399388140037174, 1e-11);
+        assert!(!result.is_outlier);
+    }
+
+    #[test]
+    fn grubbs_test_iterative_test() {
+        let mut data = PLANTED.to_vec();
+        data.extend([3.0, 10.05]);
+        assert_eq!(grubbs_test_iterative(&data, 0.05, 5), Ok(vec![10, 6]));
+        assert_eq!(grubbs_test_iterative(&data, 0.05, 1), Ok(vec![10]));
+        assert_eq!(grubbs_test_iterative(&CLEAN, 0.05, 5), Ok(vec![]));
+        assert_eq!(
+            grubbs_test_iterative(&[1.0, 1.0, 1.0, 1.0, 100.0], 0.05, 3),
+            Ok(vec![4])
+        );
+    }
+
+    #[test]
+    fn dixon_q_test_test() {
+        let data = [4.85, 6.18, 6.27, 6.49, 6.22];
+        let result = dixon_q_test(&data, 0.05).unwrap();
+        assert_eq!(result.ratio, DixonRatio::R10);
+        assert_eq!(result.suspect, OutlierEnd::Min);
+        assert_eq!(result.outlier_index, 0);
+        assert_close(result.statistic, 1.33 / 1.64, 1e-14);
+        assert_eq!(result.critical_value, 0.710);
+        assert!(result.is_outlier);
+        assert!(!dixon_q_test(&data, 0.01).unwrap().is_outlier);
+
+        // Low r11 = 0.010 / 0.020 and high r11 = 0.002 / 0.012
+        let data = [
+            0.189, 0.167, 0.187, 0.183, 0.186, 0.182, 0.181, 0.184, 0.181, 0.177,
+        ];
+        let result = dixon_q_test(&data, 0.05).unwrap();
+        assert_eq!(result.ratio, DixonRatio::R11);
+        assert_eq!(result.suspect, OutlierEnd::Min);
+        assert_eq!(result.outlier_index, 1);
+        assert_close(result.statistic, 0.5, 1e-12);
+        assert!(!result.is_outlier);
+        assert!(dixon_q_test(&data, 0.10).unwrap().is_outlier);
+
+        let mut data: Vec<f64> = (0..20).map(|i| f64::from(i % 7)).collect();
+        data.push(15.0);
+        let result = dixon_q_test(&data, 0.01).unwrap();
+        assert_eq!(result.ratio, DixonRatio::R22);
+        assert_eq!(result.suspect, OutlierEnd::Max);
+        assert_eq!(result.outlier_index, 20);
+        assert_close(result.statistic, 9.0 / 15.0, 1e-15);
+        assert!(result.is_outlier);
+
+        // The high end's range x(n) - x(2) is zero, so only the low end can
+        // be the suspect
+        let mut data = [5.0; 10];
+        data[0] = 1.0;
+        let result = dixon_q_test(&data, 0.05).unwrap();
+        assert_eq!(result.suspect, OutlierEnd::Min);
+        assert_eq!((result.outlier_index, result.statistic), (0, 1.0));
+        assert!(result.is_outlier);
+        data[0] = 9.0;
+        let result = dixon_q_test(&data, 0.05).unwrap();
+        assert_eq!((result.suspect, result.outlier_index), (OutlierEnd::Max, 0));
+    }
+
+    #[test]
+    fn dixon_q_test_invalid_input_test() {
+        let data: Vec<f64> = (0..31).map(f64::from).collect();
+        assert!(matches!(
+            dixon_q_test(&data, 0.05),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(dixon_q_test(&data[..30], 0.05).is_ok());
+        assert!(matches!(
+            dixon_q_test(&data[..5], 0.025),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            dixon_q_test(&data[..2], 0.05).err(),
+            Some(StatsError::InsufficientData {
+                required: 3,
+                actual: 2
+            })
+        );
+        assert!(matches!(
+            dixon_q_test(&[2.0; 4], 0.05),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            dixon_q_test(&[1.0, f64::NAN, 3.0, 4.0, 5.0], 0.05).err(),
+            Some(StatsError::NanInInput { index: 1 })
+        );
+    }
+
+    #[test]
+    fn grubbs_test_invalid_input_test() {
+        assert_eq!(
+            grubbs_test(&[1.0, 2.0], 0.05).err(),
+            Some(StatsError::InsufficientData {
+                required: 3,
+                actual: 2
+            })
+        );
+        assert!(matches!(
+            grubbs_test(&[4.0; 5], 0.05),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            grubbs_test(&CLEAN, 1.5),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn mahalanobis_distance_test() {
+        // Σ^-1 = [[3, -2], [-2, 4]] / 8, so d² = [1, 2] Σ^-1 [1, 2]' = 11 / 8
+        let covariance = vec![vec![4.0, 2.0], vec![2.0, 3.0]];
+        let distance = mahalanobis_distance(&[2.0, 3.0], &[1.0, 1.0], &covariance).unwrap();
+        assert_close(distance, 1.375f64.sqrt(), 1e-15);
+        // In one dimension it is |x - μ| / σ
+        let distance = mahalanobis_distance(&[7.0], &[4.0], &[vec![2.25]]).unwrap();
+        assert_close(distance, 2.0, 1e-15);
+        assert_eq!(
+            mahalanobis_distance(&[1.0, 1.0], &[1.0, 1.0], &covariance).unwrap(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn mahalanobis_distance_invalid_input_test() {
+        let covariance = vec![vec![4.0, 2.0], vec![2.0, 3.0]];
+        assert_eq!(
+            mahalanobis_distance(&[], &[], &[]),
+            Err(StatsError::EmptyInput)
+        );
+        assert_eq!(
+            mahalanobis_distance(&[1.0, 2.0], &[1.0], &covariance),
+            Err(StatsError::MismatchedLengths { left: 2, right: 1 })
+        );
+        assert_eq!(
+            mahalanobis_distance(&[1.0, 2.0], &[0.0, 0.0], &covariance[..1]),
+            Err(StatsError::InvalidParameter(
+                "covariance must be a square matrix with a row per variable"
+            ))
+        );
+        assert_eq!(
+            mahalanobis_distance(&[1.0, f64::NAN], &[0.0, 0.0], &covariance),
+            Err(StatsError::NanInInput { index: 1 })
+        );
+        let with_nan = vec![vec![4.0, f64::NAN], vec![f64::NAN, 3.0]];
+        for (mean, covariance) in [([f64::NAN, 0.0], &covariance), ([0.0, 0.0], &with_nan)] {
+            assert_eq!(
+                mahalanobis_distance(&[1.0, 2.0], &mean, covariance),
+                Err(StatsError::InvalidParameter(
+                    "mean and covariance must not contain NaN"
+                ))
+            );
+        }
+        let asymmetric = vec![vec![4.0, 2.0], vec![1.0, 3.0]];
+        assert_eq!(
+            mahalanobis_distance(&[1.0, 2.0], &[0.0, 0.0], &asymmetric),
+            Err(StatsError::InvalidParameter(
+                "covariance matrix is not symmetric"
+            ))
+        );
+        let singular = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        assert_eq!(
+            mahalanobis_distance(&[1.0, 2.0], &[0.0, 0.0], &singular),
+            Err(StatsError::InvalidParameter(
+                "covariance matrix is singular or not positive definite"
+            ))
+        );
+    }
+
+    #[test]
+    fn mahalanobis_outliers_test() {
+        // Two closely correlated variables, and a planted row (6, 14) inside
+        // the range of each but far off the line they follow together
+        let x = [
+            2.1, 3.0, 3.9, 5.2, 6.1, 6.8, 8.0, 9.1, 9.9, 11.2, 11.8, 13.1, 14.0, 14.8, 16.1,
+        ];
+        let noise = [
+            0.3, -0.4, 0.2, -0.1, 0.5, -0.3, 0.1, -0.2, 0.4, -0.5, 0.2, 0.0, -0.3, 0.3, -0.1,
+        ];
+        let mut rows: Vec<Vec<f64>> = x.iter().zip(noise).map(|(&x, e)| vec![x, x + e]).collect();
+        rows.push(vec![6.0, 14.0]);
+
+        for column in 0..2 {
+            let values: Vec<f64> = rows.iter().map(|row| row[column]).collect();
+            let z = crate::z_scores(&values).unwrap();
+            assert!(z.iter().all(|z| z.abs() < 2.0), "{z:?}");
+        }
+
+        let result = mahalanobis_outliers(&rows, 0.01).unwrap();
+        assert_eq!(result.outliers, [15]);
+        assert_close(result.threshold, 9.21034037197618, 1e-12);
+        assert_eq!(result.distances.len(), 16);
+        let columns: Vec<Vec<f64>> = (0..2)
+            .map(|j| rows.iter().map(|row| row[j]).collect())
+            .collect();
+        assert_eq!(result.centroid, [mean(&columns[0]), mean(&columns[1])]);
+        let covariance = covariance_matrix(&[&columns[0], &columns[1]]).unwrap();
+        let direct = mahalanobis_distance(&rows[15], &result.centroid, &covariance).unwrap();
+        assert_close(result.distances[15], direct, 1e-14);
+    }
+
+    #[test]
+    fn mahalanobis_outliers_invalid_input_test() {
+        assert_eq!(
+            mahalanobis_outliers(&[], 0.05).err(),
+            Some(StatsError::EmptyInput)
+        );
+        assert_eq!(
+            mahalanobis_outliers(&[vec![1.0, 2.0], vec![3.0]], 0.05).err(),
+            Some(StatsError::MismatchedLengths { left: 2, right: 1 })
+        );
+        let mut rows: Vec<Vec<f64>> = (0..5).map(|i| vec![i as f64, (i * i) as f64]).collect();
+        rows[3][1] = f64::NAN;
+        assert_eq!(
+            mahalanobis_outliers(&rows, 0.05).err(),
+            Some(StatsError::NanInInput { index: 3 })
+        );
+        // Collinear columns have a singular covariance
+        let rows: Vec<Vec<f64>> = (0..5).map(|i| vec![i as f64, 2.0 * i as f64]).collect();
+        assert!(matches!(
+            mahalanobis_outliers(&rows, 0.05),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            mahalanobis_outliers(&[vec![1.0], vec![2.0]], 1.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
@@ -0,0 +1,391 @@
+use std::fmt;
+
+use crate::distributions::ChiSquare;
+use crate::format::write_test_line;
+use crate::{binomial_test_with, Alternative, HypothesisTest, PValueMethod, StatsError};
+
+/// How a McNemar p-value was computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum McNemarMethod {
+    /// Chi-square approximation with one degree of freedom.
+    ChiSquare,
+    /// Exact binomial test on the discordant pairs.
+    Exact,
+    /// Mid-p binomial test on the discordant pairs.
+    MidP,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct McNemarResult {
+    /// The chi-square statistic, or the count in cell b for the binomial
+    /// tests.
+    pub statistic: f64,
+    pub p_value: f64,
+    pub method: McNemarMethod,
+}
+
+impl fmt::Display for McNemarResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.method {
+            McNemarMethod::ChiSquare => {
+                write_test_line(f, "χ²", &[1.0], self.statistic, self.p_value)
+            }
+            McNemarMethod::Exact | McNemarMethod::MidP => {
+                write_test_line(f, "b", &[], self.statistic, self.p_value)
+            }
+        }
+    }
+}
+
+impl HypothesisTest for McNemarResult {
+    fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        match self.method {
+            McNemarMethod::ChiSquare => Some(1.0),
+            McNemarMethod::Exact | McNemarMethod::MidP => None,
+        }
+    }
+
+    fn test_name(&self) -> &'static str {
+        "McNemar's test"
+    }
+}
+
+/// McNemar's test for paired binary outcomes, using only the discordant cells
+/// b and c of the table [[a, b], [c, d]]. With `correction` the statistic is
+/// (|b - c| - 1)^2 / (b + c), otherwise (b - c)^2 / (b + c).
+pub fn mcnemar_test(table: [[u64; 2]; 2], correction: bool) -> Result<McNemarResult, StatsError> {
+    let (b, c) = discordant_cells(table)?;
+
+    let correction = if correction { 1.0 } else { 0.0 };
+    let statistic = ((b - c).abs() - correction).powi(2) / (b + c);
+    let p_value = ChiSquare::new(1.0).sf(statistic);
+
+    Ok(McNemarResult {
+        statistic,
+        p_value,
+        method: McNemarMethod::ChiSquare,
+    })
+}
+
+/// Exact McNemar test, a two-sided binomial test of b successes in b + c
+/// trials with p = 0.5. Preferable to the chi-square version when b + c is
+/// small.
+pub fn mcnemar_exact_test(table: [[u64; 2]; 2]) -> Result<McNemarResult, StatsError> {
+    mcnemar_test_with(table, PValueMethod::Exact)
+}
+
+/// McNemar's test with the p-value chosen by `method`: the exact binomial
+/// test, its mid-p version, or for `PValueMethod::Asymptotic` the
+/// chi-square test with continuity correction, R's `mcnemar.test` default.
+pub fn mcnemar_test_with(
+    table: [[u64; 2]; 2],
+    method: PValueMethod,
+) -> Result<McNemarResult, StatsError> {
+    let recorded = match method {
+        PValueMethod::Exact => McNemarMethod::Exact,
+        PValueMethod::MidP => McNemarMethod::MidP,
+        PValueMethod::Asymptotic => return mcnemar_test(table, true),
+    };
+    let (b, c) = discordant_cells(table)?;
+
+    let result = binomial_test_with(
+        b as usize,
+        (b + c) as usize,
+        0.5,
+        Alternative::TwoSided,
+        method,
+    )?;
+
+    Ok(McNemarResult {
+        statistic: b,
+        p_value: result.p_value,
+        method: recorded,
+    })
+}
+
+fn discordant_cells(table: [[u64; 2]; 2]) -> Result<(f64, f64), StatsError> {
+    let [[_, b], [c, _]] = table;
+    if b + c == 0 {
+        return Err(StatsError::InvalidParameter(
+            "McNemar's test needs at least one discordant pair",
+        ));
+    }
+    Ok((b as f64, c as f64))
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CochranQResult {
+    pub q: f64,
+    pub df: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for CochranQResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "Q", &[self.df], self.q, self.p_value)
+    }
+}
+
+impl HypothesisTest for CochranQResult {
+    fn statistic(&self) -> f64 {
+        self.q
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Cochran's Q test"
+    }
+}
+
+/// Cochran's Q test that k related binary treatments have the same success
+/// probability. Each row of `data` holds one subject's 0/1 responses across
+/// the k conditions. Rows that are all 0 or all 1 carry no information and
+/// do not change Q.
+pub fn cochrans_q(data: &[Vec<u8>]) -> Result<CochranQResult, StatsError> {
+    let k = validate_binary_rows(data)?;
+
+    let mut column_totals = vec![0.0; k];
+    let mut total = 0.0;
+    let mut row_square_sum = 0.0;
+    for row in data {
+        let row_total: f64 = row.iter().map(|&x| x as f64).sum();
+        for (column_total, &x) in column_totals.iter_mut().zip(row) {
+            *column_total += x as f64;
+        }
+        total += row_total;
+        row_square_sum += row_total * row_total;
+    }
+
+    let k_f = k as f64;
+    let denominator = k_f * total - row_square_sum;
+    if denominator == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "every subject responded identically across all conditions",
+        ));
+    }
+    let column_square_sum: f64 = column_totals.iter().map(|c| c * c).sum();
+    let q = (k_f - 1.0) * (k_f * column_square_sum - total * total) / denominator;
+    let df = k_f - 1.0;
+
+    Ok(CochranQResult {
+        q,
+        df,
+        p_value: ChiSquare::new(df).sf(q),
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairwiseMcNemar {
+    pub condition_1: usize,
+    pub condition_2: usize,
+    pub result: McNemarResult,
+}
+
+/// McNemar's test between every pair of conditions, as a follow-up to a
+/// significant Cochran's Q. Pairs with no discordant responses are skipped.
+/// The p-values are not adjusted for multiple comparisons.
+pub fn pairwise_mcnemar(
+    data: &[Vec<u8>],
+    correction: bool,
+) -> Result<Vec<PairwiseMcNemar>, StatsError> {
+    let k = validate_binary_rows(data)?;
+
+    let mut comparisons = Vec::new();
+    for condition_1 in 0..k {
+        for condition_2 in condition_1 + 1..k {
+            let mut table = [[0; 2]; 2];
+            for row in data {
+                let i = 1 - row[condition_1] as usize;
+                let j = 1 - row[condition_2] as usize;
+                table[i][j] += 1;
+            }
+            if let Ok(result) = mcnemar_test(table, correction) {
+                comparisons.push(PairwiseMcNemar {
+                    condition_1,
+                    condition_2,
+                    result,
+                });
+            }
+        }
+    }
+
+    Ok(comparisons)
+}
+
+/// Checks that `data` is a non-empty rectangular 0/1 matrix with at least two
+/// columns, returning the number of columns.
+fn validate_binary_rows(data: &[Vec<u8>]) -> Result<usize, StatsError> {
+    let k = match data.first() {
+        Some(row) => row.len(),
+        None => return Err(StatsError::EmptyInput),
+    };
+    if k < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: k,
+        });
+    }
+    if let Some(row) = data.iter().find(|row| row.len() != k) {
+        return Err(StatsError::MismatchedLengths {
+            left: k,
+            right: row.len(),
+        });
+    }
+    if data.iter().flatten().any(|&x| x > 1) {
+        return Err(StatsError::InvalidParameter("responses must be 0 or 1"));
+    }
+    Ok(k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn mcnemar_test_test() {
+        // R: mcnemar.test(matrix(c(794, 86, 150, 570), nrow = 2), correct = FALSE)
+        let table = [[794, 150], [86, 570]];
+        let result = mcnemar_test(table, false).unwrap();
+        assert_close(result.statistic, 17.3559322033898, 1e-12);
+        assert_close(result.p_value, 3.09929344104522e-5, 1e-16);
+        assert_eq!(result.method, McNemarMethod::ChiSquare);
+
+        // R: mcnemar.test(matrix(c(794, 86, 150, 570), nrow = 2))
+        let result = mcnemar_test(table, true).unwrap();
+        assert_close(result.statistic, 16.8177966101695, 1e-12);
+        assert_close(result.p_value, 4.11456228134595e-5, 1e-16);
+    }
+
+    #[test]
+    fn mcnemar_exact_test_test() {
+        let table = [[20, 3], [9, 15]];
+        let result = mcnemar_exact_test(table).unwrap();
+        assert_eq!(result.statistic, 3.0);
+        assert_close(result.p_value, 0.14599609375, 1e-12);
+        assert_eq!(result.method, McNemarMethod::Exact);
+
+        let result = mcnemar_test(table, true).unwrap();
+        assert_close(result.statistic, 2.08333333333333, 1e-12);
+        assert_close(result.p_value, 0.148914673178766, 1e-12);
+    }
+
+    #[test]
+    fn mcnemar_test_with_test() {
+        // The exact p-value 0.14599609375 less half of P(B = 3) + P(B = 9)
+        // = 2 C(12, 3) / 2^12, as b = 9 is as likely as the observed b = 3
+        let table = [[20, 3], [9, 15]];
+        let mid_p = mcnemar_test_with(table, PValueMethod::MidP).unwrap();
+        assert_close(mid_p.p_value, 0.09228515625, 1e-12);
+        assert_eq!((mid_p.statistic, mid_p.method), (3.0, McNemarMethod::MidP));
+        assert_eq!(mid_p.degrees_of_freedom(), None);
+
+        let exact = mcnemar_test_with(table, PValueMethod::Exact).unwrap();
+        assert_eq!(exact.p_value, mcnemar_exact_test(table).unwrap().p_value);
+        assert_eq!(exact.method, McNemarMethod::Exact);
+        assert!(mid_p.p_value < exact.p_value);
+        let asymptotic = mcnemar_test_with(table, PValueMethod::Asymptotic).unwrap();
+        assert_eq!(
+            asymptotic.statistic,
+            mcnemar_test(table, true).unwrap().statistic
+        );
+        assert_eq!(asymptotic.method, McNemarMethod::ChiSquare);
+    }
+
+    #[test]
+    fn mcnemar_no_discordant_pairs_test() {
+        assert!(matches!(
+            mcnemar_test([[10, 0], [0, 12]], true),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            mcnemar_exact_test([[10, 0], [0, 12]]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    fn cochran_fixture() -> Vec<Vec<u8>> {
+        vec![
+            vec![1, 1, 0],
+            vec![1, 1, 1],
+            vec![0, 1, 0],
+            vec![1, 1, 0],
+            vec![0, 0, 0],
+            vec![1, 1, 1],
+            vec![1, 1, 0],
+            vec![1, 0, 0],
+            vec![0, 1, 0],
+            vec![1, 1, 0],
+            vec![1, 1, 1],
+            vec![0, 1, 0],
+        ]
+    }
+
+    #[test]
+    fn cochrans_q_test() {
+        let result = cochrans_q(&cochran_fixture()).unwrap();
+        assert_close(result.q, 9.75, 1e-12);
+        assert_eq!(result.df, 2.0);
+        assert_close(result.p_value, 0.00763509421885996, 1e-12);
+    }
+
+    #[test]
+    fn cochrans_q_constant_rows_test() {
+        // All-0 and all-1 rows do not change Q
+        let mut data = cochran_fixture();
+        data.push(vec![0, 0, 0]);
+        data.push(vec![1, 1, 1]);
+        let result = cochrans_q(&data).unwrap();
+        assert_close(result.q, 9.75, 1e-12);
+
+        assert!(matches!(
+            cochrans_q(&[vec![1, 1, 1], vec![0, 0, 0]]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn cochrans_q_invalid_input_test() {
+        assert_eq!(cochrans_q(&[]).err(), Some(StatsError::EmptyInput));
+        assert_eq!(
+            cochrans_q(&[vec![1, 0], vec![1]]).err(),
+            Some(StatsError::MismatchedLengths { left: 2, right: 1 })
+        );
+        assert!(matches!(
+            cochrans_q(&[vec![1, 2]]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn pairwise_mcnemar_test() {
+        let comparisons = pairwise_mcnemar(&cochran_fixture(), false).unwrap();
+        assert_eq!(comparisons.len(), 3);
+        assert_eq!(
+            (comparisons[0].condition_1, comparisons[0].condition_2),
+            (0, 1)
+        );
+        // Conditions 0 and 2: five subjects succeeded only on 0, none only on 2
+        assert_eq!(
+            (comparisons[1].condition_1, comparisons[1].condition_2),
+            (0, 2)
+        );
+        assert_close(comparisons[1].result.statistic, 5.0, 1e-12);
+    }
+}
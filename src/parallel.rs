@@ -0,0 +1,119 @@
+use rayon::prelude::*;
+
+use crate::summation::CompensatedSum;
+use crate::{mean_of, merge_moments, Float, SampleStatistics};
+
+/// Values per chunk. Fixed rather than derived from the thread count, so the
+/// chunks, and therefore the result, are the same on every machine.
+const CHUNK_SIZE: usize = 1 << 16;
+
+/// (n, mean, sum of squared deviations) of `array`, computed chunk by chunk
+/// in parallel and merged in chunk order.
+fn par_moments<T: Float + Sync>(array: &[T]) -> (usize, f64, f64) {
+    let partials: Vec<(usize, f64, f64)> = array.par_chunks(CHUNK_SIZE).map(moments).collect();
+    partials.into_iter().fold((0, f64::NAN, 0.0), merge_moments)
+}
+
+/// Two compensated passes over one chunk, as in the serial functions.
+fn moments<T: Float>(chunk: &[T]) -> (usize, f64, f64) {
+    let chunk_mean = mean_of(chunk);
+    let mut m2 = CompensatedSum::default();
+    for x in chunk {
+        let deviation = x.to_f64() - chunk_mean;
+        m2.add(deviation * deviation);
+    }
+    (chunk.len(), chunk_mean, m2.total())
+}
+
+/// `mean` computed on all cores; NaN for an empty slice.
+pub fn par_mean<T: Float + Sync>(array: &[T]) -> f64 {
+    par_moments(array).1
+}
+
+/// `sample_variance` computed on all cores; NaN for fewer than two values.
+pub fn par_sample_variance<T: Float + Sync>(array: &[T]) -> f64 {
+    let (n, _, m2) = par_moments(array);
+    if n < 2 {
+        return f64::NAN;
+    }
+    m2 / (n - 1) as f64
+}
+
+impl SampleStatistics {
+    /// `from_array` computed on all cores, in a single parallel pass.
+    pub fn par_from_array<T: Float + Sync>(array: &[T]) -> Self {
+        let (n, sample_mean, m2) = par_moments(array);
+        let standard_deviation = if n < 2 {
+            f64::NAN
+        } else {
+            (m2 / (n - 1) as f64).sqrt()
+        };
+        SampleStatistics {
+            sample_mean,
+            standard_deviation,
+            n,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, sample_variance, GetStatistics};
+
+    #[test]
+    fn parallel_matches_serial_test() {
+        let mut rng = crate::rng::Rng::new(110);
+        let data: Vec<f64> = (0..50_000_000)
+            .map(|_| 1e3 + 10.0 * rng.next_f64())
+            .collect();
+        let serial = SampleStatistics::from_array(&data);
+        let parallel = SampleStatistics::par_from_array(&data);
+        assert_eq!(parallel.n, serial.n);
+        assert_close(
+            parallel.sample_mean,
+            serial.sample_mean,
+            1e-12 * serial.sample_mean,
+        );
+        assert_close(
+            parallel.standard_deviation,
+            serial.standard_deviation,
+            1e-12 * serial.standard_deviation,
+        );
+        assert_close(
+            par_mean(&data),
+            serial.sample_mean,
+            1e-12 * serial.sample_mean,
+        );
+        let variance = sample_variance(&data);
+        assert_close(par_sample_variance(&data), variance, 1e-12 * variance);
+    }
+
+    #[test]
+    fn thread_count_test() {
+        let mut rng = crate::rng::Rng::new(7);
+        let data: Vec<f32> = (0..1_000_003).map(|_| rng.next_f64() as f32).collect();
+        let with_threads = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| (par_mean(&data), par_sample_variance(&data)))
+        };
+        let single = with_threads(1);
+        assert_eq!(with_threads(3), single);
+        assert_eq!(with_threads(8), single);
+    }
+
+    #[test]
+    fn small_input_test() {
+        let empty: [f64; 0] = [];
+        assert!(par_mean(&empty).is_nan());
+        assert!(par_sample_variance(&[2.0]).is_nan());
+        assert_eq!(par_mean(&[2.0]), 2.0);
+        let samp = SampleStatistics::par_from_array(&[1.0, 2.0, 3.0]);
+        assert_eq!(samp.n, 3);
+        assert_eq!(samp.sample_mean, 2.0);
+        assert_eq!(samp.standard_deviation, 1.0);
+    }
+}
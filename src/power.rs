@@ -0,0 +1,397 @@
+use crate::distributions::{NoncentralT, Normal, StudentsT};
+use crate::hypothesis::validate_alpha;
+use crate::{Alternative, StatsError};
+
+/// Sample sizes beyond this are not searched; reaching it means the effect
+/// is too small to detect with any practical design.
+const MAX_SAMPLE_SIZE: usize = 1 << 40;
+
+/// The layout of the experiment a t-test power calculation is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TTestDesign {
+    /// One sample against a fixed mean; `n` observations.
+    OneSample,
+    /// Paired observations, tested as one sample of differences; `n` pairs.
+    Paired,
+    /// Two independent groups of equal size; `n` observations per group.
+    TwoSample,
+}
+
+impl TTestDesign {
+    /// Degrees of freedom and noncentrality of the t statistic for
+    /// standardized effect `effect_size` (Cohen's d).
+    fn noncentral_t(self, effect_size: f64, n: usize) -> (f64, f64) {
+        let n = n as f64;
+        match self {
+            TTestDesign::OneSample | TTestDesign::Paired => (n - 1.0, n.sqrt() * effect_size),
+            TTestDesign::TwoSample => (2.0 * n - 2.0, (n / 2.0).sqrt() * effect_size),
+        }
+    }
+}
+
+/// Power of a t-test at significance level `alpha` to detect the
+/// standardized effect `effect_size` (Cohen's d) with sample size `n`,
+/// computed exactly from the noncentral t distribution as R's `pwr.t.test`
+/// and G*Power do. For `TwoSample`, `n` is the size of each group.
+///
+/// For one-sided alternatives the effect is signed: `Greater` has power to
+/// detect positive effects and `Less` negative ones.
+pub fn t_test_power(
+    effect_size: f64,
+    n: usize,
+    alpha: f64,
+    alternative: Alternative,
+    design: TTestDesign,
+) -> Result<f64, StatsError> {
+    if !effect_size.is_finite() {
+        return Err(StatsError::InvalidParameter("effect size must be finite"));
+    }
+    validate_alpha(alpha)?;
+    if n < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: n,
+        });
+    }
+    Ok(power(effect_size, n, alpha, alternative, design))
+}
+
+/// The smallest sample size whose t-test reaches `power` at significance
+/// level `alpha` for the standardized effect `effect_size`, found by
+/// searching over `t_test_power`. For `TwoSample` this is the size of each
+/// group.
+///
+/// R's `pwr.t.test` reports the fractional root instead; this is its ceiling,
+/// consistent with G*Power.
+pub fn t_test_sample_size(
+    effect_size: f64,
+    power: f64,
+    alpha: f64,
+    alternative: Alternative,
+    design: TTestDesign,
+) -> Result<usize, StatsError> {
+    if !effect_size.is_finite() || effect_size == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "effect size must be finite and nonzero",
+        ));
+    }
+    validate_alpha(alpha)?;
+    if !(power > 0.0 && power < 1.0) {
+        return Err(StatsError::InvalidParameter(
+            "power must be between 0 and 1",
+        ));
+    }
+    let wrong_direction = match alternative {
+        Alternative::TwoSided => false,
+        Alternative::Greater => effect_size < 0.0,
+        Alternative::Less => effect_size > 0.0,
+    };
+    if wrong_direction {
+        return Err(StatsError::InvalidParameter(
+            "effect size is in the opposite direction to the alternative",
+        ));
+    }
+
+    let reaches = |n| self::power(effect_size, n, alpha, alternative, design) >= power;
+    let mut low = 2;
+    if reaches(low) {
+        return Ok(low);
+    }
+    // Power increases with n, so double to bracket the answer then bisect
+    let mut high = 4;
+    while !reaches(high) {
+        if high >= MAX_SAMPLE_SIZE {
+            return Err(StatsError::InvalidParameter(
+                "effect size is too small to reach the requested power",
+            ));
+        }
+        low = high;
+        high *= 2;
+    }
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if reaches(middle) {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    Ok(high)
+}
+
+fn power(
+    effect_size: f64,
+    n: usize,
+    alpha: f64,
+    alternative: Alternative,
+    design: TTestDesign,
+) -> f64 {
+    let (df, ncp) = design.noncentral_t(effect_size, n);
+    let central = StudentsT::new(df);
+    let statistic = NoncentralT::new(df, ncp);
+    match alternative {
+        Alternative::TwoSided => {
+            let critical = central.quantile(1.0 - alpha / 2.0);
+            1.0 - statistic.cdf(critical) + statistic.cdf(-critical)
+        }
+        Alternative::Greater => 1.0 - statistic.cdf(central.quantile(1.0 - alpha)),
+        Alternative::Less => statistic.cdf(central.quantile(alpha)),
+    }
+}
+
+/// Power of a two-sided two-proportion z-test with `n` observations in each
+/// arm to detect true proportions `p1` and `p2` at significance level
+/// `alpha`, by the normal approximation of R's `power.prop.test`.
+pub fn two_prop_power(p1: f64, p2: f64, n: usize, alpha: f64) -> Result<f64, StatsError> {
+    two_prop_power_with(p1, p2, n, alpha, 1.0)
+}
+
+/// `two_prop_power` with `n1` observations in the first arm and
+/// `ratio * n1` in the second.
+pub fn two_prop_power_with(
+    p1: f64,
+    p2: f64,
+    n1: usize,
+    alpha: f64,
+    ratio: f64,
+) -> Result<f64, StatsError> {
+    let terms = TwoProportionTerms::new(p1, p2, alpha, ratio)?;
+    if n1 == 0 {
+        return Err(StatsError::InsufficientData {
+            required: 1,
+            actual: 0,
+        });
+    }
+    let normal = Normal::standard();
+    let z = ((p1 - p2).abs() * (n1 as f64).sqrt()
+        - normal.quantile(1.0 - alpha / 2.0) * terms.null_sd)
+        / terms.alternative_sd;
+    Ok(normal.cdf(z))
+}
+
+/// Observations needed in each arm for a two-sided two-proportion z-test to
+/// reach `power` at significance level `alpha` when the true proportions
+/// are `p1` and `p2`. This is the ceiling of the n that R's
+/// `power.prop.test` reports, from the closed-form normal approximation.
+pub fn two_prop_sample_size(p1: f64, p2: f64, alpha: f64, power: f64) -> Result<usize, StatsError> {
+    two_prop_sample_size_with(p1, p2, alpha, power, 1.0)
+}
+
+/// `two_prop_sample_size` for an allocation of `ratio` second-arm
+/// observations per first-arm observation. Returns the size of the first
+/// arm; the second needs `ratio` times as many, rounded up.
+pub fn two_prop_sample_size_with(
+    p1: f64,
+    p2: f64,
+    alpha: f64,
+    power: f64,
+    ratio: f64,
+) -> Result<usize, StatsError> {
+    let terms = TwoProportionTerms::new(p1, p2, alpha, ratio)?;
+    if !(power > 0.0 && power < 1.0) {
+        return Err(StatsError::InvalidParameter(
+            "power must be between 0 and 1",
+        ));
+    }
+    let normal = Normal::standard();
+    let root_n = (normal.quantile(1.0 - alpha / 2.0) * terms.null_sd
+        + normal.quantile(power) * terms.alternative_sd)
+        / (p1 - p2).abs();
+    Ok((root_n * root_n).ceil() as usize)
+}
+
+/// Per-observation standard deviations of p1 - p2 in the first arm's units,
+/// under the null (pooled proportion) and under the alternative.
+struct TwoProportionTerms {
+    null_sd: f64,
+    alternative_sd: f64,
+}
+
+impl TwoProportionTerms {
+    fn new(p1: f64, p2: f64, alpha: f64, ratio: f64) -> Result<Self, StatsError> {
+        for p in [p1, p2] {
+            if !(p > 0.0 && p < 1.0) {
+                return Err(StatsError::InvalidParameter(
+                    "proportions must be between 0 and 1",
+                ));
+            }
+        }
+        if p1 == p2 {
+            return Err(StatsError::InvalidParameter(
+                "proportions must differ; equal proportions need infinitely many observations",
+            ));
+        }
+        validate_alpha(alpha)?;
+        if !(ratio > 0.0 && ratio.is_finite()) {
+            return Err(StatsError::InvalidParameter(
+                "allocation ratio must be positive and finite",
+            ));
+        }
+        let pooled = (p1 + ratio * p2) / (1.0 + ratio);
+        Ok(TwoProportionTerms {
+            null_sd: (pooled * (1.0 - pooled) * (1.0 + 1.0 / ratio)).sqrt(),
+            alternative_sd: (p1 * (1.0 - p1) + p2 * (1.0 - p2) / ratio).sqrt(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn t_test_power_test() {
+        // R: pwr.t.test(d, n, sig.level, type, alternative)$power
+        let power = |d, n, alpha, alternative, design| {
+            t_test_power(d, n, alpha, alternative, design).unwrap()
+        };
+        assert_close(
+            power(0.3, 20, 0.05, Alternative::TwoSided, TTestDesign::OneSample),
+            0.247089089968095,
+            1e-10,
+        );
+        assert_close(
+            power(0.5, 30, 0.05, Alternative::TwoSided, TTestDesign::TwoSample),
+            0.477896520760165,
+            1e-10,
+        );
+        assert_close(
+            power(0.5, 30, 0.05, Alternative::Greater, TTestDesign::TwoSample),
+            0.606025327886786,
+            1e-10,
+        );
+        assert_close(
+            power(-0.4, 15, 0.01, Alternative::Less, TTestDesign::Paired),
+            0.178002807955153,
+            1e-10,
+        );
+        assert_close(
+            power(0.0, 10, 0.05, Alternative::TwoSided, TTestDesign::OneSample),
+            0.05,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn t_test_sample_size_test() {
+        // pwr.t.test(d = 0.3, power = 0.8): n = 175.3847 per group
+        assert_eq!(
+            t_test_sample_size(
+                0.3,
+                0.8,
+                0.05,
+                Alternative::TwoSided,
+                TTestDesign::TwoSample
+            ),
+            Ok(176)
+        );
+        // n = 89.14938
+        assert_eq!(
+            t_test_sample_size(
+                0.3,
+                0.8,
+                0.05,
+                Alternative::TwoSided,
+                TTestDesign::OneSample
+            ),
+            Ok(90)
+        );
+        // n = 138.0716
+        assert_eq!(
+            t_test_sample_size(0.3, 0.8, 0.05, Alternative::Greater, TTestDesign::TwoSample),
+            Ok(139)
+        );
+        assert_eq!(
+            t_test_sample_size(3.0, 0.7, 0.05, Alternative::TwoSided, TTestDesign::Paired),
+            Ok(3)
+        );
+        assert_eq!(
+            t_test_sample_size(3.0, 0.2, 0.05, Alternative::TwoSided, TTestDesign::Paired),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn two_prop_power_test() {
+        // R: power.prop.test(n = 100, p1 = 0.5, p2 = 0.6)$power
+        assert_close(
+            two_prop_power(0.5, 0.6, 100, 0.05).unwrap(),
+            0.294127326910698,
+            1e-12,
+        );
+        assert_close(
+            two_prop_power(0.3, 0.2, 200, 0.01).unwrap(),
+            0.394264075934865,
+            1e-12,
+        );
+        assert_close(
+            two_prop_power_with(0.1, 0.15, 500, 0.05, 2.0).unwrap(),
+            0.779434352808322,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn two_prop_sample_size_test() {
+        // R: power.prop.test(p1 = 0.5, p2 = 0.6, power = 0.8)$n = 387.3385
+        assert_eq!(two_prop_sample_size(0.5, 0.6, 0.05, 0.8), Ok(388));
+        // 917.3206
+        assert_eq!(two_prop_sample_size(0.1, 0.15, 0.05, 0.9), Ok(918));
+        // 695.3841 and 668.5558
+        assert_eq!(
+            two_prop_sample_size_with(0.1, 0.15, 0.05, 0.9, 2.0),
+            Ok(696)
+        );
+        assert_eq!(two_prop_sample_size_with(0.3, 0.2, 0.01, 0.8, 0.5), Ok(669));
+
+        let n = two_prop_sample_size(0.5, 0.6, 0.05, 0.8).unwrap();
+        assert!(two_prop_power(0.5, 0.6, n, 0.05).unwrap() >= 0.8);
+        assert!(two_prop_power(0.5, 0.6, n - 1, 0.05).unwrap() < 0.8);
+    }
+
+    #[test]
+    fn two_prop_invalid_input_test() {
+        assert!(matches!(
+            two_prop_sample_size(0.4, 0.4, 0.05, 0.8),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            two_prop_power(0.0, 0.4, 10, 0.05),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            two_prop_sample_size_with(0.3, 0.4, 0.05, 0.8, 0.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn power_invalid_input_test() {
+        assert_eq!(
+            t_test_power(0.5, 1, 0.05, Alternative::TwoSided, TTestDesign::OneSample),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert!(matches!(
+            t_test_sample_size(0.5, 0.8, 0.05, Alternative::Less, TTestDesign::OneSample),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            t_test_sample_size(
+                0.0,
+                0.8,
+                0.05,
+                Alternative::TwoSided,
+                TTestDesign::OneSample
+            ),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            t_test_power(0.5, 10, 1.5, Alternative::TwoSided, TTestDesign::OneSample),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::descriptive::sorted_quantile;
+use crate::distributions::Normal;
+use crate::{pearson_r, QuantileMethod, StatsError};
+
+/// The cumulative probability assigned to the i-th smallest of n values when
+/// plotting them against a theoretical distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlottingPosition {
+    /// (i - 0.5) / n.
+    Hazen,
+    /// (i - 0.375) / (n + 0.25), which makes normal order statistics nearly
+    /// unbiased.
+    Blom,
+}
+
+impl PlottingPosition {
+    fn probability(self, i: usize, n: usize) -> f64 {
+        let (i, n) = (i as f64, n as f64);
+        match self {
+            PlottingPosition::Hazen => (i - 0.5) / n,
+            PlottingPosition::Blom => (i - 0.375) / (n + 0.25),
+        }
+    }
+}
+
+/// Reference line through a normal Q–Q plot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QqLine {
+    pub slope: f64,
+    pub intercept: f64,
+}
+
+/// The points of a normal Q–Q plot: each sorted sample value paired with the
+/// standard normal quantile of its plotting position, as (theoretical,
+/// sample).
+pub fn qq_points(data: &[f64], position: PlottingPosition) -> Result<Vec<(f64, f64)>, StatsError> {
+    let sorted = sorted_finite(data)?;
+    let normal = Normal::standard();
+    let n = sorted.len();
+    Ok(sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| (normal.quantile(position.probability(i + 1, n)), x))
+        .collect())
+}
+
+/// The line through the first and third quartiles of the sample and of the
+/// standard normal, as R's `qqline`. Unlike a least-squares fit it is not
+/// pulled by the tails.
+pub fn qq_line(data: &[f64]) -> Result<QqLine, StatsError> {
+    let sorted = sorted_finite(data)?;
+    let normal = Normal::standard();
+    let (x1, x3) = (normal.quantile(0.25), normal.quantile(0.75));
+    let y1 = sorted_quantile(&sorted, 0.25, QuantileMethod::Linear);
+    let y3 = sorted_quantile(&sorted, 0.75, QuantileMethod::Linear);
+    let slope = (y3 - y1) / (x3 - x1);
+    Ok(QqLine {
+        slope,
+        intercept: y1 - slope * x1,
+    })
+}
+
+/// The correlation between the coordinates of `qq_points`, the
+/// probability-plot correlation coefficient. Values close to 1 indicate the
+/// sample looks normal.
+pub fn probability_plot_correlation(
+    data: &[f64],
+    position: PlottingPosition,
+) -> Result<f64, StatsError> {
+    if data.len() < 3 {
+        return Err(StatsError::InsufficientData {
+            required: 3,
+            actual: data.len(),
+        });
+    }
+    let (theoretical, sample): (Vec<f64>, Vec<f64>) =
+        qq_points(data, position)?.into_iter().unzip();
+    Ok(pearson_r(&theoretical, &sample)?.r)
+}
+
+fn sorted_finite(data: &[f64]) -> Result<Vec<f64>, StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::InvalidParameter("Q-Q plot data must be finite"));
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    Ok(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const Z: [f64; 20] = [
+        0.31, -1.42, 0.77, 1.95, -0.33, 0.12, -0.88, 2.41, 0.54, -0.07, 1.23, -1.71, 0.66, 0.98,
+        -0.25, 1.62, 0.05, -0.61, 1.37, 0.89,
+    ];
+
+    #[test]
+    fn qq_points_test() {
+        let points = qq_points(&Z, PlottingPosition::Hazen).unwrap();
+        assert_eq!(points.len(), 20);
+        assert_close(points[0].0, -1.95996398454005, 1e-13);
+        assert_close(points[9].0, -0.0627067779432138, 1e-14);
+        assert_close(points[19].0, 1.95996398454005, 1e-13);
+        assert_eq!(points[0].1, -1.71);
+        assert_eq!(points[19].1, 2.41);
+        assert!(points
+            .windows(2)
+            .all(|w| w[0].0 < w[1].0 && w[0].1 <= w[1].1));
+
+        let points = qq_points(&Z, PlottingPosition::Blom).unwrap();
+        assert_close(points[0].0, -1.86824165486393, 1e-13);
+        assert_close(points[9].0, -0.0619316234553173, 1e-14);
+    }
+
+    #[test]
+    fn qq_line_test() {
+        // R: qqline(z) passes through quantile(z, c(0.25, 0.75))
+        let line = qq_line(&Z).unwrap();
+        assert_close(line.slope, 0.972957705894301, 1e-13);
+        assert_close(line.intercept, 0.38625, 1e-14);
+    }
+
+    #[test]
+    fn probability_plot_correlation_test() {
+        assert_close(
+            probability_plot_correlation(&Z, PlottingPosition::Hazen).unwrap(),
+            0.997611695276614,
+            1e-13,
+        );
+        assert_close(
+            probability_plot_correlation(&Z, PlottingPosition::Blom).unwrap(),
+            0.997739817329931,
+            1e-13,
+        );
+
+        let skewed = [
+            0.05, 0.11, 0.13, 0.21, 0.26, 0.33, 0.38, 0.41, 0.52, 0.57, 0.66, 0.74, 0.81, 0.95,
+            1.02, 1.13, 1.31, 1.46, 1.62, 1.88, 2.09, 2.37, 2.71, 3.15, 3.88, 4.62, 5.94, 7.83,
+        ];
+        assert!(probability_plot_correlation(&skewed, PlottingPosition::Blom).unwrap() < 0.95);
+    }
+
+    #[test]
+    fn invalid_input_test() {
+        assert_eq!(
+            qq_points(&[], PlottingPosition::Hazen).err(),
+            Some(StatsError::EmptyInput)
+        );
+        assert!(matches!(
+            qq_line(&[1.0, f64::NAN]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            probability_plot_correlation(&[1.0, 2.0], PlottingPosition::Blom).err(),
+            Some(StatsError::InsufficientData {
+                required: 3,
+                actual: 2
+            })
+        );
+    }
+}
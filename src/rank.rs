@@ -0,0 +1,264 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// How `rank` assigns ranks to tied values, following scipy's `rankdata`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RankMethod {
+    /// Tied values share the mean of the ranks they span.
+    #[default]
+    Average,
+    /// Tied values share the lowest rank they span.
+    Min,
+    /// Tied values share the highest rank they span.
+    Max,
+    /// Tied values share a rank, and the next distinct value gets the next
+    /// integer, so the ranks have no gaps.
+    Dense,
+    /// Every value gets a distinct rank; tied values are ranked in the order
+    /// they appear.
+    Ordinal,
+}
+
+/// Ranks of `data` in ascending order starting at 1, with ties resolved by
+/// `method`, in O(n log n). As with scipy's default, any NaN makes every
+/// rank NaN; `rank_with` can drop them or report them instead.
+pub fn rank(data: &[f64], method: RankMethod) -> Vec<f64> {
+    if data.iter().any(|x| x.is_nan()) {
+        return vec![f64::NAN; data.len()];
+    }
+    ranks(data, method)
+}
+
+/// Ranks of `values` in ascending order starting at 1, with tied values
+/// sharing the mean of the ranks they span.
+#[cfg(feature = "std")]
+pub(crate) fn midranks(values: &[f64]) -> Vec<f64> {
+    ranks(values, RankMethod::Average)
+}
+
+fn ranks(values: &[f64], method: RankMethod) -> Vec<f64> {
+    // The sort is stable, so ordinal ranks break ties by position.
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    let mut distinct = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        distinct += 1;
+        for (offset, &index) in order[start..=end].iter().enumerate() {
+            ranks[index] = match method {
+                RankMethod::Average => (start + end) as f64 / 2.0 + 1.0,
+                RankMethod::Min => (start + 1) as f64,
+                RankMethod::Max => (end + 1) as f64,
+                RankMethod::Dense => distinct as f64,
+                RankMethod::Ordinal => (start + offset + 1) as f64,
+            };
+        }
+        start = end + 1;
+    }
+
+    ranks
+}
+
+/// Sum of t³ - t over every run of t tied values in `values`, the term rank
+/// tests use to correct their variance for ties. scipy's `tiecorrect`
+/// reports 1 - tie_term / (n³ - n) instead.
+pub fn tie_term(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let mut sum = 0.0;
+    let mut start = 0;
+    while start < sorted.len() {
+        let mut end = start + 1;
+        while end < sorted.len() && sorted[end] == sorted[start] {
+            end += 1;
+        }
+        let t = (end - start) as f64;
+        sum += t * t * t - t;
+        start = end;
+    }
+
+    sum
+}
+
+/// Tie sums over the runs of a sorted slice: Σ t(t-1)/2, Σ t(t-1)(t-2) and
+/// Σ t(t-1)(2t+5).
+#[cfg(feature = "std")]
+pub(crate) struct TieCounts {
+    pub(crate) pairs: f64,
+    pub(crate) v2: f64,
+    pub(crate) v1: f64,
+}
+
+#[cfg(feature = "std")]
+impl TieCounts {
+    pub(crate) fn from_sorted(sorted: &[f64]) -> Self {
+        let mut counts = TieCounts {
+            pairs: 0.0,
+            v2: 0.0,
+            v1: 0.0,
+        };
+        let mut start = 0;
+        for end in 1..=sorted.len() {
+            if end == sorted.len() || sorted[end] != sorted[start] {
+                let t = (end - start) as f64;
+                counts.pairs += t * (t - 1.0) / 2.0;
+                counts.v2 += t * (t - 1.0) * (t - 2.0);
+                counts.v1 += t * (t - 1.0) * (2.0 * t + 5.0);
+                start = end;
+            }
+        }
+        counts
+    }
+}
+
+/// Sorts `values` in place and returns the number of strictly inverted
+/// pairs. Equal values are never counted since the merge is stable.
+#[cfg(feature = "std")]
+pub(crate) fn merge_sort_exchanges(values: &mut [f64], buffer: &mut [f64]) -> u64 {
+    let n = values.len();
+    if n < 2 {
+        return 0;
+    }
+    let mid = n / 2;
+    let mut exchanges = merge_sort_exchanges(&mut values[..mid], &mut buffer[..mid])
+        + merge_sort_exchanges(&mut values[mid..], &mut buffer[mid..]);
+
+    let (mut i, mut j, mut k) = (0, mid, 0);
+    while i < mid && j < n {
+        if values[j] < values[i] {
+            buffer[k] = values[j];
+            exchanges += (mid - i) as u64;
+            j += 1;
+        } else {
+            buffer[k] = values[i];
+            i += 1;
+        }
+        k += 1;
+    }
+    buffer[k..k + mid - i].copy_from_slice(&values[i..mid]);
+    k += mid - i;
+    buffer[k..k + n - j].copy_from_slice(&values[j..n]);
+    values.copy_from_slice(&buffer[..n]);
+    exchanges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn midranks_test() {
+        assert_eq!(midranks(&[]), Vec::<f64>::new());
+        assert_eq!(midranks(&[3.0, 1.0, 2.0]), vec![3.0, 1.0, 2.0]);
+        assert_eq!(
+            midranks(&[2.0, 1.0, 2.0, 5.0, 2.0, 1.0]),
+            vec![4.0, 1.5, 4.0, 6.0, 4.0, 1.5]
+        );
+    }
+
+    #[test]
+    fn rank_methods_test() {
+        // scipy.stats.rankdata([0, 2, 3, 2], method=...)
+        let data = [0.0, 2.0, 3.0, 2.0];
+        assert_eq!(rank(&data, RankMethod::Average), [1.0, 2.5, 4.0, 2.5]);
+        assert_eq!(rank(&data, RankMethod::Min), [1.0, 2.0, 4.0, 2.0]);
+        assert_eq!(rank(&data, RankMethod::Max), [1.0, 3.0, 4.0, 3.0]);
+        assert_eq!(rank(&data, RankMethod::Dense), [1.0, 2.0, 3.0, 2.0]);
+        assert_eq!(rank(&data, RankMethod::Ordinal), [1.0, 2.0, 4.0, 3.0]);
+
+        // scipy.stats.rankdata([3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5], method=...)
+        let data = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0, 5.0];
+        assert_eq!(
+            rank(&data, RankMethod::Average),
+            [4.5, 1.5, 6.0, 1.5, 8.0, 11.0, 3.0, 10.0, 8.0, 4.5, 8.0]
+        );
+        assert_eq!(
+            rank(&data, RankMethod::Min),
+            [4.0, 1.0, 6.0, 1.0, 7.0, 11.0, 3.0, 10.0, 7.0, 4.0, 7.0]
+        );
+        assert_eq!(
+            rank(&data, RankMethod::Max),
+            [5.0, 2.0, 6.0, 2.0, 9.0, 11.0, 3.0, 10.0, 9.0, 5.0, 9.0]
+        );
+        assert_eq!(
+            rank(&data, RankMethod::Dense),
+            [3.0, 1.0, 4.0, 1.0, 5.0, 7.0, 2.0, 6.0, 5.0, 3.0, 5.0]
+        );
+        assert_eq!(
+            rank(&data, RankMethod::Ordinal),
+            [4.0, 1.0, 6.0, 2.0, 7.0, 11.0, 3.0, 10.0, 8.0, 5.0, 9.0]
+        );
+        assert_eq!(rank(&[], RankMethod::Dense), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn long_tie_runs_test() {
+        // scipy.stats.rankdata([2, 2, 2, 2, 2, 1, 1, 1, 3, 2, 2, 2, 1, 3], method=...)
+        let data = [
+            2.0, 2.0, 2.0, 2.0, 2.0, 1.0, 1.0, 1.0, 3.0, 2.0, 2.0, 2.0, 1.0, 3.0,
+        ];
+        assert_eq!(
+            rank(&data, RankMethod::Average),
+            [8.5, 8.5, 8.5, 8.5, 8.5, 2.5, 2.5, 2.5, 13.5, 8.5, 8.5, 8.5, 2.5, 13.5]
+        );
+        assert_eq!(
+            rank(&data, RankMethod::Min),
+            [5.0, 5.0, 5.0, 5.0, 5.0, 1.0, 1.0, 1.0, 13.0, 5.0, 5.0, 5.0, 1.0, 13.0]
+        );
+        assert_eq!(
+            rank(&data, RankMethod::Max),
+            [12.0, 12.0, 12.0, 12.0, 12.0, 4.0, 4.0, 4.0, 14.0, 12.0, 12.0, 12.0, 4.0, 14.0]
+        );
+        assert_eq!(
+            rank(&data, RankMethod::Dense),
+            [2.0, 2.0, 2.0, 2.0, 2.0, 1.0, 1.0, 1.0, 3.0, 2.0, 2.0, 2.0, 1.0, 3.0]
+        );
+        assert_eq!(
+            rank(&data, RankMethod::Ordinal),
+            [5.0, 6.0, 7.0, 8.0, 9.0, 1.0, 2.0, 3.0, 13.0, 10.0, 11.0, 12.0, 4.0, 14.0]
+        );
+        // Runs of 8, 4 and 2: (512 - 8) + (64 - 4) + (8 - 2)
+        assert_eq!(tie_term(&data), 570.0);
+
+        let mut rng = crate::rng::Rng::new(116);
+        let n: usize = 10_000;
+        let data: Vec<f64> = (0..n).map(|_| rng.below(7) as f64).collect();
+        let average = rank(&data, RankMethod::Average);
+        let min = rank(&data, RankMethod::Min);
+        let max = rank(&data, RankMethod::Max);
+        let dense = rank(&data, RankMethod::Dense);
+        let mut ordinal = rank(&data, RankMethod::Ordinal);
+        assert_eq!(average.iter().sum::<f64>(), (n * (n + 1) / 2) as f64);
+        for i in 0..n {
+            assert!(min[i] <= average[i] && average[i] <= max[i]);
+            assert_eq!(average[i], (min[i] + max[i]) / 2.0);
+            assert_eq!(dense[i], data[i] + 1.0);
+        }
+        ordinal.sort_by(f64::total_cmp);
+        assert!(ordinal
+            .iter()
+            .enumerate()
+            .all(|(i, &r)| r == (i + 1) as f64));
+    }
+
+    #[test]
+    fn rank_nan_test() {
+        let ranks = rank(&[1.0, f64::NAN, 0.5], RankMethod::Average);
+        assert_eq!(ranks.len(), 3);
+        assert!(ranks.iter().all(|r| r.is_nan()));
+    }
+
+    #[test]
+    fn tie_term_test() {
+        assert_eq!(tie_term(&[1.0, 2.0, 3.0]), 0.0);
+        // One pair and one triple: (8 - 2) + (27 - 3)
+        assert_eq!(tie_term(&[2.0, 1.0, 2.0, 5.0, 2.0, 1.0]), 30.0);
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::{RunningStatistics, SampleStatistics, StatsError};
+
+/// Why `SampleStatistics::from_reader` or `from_delimited_reader` failed.
+/// Line numbers start at 1 and count blank lines.
+#[derive(Debug)]
+pub enum ReadError {
+    /// Reading from the source failed.
+    Io(io::Error),
+    /// The value on this line is not a number.
+    Parse { line: usize },
+    /// This line has too few fields to contain the requested column.
+    MissingColumn { line: usize },
+    /// The values were read but cannot be summarized, e.g. there were fewer
+    /// than two.
+    Stats(StatsError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "read failed: {error}"),
+            ReadError::Parse { line } => write!(f, "line {line} is not a number"),
+            ReadError::MissingColumn { line } => {
+                write!(f, "line {line} does not have the requested column")
+            }
+            ReadError::Stats(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(error) => Some(error),
+            ReadError::Stats(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+impl From<StatsError> for ReadError {
+    fn from(error: StatsError) -> Self {
+        ReadError::Stats(error)
+    }
+}
+
+impl SampleStatistics {
+    /// Statistics of one number per line of `reader`, e.g. a `BufReader`
+    /// over a file. Surrounding whitespace is trimmed and blank lines are
+    /// skipped. The values are summarized as they are read with
+    /// `RunningStatistics`, so the input never has to fit in memory.
+    ///
+    /// Fails at the first line that does not parse, and, as `try_from_array`
+    /// does, when there are fewer than two values.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ReadError> {
+        summarize_lines(reader, |text, line| {
+            text.parse().map_err(|_| ReadError::Parse { line })
+        })
+    }
+
+    /// `from_reader` for simple delimited text such as CSV: the number in
+    /// field `column` (counting from 0) of each line split on `delimiter`.
+    /// Quoting is not supported. To skip a header, read its line from the
+    /// reader first and pass `&mut reader`; line numbers in errors then
+    /// count from the line after it.
+    pub fn from_delimited_reader(
+        reader: impl BufRead,
+        delimiter: char,
+        column: usize,
+    ) -> Result<Self, ReadError> {
+        summarize_lines(reader, |text, line| {
+            let field = text
+                .split(delimiter)
+                .nth(column)
+                .ok_or(ReadError::MissingColumn { line })?;
+            field.trim().parse().map_err(|_| ReadError::Parse { line })
+        })
+    }
+}
+
+/// Feeds the value `parse` extracts from each non-blank line of `reader`
+/// into running statistics, reusing one line buffer throughout.
+fn summarize_lines(
+    mut reader: impl BufRead,
+    parse: impl Fn(&str, usize) -> Result<f64, ReadError>,
+) -> Result<SampleStatistics, ReadError> {
+    let mut running = RunningStatistics::new();
+    let mut buffer = String::new();
+    let mut line = 0;
+    loop {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            break;
+        }
+        line += 1;
+        let text = buffer.trim();
+        if !text.is_empty() {
+            running.push(parse(text, line)?);
+        }
+    }
+    if running.n() < 2 {
+        return Err(ReadError::Stats(StatsError::InsufficientData {
+            required: 2,
+            actual: running.n(),
+        }));
+    }
+    Ok(running.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, GetStatistics};
+    use std::fs::File;
+    use std::io::BufReader;
+
+    fn fixture(name: &str) -> BufReader<File> {
+        let path = format!("{}/tests/data/{name}", env!("CARGO_MANIFEST_DIR"));
+        BufReader::new(File::open(path).unwrap())
+    }
+
+    /// `unwrap_err`, which needs `SampleStatistics: Debug`.
+    fn expect_error(result: Result<SampleStatistics, ReadError>) -> ReadError {
+        match result {
+            Ok(_) => panic!("expected a read error"),
+            Err(error) => error,
+        }
+    }
+
+    #[test]
+    fn from_reader_test() {
+        let samp = SampleStatistics::from_reader(fixture("clean.txt")).unwrap();
+        let expected = SampleStatistics::from_array(&[3.4, 6.7, 2.3, 1.1, -0.5, 12.25, 4.0]);
+        assert_eq!(samp.n, expected.n);
+        assert_close(samp.sample_mean, expected.sample_mean, 1e-15);
+        assert_close(samp.standard_deviation, expected.standard_deviation, 1e-14);
+
+        let samp = SampleStatistics::from_reader("1\n2\n\n  3  \n4".as_bytes()).unwrap();
+        assert_eq!(samp.n, 4);
+        assert_eq!(samp.sample_mean, 2.5);
+    }
+
+    #[test]
+    fn malformed_line_test() {
+        let error = expect_error(SampleStatistics::from_reader(fixture("malformed.txt")));
+        assert!(matches!(error, ReadError::Parse { line: 4 }), "{error:?}");
+        assert_eq!(error.to_string(), "line 4 is not a number");
+    }
+
+    #[test]
+    fn empty_input_test() {
+        let error = expect_error(SampleStatistics::from_reader(fixture("empty.txt")));
+        assert!(matches!(
+            error,
+            ReadError::Stats(StatsError::InsufficientData {
+                required: 2,
+                actual: 0
+            })
+        ));
+        let error = expect_error(SampleStatistics::from_reader("\n  \n7.5\n".as_bytes()));
+        assert!(matches!(
+            error,
+            ReadError::Stats(StatsError::InsufficientData { actual: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn delimited_reader_test() {
+        let mut reader = fixture("measurements.csv");
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        assert_eq!(header.trim(), "id,height,weight");
+
+        let samp = SampleStatistics::from_delimited_reader(&mut reader, ',', 2).unwrap();
+        let expected = SampleStatistics::from_array(&[61.2, 72.5, 58.9, 80.1, 66.0]);
+        assert_eq!(samp.n, 5);
+        assert_close(samp.sample_mean, expected.sample_mean, 1e-12);
+        assert_close(samp.standard_deviation, expected.standard_deviation, 1e-12);
+
+        let data = "a\t1.5\nb\t2.5\nc\n";
+        let error = expect_error(SampleStatistics::from_delimited_reader(
+            data.as_bytes(),
+            '\t',
+            1,
+        ));
+        assert!(matches!(error, ReadError::MissingColumn { line: 3 }));
+
+        // Without skipping it, the header is the first parse failure.
+        let error = expect_error(SampleStatistics::from_delimited_reader(
+            fixture("measurements.csv"),
+            ',',
+            1,
+        ));
+        assert!(matches!(error, ReadError::Parse { line: 1 }));
+    }
+
+    #[test]
+    fn io_error_test() {
+        struct Failing;
+        impl io::Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk on fire"))
+            }
+        }
+        let error = expect_error(SampleStatistics::from_reader(BufReader::new(Failing)));
+        assert!(matches!(error, ReadError::Io(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
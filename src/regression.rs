@@ -0,0 +1,840 @@
+use crate::confidence::validate_confidence;
+use crate::correlation::validate_pairs;
+use crate::distributions::{FisherF, StudentsT};
+use crate::linalg::least_squares;
+use crate::{mean, sample_standard_deviation, ConfidenceInterval, HypothesisTest, StatsError};
+
+/// An estimated regression coefficient with its t-test of zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coefficient {
+    pub estimate: f64,
+    pub standard_error: f64,
+    pub t: f64,
+    /// Two-sided p-value on the residual degrees of freedom.
+    pub p_value: f64,
+}
+
+impl Coefficient {
+    fn new(estimate: f64, standard_error: f64, df: f64) -> Self {
+        let t = estimate / standard_error;
+        Coefficient {
+            estimate,
+            standard_error,
+            t,
+            p_value: StudentsT::new(df).two_tailed(t),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegressionResult {
+    pub intercept: Coefficient,
+    pub slope: Coefficient,
+    pub r_squared: f64,
+    pub adjusted_r_squared: f64,
+    /// sqrt(SSE / df), R's "residual standard error".
+    pub residual_standard_error: f64,
+    /// Residual degrees of freedom, n - 2.
+    pub df: f64,
+    /// Overall F statistic on 1 and `df` degrees of freedom.
+    pub f: f64,
+    pub f_p_value: f64,
+    x: Vec<f64>,
+    residuals: Vec<f64>,
+}
+
+impl RegressionResult {
+    /// Fitted value intercept + slope * x.
+    pub fn predict(&self, x: f64) -> f64 {
+        self.intercept.estimate + self.slope.estimate * x
+    }
+
+    /// Observed minus fitted values, in the order of the input.
+    pub fn residuals(&self) -> &[f64] {
+        &self.residuals
+    }
+
+    /// Interval for the mean of y at `x0`, the height of the true
+    /// regression line there: fit ± t s sqrt(h), with leverage
+    /// h = 1 / n + (x0 - x̄)² / Sxx, as R's
+    /// `predict(fit, data.frame(x = x0), interval = "confidence")`. It
+    /// narrows towards zero width as n grows.
+    pub fn confidence_interval_mean_response(
+        &self,
+        x0: f64,
+        confidence: f64,
+    ) -> Result<ConfidenceInterval, StatsError> {
+        self.response_interval(x0, confidence, 0.0)
+    }
+
+    /// Interval for a single new observation of y at `x0`: fit ±
+    /// t s sqrt(1 + h), as R's `interval = "prediction"`. It adds the scatter
+    /// of one observation about the line to the uncertainty of the line, so
+    /// it is always wider than `confidence_interval_mean_response` and never
+    /// narrower than about ± t s.
+    pub fn prediction_interval(
+        &self,
+        x0: f64,
+        confidence: f64,
+    ) -> Result<ConfidenceInterval, StatsError> {
+        self.response_interval(x0, confidence, 1.0)
+    }
+
+    /// fit ± t s sqrt(`scatter` + h) at `x0`.
+    fn response_interval(
+        &self,
+        x0: f64,
+        confidence: f64,
+        scatter: f64,
+    ) -> Result<ConfidenceInterval, StatsError> {
+        validate_confidence(confidence)?;
+        let x_mean = mean(&self.x);
+        let sxx: f64 = self.x.iter().map(|x| (x - x_mean).powi(2)).sum();
+        let leverage = 1.0 / self.x.len() as f64 + (x0 - x_mean).powi(2) / sxx;
+        let t = StudentsT::new(self.df).quantile(0.5 + confidence / 2.0);
+        let margin = t * self.residual_standard_error * (scatter + leverage).sqrt();
+        let fit = self.predict(x0);
+        Ok(ConfidenceInterval {
+            lower: fit - margin,
+            upper: fit + margin,
+            point_estimate: fit,
+            confidence,
+        })
+    }
+}
+
+impl HypothesisTest for RegressionResult {
+    fn statistic(&self) -> f64 {
+        self.f
+    }
+
+    fn p_value(&self) -> f64 {
+        self.f_p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "regression F-test"
+    }
+}
+
+/// Ordinary least squares fit of y = intercept + slope * x, reporting the
+/// same inference as R's `summary(lm(y ~ x))`.
+pub fn linear_regression(x: &[f64], y: &[f64]) -> Result<RegressionResult, StatsError> {
+    validate_pairs(x, y, 3)?;
+
+    let x_mean = mean(x);
+    let y_mean = mean(y);
+    let mut sxx = 0.0;
+    let mut sxy = 0.0;
+    let mut syy = 0.0;
+    for (a, b) in x.iter().zip(y) {
+        sxx += (a - x_mean) * (a - x_mean);
+        sxy += (a - x_mean) * (b - y_mean);
+        syy += (b - y_mean) * (b - y_mean);
+    }
+    if sxx == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "regression is undefined when x is constant",
+        ));
+    }
+
+    let slope = sxy / sxx;
+    let intercept = y_mean - slope * x_mean;
+    let residuals: Vec<f64> = x
+        .iter()
+        .zip(y)
+        .map(|(a, b)| b - intercept - slope * a)
+        .collect();
+    let sse: f64 = residuals.iter().map(|r| r * r).sum();
+
+    let n = x.len() as f64;
+    let df = n - 2.0;
+    let sigma_sq = sse / df;
+    let r_squared = 1.0 - sse / syy;
+    let f = (syy - sse) / sigma_sq;
+
+    Ok(RegressionResult {
+        intercept: Coefficient::new(
+            intercept,
+            (sigma_sq * (1.0 / n + x_mean * x_mean / sxx)).sqrt(),
+            df,
+        ),
+        slope: Coefficient::new(slope, (sigma_sq / sxx).sqrt(), df),
+        r_squared,
+        adjusted_r_squared: 1.0 - (1.0 - r_squared) * (n - 1.0) / df,
+        residual_standard_error: sigma_sq.sqrt(),
+        df,
+        f,
+        f_p_value: FisherF::new(1.0, df).sf(f),
+        x: x.to_vec(),
+        residuals,
+    })
+}
+
+/// Observations whose standardized residual exceeds this in absolute value
+/// are reported as large residuals.
+const LARGE_RESIDUAL_THRESHOLD: f64 = 2.0;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegressionDiagnostics {
+    /// Σ (e_t - e_{t-1})² / Σ e_t². Near 2 for independent errors, toward 0
+    /// under positive and toward 4 under negative autocorrelation.
+    pub durbin_watson: f64,
+    pub residual_mean: f64,
+    /// Sample standard deviation of the residuals.
+    pub residual_sd: f64,
+    /// e_i / (σ sqrt(1 - h_i)) for a simple regression, as R's `rstandard`;
+    /// e_i / `residual_sd` when built from raw residuals.
+    pub standardized_residuals: Vec<f64>,
+    /// Hat values h_i = 1/n + (x_i - x̄)² / Sxx. Only available for a simple
+    /// regression.
+    pub leverage: Option<Vec<f64>>,
+    /// Indices with leverage above 2p/n = 4/n.
+    pub high_leverage: Vec<usize>,
+    /// Indices whose standardized residual exceeds 2 in absolute value.
+    pub large_residuals: Vec<usize>,
+}
+
+impl RegressionDiagnostics {
+    /// Diagnostics for a simple linear regression, including leverage.
+    pub fn from_regression(fit: &RegressionResult) -> Self {
+        let n = fit.x.len() as f64;
+        let x_mean = mean(&fit.x);
+        let sxx: f64 = fit.x.iter().map(|v| (v - x_mean) * (v - x_mean)).sum();
+        let leverage: Vec<f64> = fit
+            .x
+            .iter()
+            .map(|v| 1.0 / n + (v - x_mean) * (v - x_mean) / sxx)
+            .collect();
+        let standardized_residuals = fit
+            .residuals
+            .iter()
+            .zip(&leverage)
+            .map(|(e, h)| e / (fit.residual_standard_error * (1.0 - h).sqrt()))
+            .collect();
+        let high_leverage = indices_where(&leverage, |h| h > 4.0 / n);
+
+        let mut diagnostics = Self::summarize(&fit.residuals, standardized_residuals);
+        diagnostics.leverage = Some(leverage);
+        diagnostics.high_leverage = high_leverage;
+        diagnostics
+    }
+
+    /// Diagnostics from residuals alone, in observation order. Leverage is
+    /// unknown without the design, so `leverage` is `None`.
+    pub fn from_residuals(residuals: &[f64]) -> Result<Self, StatsError> {
+        if residuals.len() < 2 {
+            return Err(StatsError::InsufficientData {
+                required: 2,
+                actual: residuals.len(),
+            });
+        }
+        let sd = sample_standard_deviation(residuals);
+        let standardized_residuals = residuals.iter().map(|e| e / sd).collect();
+        Ok(Self::summarize(residuals, standardized_residuals))
+    }
+
+    fn summarize(residuals: &[f64], standardized_residuals: Vec<f64>) -> Self {
+        let squared_differences: f64 = residuals.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum();
+        let sse: f64 = residuals.iter().map(|e| e * e).sum();
+        let large_residuals = indices_where(&standardized_residuals, |r| {
+            r.abs() > LARGE_RESIDUAL_THRESHOLD
+        });
+
+        RegressionDiagnostics {
+            durbin_watson: squared_differences / sse,
+            residual_mean: mean(residuals),
+            residual_sd: sample_standard_deviation(residuals),
+            standardized_residuals,
+            leverage: None,
+            high_leverage: Vec::new(),
+            large_residuals,
+        }
+    }
+}
+
+fn indices_where(values: &[f64], predicate: impl Fn(f64) -> bool) -> Vec<usize> {
+    values
+        .iter()
+        .enumerate()
+        .filter(|(_, &v)| predicate(v))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultipleRegressionResult {
+    /// The intercept followed by one coefficient per predictor, in input
+    /// order.
+    pub coefficients: Vec<Coefficient>,
+    pub r_squared: f64,
+    pub adjusted_r_squared: f64,
+    pub residual_standard_error: f64,
+    /// Residual degrees of freedom, n - p - 1 for p predictors.
+    pub df: f64,
+    /// Overall F statistic on p and `df` degrees of freedom.
+    pub f: f64,
+    pub f_p_value: f64,
+    residuals: Vec<f64>,
+}
+
+impl MultipleRegressionResult {
+    /// Fitted value for one observation's predictors. Panics if `predictors`
+    /// does not have one value per fitted slope.
+    pub fn predict(&self, predictors: &[f64]) -> f64 {
+        assert_eq!(predictors.len() + 1, self.coefficients.len());
+        self.coefficients[0].estimate
+            + self.coefficients[1..]
+                .iter()
+                .zip(predictors)
+                .map(|(c, x)| c.estimate * x)
+                .sum::<f64>()
+    }
+
+    /// Observed minus fitted values, in the order of the input.
+    pub fn residuals(&self) -> &[f64] {
+        &self.residuals
+    }
+}
+
+impl HypothesisTest for MultipleRegressionResult {
+    fn statistic(&self) -> f64 {
+        self.f
+    }
+
+    fn p_value(&self) -> f64 {
+        self.f_p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "regression F-test"
+    }
+}
+
+/// Ordinary least squares with an intercept, where `x[i]` holds the
+/// predictors of observation i. The fit is solved by QR decomposition, and
+/// rank-deficient designs (a constant or collinear predictor) are rejected
+/// with `InvalidParameter`.
+pub fn multiple_regression(
+    x: &[Vec<f64>],
+    y: &[f64],
+) -> Result<MultipleRegressionResult, StatsError> {
+    if x.len() != y.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: x.len(),
+            right: y.len(),
+        });
+    }
+    let Some(first) = x.first() else {
+        return Err(StatsError::EmptyInput);
+    };
+    let predictors = first.len();
+    if predictors == 0 {
+        return Err(StatsError::InvalidParameter(
+            "at least one predictor is required",
+        ));
+    }
+    if let Some(row) = x.iter().find(|row| row.len() != predictors) {
+        return Err(StatsError::MismatchedLengths {
+            left: predictors,
+            right: row.len(),
+        });
+    }
+    if x.len() < predictors + 2 {
+        return Err(StatsError::InsufficientData {
+            required: predictors + 2,
+            actual: x.len(),
+        });
+    }
+
+    let mut columns = vec![vec![1.0; x.len()]];
+    columns.extend((0..predictors).map(|j| x.iter().map(|row| row[j]).collect::<Vec<f64>>()));
+    let fit = least_squares(columns, y)?;
+
+    let residuals: Vec<f64> = x
+        .iter()
+        .zip(y)
+        .map(|(row, yi)| {
+            yi - fit.coefficients[0]
+                - fit.coefficients[1..]
+                    .iter()
+                    .zip(row)
+                    .map(|(b, xi)| b * xi)
+                    .sum::<f64>()
+        })
+        .collect();
+    let sse: f64 = residuals.iter().map(|r| r * r).sum();
+    let y_mean = mean(y);
+    let sst: f64 = y.iter().map(|v| (v - y_mean) * (v - y_mean)).sum();
+
+    let n = x.len() as f64;
+    let p = predictors as f64;
+    let df = n - p - 1.0;
+    let sigma_sq = sse / df;
+    let r_squared = 1.0 - sse / sst;
+    let f = (sst - sse) / p / sigma_sq;
+
+    let coefficients = fit
+        .coefficients
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| Coefficient::new(b, (sigma_sq * fit.unscaled_covariance[i][i]).sqrt(), df))
+        .collect();
+
+    Ok(MultipleRegressionResult {
+        coefficients,
+        r_squared,
+        adjusted_r_squared: 1.0 - (1.0 - r_squared) * (n - 1.0) / df,
+        residual_standard_error: sigma_sq.sqrt(),
+        df,
+        f,
+        f_p_value: FisherF::new(p, df).sf(f),
+        residuals,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolynomialRegressionResult {
+    /// Coefficients of 1, x, x², ... in the original units of x, constant
+    /// first.
+    pub coefficients: Vec<f64>,
+    pub r_squared: f64,
+    pub adjusted_r_squared: f64,
+    center: f64,
+    scale: f64,
+    scaled_coefficients: Vec<f64>,
+}
+
+impl PolynomialRegressionResult {
+    /// Fitted value at `x`, evaluated in the internal scaled coordinates so
+    /// it stays accurate far from the origin.
+    pub fn predict(&self, x: f64) -> f64 {
+        horner(&self.scaled_coefficients, (x - self.center) / self.scale)
+    }
+}
+
+/// Least squares fit of a polynomial of the given degree.
+///
+/// x is mapped onto [-1, 1] before building the Vandermonde design, which
+/// keeps degrees up to 5 or so well conditioned; the coefficients are then
+/// expanded back into powers of the original x.
+pub fn polynomial_regression(
+    x: &[f64],
+    y: &[f64],
+    degree: usize,
+) -> Result<PolynomialRegressionResult, StatsError> {
+    if degree == 0 {
+        return Err(StatsError::InvalidParameter(
+            "polynomial degree must be at least 1",
+        ));
+    }
+    validate_pairs(x, y, degree + 2)?;
+
+    let min = x.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = x.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if min == max {
+        return Err(StatsError::InvalidParameter(
+            "regression is undefined when x is constant",
+        ));
+    }
+    let center = (max + min) / 2.0;
+    let scale = (max - min) / 2.0;
+    let u: Vec<f64> = x.iter().map(|v| (v - center) / scale).collect();
+
+    let mut columns = vec![vec![1.0; x.len()]];
+    for k in 1..=degree {
+        let column = columns[k - 1].iter().zip(&u).map(|(a, b)| a * b).collect();
+        columns.push(column);
+    }
+    let scaled_coefficients = least_squares(columns, y)?.coefficients;
+
+    // Σ a_k ((x - c) / s)^k = Σ_j x^j Σ_{k >= j} a_k C(k, j) (-c)^(k - j) / s^k
+    let mut coefficients = vec![0.0; degree + 1];
+    for (k, a) in scaled_coefficients.iter().enumerate() {
+        let mut binomial = 1.0;
+        for (j, coefficient) in coefficients.iter_mut().enumerate().take(k + 1) {
+            *coefficient += a * binomial * (-center).powi((k - j) as i32) / scale.powi(k as i32);
+            binomial *= (k - j) as f64 / (j + 1) as f64;
+        }
+    }
+
+    let sse: f64 = u
+        .iter()
+        .zip(y)
+        .map(|(a, b)| (b - horner(&scaled_coefficients, *a)).powi(2))
+        .sum();
+    let y_mean = mean(y);
+    let sst: f64 = y.iter().map(|v| (v - y_mean) * (v - y_mean)).sum();
+    let n = x.len() as f64;
+    let r_squared = 1.0 - sse / sst;
+
+    Ok(PolynomialRegressionResult {
+        coefficients,
+        r_squared,
+        adjusted_r_squared: 1.0 - (1.0 - r_squared) * (n - 1.0) / (n - degree as f64 - 1.0),
+        center,
+        scale,
+        scaled_coefficients,
+    })
+}
+
+fn horner(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const ANSCOMBE_X: [f64; 11] = [10.0, 8.0, 13.0, 9.0, 11.0, 14.0, 6.0, 4.0, 12.0, 7.0, 5.0];
+    const ANSCOMBE_Y: [f64; 11] = [
+        8.04, 6.95, 7.58, 8.81, 8.33, 9.96, 7.24, 4.26, 10.84, 4.82, 5.68,
+    ];
+
+    #[test]
+    fn linear_regression_test() {
+        // R: summary(lm(y1 ~ x1, data = anscombe))
+        let fit = linear_regression(&ANSCOMBE_X, &ANSCOMBE_Y).unwrap();
+        assert_close(fit.intercept.estimate, 3.00009090909091, 1e-12);
+        assert_close(fit.intercept.standard_error, 1.12474679080864, 1e-12);
+        assert_close(fit.intercept.t, 2.66734782762436, 1e-12);
+        assert_close(fit.intercept.p_value, 0.0257340513991625, 1e-13);
+        assert_close(fit.slope.estimate, 0.500090909090909, 1e-13);
+        assert_close(fit.slope.standard_error, 0.117905500595634, 1e-13);
+        assert_close(fit.slope.t, 4.24145528889283, 1e-12);
+        assert_close(fit.slope.p_value, 0.0021696288730788, 1e-14);
+        assert_close(fit.r_squared, 0.666542459508775, 1e-13);
+        assert_close(fit.adjusted_r_squared, 0.629491621676417, 1e-13);
+        assert_close(fit.residual_standard_error, 1.23660332272632, 1e-12);
+        assert_eq!(fit.df, 9.0);
+        assert_close(fit.f, 17.989942967677, 1e-11);
+        assert_close(fit.f_p_value, fit.slope.p_value, 1e-14);
+    }
+
+    #[test]
+    fn linear_regression_predict_and_residuals_test() {
+        let fit = linear_regression(&ANSCOMBE_X, &ANSCOMBE_Y).unwrap();
+        assert_close(fit.predict(20.0), 13.0019090909091, 1e-12);
+
+        let residuals = fit.residuals();
+        assert_eq!(residuals.len(), 11);
+        assert_close(residuals[0], 0.039, 1e-13);
+        assert_close(residuals[10], 0.179454545454545, 1e-13);
+        assert_close(residuals.iter().sum::<f64>(), 0.0, 1e-13);
+        for ((x, y), r) in ANSCOMBE_X.iter().zip(&ANSCOMBE_Y).zip(residuals) {
+            assert_close(fit.predict(*x) + r, *y, 1e-13);
+        }
+    }
+
+    #[test]
+    fn regression_intervals_test() {
+        // R: predict(lm(y1 ~ x1, data = anscombe), data.frame(x1 = x0),
+        //            interval = "confidence") and interval = "prediction"
+        let fit = linear_regression(&ANSCOMBE_X, &ANSCOMBE_Y).unwrap();
+        // x0 = 9 is the mean of x; 0 and 20 extrapolate beyond [4, 14]
+        let expected = [
+            (
+                9.0,
+                6.657463949878939,
+                8.344354231939242,
+                4.5791294155864515,
+                10.422688766231731,
+            ),
+            (
+                4.0,
+                3.4225131743175177,
+                6.578395916591574,
+                1.7887111347900353,
+                8.212197956119056,
+            ),
+            (
+                14.0,
+                8.423422265226609,
+                11.579305007500665,
+                6.789620225699126,
+                13.213107047028146,
+            ),
+            (
+                20.0,
+                9.949150314384392,
+                16.05466786743379,
+                8.861289220304005,
+                17.142528961514177,
+            ),
+            (
+                0.0,
+                0.4557368999288409,
+                5.544444918252977,
+                -0.7813286774057001,
+                6.7815104955875185,
+            ),
+        ];
+        for (x0, mean_lower, mean_upper, lower, upper) in expected {
+            let mean_response = fit.confidence_interval_mean_response(x0, 0.95).unwrap();
+            assert_close(mean_response.lower, mean_lower, 1e-12);
+            assert_close(mean_response.upper, mean_upper, 1e-12);
+            assert_eq!(mean_response.point_estimate, fit.predict(x0));
+            let prediction = fit.prediction_interval(x0, 0.95).unwrap();
+            assert_close(prediction.lower, lower, 1e-12);
+            assert_close(prediction.upper, upper, 1e-12);
+            assert!(prediction.lower < mean_response.lower);
+            assert!(prediction.upper > mean_response.upper);
+        }
+
+        let prediction = fit.prediction_interval(20.0, 0.99).unwrap();
+        assert_close(prediction.lower, 7.053456949294842, 1e-11);
+        assert_close(prediction.upper, 18.95036123252334, 1e-11);
+        assert!(matches!(
+            fit.prediction_interval(5.0, 1.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn linear_regression_invalid_input_test() {
+        assert!(matches!(
+            linear_regression(&[2.0, 2.0, 2.0], &[1.0, 2.0, 3.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            linear_regression(&[1.0, 2.0], &[1.0, 2.0]).err(),
+            Some(StatsError::InsufficientData {
+                required: 3,
+                actual: 2
+            })
+        );
+    }
+
+    fn three_predictor_fixture() -> (Vec<Vec<f64>>, Vec<f64>) {
+        let x = vec![
+            vec![4.2, 1.0, 12.0],
+            vec![5.1, 0.0, 15.5],
+            vec![3.8, 1.0, 10.2],
+            vec![6.0, 2.0, 18.1],
+            vec![4.9, 1.0, 14.0],
+            vec![5.5, 3.0, 16.3],
+            vec![3.2, 0.0, 9.8],
+            vec![6.4, 2.0, 19.5],
+            vec![4.0, 2.0, 11.7],
+            vec![5.8, 1.0, 17.2],
+            vec![4.6, 3.0, 13.1],
+            vec![5.2, 0.0, 15.0],
+        ];
+        let y = vec![
+            22.1, 25.3, 19.8, 31.2, 24.6, 29.9, 17.5, 33.4, 22.8, 28.7, 25.1, 24.9,
+        ];
+        (x, y)
+    }
+
+    #[test]
+    fn multiple_regression_test() {
+        // statsmodels: OLS(y, add_constant(x)).fit()
+        let (x, y) = three_predictor_fixture();
+        let fit = multiple_regression(&x, &y).unwrap();
+        let expected = [
+            (
+                4.26041720698524,
+                0.512142716409076,
+                8.31880854785449,
+                3.29258372060507e-5,
+            ),
+            (
+                0.62860932805983,
+                0.667361682463723,
+                0.941932005654221,
+                0.373797287837767,
+            ),
+            (
+                1.08383369084412,
+                0.0851407424823807,
+                12.7299064965097,
+                1.36535871384502e-6,
+            ),
+            (
+                1.15971164098352,
+                0.205205187454607,
+                5.65147331492317,
+                0.000480602081671088,
+            ),
+        ];
+        assert_eq!(fit.coefficients.len(), 4);
+        for (c, (estimate, se, t, p)) in fit.coefficients.iter().zip(expected) {
+            assert_close(c.estimate, estimate, 1e-11);
+            assert_close(c.standard_error, se, 1e-12);
+            assert_close(c.t, t, 1e-10);
+            assert_close(c.p_value, p, 1e-12 * p.max(1e-3));
+        }
+        assert_close(fit.r_squared, 0.997356153052264, 1e-13);
+        assert_close(fit.adjusted_r_squared, 0.996364710446863, 1e-13);
+        assert_close(fit.residual_standard_error, 0.282315064506773, 1e-13);
+        assert_eq!(fit.df, 8.0);
+        assert_close(fit.f, 1005.96458899289, 1e-8);
+        assert_close(fit.f_p_value, 1.20111902306874e-10, 1e-20);
+        assert_close(fit.predict(&[5.0, 1.0, 14.0]), 24.7232605118978, 1e-11);
+        assert_close(fit.residuals().iter().sum::<f64>(), 0.0, 1e-12);
+    }
+
+    #[test]
+    fn multiple_regression_matches_simple_test() {
+        let x: Vec<Vec<f64>> = ANSCOMBE_X.iter().map(|&v| vec![v]).collect();
+        let multiple = multiple_regression(&x, &ANSCOMBE_Y).unwrap();
+        let simple = linear_regression(&ANSCOMBE_X, &ANSCOMBE_Y).unwrap();
+        for (m, s) in multiple
+            .coefficients
+            .iter()
+            .zip([simple.intercept, simple.slope])
+        {
+            assert_close(m.estimate, s.estimate, 1e-13);
+            assert_close(m.standard_error, s.standard_error, 1e-13);
+            assert_close(m.p_value, s.p_value, 1e-13);
+        }
+        assert_close(multiple.f, simple.f, 1e-11);
+    }
+
+    #[test]
+    fn multiple_regression_invalid_input_test() {
+        let (mut x, y) = three_predictor_fixture();
+        for row in x.iter_mut() {
+            row[2] = 2.0 * row[0] - row[1];
+        }
+        assert!(matches!(
+            multiple_regression(&x, &y),
+            Err(StatsError::InvalidParameter(_))
+        ));
+
+        x[3].pop();
+        assert_eq!(
+            multiple_regression(&x, &y).err(),
+            Some(StatsError::MismatchedLengths { left: 3, right: 2 })
+        );
+        assert_eq!(
+            multiple_regression(&x[..4], &y[..4]).err(),
+            Some(StatsError::MismatchedLengths { left: 3, right: 2 })
+        );
+        let (x, y) = three_predictor_fixture();
+        assert_eq!(
+            multiple_regression(&x[..4], &y[..4]).err(),
+            Some(StatsError::InsufficientData {
+                required: 5,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn polynomial_regression_degree_one_test() {
+        let poly = polynomial_regression(&ANSCOMBE_X, &ANSCOMBE_Y, 1).unwrap();
+        let line = linear_regression(&ANSCOMBE_X, &ANSCOMBE_Y).unwrap();
+        assert_close(poly.coefficients[0], line.intercept.estimate, 1e-13);
+        assert_close(poly.coefficients[1], line.slope.estimate, 1e-14);
+        assert_close(poly.r_squared, line.r_squared, 1e-14);
+        assert_close(poly.adjusted_r_squared, line.adjusted_r_squared, 1e-14);
+        assert_close(poly.predict(20.0), line.predict(20.0), 1e-13);
+    }
+
+    #[test]
+    fn polynomial_regression_exact_fit_test() {
+        let truth = [1.0, -2.0, 0.5, 0.1, -0.02, 0.001];
+        let evaluate = |x: f64| truth.iter().rev().fold(0.0, |acc, c| acc * x + c);
+        let x: Vec<f64> = (0..15).map(|i| i as f64 * 0.7).collect();
+        let y: Vec<f64> = x.iter().map(|&v| evaluate(v)).collect();
+
+        let fit = polynomial_regression(&x, &y, 5).unwrap();
+        for (c, t) in fit.coefficients.iter().zip(truth) {
+            assert_close(*c, t, 1e-9);
+        }
+        assert_close(fit.r_squared, 1.0, 1e-14);
+        assert_close(fit.predict(3.3), evaluate(3.3), 1e-10);
+
+        // Far from the origin the design is still well conditioned
+        let shifted: Vec<f64> = x.iter().map(|v| v + 1000.0).collect();
+        let fit = polynomial_regression(&shifted, &y, 5).unwrap();
+        assert_close(fit.predict(1003.3), evaluate(3.3), 1e-8);
+    }
+
+    #[test]
+    fn polynomial_regression_invalid_input_test() {
+        assert!(matches!(
+            polynomial_regression(&ANSCOMBE_X, &ANSCOMBE_Y, 0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            polynomial_regression(&ANSCOMBE_X[..4], &ANSCOMBE_Y[..4], 3).err(),
+            Some(StatsError::InsufficientData {
+                required: 5,
+                actual: 4
+            })
+        );
+        // Only two distinct x values cannot pin down a quadratic
+        let x = [1.0, 1.0, 2.0, 2.0, 1.0];
+        assert!(matches!(
+            polynomial_regression(&x, &[1.0, 2.0, 3.0, 4.0, 5.0], 2),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn regression_diagnostics_test() {
+        // statsmodels.stats.stattools.durbin_watson on the OLS residuals;
+        // the errors follow a slow cycle, so they are positively autocorrelated
+        let x: Vec<f64> = (1..=15).map(f64::from).collect();
+        let y = [
+            3.3, 4.1, 4.4, 4.4, 4.3, 4.3, 4.5, 5.4, 6.4, 7.5, 8.4, 9.2, 9.1, 8.7, 8.6,
+        ];
+        let fit = linear_regression(&x, &y).unwrap();
+        let diagnostics = RegressionDiagnostics::from_regression(&fit);
+        assert_close(diagnostics.durbin_watson, 0.467062908703807, 1e-13);
+        assert_close(diagnostics.residual_mean, 0.0, 1e-14);
+        assert_close(diagnostics.residual_sd, 0.732658935416923, 1e-13);
+
+        let leverage = diagnostics.leverage.as_ref().unwrap();
+        assert_close(leverage[0], 0.241666666666667, 1e-14);
+        assert_close(leverage[7], 0.0666666666666667, 1e-14);
+        assert_close(leverage.iter().sum::<f64>(), 2.0, 1e-13);
+        // R: rstandard(lm(y ~ x))
+        assert_close(
+            diagnostics.standardized_residuals[0],
+            0.493378880911791,
+            1e-12,
+        );
+        assert_close(
+            diagnostics.standardized_residuals[6],
+            -1.6589050607068,
+            1e-12,
+        );
+        assert!(diagnostics.high_leverage.is_empty());
+        assert!(diagnostics.large_residuals.is_empty());
+
+        let raw = RegressionDiagnostics::from_residuals(fit.residuals()).unwrap();
+        assert_eq!(raw.durbin_watson, diagnostics.durbin_watson);
+        assert!(raw.leverage.is_none());
+    }
+
+    #[test]
+    fn regression_diagnostics_flags_test() {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 30.0];
+        let y = [1.1, 1.9, 3.2, 3.9, 5.1, 12.0, 7.1, 7.8, 9.2, 9.9, 30.5];
+        let fit = linear_regression(&x, &y).unwrap();
+        let diagnostics = RegressionDiagnostics::from_regression(&fit);
+        assert_eq!(diagnostics.high_leverage, vec![10]);
+        assert_eq!(diagnostics.large_residuals, vec![5]);
+
+        assert_eq!(
+            RegressionDiagnostics::from_residuals(&[1.0]).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use std::fmt;
+
+use crate::distributions::FisherF;
+use crate::format::{significant, write_test_line};
+use crate::{mean, sample_variance, ConfidenceInterval, HypothesisTest, StatsError};
+
+/// Confidence level of the interval reported by `icc`.
+const ICC_CONFIDENCE: f64 = 0.95;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CronbachAlphaResult {
+    pub alpha: f64,
+    /// Alpha recomputed without each item in turn, in item order. An item
+    /// whose removal raises alpha is weakening the scale. NaN for a two-item
+    /// scale, where one item is left.
+    pub alpha_if_deleted: Vec<f64>,
+}
+
+/// Cronbach's alpha, (k / (k - 1)) (1 - Σ item variances / total score
+/// variance), for k items each scored by the same respondents, as the raw
+/// alpha of R's `psych::alpha`.
+pub fn cronbachs_alpha(items: &[&[f64]]) -> Result<CronbachAlphaResult, StatsError> {
+    if items.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: items.len(),
+        });
+    }
+    let respondents = items[0].len();
+    if let Some(item) = items.iter().find(|item| item.len() != respondents) {
+        return Err(StatsError::MismatchedLengths {
+            left: respondents,
+            right: item.len(),
+        });
+    }
+    if respondents < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: respondents,
+        });
+    }
+
+    let alpha_if_deleted = (0..items.len())
+        .map(|deleted| {
+            let kept: Vec<&[f64]> = items
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != deleted)
+                .map(|(_, &item)| item)
+                .collect();
+            alpha(&kept)
+        })
+        .collect();
+    Ok(CronbachAlphaResult {
+        alpha: alpha(items),
+        alpha_if_deleted,
+    })
+}
+
+fn alpha(items: &[&[f64]]) -> f64 {
+    let k = items.len() as f64;
+    let totals: Vec<f64> = (0..items[0].len())
+        .map(|respondent| items.iter().map(|item| item[respondent]).sum())
+        .collect();
+    let item_variance: f64 = items.iter().map(|item| sample_variance(item)).sum();
+    let total_variance = sample_variance(&totals);
+    k / (k - 1.0) * (1.0 - item_variance / total_variance)
+}
+
+/// Single-measure intraclass correlation models in the Shrout–Fleiss
+/// taxonomy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IccModel {
+    /// ICC(1,1): each subject rated by a different random set of raters;
+    /// one-way random effects.
+    OneWay,
+    /// ICC(2,1): the same random sample of raters rates every subject;
+    /// two-way random effects, absolute agreement.
+    TwoWayRandom,
+    /// ICC(3,1): the raters are the only ones of interest; two-way mixed
+    /// effects, consistency.
+    TwoWayMixed,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IccResult {
+    pub icc: f64,
+    /// F statistic for the null hypothesis ICC = 0.
+    pub f: f64,
+    pub df1: f64,
+    pub df2: f64,
+    pub p_value: f64,
+    /// 95% interval, using the Satterthwaite degrees of freedom for the
+    /// absolute agreement model.
+    pub confidence_interval: ConfidenceInterval,
+}
+
+impl fmt::Display for IccResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ICC = {}, ", significant(self.icc, 3))?;
+        write_test_line(f, "F", &[self.df1, self.df2], self.f, self.p_value)
+    }
+}
+
+impl HypothesisTest for IccResult {
+    fn statistic(&self) -> f64 {
+        self.f
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "intraclass correlation F-test"
+    }
+}
+
+/// Intraclass correlation for a complete table of ratings with one row per
+/// subject and one column per rater, computed from the two-way ANOVA mean
+/// squares as R's `irr::icc` does for single measures.
+pub fn icc(data: &[Vec<f64>], model: IccModel) -> Result<IccResult, StatsError> {
+    let n = data.len();
+    let k = data.first().map_or(0, |row| row.len());
+    if n < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: n,
+        });
+    }
+    if k < 2 {
+        return Err(StatsError::InvalidParameter(
+            "ICC needs at least two raters",
+        ));
+    }
+    if let Some(row) = data.iter().find(|row| row.len() != k) {
+        return Err(StatsError::MismatchedLengths {
+            left: k,
+            right: row.len(),
+        });
+    }
+
+    let (n_f, k_f) = (n as f64, k as f64);
+    let subject_means: Vec<f64> = data.iter().map(|row| mean(row)).collect();
+    let rater_means: Vec<f64> = (0..k)
+        .map(|j| data.iter().map(|row| row[j]).sum::<f64>() / n_f)
+        .collect();
+    let grand_mean = mean(&subject_means);
+    let ss_total: f64 = data
+        .iter()
+        .flatten()
+        .map(|x| (x - grand_mean).powi(2))
+        .sum();
+    let ss_subjects = k_f
+        * subject_means
+            .iter()
+            .map(|m| (m - grand_mean).powi(2))
+            .sum::<f64>();
+    let ss_raters = n_f
+        * rater_means
+            .iter()
+            .map(|m| (m - grand_mean).powi(2))
+            .sum::<f64>();
+    let ms_subjects = ss_subjects / (n_f - 1.0);
+    let ms_raters = ss_raters / (k_f - 1.0);
+    let ms_within = (ss_total - ss_subjects) / (n_f * (k_f - 1.0));
+    let ms_error = (ss_total - ss_subjects - ss_raters) / ((n_f - 1.0) * (k_f - 1.0));
+
+    let half_alpha = (1.0 - ICC_CONFIDENCE) / 2.0;
+    // Bounds for the one-way and consistency models from the F interval
+    let f_interval = |f: f64, df1: f64, df2: f64| {
+        let f_lower = f / FisherF::new(df1, df2).quantile(1.0 - half_alpha);
+        let f_upper = f * FisherF::new(df2, df1).quantile(1.0 - half_alpha);
+        (
+            (f_lower - 1.0) / (f_lower + k_f - 1.0),
+            (f_upper - 1.0) / (f_upper + k_f - 1.0),
+        )
+    };
+
+    let (icc, f, df1, df2, (lower, upper)) = match model {
+        IccModel::OneWay => {
+            let icc = (ms_subjects - ms_within) / (ms_subjects + (k_f - 1.0) * ms_within);
+            let f = ms_subjects / ms_within;
+            let (df1, df2) = (n_f - 1.0, n_f * (k_f - 1.0));
+            (icc, f, df1, df2, f_interval(f, df1, df2))
+        }
+        IccModel::TwoWayMixed => {
+            let icc = (ms_subjects - ms_error) / (ms_subjects + (k_f - 1.0) * ms_error);
+            let f = ms_subjects / ms_error;
+            let (df1, df2) = (n_f - 1.0, (n_f - 1.0) * (k_f - 1.0));
+            (icc, f, df1, df2, f_interval(f, df1, df2))
+        }
+        IccModel::TwoWayRandom => {
+            let icc = (ms_subjects - ms_error)
+                / (ms_subjects + (k_f - 1.0) * ms_error + k_f * (ms_raters - ms_error) / n_f);
+            let f = ms_subjects / ms_error;
+            let (df1, df2) = (n_f - 1.0, (n_f - 1.0) * (k_f - 1.0));
+
+            let a = k_f * icc / (n_f * (1.0 - icc));
+            let b = 1.0 + k_f * icc * (n_f - 1.0) / (n_f * (1.0 - icc));
+            let v = (a * ms_raters + b * ms_error).powi(2)
+                / ((a * ms_raters).powi(2) / (k_f - 1.0) + (b * ms_error).powi(2) / df2);
+            let f_lower = FisherF::new(df1, v).quantile(1.0 - half_alpha);
+            let f_upper = FisherF::new(v, df1).quantile(1.0 - half_alpha);
+            let spread = k_f * ms_raters + (k_f * n_f - k_f - n_f) * ms_error;
+            let lower =
+                n_f * (ms_subjects - f_lower * ms_error) / (f_lower * spread + n_f * ms_subjects);
+            let upper =
+                n_f * (f_upper * ms_subjects - ms_error) / (spread + n_f * f_upper * ms_subjects);
+            (icc, f, df1, df2, (lower, upper))
+        }
+    };
+
+    Ok(IccResult {
+        icc,
+        f,
+        df1,
+        df2,
+        p_value: FisherF::new(df1, df2).sf(f),
+        confidence_interval: ConfidenceInterval {
+            lower,
+            upper,
+            point_estimate: icc,
+            confidence: ICC_CONFIDENCE,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const ITEMS: [[f64; 8]; 4] = [
+        [4.0, 5.0, 3.0, 4.0, 2.0, 5.0, 3.0, 4.0],
+        [3.0, 5.0, 4.0, 4.0, 2.0, 4.0, 3.0, 5.0],
+        [4.0, 4.0, 3.0, 5.0, 1.0, 5.0, 2.0, 4.0],
+        [2.0, 3.0, 2.0, 3.0, 3.0, 2.0, 4.0, 3.0],
+    ];
+
+    #[test]
+    fn cronbachs_alpha_test() {
+        // R: psych::alpha(items)$total$raw_alpha and $alpha.drop$raw_alpha
+        let items: Vec<&[f64]> = ITEMS.iter().map(|item| item.as_slice()).collect();
+        let result = cronbachs_alpha(&items).unwrap();
+        assert_close(result.alpha, 0.67175572519084, 1e-14);
+        let expected = [
+            0.328125,
+            0.397058823529412,
+            0.411764705882353,
+            0.895833333333333,
+        ];
+        for (actual, expected) in result.alpha_if_deleted.iter().zip(expected) {
+            assert_close(*actual, expected, 1e-14);
+        }
+
+        let result = cronbachs_alpha(&items[..2]).unwrap();
+        assert!(result.alpha_if_deleted.iter().all(|a| a.is_nan()));
+    }
+
+    #[test]
+    fn cronbachs_alpha_invalid_input_test() {
+        assert_eq!(
+            cronbachs_alpha(&[&[1.0, 2.0, 3.0], &[1.0, 2.0]]).err(),
+            Some(StatsError::MismatchedLengths { left: 3, right: 2 })
+        );
+        assert_eq!(
+            cronbachs_alpha(&[&[1.0, 2.0]]).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+
+    fn shrout_fleiss() -> Vec<Vec<f64>> {
+        // Shrout and Fleiss (1979), six subjects rated by four judges
+        vec![
+            vec![9.0, 2.0, 5.0, 8.0],
+            vec![6.0, 1.0, 3.0, 2.0],
+            vec![8.0, 4.0, 6.0, 8.0],
+            vec![7.0, 1.0, 2.0, 6.0],
+            vec![10.0, 5.0, 6.0, 9.0],
+            vec![6.0, 2.0, 4.0, 7.0],
+        ]
+    }
+
+    #[test]
+    fn icc_test() {
+        // R: irr::icc(data, model, type, unit = "single")
+        let data = shrout_fleiss();
+        let result = icc(&data, IccModel::OneWay).unwrap();
+        assert_close(result.icc, 0.165741768405475, 1e-14);
+        assert_close(result.f, 1.79467849223947, 1e-13);
+        assert_eq!((result.df1, result.df2), (5.0, 18.0));
+        assert_close(result.p_value, 0.16476880834464, 1e-12);
+        assert_close(result.confidence_interval.lower, -0.132932324874751, 1e-10);
+        assert_close(result.confidence_interval.upper, 0.722560062328121, 1e-10);
+
+        let result = icc(&data, IccModel::TwoWayRandom).unwrap();
+        assert_close(result.icc, 0.289763779527559, 1e-14);
+        assert_close(result.f, 11.0272479564033, 1e-12);
+        assert_eq!((result.df1, result.df2), (5.0, 15.0));
+        assert_close(result.p_value, 0.000134566516484337, 1e-15);
+        assert_close(result.confidence_interval.lower, 0.018786513374712, 1e-10);
+        assert_close(result.confidence_interval.upper, 0.761084369648953, 1e-10);
+
+        let result = icc(&data, IccModel::TwoWayMixed).unwrap();
+        assert_close(result.icc, 0.714840714840715, 1e-14);
+        assert_close(result.p_value, 0.000134566516484337, 1e-15);
+        assert_close(result.confidence_interval.lower, 0.342464765033925, 1e-10);
+        assert_close(result.confidence_interval.upper, 0.94585825995536, 1e-10);
+    }
+
+    #[test]
+    fn icc_invalid_input_test() {
+        let mut data = shrout_fleiss();
+        data[3].pop();
+        assert_eq!(
+            icc(&data, IccModel::OneWay).err(),
+            Some(StatsError::MismatchedLengths { left: 4, right: 3 })
+        );
+        assert!(matches!(
+            icc(&[vec![1.0], vec![2.0]], IccModel::TwoWayMixed),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
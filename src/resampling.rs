@@ -0,0 +1,561 @@
+use std::fmt;
+
+use crate::confidence::validate_confidence;
+use crate::descriptive::sorted_quantile;
+use crate::distributions::Normal;
+use crate::format::write_test_line;
+use crate::rng::Rng;
+use crate::{
+    mean, sample_standard_deviation, Alternative, ConfidenceInterval, HypothesisTest,
+    QuantileMethod, StatsError,
+};
+
+/// Combined sample sizes up to this are permuted exhaustively rather than
+/// sampled.
+pub const PERMUTATION_EXACT_MAX_SAMPLE_SIZE: usize = 12;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BootstrapResult {
+    /// The statistic on the original data.
+    pub estimate: f64,
+    /// Standard deviation of the bootstrap replicates.
+    pub standard_error: f64,
+    /// Percentile interval: quantiles alpha / 2 and 1 - alpha / 2 of the
+    /// replicates.
+    pub percentile: ConfidenceInterval,
+    /// Bias-corrected and accelerated interval, with the acceleration
+    /// estimated by the jackknife. NaN when every replicate falls on one side
+    /// of the estimate, since the bias correction is then infinite.
+    pub bca: ConfidenceInterval,
+    pub iterations: usize,
+}
+
+/// Nonparametric bootstrap confidence intervals for an arbitrary statistic,
+/// following scipy's `bootstrap`. Resampling is driven by an internal
+/// xoshiro256** generator, so a fixed `seed` always gives the same result.
+pub fn bootstrap_ci<F: Fn(&[f64]) -> f64>(
+    data: &[f64],
+    statistic: F,
+    iterations: usize,
+    confidence: f64,
+    seed: u64,
+) -> Result<BootstrapResult, StatsError> {
+    if data.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: data.len(),
+        });
+    }
+    if iterations == 0 {
+        return Err(StatsError::InvalidParameter(
+            "at least one bootstrap iteration is required",
+        ));
+    }
+    validate_confidence(confidence)?;
+
+    let estimate = statistic(data);
+    let mut rng = Rng::new(seed);
+    let mut resample = vec![0.0; data.len()];
+    let mut replicates: Vec<f64> = (0..iterations)
+        .map(|_| {
+            for value in resample.iter_mut() {
+                *value = data[rng.below(data.len())];
+            }
+            statistic(&resample)
+        })
+        .collect();
+    replicates.sort_by(f64::total_cmp);
+
+    let alpha = 1.0 - confidence;
+    let percentile = ConfidenceInterval {
+        lower: sorted_quantile(&replicates, alpha / 2.0, QuantileMethod::Linear),
+        upper: sorted_quantile(&replicates, 1.0 - alpha / 2.0, QuantileMethod::Linear),
+        point_estimate: estimate,
+        confidence,
+    };
+
+    // Bias correction from the share of replicates below the estimate,
+    // counting ties as half
+    let below = replicates.iter().filter(|&&r| r < estimate).count() as f64;
+    let at_most = replicates.iter().filter(|&&r| r <= estimate).count() as f64;
+    let normal = Normal::standard();
+    let z0 = normal.quantile((below + at_most) / (2.0 * iterations as f64));
+
+    // Acceleration from the skewness of the jackknife estimates
+    let jackknife: Vec<f64> = (0..data.len())
+        .map(|i| {
+            let left_out: Vec<f64> = data
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &v)| v)
+                .collect();
+            statistic(&left_out)
+        })
+        .collect();
+    let jackknife_mean = mean(&jackknife);
+    let (mut sum_sq, mut sum_cubed) = (0.0, 0.0);
+    for value in &jackknife {
+        let d = jackknife_mean - value;
+        sum_sq += d * d;
+        sum_cubed += d * d * d;
+    }
+    let acceleration = if sum_sq == 0.0 {
+        0.0
+    } else {
+        sum_cubed / (6.0 * sum_sq.powf(1.5))
+    };
+
+    let adjusted = |level: f64| {
+        let z = z0 + normal.quantile(level);
+        normal.cdf(z0 + z / (1.0 - acceleration * z))
+    };
+    let bca = ConfidenceInterval {
+        lower: sorted_quantile(&replicates, adjusted(alpha / 2.0), QuantileMethod::Linear),
+        upper: sorted_quantile(
+            &replicates,
+            adjusted(1.0 - alpha / 2.0),
+            QuantileMethod::Linear,
+        ),
+        point_estimate: estimate,
+        confidence,
+    };
+
+    Ok(BootstrapResult {
+        estimate,
+        standard_error: sample_standard_deviation(&replicates),
+        percentile,
+        bca,
+        iterations,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoSampleBootstrapResult {
+    /// The statistic on the original samples.
+    pub estimate: f64,
+    /// Standard deviation of the bootstrap replicates.
+    pub standard_error: f64,
+    /// Percentile interval: quantiles alpha / 2 and 1 - alpha / 2 of the
+    /// replicates.
+    pub percentile: ConfidenceInterval,
+    /// Two-sided achieved significance level against a statistic of zero:
+    /// twice the smaller share of replicates at or below zero and at or
+    /// above it, capped at 1. It is below 1 - `confidence` about when the
+    /// percentile interval excludes zero, and can be exactly zero when no
+    /// replicate reaches it.
+    pub achieved_significance: f64,
+    pub iterations: usize,
+}
+
+/// Bootstrap of a statistic comparing two groups, such as a difference in
+/// medians. Each replicate resamples `sample_1` and `sample_2` independently
+/// with replacement, keeping their sizes, and evaluates
+/// `statistic(resample_1, resample_2)`. As with `bootstrap_ci`, a fixed
+/// `seed` always gives the same result. Fewer than two `iterations` leave
+/// no standard error and return `StatsError::InvalidParameter`.
+pub fn bootstrap_two_sample<F: Fn(&[f64], &[f64]) -> f64>(
+    sample_1: &[f64],
+    sample_2: &[f64],
+    statistic: F,
+    iterations: usize,
+    confidence: f64,
+    seed: u64,
+) -> Result<TwoSampleBootstrapResult, StatsError> {
+    for sample in [sample_1, sample_2] {
+        if sample.len() < 2 {
+            return Err(StatsError::InsufficientData {
+                required: 2,
+                actual: sample.len(),
+            });
+        }
+    }
+    // The standard error is the standard deviation of the replicates
+    if iterations < 2 {
+        return Err(StatsError::InvalidParameter(
+            "at least two bootstrap iterations are required",
+        ));
+    }
+    validate_confidence(confidence)?;
+
+    let estimate = statistic(sample_1, sample_2);
+    let mut rng = Rng::new(seed);
+    let mut resample_1 = vec![0.0; sample_1.len()];
+    let mut resample_2 = vec![0.0; sample_2.len()];
+    let mut replicates: Vec<f64> = (0..iterations)
+        .map(|_| {
+            for value in resample_1.iter_mut() {
+                *value = sample_1[rng.below(sample_1.len())];
+            }
+            for value in resample_2.iter_mut() {
+                *value = sample_2[rng.below(sample_2.len())];
+            }
+            statistic(&resample_1, &resample_2)
+        })
+        .collect();
+    replicates.sort_by(f64::total_cmp);
+
+    let alpha = 1.0 - confidence;
+    let percentile = ConfidenceInterval {
+        lower: sorted_quantile(&replicates, alpha / 2.0, QuantileMethod::Linear),
+        upper: sorted_quantile(&replicates, 1.0 - alpha / 2.0, QuantileMethod::Linear),
+        point_estimate: estimate,
+        confidence,
+    };
+
+    let at_most_zero = replicates.iter().filter(|&&r| r <= 0.0).count();
+    let at_least_zero = replicates.iter().filter(|&&r| r >= 0.0).count();
+    let achieved_significance =
+        (2.0 * at_most_zero.min(at_least_zero) as f64 / iterations as f64).min(1.0);
+
+    Ok(TwoSampleBootstrapResult {
+        estimate,
+        standard_error: sample_standard_deviation(&replicates),
+        percentile,
+        achieved_significance,
+        iterations,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PermutationTestResult {
+    /// mean(sample_1) - mean(sample_2) on the observed labels.
+    pub observed_difference: f64,
+    pub p_value: f64,
+    /// Label assignments evaluated: every one when `exact`, otherwise the
+    /// requested number of random shuffles.
+    pub permutations: usize,
+    pub exact: bool,
+}
+
+impl fmt::Display for PermutationTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "difference", &[], self.observed_difference, self.p_value)
+    }
+}
+
+impl HypothesisTest for PermutationTestResult {
+    fn statistic(&self) -> f64 {
+        self.observed_difference
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "permutation test"
+    }
+}
+
+/// Permutation test for a difference in means, computing
+/// mean(sample_1) - mean(sample_2) under relabelings of the pooled data.
+///
+/// When the combined size is at most `PERMUTATION_EXACT_MAX_SAMPLE_SIZE`
+/// every assignment of labels is enumerated and `iterations` and `seed` are
+/// unused. Otherwise `iterations` random shuffles are drawn and the p-value
+/// is (b + 1) / (iterations + 1), where b counts shuffles at least as extreme
+/// as the observed difference, so it is never exactly zero.
+pub fn permutation_test(
+    sample_1: &[f64],
+    sample_2: &[f64],
+    iterations: usize,
+    seed: u64,
+    alternative: Alternative,
+) -> Result<PermutationTestResult, StatsError> {
+    if sample_1.is_empty() || sample_2.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    let pooled: Vec<f64> = sample_1.iter().chain(sample_2).copied().collect();
+    let n1 = sample_1.len();
+    let n2 = sample_2.len() as f64;
+    let total: f64 = pooled.iter().sum();
+    let difference = |sum_1: f64| sum_1 / n1 as f64 - (total - sum_1) / n2;
+
+    let observed_difference = mean(sample_1) - mean(sample_2);
+    // Allow for rounding so relabelings tied with the observed value count
+    let slack = 1e-12 * observed_difference.abs();
+    let is_extreme = |d: f64| match alternative {
+        Alternative::Greater => d >= observed_difference - slack,
+        Alternative::Less => d <= observed_difference + slack,
+        Alternative::TwoSided => d.abs() >= observed_difference.abs() - slack,
+    };
+
+    if pooled.len() <= PERMUTATION_EXACT_MAX_SAMPLE_SIZE {
+        let mut permutations = 0;
+        let mut extreme = 0;
+        for mask in 0u32..1 << pooled.len() {
+            if mask.count_ones() as usize != n1 {
+                continue;
+            }
+            let sum_1: f64 = (0..pooled.len())
+                .filter(|i| mask & 1 << i != 0)
+                .map(|i| pooled[i])
+                .sum();
+            permutations += 1;
+            if is_extreme(difference(sum_1)) {
+                extreme += 1;
+            }
+        }
+        return Ok(PermutationTestResult {
+            observed_difference,
+            p_value: extreme as f64 / permutations as f64,
+            permutations,
+            exact: true,
+        });
+    }
+
+    if iterations == 0 {
+        return Err(StatsError::InvalidParameter(
+            "at least one permutation is required",
+        ));
+    }
+    let mut rng = Rng::new(seed);
+    let mut shuffled = pooled;
+    let mut extreme = 0;
+    for _ in 0..iterations {
+        rng.shuffle(&mut shuffled);
+        if is_extreme(difference(shuffled[..n1].iter().sum())) {
+            extreme += 1;
+        }
+    }
+
+    Ok(PermutationTestResult {
+        observed_difference,
+        p_value: (extreme + 1) as f64 / (iterations + 1) as f64,
+        permutations: iterations,
+        exact: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, mean_confidence_interval};
+
+    fn normal_sample(n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = Rng::new(seed);
+        let normal = Normal::new(10.0, 2.0);
+        (0..n).map(|_| normal.quantile(rng.next_f64())).collect()
+    }
+
+    #[test]
+    fn bootstrap_mean_matches_t_interval_test() {
+        let data = normal_sample(80, 3);
+        let result = bootstrap_ci(&data, mean, 4000, 0.95, 11).unwrap();
+        let t_interval = mean_confidence_interval(&data, 0.95).unwrap();
+        let width = t_interval.upper - t_interval.lower;
+
+        assert_eq!(result.estimate, mean(&data));
+        for interval in [result.percentile, result.bca] {
+            assert!((interval.lower - t_interval.lower).abs() < 0.1 * width);
+            assert!((interval.upper - t_interval.upper).abs() < 0.1 * width);
+            assert!(interval.contains(result.estimate));
+        }
+        let standard_error = sample_standard_deviation(&data) / (data.len() as f64).sqrt();
+        assert!((result.standard_error / standard_error - 1.0).abs() < 0.1);
+        assert_eq!(result.iterations, 4000);
+    }
+
+    #[test]
+    fn bootstrap_is_deterministic_test() {
+        let data = normal_sample(30, 5);
+        let median = |values: &[f64]| crate::median(values).unwrap();
+        let first = bootstrap_ci(&data, median, 500, 0.9, 99).unwrap();
+        let second = bootstrap_ci(&data, median, 500, 0.9, 99).unwrap();
+        assert_eq!(first.percentile, second.percentile);
+        assert_eq!(first.bca, second.bca);
+
+        let other = bootstrap_ci(&data, median, 500, 0.9, 100).unwrap();
+        assert_ne!(first.percentile, other.percentile);
+    }
+
+    #[test]
+    fn bootstrap_invalid_input_test() {
+        assert_eq!(
+            bootstrap_ci(&[1.0], mean, 100, 0.95, 0).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert!(matches!(
+            bootstrap_ci(&[1.0, 2.0], mean, 0, 0.95, 0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            bootstrap_ci(&[1.0, 2.0], mean, 10, 1.0, 0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    fn mean_difference(sample_1: &[f64], sample_2: &[f64]) -> f64 {
+        mean(sample_1) - mean(sample_2)
+    }
+
+    #[test]
+    fn bootstrap_two_sample_matches_welch_interval_test() {
+        let sample_1 = normal_sample(60, 7);
+        let sample_2: Vec<f64> = normal_sample(45, 8).iter().map(|v| 2.0 * v - 9.0).collect();
+        let result =
+            bootstrap_two_sample(&sample_1, &sample_2, mean_difference, 4000, 0.95, 13).unwrap();
+        let samp_1 = crate::GetStatistics::from_array(&sample_1);
+        let samp_2 = crate::GetStatistics::from_array(&sample_2);
+        let welch = crate::mean_diff_confidence_interval(
+            &samp_1,
+            &samp_2,
+            0.95,
+            crate::TTestKind::UnequalVariance,
+        )
+        .unwrap();
+        let width = welch.upper - welch.lower;
+
+        assert_eq!(result.estimate, mean_difference(&sample_1, &sample_2));
+        assert!((result.percentile.lower - welch.lower).abs() < 0.1 * width);
+        assert!((result.percentile.upper - welch.upper).abs() < 0.1 * width);
+        assert!(result.percentile.contains(result.estimate));
+        let standard_error = crate::welch_t_test(&samp_1, &samp_2).standard_error;
+        assert!((result.standard_error / standard_error - 1.0).abs() < 0.1);
+        assert_eq!(result.iterations, 4000);
+    }
+
+    #[test]
+    fn bootstrap_two_sample_significance_test() {
+        let sample_1 = normal_sample(40, 21);
+        let shifted: Vec<f64> = normal_sample(40, 22).iter().map(|v| v + 3.0).collect();
+        let result =
+            bootstrap_two_sample(&sample_1, &shifted, mean_difference, 2000, 0.95, 1).unwrap();
+        assert!(result.percentile.upper < 0.0);
+        assert!(result.achieved_significance < 0.05);
+
+        // Identical samples put zero in the middle of the replicates
+        let result =
+            bootstrap_two_sample(&sample_1, &sample_1, mean_difference, 2000, 0.95, 1).unwrap();
+        assert_eq!(result.estimate, 0.0);
+        assert!(result.percentile.contains(0.0));
+        assert!(result.achieved_significance > 0.5);
+        assert!(result.achieved_significance <= 1.0);
+    }
+
+    #[test]
+    fn bootstrap_two_sample_is_deterministic_test() {
+        let sample_1 = normal_sample(30, 31);
+        let sample_2 = normal_sample(25, 32);
+        let median_difference =
+            |a: &[f64], b: &[f64]| crate::median(a).unwrap() - crate::median(b).unwrap();
+        let first =
+            bootstrap_two_sample(&sample_1, &sample_2, median_difference, 500, 0.9, 99).unwrap();
+        let second =
+            bootstrap_two_sample(&sample_1, &sample_2, median_difference, 500, 0.9, 99).unwrap();
+        assert_eq!(first.percentile, second.percentile);
+        assert_eq!(first.standard_error, second.standard_error);
+        assert_eq!(first.achieved_significance, second.achieved_significance);
+
+        let other =
+            bootstrap_two_sample(&sample_1, &sample_2, median_difference, 500, 0.9, 100).unwrap();
+        assert_ne!(first.percentile, other.percentile);
+    }
+
+    #[test]
+    fn bootstrap_two_sample_invalid_input_test() {
+        assert_eq!(
+            bootstrap_two_sample(&[1.0, 2.0], &[1.0], mean_difference, 100, 0.95, 0).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        for iterations in [0, 1] {
+            assert!(matches!(
+                bootstrap_two_sample(
+                    &[1.0, 2.0],
+                    &[3.0, 4.0],
+                    mean_difference,
+                    iterations,
+                    0.95,
+                    0
+                ),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        assert!(matches!(
+            bootstrap_two_sample(&[1.0, 2.0], &[3.0, 4.0], mean_difference, 10, 0.0, 0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn permutation_test_exact_test() {
+        // Of the 20 ways to split the six values in two, only the observed
+        // one gives a difference as low as -3
+        let result =
+            permutation_test(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], 0, 0, Alternative::Less).unwrap();
+        assert!(result.exact);
+        assert_eq!(result.permutations, 20);
+        assert_close(result.observed_difference, -3.0, 1e-15);
+        assert_close(result.p_value, 0.05, 1e-15);
+
+        let two_sided = permutation_test(
+            &[1.0, 2.0, 3.0],
+            &[4.0, 5.0, 6.0],
+            0,
+            0,
+            Alternative::TwoSided,
+        )
+        .unwrap();
+        assert_close(two_sided.p_value, 0.1, 1e-15);
+        let greater = permutation_test(
+            &[1.0, 2.0, 3.0],
+            &[4.0, 5.0, 6.0],
+            0,
+            0,
+            Alternative::Greater,
+        )
+        .unwrap();
+        assert_close(greater.p_value, 1.0, 1e-15);
+    }
+
+    #[test]
+    fn permutation_test_sampled_test() {
+        let sample_1 = normal_sample(25, 1);
+        let sample_2: Vec<f64> = normal_sample(30, 2).iter().map(|v| v + 1.0).collect();
+        let result =
+            permutation_test(&sample_1, &sample_2, 5000, 42, Alternative::TwoSided).unwrap();
+        assert!(!result.exact);
+        assert_eq!(result.permutations, 5000);
+        assert_close(
+            result.observed_difference,
+            mean(&sample_1) - mean(&sample_2),
+            1e-15,
+        );
+
+        // Close to the Welch t-test on data this well behaved
+        let t_test = crate::welch_t_test(
+            &crate::GetStatistics::from_array(&sample_1),
+            &crate::GetStatistics::from_array(&sample_2),
+        );
+        assert!((result.p_value - t_test.p_value).abs() < 0.02);
+        assert!(result.p_value > 0.0);
+
+        let again =
+            permutation_test(&sample_1, &sample_2, 5000, 42, Alternative::TwoSided).unwrap();
+        assert_eq!(again.p_value, result.p_value);
+    }
+
+    #[test]
+    fn permutation_test_invalid_input_test() {
+        assert_eq!(
+            permutation_test(&[], &[1.0], 10, 0, Alternative::TwoSided).err(),
+            Some(StatsError::EmptyInput)
+        );
+        let large: Vec<f64> = (0..20).map(f64::from).collect();
+        assert!(matches!(
+            permutation_test(&large, &large, 0, 0, Alternative::TwoSided),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
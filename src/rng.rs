@@ -0,0 +1,95 @@
+/// xoshiro256** generator, seeded through SplitMix64 so that any u64 seed,
+/// including zero, gives a well-mixed state. Used by the resampling methods
+/// so results are reproducible for a fixed seed without pulling in `rand`.
+pub(crate) struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        let mut x = seed;
+        let mut state = [0; 4];
+        for word in &mut state {
+            x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            *word = z ^ (z >> 31);
+        }
+        Rng { state }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Uniform on [0, 1) with 53 random bits.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform integer in [0, n), without modulo bias. Panics if n is zero.
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0);
+        let n = n as u64;
+        // Reject the final partial block of 2^64 so every residue is equally likely
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return (x % n) as usize;
+            }
+        }
+    }
+
+    /// Fisher-Yates shuffle.
+    pub(crate) fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_reference_sequence_test() {
+        // xoshiro256** reference implementation seeded by splitmix64(42)
+        let mut rng = Rng::new(42);
+        assert_eq!(rng.next_u64(), 1546998764402558742);
+        assert_eq!(rng.next_u64(), 6990951692964543102);
+        assert_eq!(rng.next_u64(), 12544586762248559009);
+    }
+
+    #[test]
+    fn rng_ranges_test() {
+        let mut rng = Rng::new(7);
+        let mut counts = [0; 6];
+        for _ in 0..60_000 {
+            let u = rng.next_f64();
+            assert!((0.0..1.0).contains(&u));
+            counts[rng.below(6)] += 1;
+        }
+        for count in counts {
+            assert!((9_500..10_500).contains(&count), "{count}");
+        }
+
+        let mut values: Vec<usize> = (0..20).collect();
+        Rng::new(1).shuffle(&mut values);
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+        assert_ne!(values, sorted);
+    }
+}
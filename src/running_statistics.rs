@@ -0,0 +1,557 @@
+#[cfg(feature = "alloc")]
+use crate::distributions::Normal;
+#[cfg(feature = "alloc")]
+use crate::hypothesis::validate_alpha;
+#[cfg(not(feature = "std"))]
+use crate::math::F64Ext;
+#[cfg(feature = "alloc")]
+use crate::StatsError;
+use crate::{merge_moments, welch_t_test, Float, SampleStatistics, TTestResult};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Mean, variance and range of a stream of values, updated one value at a
+/// time with Welford's algorithm, so the data never has to be held in
+/// memory. Accumulating deviations from the running mean, rather than raw
+/// sums of squares, keeps the variance accurate when the values share a
+/// large offset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningStatistics {
+    n: usize,
+    mean: f64,
+    /// Sum of squared deviations from the current mean.
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl RunningStatistics {
+    pub fn new() -> Self {
+        RunningStatistics {
+            n: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn push(&mut self, x: f64) {
+        self.n += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    /// The number of values pushed.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// NaN before the first value.
+    pub fn mean(&self) -> f64 {
+        if self.n == 0 {
+            return f64::NAN;
+        }
+        self.mean
+    }
+
+    /// Variance with divisor n - 1; NaN before the second value.
+    pub fn sample_variance(&self) -> f64 {
+        if self.n < 2 {
+            return f64::NAN;
+        }
+        self.m2 / (self.n - 1) as f64
+    }
+
+    /// Variance with divisor n; NaN before the first value.
+    pub fn population_variance(&self) -> f64 {
+        if self.n == 0 {
+            return f64::NAN;
+        }
+        self.m2 / self.n as f64
+    }
+
+    /// The sample standard deviation, the square root of `sample_variance`.
+    pub fn standard_deviation(&self) -> f64 {
+        self.sample_variance().sqrt()
+    }
+
+    /// NaN before the first value.
+    pub fn min(&self) -> f64 {
+        if self.n == 0 {
+            return f64::NAN;
+        }
+        self.min
+    }
+
+    /// NaN before the first value.
+    pub fn max(&self) -> f64 {
+        if self.n == 0 {
+            return f64::NAN;
+        }
+        self.max
+    }
+
+    /// The statistics of both streams together, as if every value of
+    /// `other` had been pushed onto `self`; see `SampleStatistics::merge`.
+    pub fn merge(&self, other: &RunningStatistics) -> RunningStatistics {
+        let (n, mean, m2) = merge_moments(
+            (self.n, self.mean, self.m2),
+            (other.n, other.mean, other.m2),
+        );
+        RunningStatistics {
+            n,
+            mean,
+            m2,
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+}
+
+impl Default for RunningStatistics {
+    fn default() -> Self {
+        RunningStatistics::new()
+    }
+}
+
+impl Extend<f64> for RunningStatistics {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, values: I) {
+        for x in values {
+            self.push(x);
+        }
+    }
+}
+
+impl<T: Float> FromIterator<T> for RunningStatistics {
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let mut running = RunningStatistics::new();
+        for x in values {
+            running.push(x.to_f64());
+        }
+        running
+    }
+}
+
+impl From<RunningStatistics> for SampleStatistics {
+    fn from(running: RunningStatistics) -> Self {
+        SampleStatistics {
+            sample_mean: running.mean(),
+            standard_deviation: running.standard_deviation(),
+            n: running.n,
+        }
+    }
+}
+
+/// Single-pass sample statistics from an iterator, which is consumed and
+/// need not be rewindable. The variance is computed with Welford's
+/// algorithm, so it can differ from `from_array` in the last few bits.
+impl<T: Float> FromIterator<T> for SampleStatistics {
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
+        values.into_iter().collect::<RunningStatistics>().into()
+    }
+}
+
+/// Summary statistics taken directly from an iterator, e.g.
+/// `data.iter().copied().sample_statistics()`.
+pub trait StatisticsIteratorExt: Iterator + Sized
+where
+    Self::Item: Float,
+{
+    fn running_statistics(self) -> RunningStatistics {
+        self.collect()
+    }
+
+    fn sample_statistics(self) -> SampleStatistics {
+        self.collect()
+    }
+}
+
+impl<I: Iterator> StatisticsIteratorExt for I where I::Item: Float {}
+
+/// A Welch t-test of a treatment group against a control, kept up to date
+/// as observations arrive. Each group is a `RunningStatistics`, so a push
+/// and a look at the current result both take constant time and memory.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SequentialTTest {
+    control: RunningStatistics,
+    treatment: RunningStatistics,
+}
+
+impl SequentialTTest {
+    pub fn new() -> Self {
+        SequentialTTest::default()
+    }
+
+    pub fn push_control(&mut self, x: f64) {
+        self.control.push(x);
+    }
+
+    pub fn push_treatment(&mut self, x: f64) {
+        self.treatment.push(x);
+    }
+
+    pub fn control(&self) -> &RunningStatistics {
+        &self.control
+    }
+
+    pub fn treatment(&self) -> &RunningStatistics {
+        &self.treatment
+    }
+
+    /// `welch_t_test` of the treatment against the control on everything
+    /// pushed so far, so `mean_difference` is treatment minus control. The
+    /// fields are NaN until each group has two values.
+    pub fn current_result(&self) -> TTestResult {
+        welch_t_test(&self.treatment.into(), &self.control.into())
+    }
+}
+
+/// Simpson subintervals per standard deviation of the step between two
+/// looks, when integrating over the statistic at the earlier look.
+#[cfg(feature = "alloc")]
+const SEQUENTIAL_STEPS_PER_SD: f64 = 8.0;
+
+/// Bounds on the Simpson subintervals across one look's continuation region.
+#[cfg(feature = "alloc")]
+const SEQUENTIAL_MIN_STEPS: usize = 400;
+#[cfg(feature = "alloc")]
+const SEQUENTIAL_MAX_STEPS: usize = 4000;
+
+/// The continuation region is cut off here; the standard normal density
+/// beyond it is below 1e-17.
+#[cfg(feature = "alloc")]
+const SEQUENTIAL_Z_LIMIT: f64 = 9.0;
+
+/// The two-sided significance threshold for the latest of a series of
+/// interim looks at a trial planned to reach `planned_n` observations,
+/// spending `alpha` over the looks with the O'Brien-Fleming-type function
+/// of Lan and DeMets (1983), α(t) = 2 - 2 Φ(z_{1-α/2} / sqrt(t)).
+/// `looks` holds the cumulative sample size at each look so far, and the
+/// information fraction of a look is its size over `planned_n`.
+///
+/// The boundary is the exact group-sequential one: the z statistics at
+/// successive looks are correlated, corr(Z_j, Z_k) = sqrt(t_j / t_k), and
+/// the boundary c_k solves P(|Z_1| < c_1, ..., |Z_{k-1}| < c_{k-1},
+/// |Z_k| >= c_k) = α(t_k) - α(t_{k-1}), found by the recursive numerical
+/// integration of Armitage, McPherson and Rowe (1969). The threshold is
+/// the nominal p-value at it, 2 (1 - Φ(c_k)), so a look rejects when its
+/// two-sided p-value falls below the threshold, and the overall type I
+/// error is `alpha`. With four equally spaced looks at alpha = 0.05 the
+/// thresholds are about 0.00009, 0.0055, 0.022 and 0.041, and the last is
+/// close to the fixed-sample 0.05, which is why these boundaries cost
+/// little power.
+///
+/// Returns `StatsError::EmptyInput` for no looks and
+/// `StatsError::InvalidParameter` unless `alpha` is strictly between 0 and
+/// 1 and the looks are positive, strictly increasing and at most
+/// `planned_n`.
+#[cfg(feature = "alloc")]
+pub fn obrien_fleming_threshold(
+    looks: &[usize],
+    planned_n: usize,
+    alpha: f64,
+) -> Result<f64, StatsError> {
+    validate_alpha(alpha)?;
+    let Some(&latest) = looks.last() else {
+        return Err(StatsError::EmptyInput);
+    };
+    if looks[0] == 0 || looks.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(StatsError::InvalidParameter(
+            "looks must be positive and strictly increasing",
+        ));
+    }
+    if latest > planned_n {
+        return Err(StatsError::InvalidParameter(
+            "looks cannot exceed the planned sample size",
+        ));
+    }
+    let normal = Normal::standard();
+    let z = normal.quantile(1.0 - alpha / 2.0);
+    // At t = 1 the bound is exactly z, so the full alpha is spent
+    let spending = |t: f64| {
+        if t >= 1.0 {
+            alpha
+        } else {
+            2.0 * normal.sf(z / t.sqrt())
+        }
+    };
+    let fractions: Vec<f64> = looks
+        .iter()
+        .map(|&look| look as f64 / planned_n as f64)
+        .collect();
+
+    // Simpson weight times the sub-density of Z at the previous look, over
+    // the region where the trial carried on, as (z, weighted density)
+    let mut density: Vec<(f64, f64)> = Vec::new();
+    let mut bound = 0.0;
+    let mut spent = 0.0;
+    for (k, &t) in fractions.iter().enumerate() {
+        let increment = spending(t) - spent;
+        spent = spending(t);
+        // Z_k = sqrt(t_{k-1} / t_k) Z_{k-1} plus an independent normal step
+        // of standard deviation `step`
+        let (shrink, step) = match k {
+            0 => (0.0, 1.0),
+            _ => {
+                let previous = fractions[k - 1];
+                ((previous / t).sqrt(), ((t - previous) / t).sqrt())
+            }
+        };
+        let transition = |zp: f64, y: f64| normal.pdf((y - shrink * zp) / step) / step;
+        bound = if k == 0 {
+            -normal.quantile(increment / 2.0)
+        } else {
+            let crossing = |c: f64| {
+                density
+                    .iter()
+                    .map(|&(zp, weight)| {
+                        weight
+                            * (normal.sf((c - shrink * zp) / step)
+                                + normal.cdf((-c - shrink * zp) / step))
+                    })
+                    .sum::<f64>()
+            };
+            // The crossing probability falls from the whole continuing
+            // mass at c = 0 to nothing
+            let (mut low, mut high) = (0.0, 2.0 * SEQUENTIAL_Z_LIMIT);
+            for _ in 0..100 {
+                let mid = (low + high) / 2.0;
+                if crossing(mid) > increment {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            (low + high) / 2.0
+        };
+
+        if let Some(&next) = fractions.get(k + 1) {
+            // The next step, in units of this look's z
+            let next_step = ((next - t) / t).sqrt();
+            let limit = bound.min(SEQUENTIAL_Z_LIMIT);
+            let steps = (2.0 * limit / next_step * SEQUENTIAL_STEPS_PER_SD) as usize;
+            let steps = steps
+                .clamp(SEQUENTIAL_MIN_STEPS, SEQUENTIAL_MAX_STEPS)
+                .next_multiple_of(2);
+            let h = 2.0 * limit / steps as f64;
+            density = (0..=steps)
+                .map(|i| {
+                    let y = -limit + i as f64 * h;
+                    let simpson = match i {
+                        0 => 1.0,
+                        i if i == steps => 1.0,
+                        i if i % 2 == 1 => 4.0,
+                        _ => 2.0,
+                    };
+                    let value = if k == 0 {
+                        normal.pdf(y)
+                    } else {
+                        density
+                            .iter()
+                            .map(|&(zp, weight)| weight * transition(zp, y))
+                            .sum()
+                    };
+                    (y, simpson * h / 3.0 * value)
+                })
+                .collect();
+        }
+    }
+    Ok(2.0 * normal.sf(bound))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulate::Simulator;
+    use crate::{assert_close, pooled_t_test, GetStatistics};
+
+    #[test]
+    fn running_statistics_test() {
+        let data = [1.0, 5.5, 7.7, 8.9];
+        let mut running = RunningStatistics::new();
+        running.extend(data);
+        assert_eq!(running.n(), 4);
+        assert_close(running.mean(), 5.775, 1e-15);
+        assert_close(running.sample_variance(), 12.115833333333333, 1e-14);
+        assert_close(running.population_variance(), 9.086875, 1e-14);
+        assert_close(running.standard_deviation(), 3.4807805638007885, 1e-15);
+        assert_eq!(running.min(), 1.0);
+        assert_eq!(running.max(), 8.9);
+
+        let samp = SampleStatistics::from(running);
+        let expected = SampleStatistics::from_array(&data);
+        assert_eq!(samp.n, expected.n);
+        assert_close(samp.sample_mean, expected.sample_mean, 1e-15);
+        assert_close(samp.standard_deviation, expected.standard_deviation, 1e-15);
+        let other = SampleStatistics::from_array(&[2.0, 3.5, 4.1]);
+        let t = pooled_t_test(&running.into(), &other);
+        let expected_t = pooled_t_test(&expected, &other);
+        assert_close(t.t, expected_t.t, 1e-14);
+    }
+
+    #[test]
+    fn large_offset_test() {
+        // Offsets 0, 1/8, ..., 7/8 are exact at 1e9, so the variance is
+        // exactly that of {0, ..., 7} / 8. A naive Σx² - n x̄² loses every
+        // digit here, since Σx² is about 1e25.
+        let n = 10_000_000;
+        let mut running = RunningStatistics::new();
+        for i in 0..n {
+            running.push(1e9 + (i % 8) as f64 / 8.0);
+        }
+        let population_variance = 5.25 / 64.0;
+        assert_eq!(running.n(), n);
+        assert_close(running.mean(), 1e9 + 0.4375, 1e-6);
+        assert_close(running.population_variance(), population_variance, 1e-9);
+        assert_close(
+            running.sample_variance(),
+            population_variance * n as f64 / (n - 1) as f64,
+            1e-9,
+        );
+        assert_eq!(running.min(), 1e9);
+        assert_eq!(running.max(), 1e9 + 0.875);
+    }
+
+    #[test]
+    fn merge_test() {
+        let mut rng = crate::rng::Rng::new(93);
+        let data: Vec<f64> = (0..100).map(|_| rng.next_f64() - 0.5).collect();
+        let mut whole = RunningStatistics::new();
+        whole.extend(data.iter().copied());
+        for _ in 0..20 {
+            let (a, b) = data.split_at(rng.below(data.len() + 1));
+            let (mut left, mut right) = (RunningStatistics::new(), RunningStatistics::new());
+            left.extend(a.iter().copied());
+            right.extend(b.iter().copied());
+            let merged = left.merge(&right);
+            assert_eq!(merged.n(), 100);
+            assert_close(merged.mean(), whole.mean(), 1e-14);
+            assert_close(merged.sample_variance(), whole.sample_variance(), 1e-14);
+            assert_eq!(merged.min(), whole.min());
+            assert_eq!(merged.max(), whole.max());
+        }
+        assert_eq!(RunningStatistics::new().merge(&whole), whole);
+        assert_eq!(whole.merge(&RunningStatistics::new()), whole);
+    }
+
+    #[test]
+    fn from_iterator_test() {
+        let data = [1.0, 5.5, 7.7, 8.9];
+        let samp: SampleStatistics = data.iter().copied().collect();
+        assert_eq!(samp.n, 4);
+        assert_close(samp.sample_mean, 5.775, 1e-15);
+        assert_close(samp.standard_deviation, 3.4807805638007885, 1e-15);
+
+        let lazy = data.iter().map(|x| x * 2.0).sample_statistics();
+        assert_close(lazy.standard_deviation, 2.0 * 3.4807805638007885, 1e-14);
+        let running = data.iter().copied().running_statistics();
+        let mut pushed = RunningStatistics::new();
+        pushed.extend(data);
+        assert_eq!(running, pushed);
+        let from_f32: RunningStatistics = [1.5f32, 2.5].into_iter().collect();
+        assert_eq!(from_f32.mean(), 2.0);
+    }
+
+    #[test]
+    fn empty_test() {
+        let mut running = RunningStatistics::default();
+        assert_eq!(running.n(), 0);
+        assert!(running.mean().is_nan());
+        assert!(running.population_variance().is_nan());
+        assert!(running.min().is_nan());
+        assert!(running.max().is_nan());
+        running.push(3.0);
+        assert_eq!(running.mean(), 3.0);
+        assert_eq!(running.population_variance(), 0.0);
+        assert!(running.sample_variance().is_nan());
+    }
+
+    #[test]
+    fn sequential_t_test_test() {
+        // After every insertion the running test matches a two-pass
+        // welch_t_test on all the data so far
+        let mut simulator = Simulator::new(142);
+        let mut sequential = SequentialTTest::new();
+        let (mut control, mut treatment) = (Vec::new(), Vec::new());
+        for _ in 0..1000 {
+            if simulator.uniform(0.0, 1.0).unwrap() < 0.5 {
+                let x = simulator.normal(100.0, 15.0).unwrap();
+                sequential.push_control(x);
+                control.push(x);
+            } else {
+                let x = simulator.normal(103.0, 20.0).unwrap();
+                sequential.push_treatment(x);
+                treatment.push(x);
+            }
+            let result = sequential.current_result();
+            if control.len() < 2 || treatment.len() < 2 {
+                assert!(result.t.is_nan());
+                continue;
+            }
+            let expected = welch_t_test(
+                &SampleStatistics::from_array(&treatment),
+                &SampleStatistics::from_array(&control),
+            );
+            assert_close(result.t, expected.t, 1e-9);
+            assert_close(result.df, expected.df, 1e-9);
+            assert_close(result.p_value, expected.p_value, 1e-9);
+            assert_close(result.mean_difference, expected.mean_difference, 1e-9);
+            assert_close(result.standard_error, expected.standard_error, 1e-9);
+        }
+        assert_eq!(sequential.control().n(), control.len());
+        assert_eq!(sequential.treatment().n(), treatment.len());
+        assert!(sequential.current_result().mean_difference > 0.0);
+    }
+
+    #[test]
+    fn obrien_fleming_threshold_test() {
+        // The same recursive integration in Python on a finer grid, with the
+        // two-look case checked against mpmath's bivariate normal integral.
+        // The first look spends α(0.25) = 2 - 2 Φ(z_0.975 / 0.5) outright
+        let looks = [250, 500, 750, 1000];
+        let expected = [
+            8.857543832140501e-05,
+            0.005537975583133916,
+            0.02154799100433628,
+            0.04108823841634132,
+        ];
+        for (k, expected) in expected.into_iter().enumerate() {
+            let threshold = obrien_fleming_threshold(&looks[..=k], 1000, 0.05).unwrap();
+            assert_close(threshold, expected, 1e-9 * expected);
+        }
+        // mpmath: P(|Z1| < c1, |Z2| >= c2) = 0.05 - α(0.5), corr sqrt(0.5)
+        assert_close(
+            obrien_fleming_threshold(&[50, 100], 100, 0.05).unwrap(),
+            0.04778096332160403,
+            1e-10,
+        );
+        // A single look at the planned size is the fixed-sample test
+        assert_close(
+            obrien_fleming_threshold(&[60], 60, 0.01).unwrap(),
+            0.01,
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn obrien_fleming_invalid_input_test() {
+        assert_eq!(
+            obrien_fleming_threshold(&[], 100, 0.05),
+            Err(StatsError::EmptyInput)
+        );
+        for looks in [&[0, 50][..], &[50, 50], &[60, 40], &[50, 120]] {
+            assert!(matches!(
+                obrien_fleming_threshold(looks, 100, 0.05),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        assert!(matches!(
+            obrien_fleming_threshold(&[50], 100, 1.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
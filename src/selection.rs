@@ -0,0 +1,200 @@
+use crate::StatsError;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// The value of rank `k` (counting from zero, so `k = 0` is the minimum) in
+/// `data`, without sorting it. A copy is partitioned by
+/// `select_nth_unstable`, an introselect whose median-of-medians fallback
+/// keeps even adversarial input linear. Values are ordered by
+/// `f64::total_cmp`, which puts -0.0 below 0.0.
+///
+/// Returns `StatsError::EmptyInput` for no data, `StatsError::NanInInput`
+/// if it contains NaN and `StatsError::InvalidParameter` unless `k` is less
+/// than its length.
+pub fn kth_smallest(data: &[f64], k: usize) -> Result<f64, StatsError> {
+    validate_selection(data)?;
+    if k >= data.len() {
+        return Err(StatsError::InvalidParameter(
+            "k must be less than the number of values",
+        ));
+    }
+    let mut values = data.to_vec();
+    let (_, &mut kth, _) = values.select_nth_unstable_by(k, f64::total_cmp);
+    Ok(kth)
+}
+
+/// The `k` smallest values of `data` in ascending order, found by
+/// partitioning a copy around rank k - 1 and sorting only the k values
+/// below it, O(n + k log k) in all. Ordering and errors are as
+/// `kth_smallest`, except that `k` may equal the length, and `k = 0` gives
+/// an empty vector once the data has passed those checks.
+pub fn partial_sort_copy(data: &[f64], k: usize) -> Result<Vec<f64>, StatsError> {
+    validate_selection(data)?;
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+    if k > data.len() {
+        return Err(StatsError::InvalidParameter(
+            "k must not exceed the number of values",
+        ));
+    }
+    let mut values = data.to_vec();
+    if k < values.len() {
+        values.select_nth_unstable_by(k - 1, f64::total_cmp);
+        values.truncate(k);
+    }
+    values.sort_unstable_by(f64::total_cmp);
+    Ok(values)
+}
+
+/// The smallest and largest values of `data`, in one pass, ordered as
+/// `kth_smallest`. Returns `StatsError::EmptyInput` for no data and
+/// `StatsError::NanInInput` if it contains NaN.
+pub fn min_max(data: &[f64]) -> Result<(f64, f64), StatsError> {
+    let (&first, rest) = data.split_first().ok_or(StatsError::EmptyInput)?;
+    if first.is_nan() {
+        return Err(StatsError::NanInInput { index: 0 });
+    }
+    let (mut min, mut max) = (first, first);
+    for (i, &x) in rest.iter().enumerate() {
+        if x.is_nan() {
+            return Err(StatsError::NanInInput { index: i + 1 });
+        }
+        if x.total_cmp(&min).is_lt() {
+            min = x;
+        } else if x.total_cmp(&max).is_gt() {
+            max = x;
+        }
+    }
+    Ok((min, max))
+}
+
+fn validate_selection(data: &[f64]) -> Result<(), StatsError> {
+    if data.is_empty() {
+        return Err(StatsError::EmptyInput);
+    }
+    match data.iter().position(|x| x.is_nan()) {
+        Some(index) => Err(StatsError::NanInInput { index }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    /// Random inputs of assorted lengths: continuous values, a handful of
+    /// distinct values repeated many times, signed zeros and infinities, and
+    /// sorted, reversed and organ-pipe orders.
+    fn random_inputs(rng: &mut Rng) -> Vec<Vec<f64>> {
+        let mut inputs = Vec::new();
+        for _ in 0..300 {
+            let n = 1 + rng.below(200);
+            let distinct = 1 + rng.below(6);
+            let mut data: Vec<f64> = match rng.below(3) {
+                0 => (0..n).map(|_| rng.next_f64() * 200.0 - 100.0).collect(),
+                1 => (0..n).map(|_| rng.below(distinct) as f64).collect(),
+                _ => {
+                    let special = [-0.0, 0.0, f64::INFINITY, f64::NEG_INFINITY, 1.5];
+                    (0..n).map(|_| special[rng.below(special.len())]).collect()
+                }
+            };
+            match rng.below(4) {
+                0 => data.sort_by(f64::total_cmp),
+                1 => data.sort_by(|a, b| b.total_cmp(a)),
+                2 => {
+                    data.sort_by(f64::total_cmp);
+                    let descending: Vec<f64> =
+                        data.iter().skip(1).step_by(2).rev().copied().collect();
+                    data = data.iter().step_by(2).copied().chain(descending).collect();
+                }
+                _ => {}
+            }
+            inputs.push(data);
+        }
+        inputs
+    }
+
+    #[test]
+    fn selection_matches_full_sort_test() {
+        let mut rng = Rng::new(144);
+        for data in random_inputs(&mut rng) {
+            let original = data.clone();
+            let mut sorted = data.clone();
+            sorted.sort_by(f64::total_cmp);
+            let n = data.len();
+
+            for k in [0, n / 3, n / 2, n - 1, rng.below(n)] {
+                let kth = kth_smallest(&data, k).unwrap();
+                assert_eq!(kth.to_bits(), sorted[k].to_bits(), "k = {k} of {data:?}");
+            }
+            for k in [0, 1, n / 4, n / 2, n, rng.below(n + 1)] {
+                let smallest = partial_sort_copy(&data, k).unwrap();
+                let bits = |values: &[f64]| values.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+                assert_eq!(bits(&smallest), bits(&sorted[..k]), "k = {k} of {data:?}");
+            }
+            let (min, max) = min_max(&data).unwrap();
+            assert_eq!(
+                (min.to_bits(), max.to_bits()),
+                (sorted[0].to_bits(), sorted[n - 1].to_bits())
+            );
+            assert_eq!(
+                data.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+                original.iter().map(|x| x.to_bits()).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn selection_test() {
+        let data = [4.0, -1.0, 7.5, 3.0, 3.0, 10.0, 0.5];
+        assert_eq!(kth_smallest(&data, 0), Ok(-1.0));
+        assert_eq!(kth_smallest(&data, 3), Ok(3.0));
+        assert_eq!(kth_smallest(&data, 4), Ok(4.0));
+        assert_eq!(kth_smallest(&data, 6), Ok(10.0));
+        assert_eq!(partial_sort_copy(&data, 3), Ok(vec![-1.0, 0.5, 3.0]));
+        assert_eq!(partial_sort_copy(&data, 0), Ok(vec![]));
+        assert_eq!(min_max(&data), Ok((-1.0, 10.0)));
+        assert_eq!(min_max(&[2.5]), Ok((2.5, 2.5)));
+        assert_eq!(
+            min_max(&[0.0, -0.0]).unwrap().0.to_bits(),
+            (-0.0f64).to_bits()
+        );
+    }
+
+    #[test]
+    fn selection_invalid_input_test() {
+        let with_nan = [1.0, 2.0, f64::NAN, 0.0];
+        assert_eq!(
+            kth_smallest(&with_nan, 0),
+            Err(StatsError::NanInInput { index: 2 })
+        );
+        assert_eq!(
+            partial_sort_copy(&with_nan, 2),
+            Err(StatsError::NanInInput { index: 2 })
+        );
+        assert_eq!(
+            partial_sort_copy(&with_nan, 0),
+            Err(StatsError::NanInInput { index: 2 })
+        );
+        assert_eq!(min_max(&with_nan), Err(StatsError::NanInInput { index: 2 }));
+        assert_eq!(
+            min_max(&[f64::NAN]),
+            Err(StatsError::NanInInput { index: 0 })
+        );
+
+        assert_eq!(kth_smallest(&[], 0), Err(StatsError::EmptyInput));
+        assert_eq!(partial_sort_copy(&[], 1), Err(StatsError::EmptyInput));
+        assert_eq!(partial_sort_copy(&[], 0), Err(StatsError::EmptyInput));
+        assert_eq!(min_max(&[]), Err(StatsError::EmptyInput));
+        assert!(matches!(
+            kth_smallest(&[1.0, 2.0], 2),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            partial_sort_copy(&[1.0, 2.0], 3),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}
@@ -0,0 +1,235 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::*;
+
+const X: [f64; 10] = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+const Y: [f64; 10] = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+const Z: [f64; 10] = [2.1, 0.3, 1.7, 0.9, 1.2, 3.3, 2.8, 1.5, 0.4, 2.6];
+
+/// Serializes, deserializes and serializes again, so that every field
+/// survives the trip without the structs needing `PartialEq`.
+#[track_caller]
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) {
+    let json = serde_json::to_string(value).unwrap();
+    let back: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&back).unwrap(), json);
+}
+
+#[test]
+fn wire_format_test() {
+    // Field names are the Rust field names, and form the wire format
+    let samp = SampleStatistics::from_summary(2.5, 1.25, 8).unwrap();
+    assert_eq!(
+        serde_json::to_string(&samp).unwrap(),
+        r#"{"sample_mean":2.5,"standard_deviation":1.25,"n":8}"#
+    );
+    let pop = PopulationStatistics::from_summary(2.5, 1.25, 8).unwrap();
+    assert_eq!(
+        serde_json::to_string(&pop).unwrap(),
+        r#"{"population_mean":2.5,"standard_deviation":1.25,"n":8}"#
+    );
+    let interval = mean_confidence_interval(&X, 0.95).unwrap();
+    let json = serde_json::to_value(interval).unwrap();
+    let keys: Vec<&String> = json.as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["confidence", "lower", "point_estimate", "upper"]);
+    assert_eq!(
+        serde_json::to_string(&Trend::Increasing).unwrap(),
+        r#""Increasing""#
+    );
+}
+
+#[test]
+fn invalid_json_test() {
+    // serde_json writes non-finite numbers as null, which is then rejected
+    let nan = SampleStatistics {
+        sample_mean: f64::NAN,
+        standard_deviation: 1.0,
+        n: 0,
+    };
+    let json = serde_json::to_string(&nan).unwrap();
+    assert!(serde_json::from_str::<SampleStatistics>(&json).is_err());
+    for json in [
+        r#"{"sample_mean":1.0,"standard_deviation":1.0}"#,
+        r#"{"sample_mean":"1","standard_deviation":1.0,"n":3}"#,
+        r#"{"sample_mean":1.0,"standard_deviation":1.0,"n":-3}"#,
+        r#"{"sample_mean":NaN,"standard_deviation":1.0,"n":3}"#,
+    ] {
+        assert!(serde_json::from_str::<SampleStatistics>(json).is_err());
+    }
+}
+
+#[test]
+fn statistics_round_trip_test() {
+    round_trip(&SampleStatistics::from_array(&X));
+    round_trip(&PopulationStatistics::from_array(&X));
+    round_trip(&five_number_summary(&X).unwrap());
+    round_trip(&l_moments(&X, 4).unwrap());
+    round_trip(&mean_confidence_interval(&X, 0.95).unwrap());
+    round_trip(&median_confidence_interval(&X, 0.95).unwrap());
+    round_trip(&qq_line(&X).unwrap());
+    let table = TwoByTwoTable {
+        exposed_cases: 10,
+        exposed_non_cases: 20,
+        unexposed_cases: 5,
+        unexposed_non_cases: 40,
+    };
+    round_trip(&table.odds_ratio(0.95).unwrap());
+}
+
+#[test]
+fn t_and_z_test_round_trip_test() {
+    let (samp_x, samp_y) = (
+        SampleStatistics::from_array(&X),
+        SampleStatistics::from_array(&Y),
+    );
+    round_trip(&welch_t_test(&samp_x, &samp_y));
+    round_trip(&tost_equivalence(&samp_x, &samp_y, -1.0, 1.0, 0.05).unwrap());
+    round_trip(&hedges_g(&samp_x, &samp_y));
+    round_trip(&one_samp_z_test(1.0, 10, 0.0, 2.0));
+    round_trip(&two_prop_z_test(30, 100, 45, 100).unwrap());
+}
+
+#[test]
+fn anova_round_trip_test() {
+    let groups: [&[f64]; 3] = [&X, &Y, &Z];
+    round_trip(&one_way_anova(&groups).unwrap());
+    round_trip(&welch_anova(&groups).unwrap());
+    round_trip(&tukey_hsd(&groups, 0.05).unwrap());
+    round_trip(&conover_iman(&groups, CorrectionMethod::Holm).unwrap());
+    let cells = vec![
+        vec![X[..3].to_vec(), Y[..3].to_vec()],
+        vec![Z[..3].to_vec(), X[3..6].to_vec()],
+    ];
+    round_trip(&two_way_anova(&cells).unwrap());
+    round_trip(&levene_test(&groups, Center::Median).unwrap());
+    round_trip(&brown_forsythe(&groups).unwrap());
+    round_trip(&bartlett_test(&groups).unwrap());
+    round_trip(&var_f_test(&X, &Y, Alternative::TwoSided).unwrap());
+    round_trip(&var_chi_square_test(&X, 2.0, Alternative::TwoSided).unwrap());
+}
+
+#[test]
+fn categorical_round_trip_test() {
+    let observed = [18.0, 22.0, 31.0, 29.0];
+    round_trip(&chi_square_gof(&observed, &[25.0; 4]).unwrap());
+    let normal = distributions::Normal::new(0.75, 2.0);
+    let edges = BinSpec::Edges(vec![-2.0, 0.75, 4.0]);
+    round_trip(&chi_square_gof_distribution(&X, |x| normal.cdf(x), 0, edges).unwrap());
+    let table = ContingencyTable::new(vec![vec![12.0, 5.0], vec![7.0, 15.0]]).unwrap();
+    round_trip(&table.chi_square_test());
+    round_trip(&table.cramers_v(false));
+    round_trip(
+        &table
+            .chi_square_test()
+            .cell_post_hoc(0.05, CorrectionMethod::Holm)
+            .unwrap(),
+    );
+    round_trip(
+        &table
+            .pairwise_row_tests(ContingencyTable::g_test, 0.05, CorrectionMethod::Holm)
+            .unwrap(),
+    );
+    round_trip(&binomial_test(7, 20, 0.5, Alternative::TwoSided).unwrap());
+    round_trip(&fishers_exact([[12, 5], [7, 15]], Alternative::TwoSided).unwrap());
+    round_trip(&poisson_test(12, 2.5, 3.0, Alternative::TwoSided).unwrap());
+    round_trip(&poisson_two_sample(12, 2.5, 20, 3.0, Alternative::TwoSided).unwrap());
+    round_trip(&mcnemar_test([[20, 5], [12, 30]], true).unwrap());
+    let responses = vec![vec![1, 0, 1], vec![1, 1, 0], vec![0, 0, 1], vec![1, 1, 1]];
+    round_trip(&cochrans_q(&responses).unwrap());
+    round_trip(&pairwise_mcnemar(&responses, false).unwrap());
+    round_trip(
+        &cohens_kappa(
+            &[0, 1, 2, 1, 0, 2],
+            &[0, 1, 1, 1, 0, 2],
+            KappaWeights::Linear,
+        )
+        .unwrap(),
+    );
+    round_trip(&fleiss_kappa(&[vec![2, 1], vec![0, 3], vec![3, 0], vec![1, 2]]).unwrap());
+}
+
+#[test]
+fn correlation_and_regression_round_trip_test() {
+    round_trip(&pearson_r(&X, &Y).unwrap());
+    round_trip(&spearman_rho(&X, &Y).unwrap());
+    round_trip(&kendall_tau(&X, &Y).unwrap());
+    let binary = X.map(|x| x > 0.5);
+    round_trip(&point_biserial(&binary, &Y).unwrap());
+    round_trip(&partial_correlation(&X, &Y, &Z).unwrap());
+
+    let fit = linear_regression(&X, &Y).unwrap();
+    round_trip(&fit);
+    round_trip(&RegressionDiagnostics::from_regression(&fit));
+    let predictors: Vec<Vec<f64>> = X.iter().zip(Z).map(|(&x, z)| vec![x, z]).collect();
+    round_trip(&multiple_regression(&predictors, &Y).unwrap());
+    round_trip(&polynomial_regression(&X, &Y, 2).unwrap());
+    let outcomes = [
+        false, false, true, false, false, true, true, true, false, true,
+    ];
+    round_trip(&logistic_regression(&Z, &outcomes).unwrap());
+    round_trip(&theil_sen(&X, &Y).unwrap());
+}
+
+#[test]
+fn nonparametric_round_trip_test() {
+    let groups: [&[f64]; 3] = [&X, &Y, &Z];
+    round_trip(&kruskal_wallis(&groups).unwrap());
+    round_trip(&mood_median_test(&groups).unwrap());
+    let blocks: Vec<Vec<f64>> = (0..10).map(|i| vec![X[i], Y[i], Z[i]]).collect();
+    round_trip(&friedman_test(&blocks).unwrap());
+    round_trip(&mann_whitney_u(&X, &Y, Alternative::TwoSided).unwrap());
+    round_trip(&ansari_bradley(&X, &Y, Alternative::TwoSided).unwrap());
+    round_trip(&hodges_lehmann(&X, &Y, 0.95).unwrap());
+    round_trip(&hodges_lehmann_paired(&X, &Y, 0.95).unwrap());
+    round_trip(&wilcoxon_signed_rank(&X, &Y, Alternative::TwoSided).unwrap());
+    round_trip(&sign_test(&X, 0.0, Alternative::TwoSided).unwrap());
+    round_trip(&runs_test(&X).unwrap());
+    round_trip(&ks_2samp(&X, &Y).unwrap());
+    round_trip(&mann_kendall(&Y).unwrap());
+    round_trip(&trend_analysis(&Y).unwrap());
+}
+
+#[test]
+fn normality_and_outlier_round_trip_test() {
+    round_trip(&shapiro_wilk(&X).unwrap());
+    round_trip(&anderson_darling(&X).unwrap());
+    round_trip(&jarque_bera(&X).unwrap());
+    round_trip(&dagostino_k2(&X).unwrap());
+    round_trip(&grubbs_test(&X, 0.05).unwrap());
+    round_trip(&dixon_q_test(&X, 0.05).unwrap());
+    let rows: Vec<Vec<f64>> = (0..10).map(|i| vec![X[i], Y[i], Z[i]]).collect();
+    round_trip(&mahalanobis_outliers(&rows, 0.05).unwrap());
+}
+
+#[test]
+fn reliability_and_resampling_round_trip_test() {
+    let items: [&[f64]; 3] = [&X, &Y, &Z];
+    round_trip(&cronbachs_alpha(&items).unwrap());
+    let ratings: Vec<Vec<f64>> = (0..10).map(|i| vec![X[i], Y[i], Z[i]]).collect();
+    round_trip(&icc(&ratings, IccModel::TwoWayRandom).unwrap());
+    round_trip(&bootstrap_ci(&X, mean, 200, 0.95, 1).unwrap());
+    round_trip(&bootstrap_two_sample(&X, &Y, |a, b| mean(a) - mean(b), 200, 0.95, 1).unwrap());
+    round_trip(&permutation_test(&X, &Y, 200, 1, Alternative::TwoSided).unwrap());
+    let pairs: Vec<(String, f64)> = [("x", X), ("y", Y), ("z", Z)]
+        .iter()
+        .flat_map(|&(label, values)| values.map(|v| (label.to_string(), v)))
+        .collect();
+    round_trip(&grouped_anova(pairs.clone()).unwrap());
+    round_trip(
+        &grouped_pairwise_t_tests(
+            pairs,
+            TTestKind::EqualVariance,
+            0.05,
+            CorrectionMethod::Holm,
+        )
+        .unwrap(),
+    );
+    round_trip(&ljung_box(&Y, 3).unwrap());
+    round_trip(&SummaryReport::from_array(&X, &[50.0, 90.0, 99.0]).unwrap());
+    round_trip(&fit_normal(&X).unwrap());
+    round_trip(&describe_columns(&[&X, &[], &[1.0]], NanPolicy::Propagate).unwrap());
+    round_trip(&fit_exponential(&Y.map(f64::abs).map(|y| y + 0.5)).unwrap());
+    round_trip(&random_effects_meta(&X, &Z.map(f64::abs).map(|z| z + 0.1), 0.95).unwrap());
+    round_trip(&normal_tolerance_interval(&X, 0.9, 0.95, Sides::TwoSided).unwrap());
+}
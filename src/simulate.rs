@@ -0,0 +1,231 @@
+use crate::rng::Rng;
+use crate::StatsError;
+
+/// A seeded source of random variates, for drawing a mix of distributions
+/// from one stream. The generator is xoshiro256** (Blackman and Vigna), its
+/// state filled from the `u64` seed by SplitMix64, and the transcendental
+/// functions are `libm`'s rather than the platform's, so a seed gives
+/// bit-for-bit the same values on every target. The algorithm is part of
+/// the API: changing it would change the data existing seeds produce.
+pub struct Simulator {
+    rng: Rng,
+    /// The second normal of the last Box-Muller pair, not yet returned.
+    spare_normal: Option<f64>,
+}
+
+impl Simulator {
+    pub fn new(seed: u64) -> Self {
+        Simulator {
+            rng: Rng::new(seed),
+            spare_normal: None,
+        }
+    }
+
+    /// The next raw 64-bit output of the generator.
+    pub fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    /// Uniform on [`low`, `high`), from the top 53 bits of one output.
+    /// Returns `StatsError::InvalidParameter` unless `low` <= `high`, both
+    /// finite.
+    pub fn uniform(&mut self, low: f64, high: f64) -> Result<f64, StatsError> {
+        validate_uniform(low, high)?;
+        Ok(low + (high - low) * self.rng.next_f64())
+    }
+
+    /// Normal with the given `mean` and standard deviation `sd`, from the
+    /// Box-Muller transform, which makes normals in pairs. Returns
+    /// `StatsError::InvalidParameter` unless `mean` is finite and `sd` is
+    /// finite and not negative.
+    pub fn normal(&mut self, mean: f64, sd: f64) -> Result<f64, StatsError> {
+        validate_normal(mean, sd)?;
+        let z = match self.spare_normal.take() {
+            Some(z) => z,
+            None => {
+                // 1 - u lies in (0, 1], keeping the logarithm finite
+                let radius = libm::sqrt(-2.0 * libm::log(1.0 - self.rng.next_f64()));
+                let angle = 2.0 * core::f64::consts::PI * self.rng.next_f64();
+                self.spare_normal = Some(radius * libm::sin(angle));
+                radius * libm::cos(angle)
+            }
+        };
+        Ok(mean + sd * z)
+    }
+
+    /// Successes in `trials` independent trials with success probability
+    /// `p`, counted one Bernoulli draw at a time, so a call costs O(`trials`).
+    /// Returns `StatsError::InvalidParameter` unless `p` is in [0, 1].
+    pub fn binomial(&mut self, trials: u64, p: f64) -> Result<u64, StatsError> {
+        validate_probability(p)?;
+        Ok((0..trials).filter(|_| self.rng.next_f64() < p).count() as u64)
+    }
+}
+
+/// `n` draws from the normal distribution with `mean` and `sd`, the same as
+/// calling `Simulator::normal` `n` times on `Simulator::new(seed)`. The
+/// parameters are checked even when `n` is zero.
+pub fn normal_sample(n: usize, mean: f64, sd: f64, seed: u64) -> Result<Vec<f64>, StatsError> {
+    validate_normal(mean, sd)?;
+    let mut simulator = Simulator::new(seed);
+    (0..n).map(|_| simulator.normal(mean, sd)).collect()
+}
+
+/// `n` draws uniform on [`low`, `high`), as `Simulator::uniform`.
+pub fn uniform_sample(n: usize, low: f64, high: f64, seed: u64) -> Result<Vec<f64>, StatsError> {
+    validate_uniform(low, high)?;
+    let mut simulator = Simulator::new(seed);
+    (0..n).map(|_| simulator.uniform(low, high)).collect()
+}
+
+/// `n` draws from the binomial distribution with `trials` and `p`, as
+/// `Simulator::binomial`, in O(`n` `trials`) time.
+pub fn binomial_sample(n: usize, trials: u64, p: f64, seed: u64) -> Result<Vec<u64>, StatsError> {
+    validate_probability(p)?;
+    let mut simulator = Simulator::new(seed);
+    (0..n).map(|_| simulator.binomial(trials, p)).collect()
+}
+
+fn validate_uniform(low: f64, high: f64) -> Result<(), StatsError> {
+    if !(low.is_finite() && high.is_finite() && low <= high) {
+        return Err(StatsError::InvalidParameter(
+            "uniform requires finite low <= high",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_normal(mean: f64, sd: f64) -> Result<(), StatsError> {
+    if !(mean.is_finite() && sd.is_finite() && sd >= 0.0) {
+        return Err(StatsError::InvalidParameter(
+            "normal requires a finite mean and a finite, non-negative sd",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_probability(p: f64) -> Result<(), StatsError> {
+    if !(0.0..=1.0).contains(&p) {
+        return Err(StatsError::InvalidParameter(
+            "binomial requires p between 0 and 1",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, mean, one_samp_t_test, sample_variance, welch_t_test};
+    use crate::{GetStatistics, SampleStatistics};
+
+    #[test]
+    fn simulate_reference_sequence_test() {
+        // xoshiro256** seeded by splitmix64(136), then Box-Muller
+        let normals = normal_sample(4, 5.0, 2.0, 136).unwrap();
+        let expected = [
+            7.60420181683781,
+            7.898728383242284,
+            4.097737116536603,
+            7.300425518602042,
+        ];
+        for (actual, expected) in normals.iter().zip(expected) {
+            assert_close(*actual, expected, 1e-14);
+        }
+        assert_eq!(
+            uniform_sample(3, -1.0, 3.0, 136).unwrap(),
+            [2.40056474381961, -0.4659594618998586, 1.135412448866107]
+        );
+
+        assert_eq!(
+            normal_sample(50, 0.0, 1.0, 9).unwrap(),
+            normal_sample(50, 0.0, 1.0, 9).unwrap()
+        );
+        assert_ne!(
+            normal_sample(50, 0.0, 1.0, 9).unwrap(),
+            normal_sample(50, 0.0, 1.0, 10).unwrap()
+        );
+        assert_eq!(
+            binomial_sample(20, 10, 0.3, 9).unwrap(),
+            binomial_sample(20, 10, 0.3, 9).unwrap()
+        );
+    }
+
+    #[test]
+    fn simulate_moments_test() {
+        let normals = normal_sample(100_000, 10.0, 3.0, 1).unwrap();
+        assert!((mean(&normals) - 10.0).abs() < 0.05);
+        assert!((sample_variance(&normals) - 9.0).abs() < 0.15);
+
+        let uniforms = uniform_sample(100_000, 2.0, 5.0, 2).unwrap();
+        assert!(uniforms.iter().all(|u| (2.0..5.0).contains(u)));
+        assert!((mean(&uniforms) - 3.5).abs() < 0.02);
+        assert!((sample_variance(&uniforms) - 0.75).abs() < 0.02);
+
+        let counts = binomial_sample(20_000, 12, 0.25, 3).unwrap();
+        assert!(counts.iter().all(|&k| k <= 12));
+        let counts: Vec<f64> = counts.iter().map(|&k| k as f64).collect();
+        assert!((mean(&counts) - 3.0).abs() < 0.05);
+        assert!((sample_variance(&counts) - 2.25).abs() < 0.1);
+
+        assert_eq!(binomial_sample(3, 7, 0.0, 4).unwrap(), [0, 0, 0]);
+        assert_eq!(binomial_sample(3, 7, 1.0, 4).unwrap(), [7, 7, 7]);
+        assert_eq!(normal_sample(2, 1.5, 0.0, 5).unwrap(), [1.5, 1.5]);
+    }
+
+    #[test]
+    fn simulate_invalid_parameter_test() {
+        let mut simulator = Simulator::new(0);
+        for result in [
+            simulator.uniform(3.0, 1.0),
+            simulator.uniform(0.0, f64::INFINITY),
+            simulator.normal(f64::NAN, 1.0),
+            simulator.normal(0.0, -1.0),
+        ] {
+            assert!(matches!(result, Err(StatsError::InvalidParameter(_))));
+        }
+        assert!(matches!(
+            simulator.binomial(5, 1.5),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        // Checked before any draw, so an empty sample still fails
+        assert!(matches!(
+            binomial_sample(0, 5, -0.5, 0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(normal_sample(0, 0.0, -1.0, 0).is_err());
+        assert!(uniform_sample(0, 1.0, 0.0, 0).is_err());
+    }
+
+    #[test]
+    #[ignore = "simulates a thousand datasets"]
+    fn t_test_size_under_null_test() {
+        // With the null true, a test at alpha = 0.05 should reject in about
+        // 5% of 1000 datasets: 50, with a binomial sd of about 7
+        let mut simulator = Simulator::new(136);
+        let datasets = 1000;
+        let mut one_sample = 0;
+        let mut two_sample = 0;
+        for _ in 0..datasets {
+            let x: Vec<f64> = (0..15)
+                .map(|_| simulator.normal(3.0, 2.0).unwrap())
+                .collect();
+            let y: Vec<f64> = (0..25)
+                .map(|_| simulator.normal(3.0, 5.0).unwrap())
+                .collect();
+            if one_samp_t_test(&x, 3.0).unwrap().p_value < 0.05 {
+                one_sample += 1;
+            }
+            let welch = welch_t_test(
+                &SampleStatistics::from_array(&x),
+                &SampleStatistics::from_array(&y),
+            );
+            if welch.p_value < 0.05 {
+                two_sample += 1;
+            }
+        }
+        for rejections in [one_sample, two_sample] {
+            assert!((30..=70).contains(&rejections), "{rejections}");
+        }
+    }
+}
@@ -0,0 +1,568 @@
+// Some crate-private items here serve only the tests that need std.
+#![cfg_attr(not(feature = "std"), allow(dead_code))]
+
+#[cfg(not(feature = "std"))]
+use crate::math::F64Ext;
+
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+const EPSILON: f64 = 1e-15;
+const TINY: f64 = 1e-300;
+const MAX_ITERATIONS: usize = 500;
+
+/// Below this point erfc is computed from the erf series, above it from a
+/// continued fraction so the upper tail keeps full relative precision.
+const ERFC_CONTINUED_FRACTION_CUTOFF: f64 = 1.5;
+
+/// The error function, erf(x) = 2/sqrt(pi) ∫_0^x exp(-t^2) dt.
+pub fn erf(x: f64) -> f64 {
+    if x < 0.0 {
+        -erf(-x)
+    } else if x < ERFC_CONTINUED_FRACTION_CUTOFF {
+        erf_series(x)
+    } else {
+        1.0 - erfc_continued_fraction(x)
+    }
+}
+
+/// The complementary error function, 1 - erf(x), accurate far into the
+/// upper tail where 1 - erf(x) would round to zero.
+pub fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        2.0 - erfc(-x)
+    } else if x < ERFC_CONTINUED_FRACTION_CUTOFF {
+        1.0 - erf_series(x)
+    } else {
+        erfc_continued_fraction(x)
+    }
+}
+
+/// erf(x) = 2/sqrt(pi) exp(-x^2) sum 2^n x^(2n+1) / (1 * 3 * ... * (2n+1)), a
+/// series with only positive terms for x >= 0.
+fn erf_series(x: f64) -> f64 {
+    let mut term = x;
+    let mut sum = x;
+    for n in 1..=MAX_ITERATIONS {
+        term *= 2.0 * x * x / (2 * n + 1) as f64;
+        sum += term;
+        if term < sum * EPSILON {
+            break;
+        }
+    }
+
+    2.0 / core::f64::consts::PI.sqrt() * (-x * x).exp() * sum
+}
+
+/// erfc(x) = exp(-x^2)/sqrt(pi) / (x + (1/2)/(x + 1/(x + (3/2)/(x + ...)))),
+/// evaluated with the modified Lentz method. Converges quickly for x >= 1.5.
+fn erfc_continued_fraction(x: f64) -> f64 {
+    let mut f = x;
+    let mut c = x;
+    let mut d = 0.0;
+    for k in 1..=MAX_ITERATIONS {
+        let a = k as f64 / 2.0;
+        d = x + a * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = x + a / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = c * d;
+        f *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    (-x * x).exp() / core::f64::consts::PI.sqrt() / f
+}
+
+/// Natural logarithm of the gamma function, using the Lanczos approximation.
+pub fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection formula
+        let pi = core::f64::consts::PI;
+        return (pi / (pi * x).sin()).abs().ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let mut sum = LANCZOS_COEFFICIENTS[0];
+    for (i, c) in LANCZOS_COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += c / (x + i as f64);
+    }
+
+    let t = x + LANCZOS_G + 0.5;
+    0.5 * (2.0 * core::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Regularized incomplete beta function I_x(a, b).
+pub fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    // The continued fraction converges quickly only below this point, so use
+    // the symmetry I_x(a, b) = 1 - I_(1-x)(b, a) above it.
+    if x > (a + 1.0) / (a + b + 2.0) {
+        return 1.0 - regularized_incomplete_beta(b, a, 1.0 - x);
+    }
+
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (-x).ln_1p();
+    ln_front.exp() * beta_continued_fraction(a, b, x) / a
+}
+
+/// Inverse of the regularized incomplete beta function: the x with
+/// I_x(a, b) = p. Starts from the initial guesses in Numerical Recipes
+/// (`invbetai`) and refines with Halley's method.
+pub fn inverse_regularized_incomplete_beta(a: f64, b: f64, p: f64) -> f64 {
+    if p <= 0.0 {
+        return 0.0;
+    }
+    if p >= 1.0 {
+        return 1.0;
+    }
+
+    let mut x = if a >= 1.0 && b >= 1.0 {
+        let pp = if p < 0.5 { p } else { 1.0 - p };
+        let t = (-2.0 * pp.ln()).sqrt();
+        let mut z = (2.30753 + t * 0.27061) / (1.0 + t * (0.99229 + t * 0.04481)) - t;
+        if p < 0.5 {
+            z = -z;
+        }
+        let al = (z * z - 3.0) / 6.0;
+        let h = 2.0 / (1.0 / (2.0 * a - 1.0) + 1.0 / (2.0 * b - 1.0));
+        let w = z * (al + h).sqrt() / h
+            - (1.0 / (2.0 * b - 1.0) - 1.0 / (2.0 * a - 1.0)) * (al + 5.0 / 6.0 - 2.0 / (3.0 * h));
+        a / (a + b * (2.0 * w).exp())
+    } else {
+        let t = (a * (a / (a + b)).ln()).exp() / a;
+        let u = (b * (b / (a + b)).ln()).exp() / b;
+        let w = t + u;
+        if p < t / w {
+            (a * w * p).powf(1.0 / a)
+        } else {
+            1.0 - (b * w * (1.0 - p)).powf(1.0 / b)
+        }
+    };
+
+    let ln_beta = ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b);
+    for i in 0..MAX_ITERATIONS {
+        if x == 0.0 || x == 1.0 {
+            return x;
+        }
+        let error = regularized_incomplete_beta(a, b, x) - p;
+        let density = ((a - 1.0) * x.ln() + (b - 1.0) * (-x).ln_1p() - ln_beta).exp();
+        let u = error / density;
+        let step = u / (1.0 - 0.5 * (u * ((a - 1.0) / x - (b - 1.0) / (1.0 - x))).min(1.0));
+        x -= step;
+        if x <= 0.0 {
+            x = 0.5 * (x + step);
+        }
+        if x >= 1.0 {
+            x = 0.5 * (x + step + 1.0);
+        }
+        if step.abs() < 1e-14 * x && i > 0 {
+            break;
+        }
+    }
+
+    x
+}
+
+/// Regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x), where P
+/// is the regularized lower incomplete gamma function.
+pub fn regularized_upper_incomplete_gamma(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < a + 1.0 {
+        1.0 - gamma_series(a, x)
+    } else {
+        gamma_continued_fraction(a, x)
+    }
+}
+
+/// Regularized lower incomplete gamma function P(a, x).
+pub fn regularized_lower_incomplete_gamma(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x < a + 1.0 {
+        gamma_series(a, x)
+    } else {
+        1.0 - gamma_continued_fraction(a, x)
+    }
+}
+
+/// Inverse of the regularized lower incomplete gamma function: the x with
+/// P(a, x) = p. Starts from the initial guesses in Numerical Recipes
+/// (`invgammp`) and refines with Halley's method.
+pub fn inverse_regularized_lower_incomplete_gamma(a: f64, p: f64) -> f64 {
+    if p <= 0.0 {
+        return 0.0;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let a1 = a - 1.0;
+    let ln_gamma_a = ln_gamma(a);
+    let mut x = if a > 1.0 {
+        let pp = if p < 0.5 { p } else { 1.0 - p };
+        let t = (-2.0 * pp.ln()).sqrt();
+        let mut z = (2.30753 + t * 0.27061) / (1.0 + t * (0.99229 + t * 0.04481)) - t;
+        if p < 0.5 {
+            z = -z;
+        }
+        (a * (1.0 - 1.0 / (9.0 * a) - z / (3.0 * a.sqrt())).powi(3)).max(1e-3)
+    } else {
+        let t = 1.0 - a * (0.253 + a * 0.12);
+        if p < t {
+            (p / t).powf(1.0 / a)
+        } else {
+            1.0 - (1.0 - (p - t) / (1.0 - t)).ln()
+        }
+    };
+
+    for _ in 0..MAX_ITERATIONS {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        let error = regularized_lower_incomplete_gamma(a, x) - p;
+        let density = (-x + a1 * x.ln() - ln_gamma_a).exp();
+        let u = error / density;
+        let step = u / (1.0 - 0.5 * (u * (a1 / x - 1.0)).min(1.0));
+        x -= step;
+        if x <= 0.0 {
+            x = 0.5 * (x + step);
+        }
+        if step.abs() < 1e-14 * x {
+            break;
+        }
+    }
+
+    x
+}
+
+/// Series expansion of P(a, x), which converges quickly for x < a + 1.
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let mut ap = a;
+    let mut term = 1.0 / a;
+    let mut sum = term;
+    for _ in 0..gamma_iterations(a) {
+        ap += 1.0;
+        term *= x / ap;
+        sum += term;
+        if term.abs() < sum.abs() * EPSILON {
+            break;
+        }
+    }
+
+    sum * ln_gamma_prefactor(a, x).exp()
+}
+
+/// Continued fraction for Q(a, x), which converges quickly for x >= a + 1.
+fn gamma_continued_fraction(a: f64, x: f64) -> f64 {
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..=gamma_iterations(a) {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    ln_gamma_prefactor(a, x).exp() * h
+}
+
+/// Near x = a both the series and the continued fraction need on the order
+/// of sqrt(a) terms, which exceeds `MAX_ITERATIONS` for a in the thousands.
+fn gamma_iterations(a: f64) -> usize {
+    MAX_ITERATIONS.max(10 * a.sqrt() as usize)
+}
+
+/// Above this a, `ln_gamma_prefactor` uses Stirling's series.
+const STIRLING_CUTOFF: f64 = 10.0;
+
+/// ln(x^a e^-x / Γ(a)), the common factor of the incomplete gamma series
+/// and continued fraction. For large a, a ln x - x and ln Γ(a) are large and
+/// nearly cancel, so the difference is rewritten with Stirling's series as
+/// a ln(x / a) - (x - a) + ln(a / 2π) / 2 - (ln Γ(a) - Stirling(a)).
+fn ln_gamma_prefactor(a: f64, x: f64) -> f64 {
+    if a < STIRLING_CUTOFF {
+        return a * x.ln() - x - ln_gamma(a);
+    }
+    let d = (x - a) / a;
+    let r = 1.0 / (a * a);
+    let stirling_error =
+        (1.0 / 12.0 - r * (1.0 / 360.0 - r * (1.0 / 1260.0 - r * (1.0 / 1680.0 - r / 1188.0)))) / a;
+    -a * (d - d.ln_1p()) + (a / (2.0 * core::f64::consts::PI)).ln() / 2.0 - stirling_error
+}
+
+/// Continued fraction for the incomplete beta function, evaluated with the
+/// modified Lentz method.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let qab = a + b;
+    let qap = a + 1.0;
+    let qam = a - 1.0;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn erfc_test() {
+        assert_eq!(erfc(0.0), 1.0);
+        assert_close(erfc(1.0), 0.157299207050285, 1e-15);
+        assert_close(erfc(-1.0), 1.842700792949715, 1e-15);
+        assert_close(erfc(2.5), 4.06952017444959e-4, 1e-18);
+        assert_close(erfc(5.0), 1.53745979442803e-12, 1e-26);
+        assert_close(erfc(10.0), 2.08848758376254e-45, 1e-59);
+    }
+
+    #[test]
+    fn erf_test() {
+        // mpmath.erf, mpmath.erfc
+        assert_eq!(erf(0.0), 0.0);
+        let cases = [
+            (1e-10, 1.128379167095513e-10),
+            (0.3, 0.3286267594591274),
+            (-0.8, -0.7421009647076605),
+            (1.2, 0.9103139782296354),
+            (2.0, 0.9953222650189527),
+            (3.5, 0.9999992569016277),
+        ];
+        for (x, expected) in cases {
+            assert_close(erf(x), expected, 1e-15 * expected.abs());
+            assert_close(erf(x) + erfc(x), 1.0, 1e-15);
+        }
+        assert_close(erfc(0.3), 0.6713732405408726, 1e-15);
+        assert_close(erfc(-0.8), 1.74210096470766, 1e-15);
+        assert_close(
+            erfc(3.5),
+            7.430983723414127e-7,
+            1e-13 * 7.430983723414127e-7,
+        );
+        assert_close(
+            erfc(20.0),
+            5.395865611607901e-176,
+            1e-13 * 5.395865611607901e-176,
+        );
+    }
+
+    #[test]
+    fn ln_gamma_test() {
+        assert_close(ln_gamma(1.0), 0.0, 1e-14);
+        assert_close(ln_gamma(2.0), 0.0, 1e-14);
+        assert_close(ln_gamma(5.0), 24.0_f64.ln(), 1e-13);
+        assert_close(ln_gamma(0.5), core::f64::consts::PI.sqrt().ln(), 1e-14);
+        // mpmath.loggamma
+        let cases = [
+            (1e-5, 11.51291969289583),
+            (0.1, 2.252712651734206),
+            (3.7, 1.428072326665388),
+            (100.5, 361.4355404677776),
+            (1e6, 12815504.56914761),
+            (-2.5, -0.05624371649767405),
+        ];
+        for (x, expected) in cases {
+            assert_close(ln_gamma(x), expected, 1e-13 * expected.abs());
+        }
+    }
+
+    #[test]
+    fn regularized_upper_incomplete_gamma_test() {
+        assert_eq!(regularized_upper_incomplete_gamma(2.0, 0.0), 1.0);
+        assert_close(
+            1.0 - regularized_upper_incomplete_gamma(2.5, 1.7),
+            0.361430076896205,
+            1e-14,
+        );
+        assert_close(
+            1.0 - regularized_upper_incomplete_gamma(100.0, 90.0),
+            0.15822098918643,
+            1e-12,
+        );
+        assert_close(
+            1.0 - regularized_upper_incomplete_gamma(0.5, 1e-4),
+            0.0112834155558496,
+            1e-15,
+        );
+        assert_close(
+            regularized_upper_incomplete_gamma(50.0, 120.0),
+            1.60082267933448e-13,
+            1e-25,
+        );
+    }
+
+    #[test]
+    fn incomplete_gamma_reference_test() {
+        // mpmath.gammainc(a, 0, x, regularized=True) and its upper tail,
+        // including large a, where the exponent a ln x - x - ln Γ(a) is the
+        // difference of two large numbers
+        let lower = [
+            (1000.0, 950.0, 0.05505468623073803),
+            (500.5, 510.0, 0.6688936562398025),
+            (10000.0, 9800.0, 0.02220754381396969),
+            (0.01, 1e-3, 0.938570652526129),
+            (3.0, 1e-8, 1.666666654166667e-25),
+        ];
+        for (a, x, expected) in lower {
+            let p = regularized_lower_incomplete_gamma(a, x);
+            assert_close(p, expected, 1e-12 * expected);
+        }
+        let upper = [
+            (1000.0, 1100.0, 0.001059323253929977),
+            (1000.0, 1300.0, 1.873615571578555e-18),
+            (10000.0, 10300.0, 0.001470494896385681),
+            (5.0, 60.0, 5.060046065842574e-21),
+        ];
+        for (a, x, expected) in upper {
+            let q = regularized_upper_incomplete_gamma(a, x);
+            assert_close(q, expected, 1e-12 * expected);
+        }
+    }
+
+    #[test]
+    fn inverse_regularized_lower_incomplete_gamma_test() {
+        assert_eq!(inverse_regularized_lower_incomplete_gamma(2.0, 0.0), 0.0);
+        let cases = [
+            (2.5, 0.3, 1.49995406637995),
+            (0.5, 0.9, 1.35277172704771),
+            (100.0, 0.01, 78.2159830537958),
+            (0.1, 1e-5, 6.07304836240788e-51),
+            (3.0, 0.999, 11.2288722424127),
+        ];
+        for (a, p, expected) in cases {
+            let x = inverse_regularized_lower_incomplete_gamma(a, p);
+            assert_close(x, expected, expected * 1e-12);
+        }
+    }
+
+    #[test]
+    fn regularized_incomplete_beta_test() {
+        assert_eq!(regularized_incomplete_beta(2.0, 3.0, 0.0), 0.0);
+        assert_eq!(regularized_incomplete_beta(2.0, 3.0, 1.0), 1.0);
+        // I_x(1, 1) is the uniform CDF
+        assert_close(regularized_incomplete_beta(1.0, 1.0, 0.3), 0.3, 1e-14);
+        // I_x(2, 3) = 6x^2 - 8x^3 + 3x^4
+        let x: f64 = 0.4;
+        let expected = 6.0 * x.powi(2) - 8.0 * x.powi(3) + 3.0 * x.powi(4);
+        assert_close(regularized_incomplete_beta(2.0, 3.0, x), expected, 1e-14);
+
+        // mpmath.betainc(a, b, 0, x, regularized=True), with x near 0 and 1
+        let cases = [
+            (2.0, 3.0, 1e-10, 5.9999999992e-20),
+            (0.5, 0.5, 1e-12, 6.366197723676874e-7),
+            (0.01, 0.01, 1e-8, 0.4159493165585905),
+            (50.0, 1.5, 0.9, 0.01417533966772805),
+            (50.0, 1.5, 0.999, 0.991771560419598),
+            (200.0, 200.0, 0.45, 0.02254705356853989),
+            (3.5, 7.25, 0.3, 0.4599498772165036),
+        ];
+        for (a, b, x, expected) in cases {
+            let value = regularized_incomplete_beta(a, b, x);
+            assert_close(value, expected, 1e-12 * expected);
+            // I_x(a, b) = 1 - I_(1-x)(b, a), at an x for which 1 - x is exact
+            let y = 1.0 - x;
+            let mirrored =
+                regularized_incomplete_beta(a, b, 1.0 - y) + regularized_incomplete_beta(b, a, y);
+            assert_close(mirrored, 1.0, 1e-14);
+        }
+    }
+
+    #[test]
+    fn inverse_regularized_incomplete_beta_test() {
+        assert_eq!(inverse_regularized_incomplete_beta(2.0, 3.0, 0.0), 0.0);
+        assert_eq!(inverse_regularized_incomplete_beta(2.0, 3.0, 1.0), 1.0);
+        let cases = [
+            (2.0, 3.0, 0.3, 0.272383942075105),
+            (0.5, 0.5, 0.9, 0.975528258147577),
+            (10.0, 0.5, 0.01, 0.711846165002839),
+            (0.2, 5.0, 0.5, 0.00449136140033797),
+            (3.0, 4.0, 1e-12, 3.68413329473887e-5),
+        ];
+        for (a, b, p, expected) in cases {
+            let x = inverse_regularized_incomplete_beta(a, b, p);
+            assert_close(x, expected, expected * 1e-12);
+            assert_close(regularized_incomplete_beta(a, b, x), p, p * 1e-12);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+use crate::descriptive::sorted_quantile;
+use crate::format::significant;
+use crate::{mean, sample_standard_deviation, QuantileMethod, StatsError};
+
+/// The summary a monitoring dashboard shows for a batch of measurements:
+/// count, mean, standard deviation, extremes and chosen percentiles.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SummaryReport {
+    pub count: usize,
+    pub mean: f64,
+    /// Sample standard deviation; NaN for a single value.
+    pub standard_deviation: f64,
+    pub min: f64,
+    pub max: f64,
+    /// `(p, value)` for each requested percentile p, in the order requested.
+    pub percentiles: Vec<(f64, f64)>,
+}
+
+impl SummaryReport {
+    /// Summarizes `data` with the given `percentiles`, each in [0, 100],
+    /// e.g. `&[50.0, 90.0, 95.0, 99.0]`. The data are sorted once and every
+    /// percentile is read from the sorted copy with `QuantileMethod::Linear`,
+    /// as numpy's default `percentile`, so large inputs cost O(n log n).
+    ///
+    /// Returns `StatsError::EmptyInput` for no data,
+    /// `StatsError::NanInInput` if it contains NaN and
+    /// `StatsError::InvalidParameter` for a percentile outside [0, 100].
+    pub fn from_array(data: &[f64], percentiles: &[f64]) -> Result<Self, StatsError> {
+        if data.is_empty() {
+            return Err(StatsError::EmptyInput);
+        }
+        if !percentiles.iter().all(|p| (0.0..=100.0).contains(p)) {
+            return Err(StatsError::InvalidParameter(
+                "percentile must be between 0 and 100",
+            ));
+        }
+        if let Some(index) = data.iter().position(|x| x.is_nan()) {
+            return Err(StatsError::NanInInput { index });
+        }
+
+        let mut sorted = data.to_vec();
+        sorted.sort_unstable_by(f64::total_cmp);
+        Ok(SummaryReport {
+            count: data.len(),
+            mean: mean(data),
+            standard_deviation: sample_standard_deviation(data),
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            percentiles: percentiles
+                .iter()
+                .map(|&p| {
+                    (
+                        p,
+                        sorted_quantile(&sorted, p / 100.0, QuantileMethod::Linear),
+                    )
+                })
+                .collect(),
+        })
+    }
+
+    /// The value of percentile `p`, if it was requested.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        self.percentiles
+            .iter()
+            .find(|&&(requested, _)| requested == p)
+            .map(|&(_, value)| value)
+    }
+}
+
+/// One line per statistic, the label left-aligned and the value, to six
+/// significant digits, right-aligned in a fixed-width column; percentiles
+/// sit between min and max as "p50", "p99.9" and so on.
+impl fmt::Display for SummaryReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut line = |label: &str, value: String| writeln!(f, "{label:<8}{value:>14}");
+        line("count", self.count.to_string())?;
+        line("mean", significant(self.mean, 6))?;
+        line("sd", significant(self.standard_deviation, 6))?;
+        line("min", significant(self.min, 6))?;
+        for &(p, value) in &self.percentiles {
+            line(&format!("p{}", significant(p, 6)), significant(value, 6))?;
+        }
+        line("max", significant(self.max, 6))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, quantiles};
+
+    const LATENCY_PERCENTILES: [f64; 4] = [50.0, 90.0, 95.0, 99.0];
+
+    #[test]
+    fn summary_report_test() {
+        // numpy: np.percentile(np.arange(1, 101), [50, 90, 95, 99])
+        let data: Vec<f64> = (1..=100).map(f64::from).collect();
+        let report = SummaryReport::from_array(&data, &LATENCY_PERCENTILES).unwrap();
+        assert_eq!(report.count, 100);
+        assert_eq!(report.mean, 50.5);
+        assert_close(report.standard_deviation, 29.011491975882016, 1e-13);
+        assert_eq!((report.min, report.max), (1.0, 100.0));
+        let expected = [50.5, 90.1, 95.05, 99.01];
+        for (&(p, value), (requested, expected)) in report
+            .percentiles
+            .iter()
+            .zip(LATENCY_PERCENTILES.iter().zip(expected))
+        {
+            assert_eq!(p, *requested);
+            assert_close(value, expected, 1e-12);
+        }
+        assert_close(report.percentile(99.0).unwrap(), 99.01, 1e-12);
+        assert_eq!(report.percentile(75.0), None);
+
+        let report = SummaryReport::from_array(&[4.0], &[0.0, 100.0]).unwrap();
+        assert_eq!(report.percentiles, [(0.0, 4.0), (100.0, 4.0)]);
+        assert!(report.standard_deviation.is_nan());
+    }
+
+    #[test]
+    fn summary_report_large_input_test() {
+        let mut data: Vec<f64> = (0..1_000_000).map(f64::from).collect();
+        crate::rng::Rng::new(130).shuffle(&mut data);
+        let report = SummaryReport::from_array(&data, &LATENCY_PERCENTILES).unwrap();
+        let qs: Vec<f64> = LATENCY_PERCENTILES.iter().map(|p| p / 100.0).collect();
+        let expected = quantiles(&data, &qs, QuantileMethod::Linear).unwrap();
+        let values: Vec<f64> = report.percentiles.iter().map(|&(_, v)| v).collect();
+        assert_eq!(values, expected);
+        assert_close(report.percentile(50.0).unwrap(), 499999.5, 1e-9);
+        assert_eq!((report.min, report.max), (0.0, 999999.0));
+    }
+
+    #[test]
+    fn summary_report_display_test() {
+        let data = [12.5, 7.25, 30.0, 9.75, 14.0, 8.5, 11.0, 21.0];
+        let report = SummaryReport::from_array(&data, &[50.0, 90.0, 99.9]).unwrap();
+        assert_eq!(
+            report.to_string(),
+            "count                8\n\
+             mean             14.25\n\
+             sd              7.6497\n\
+             min               7.25\n\
+             p50              11.75\n\
+             p90               23.7\n\
+             p99.9           29.937\n\
+             max                 30\n"
+        );
+    }
+
+    #[test]
+    fn summary_report_invalid_input_test() {
+        assert_eq!(
+            SummaryReport::from_array(&[], &[50.0]),
+            Err(StatsError::EmptyInput)
+        );
+        for p in [-1.0, 100.5, f64::NAN] {
+            assert!(matches!(
+                SummaryReport::from_array(&[1.0, 2.0], &[50.0, p]),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        assert_eq!(
+            SummaryReport::from_array(&[1.0, f64::NAN], &[50.0]),
+            Err(StatsError::NanInInput { index: 1 })
+        );
+    }
+}
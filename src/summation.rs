@@ -0,0 +1,97 @@
+/// A running sum with Neumaier's compensation: the rounding error of each
+/// addition is collected separately and added back at the end, so long sums
+/// of values with a large common offset keep nearly full precision.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    pub(crate) fn add(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.sum.abs() >= x.abs() {
+            self.compensation += (self.sum - t) + x;
+        } else {
+            self.compensation += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    /// The compensated total. The compensation is NaN once the sum has
+    /// overflowed or met an infinity, so it is dropped then.
+    pub(crate) fn total(&self) -> f64 {
+        if self.sum.is_finite() {
+            self.sum + self.compensation
+        } else {
+            self.sum
+        }
+    }
+}
+
+/// `a + b` and its rounding error, exactly: the pair sums to `a + b`.
+pub(crate) fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_part = sum - a;
+    (sum, (a - (sum - b_part)) + (b - b_part))
+}
+
+/// `a * b` and its rounding error, by Dekker's splitting into 26-bit
+/// halves, which needs no fused multiply-add and so works without `std`.
+pub(crate) fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    let (a_high, a_low) = split(a);
+    let (b_high, b_low) = split(b);
+    let error = ((a_high * b_high - product) + a_high * b_low + a_low * b_high) + a_low * b_low;
+    (product, error)
+}
+
+fn split(x: f64) -> (f64, f64) {
+    let scaled = 134_217_729.0 * x;
+    let high = scaled - (scaled - x);
+    (high, x - high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compensated_sum_test() {
+        let mut sum = CompensatedSum::default();
+        for x in [1.0, 1e100, 1.0, -1e100] {
+            sum.add(x);
+        }
+        assert_eq!(sum.total(), 2.0);
+
+        let mut naive = 0.0;
+        let mut sum = CompensatedSum::default();
+        for _ in 0..10 {
+            naive += 0.1;
+            sum.add(0.1);
+        }
+        assert_ne!(naive, 1.0);
+        assert_eq!(sum.total(), 1.0);
+
+        let mut sum = CompensatedSum::default();
+        for x in [1.0, f64::INFINITY, 3.0] {
+            sum.add(x);
+        }
+        assert_eq!(sum.total(), f64::INFINITY);
+    }
+
+    #[test]
+    fn error_free_transformation_test() {
+        assert_eq!(two_sum(1.0, 1e-17), (1.0, 1e-17));
+        assert_eq!(
+            two_sum(0.1, 0.2),
+            (0.30000000000000004, -2.7755575615628914e-17)
+        );
+        // 0.1² = 0.010000000000000002 - 8.326672684688674e-19 exactly
+        assert_eq!(
+            two_product(0.1, 0.1),
+            (0.010000000000000002, -8.326672684688674e-19)
+        );
+        assert_eq!(two_product(3.0, 7.0), (21.0, 0.0));
+    }
+}
@@ -0,0 +1,980 @@
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+#[cfg(feature = "std")]
+use crate::cohens_d;
+use crate::confidence::validate_confidence;
+use crate::distributions::StudentsT;
+#[cfg(feature = "alloc")]
+use crate::format::{p_value, significant, write_test_line};
+use crate::hypothesis::validate_alpha;
+#[cfg(not(feature = "std"))]
+use crate::math::F64Ext;
+use crate::summation::CompensatedSum;
+use crate::{
+    mean, Alternative, ConfidenceInterval, GetStatistics, HypothesisTest, SampleStatistics,
+    StatsError,
+};
+
+/// Confidence level of the interval in `TTestResult::report`.
+#[cfg(feature = "alloc")]
+const REPORT_CONFIDENCE: f64 = 0.95;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TTestResult {
+    pub t: f64,
+    pub df: f64,
+    pub p_value: f64,
+    /// The estimated difference being tested, e.g. x̄ - μ0 or x̄1 - x̄2.
+    pub mean_difference: f64,
+    /// Standard error of `mean_difference`.
+    pub standard_error: f64,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for TTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "t", &[self.df], self.t, self.p_value)
+    }
+}
+
+impl HypothesisTest for TTestResult {
+    fn statistic(&self) -> f64 {
+        self.t
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "t-test"
+    }
+}
+
+impl TTestResult {
+    /// Whether the p-value is below the significance level `alpha`.
+    pub fn is_significant(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+
+    /// Whether the null hypothesis is rejected at level `alpha`; the same as
+    /// `is_significant`.
+    pub fn reject_null(&self, alpha: f64) -> bool {
+        self.is_significant(alpha)
+    }
+
+    /// The same test against a one-sided or two-sided `alternative`, with
+    /// the p-value recomputed from `t` and `df`. The t-test functions are
+    /// two-sided; `Greater` tests that the true difference exceeds zero, e.g.
+    /// `one_samp_t_test(&x, mu0)?.with_alternative(Alternative::Greater)`
+    /// tests μ > μ0.
+    pub fn with_alternative(self, alternative: Alternative) -> TTestResult {
+        TTestResult {
+            p_value: StudentsT::new(self.df).p_value(self.t, alternative),
+            ..self
+        }
+    }
+
+    /// A multi-line summary in the manner of R's printed `t.test`: the test
+    /// line, the estimated difference with its standard error, and a 95%
+    /// confidence interval for it.
+    #[cfg(feature = "alloc")]
+    pub fn report(&self) -> String {
+        let t = StudentsT::new(self.df).quantile(0.5 + REPORT_CONFIDENCE / 2.0);
+        let margin = t * self.standard_error;
+        let interval = ConfidenceInterval {
+            lower: self.mean_difference - margin,
+            upper: self.mean_difference + margin,
+            point_estimate: self.mean_difference,
+            confidence: REPORT_CONFIDENCE,
+        };
+        format!(
+            "{self}\nmean difference = {}, standard error = {}\n{interval}",
+            significant(self.mean_difference, 3),
+            significant(self.standard_error, 3)
+        )
+    }
+}
+
+/// Which assumption about the two population variances a two-sample t-test makes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TTestKind {
+    /// Classical Student's t-test with a pooled variance estimate.
+    EqualVariance,
+    /// Welch's t-test.
+    UnequalVariance,
+}
+
+pub fn two_samp_t_test(samp_1: SampleStatistics, samp_2: SampleStatistics) -> TTestResult {
+    two_samp_t_test_with(&samp_1, &samp_2, TTestKind::UnequalVariance)
+}
+
+pub fn two_samp_t_test_with(
+    samp_1: &SampleStatistics,
+    samp_2: &SampleStatistics,
+    kind: TTestKind,
+) -> TTestResult {
+    match kind {
+        TTestKind::EqualVariance => pooled_t_test(samp_1, samp_2),
+        TTestKind::UnequalVariance => welch_t_test(samp_1, samp_2),
+    }
+}
+
+/// Student's t-test assuming equal variances, with df = n1 + n2 - 2.
+pub fn pooled_t_test(samp_1: &SampleStatistics, samp_2: &SampleStatistics) -> TTestResult {
+    let mean_delta = samp_1.sample_mean - samp_2.sample_mean;
+    let (standard_error, df) =
+        mean_difference_standard_error(samp_1, samp_2, TTestKind::EqualVariance);
+    let t = mean_delta / standard_error;
+
+    let p_value = StudentsT::new(df).two_tailed(t);
+
+    TTestResult {
+        t,
+        df,
+        p_value,
+        mean_difference: mean_delta,
+        standard_error,
+    }
+}
+
+/// `TTestResult::report` for a two-sample test, headed by the kind of test
+/// and followed by the group means and Cohen's d.
+#[cfg(feature = "std")]
+pub fn two_samp_t_test_report(
+    samp_1: &SampleStatistics,
+    samp_2: &SampleStatistics,
+    kind: TTestKind,
+) -> String {
+    let title = match kind {
+        TTestKind::EqualVariance => "Two-sample t-test",
+        TTestKind::UnequalVariance => "Welch two-sample t-test",
+    };
+    format!(
+        "{title}\n{}\nmeans = {} and {}\nCohen's d = {}",
+        two_samp_t_test_with(samp_1, samp_2, kind).report(),
+        significant(samp_1.sample_mean, 3),
+        significant(samp_2.sample_mean, 3),
+        significant(cohens_d(samp_1, samp_2), 3)
+    )
+}
+
+/// Welch's unequal-variance t-test, with Welch-Satterthwaite degrees of freedom.
+pub fn welch_t_test(samp_1: &SampleStatistics, samp_2: &SampleStatistics) -> TTestResult {
+    let mean_delta = samp_1.sample_mean - samp_2.sample_mean;
+    let (standard_error, df) =
+        mean_difference_standard_error(samp_1, samp_2, TTestKind::UnequalVariance);
+    let t = mean_delta / standard_error;
+
+    let p_value = StudentsT::new(df).two_tailed(t);
+
+    TTestResult {
+        t,
+        df,
+        p_value,
+        mean_difference: mean_delta,
+        standard_error,
+    }
+}
+
+/// Two-sided confidence interval for μ1 - μ2, using the same standard error
+/// and degrees of freedom as the matching t-test, so it excludes zero exactly
+/// when that test's p-value is below 1 - `confidence`.
+pub fn mean_diff_confidence_interval(
+    samp_1: &SampleStatistics,
+    samp_2: &SampleStatistics,
+    confidence: f64,
+    kind: TTestKind,
+) -> Result<ConfidenceInterval, StatsError> {
+    for samp in [samp_1, samp_2] {
+        if samp.n < 2 {
+            return Err(StatsError::InsufficientData {
+                required: 2,
+                actual: samp.n,
+            });
+        }
+    }
+    validate_confidence(confidence)?;
+
+    let mean_difference = samp_1.sample_mean - samp_2.sample_mean;
+    let (standard_error, df) = mean_difference_standard_error(samp_1, samp_2, kind);
+    let margin = StudentsT::new(df).quantile(0.5 + confidence / 2.0) * standard_error;
+    Ok(ConfidenceInterval {
+        lower: mean_difference - margin,
+        upper: mean_difference + margin,
+        point_estimate: mean_difference,
+        confidence,
+    })
+}
+
+/// Standard error of x̄1 - x̄2 and its degrees of freedom: n1 + n2 - 2 with a
+/// pooled variance, or Welch-Satterthwaite otherwise.
+fn mean_difference_standard_error(
+    samp_1: &SampleStatistics,
+    samp_2: &SampleStatistics,
+    kind: TTestKind,
+) -> (f64, f64) {
+    let n1 = samp_1.n as f64;
+    let n2 = samp_2.n as f64;
+    match kind {
+        TTestKind::EqualVariance => {
+            let df = n1 + n2 - 2.0;
+            let pooled_variance =
+                ((n1 - 1.0) * samp_1.variance() + (n2 - 1.0) * samp_2.variance()) / df;
+            ((pooled_variance * (1.0 / n1 + 1.0 / n2)).sqrt(), df)
+        }
+        TTestKind::UnequalVariance => {
+            let v1 = samp_1.variance() / n1;
+            let v2 = samp_2.variance() / n2;
+            let df = (v1 + v2).powi(2) / (v1.powi(2) / (n1 - 1.0) + v2.powi(2) / (n2 - 1.0));
+            ((v1 + v2).sqrt(), df)
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TostResult {
+    /// t statistic of the test that the difference exceeds `lower_bound`.
+    pub t_lower: f64,
+    /// Upper-tail p-value for `t_lower`.
+    pub p_lower: f64,
+    /// t statistic of the test that the difference is below `upper_bound`.
+    pub t_upper: f64,
+    /// Lower-tail p-value for `t_upper`.
+    pub p_upper: f64,
+    pub df: f64,
+    pub mean_difference: f64,
+    /// The larger of the two one-sided p-values.
+    pub p_value: f64,
+    /// Whether equivalence is established, p_value < alpha.
+    pub equivalent: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for TostResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "t({}) = {} and {}, {}",
+            significant(self.df, 3),
+            significant(self.t_lower, 3),
+            significant(self.t_upper, 3),
+            p_value(self.p_value)
+        )
+    }
+}
+
+impl HypothesisTest for TostResult {
+    fn statistic(&self) -> f64 {
+        if self.p_lower >= self.p_upper {
+            self.t_lower
+        } else {
+            self.t_upper
+        }
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "TOST equivalence test"
+    }
+}
+
+/// Two one-sided tests (TOST) for equivalence of two means within
+/// [`lower_bound`, `upper_bound`], using Welch t-tests as TOSTER's
+/// `tsum_TOST` does by default. Equivalence is concluded when both
+/// one-sided tests reject at `alpha`.
+pub fn tost_equivalence(
+    samp_1: &SampleStatistics,
+    samp_2: &SampleStatistics,
+    lower_bound: f64,
+    upper_bound: f64,
+    alpha: f64,
+) -> Result<TostResult, StatsError> {
+    for samp in [samp_1, samp_2] {
+        if samp.n < 2 {
+            return Err(StatsError::InsufficientData {
+                required: 2,
+                actual: samp.n,
+            });
+        }
+    }
+    let (standard_error, df) =
+        mean_difference_standard_error(samp_1, samp_2, TTestKind::UnequalVariance);
+    tost(
+        samp_1.sample_mean - samp_2.sample_mean,
+        standard_error,
+        df,
+        lower_bound,
+        upper_bound,
+        alpha,
+    )
+}
+
+/// TOST equivalence test on the paired differences `before[i] - after[i]`.
+#[cfg(feature = "alloc")]
+pub fn tost_paired(
+    before: &[f64],
+    after: &[f64],
+    lower_bound: f64,
+    upper_bound: f64,
+    alpha: f64,
+) -> Result<TostResult, StatsError> {
+    if before.len() != after.len() {
+        return Err(StatsError::MismatchedLengths {
+            left: before.len(),
+            right: after.len(),
+        });
+    }
+    if before.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: before.len(),
+        });
+    }
+    let differences: Vec<f64> = before.iter().zip(after).map(|(a, b)| a - b).collect();
+    let samp = SampleStatistics::from_array(&differences);
+    tost(
+        samp.sample_mean,
+        samp.standard_error(),
+        samp.n as f64 - 1.0,
+        lower_bound,
+        upper_bound,
+        alpha,
+    )
+}
+
+fn tost(
+    mean_difference: f64,
+    standard_error: f64,
+    df: f64,
+    lower_bound: f64,
+    upper_bound: f64,
+    alpha: f64,
+) -> Result<TostResult, StatsError> {
+    if lower_bound >= upper_bound {
+        return Err(StatsError::InvalidParameter(
+            "lower equivalence bound must be below the upper bound",
+        ));
+    }
+    validate_alpha(alpha)?;
+
+    let distribution = StudentsT::new(df);
+    let t_lower = (mean_difference - lower_bound) / standard_error;
+    let t_upper = (mean_difference - upper_bound) / standard_error;
+    let p_lower = distribution.sf(t_lower);
+    let p_upper = distribution.cdf(t_upper);
+    let p_value = p_lower.max(p_upper);
+
+    Ok(TostResult {
+        t_lower,
+        p_lower,
+        t_upper,
+        p_upper,
+        df,
+        mean_difference,
+        p_value,
+        equivalent: p_value < alpha,
+    })
+}
+
+pub fn one_samp_t_test(array: &[f64], mu0: f64) -> Result<TTestResult, StatsError> {
+    one_samp_t_test_from_stats(&SampleStatistics::from_array(array), mu0)
+}
+
+pub fn one_samp_t_test_from_stats(
+    samp: &SampleStatistics,
+    mu0: f64,
+) -> Result<TTestResult, StatsError> {
+    if samp.n == 0 {
+        return Err(StatsError::EmptyInput);
+    }
+    if samp.n < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: samp.n,
+        });
+    }
+
+    let mean_difference = samp.sample_mean - mu0;
+    let standard_error = samp.standard_error();
+    let t = mean_difference / standard_error;
+    let df = samp.n as f64 - 1.0;
+    let p_value = StudentsT::new(df).two_tailed(t);
+
+    Ok(TTestResult {
+        t,
+        df,
+        p_value,
+        mean_difference,
+        standard_error,
+    })
+}
+
+/// Descriptive statistics of paired observations `(x[i], y[i])`, holding
+/// what the paired t-test, its interval and the paired Cohen's d need.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairedStatistics {
+    pub n: usize,
+    pub mean_x: f64,
+    pub mean_y: f64,
+    /// Sample standard deviation of `x`.
+    pub sd_x: f64,
+    /// Sample standard deviation of `y`.
+    pub sd_y: f64,
+    /// The mean of the differences `x[i] - y[i]`, which is x̄ - ȳ up to
+    /// rounding.
+    pub mean_difference: f64,
+    /// Sample standard deviation of the differences, equal to
+    /// sqrt(sd_x² + sd_y² - 2 r sd_x sd_y).
+    pub sd_difference: f64,
+    /// Pearson's r between `x` and `y`; NaN if either is constant.
+    pub correlation: f64,
+    /// Standard error of the mean difference, sd_difference / sqrt(n). It
+    /// shrinks as the correlation grows, which is what pairing buys.
+    pub standard_error: f64,
+}
+
+impl PairedStatistics {
+    /// Returns `StatsError::MismatchedLengths` if `x` and `y` differ in
+    /// length, `StatsError::EmptyInput` for no pairs and
+    /// `StatsError::InsufficientData` for a single pair.
+    pub fn from_arrays(x: &[f64], y: &[f64]) -> Result<Self, StatsError> {
+        if x.len() != y.len() {
+            return Err(StatsError::MismatchedLengths {
+                left: x.len(),
+                right: y.len(),
+            });
+        }
+        if x.is_empty() {
+            return Err(StatsError::EmptyInput);
+        }
+        if x.len() < 2 {
+            return Err(StatsError::InsufficientData {
+                required: 2,
+                actual: x.len(),
+            });
+        }
+
+        let n = x.len() as f64;
+        let mean_x = mean(x);
+        let mean_y = mean(y);
+        // The differences are taken pair by pair rather than as x̄ - ȳ, which
+        // would cancel when the means are large and close
+        let mut differences = CompensatedSum::default();
+        for (a, b) in x.iter().zip(y) {
+            differences.add(a - b);
+        }
+        let mean_difference = differences.total() / n;
+        let mut sums = [CompensatedSum::default(); 4];
+        for (a, b) in x.iter().zip(y) {
+            let (dx, dy, dd) = (a - mean_x, b - mean_y, (a - b) - mean_difference);
+            for (sum, term) in sums.iter_mut().zip([dx * dx, dy * dy, dx * dy, dd * dd]) {
+                sum.add(term);
+            }
+        }
+        let [sxx, syy, sxy, sdd] = sums.map(|sum| sum.total());
+        let sd_difference = (sdd / (n - 1.0)).sqrt();
+        Ok(PairedStatistics {
+            n: x.len(),
+            mean_x,
+            mean_y,
+            sd_x: (sxx / (n - 1.0)).sqrt(),
+            sd_y: (syy / (n - 1.0)).sqrt(),
+            mean_difference,
+            sd_difference,
+            correlation: sxy / (sxx * syy).sqrt(),
+            standard_error: sd_difference / n.sqrt(),
+        })
+    }
+
+    /// The differences summarized as one sample, for the one-sample
+    /// functions.
+    pub fn differences(&self) -> SampleStatistics {
+        SampleStatistics {
+            sample_mean: self.mean_difference,
+            standard_deviation: self.sd_difference,
+            n: self.n,
+        }
+    }
+}
+
+/// Paired t-test on the differences `x[i] - y[i]` summarized by `stats`.
+pub fn paired_t_test_from_stats(stats: &PairedStatistics) -> Result<TTestResult, StatsError> {
+    one_samp_t_test_from_stats(&stats.differences(), 0.0)
+}
+
+/// Paired t-test on the differences `before[i] - after[i]`.
+pub fn paired_t_test(before: &[f64], after: &[f64]) -> Result<TTestResult, StatsError> {
+    paired_t_test_from_stats(&PairedStatistics::from_arrays(before, after)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn display_test() {
+        let result = TTestResult {
+            t: 2.3114,
+            df: 23.44,
+            p_value: 0.030149,
+            mean_difference: 1.5,
+            standard_error: 0.649,
+        };
+        assert_eq!(result.to_string(), "t(23.4) = 2.31, p = 0.0301");
+        let result = TTestResult {
+            t: -5.2,
+            df: 9.0,
+            p_value: 0.00056,
+            mean_difference: -1.58,
+            standard_error: 0.304,
+        };
+        assert_eq!(result.to_string(), "t(9) = -5.2, p < 0.001");
+    }
+
+    #[test]
+    fn is_significant_test() {
+        // R: t.test(extra ~ group, data = sleep, paired = TRUE)
+        let differences = [1.2, 2.4, 1.3, 1.3, 0.0, 1.0, 1.8, 0.8, 4.6, 1.4];
+        let result = one_samp_t_test(&differences, 0.0).unwrap();
+        assert_close(result.t, 4.06212768338204, 1e-12);
+        assert_eq!(result.df, 9.0);
+        assert_close(result.mean_difference, 1.58, 1e-14);
+        assert_close(result.standard_error, 0.388958723888395, 1e-14);
+        assert!(result.is_significant(0.01));
+        assert!(!result.is_significant(0.001));
+        assert_eq!(result.reject_null(0.01), result.is_significant(0.01));
+
+        let copy = result;
+        assert_eq!(copy, result);
+        assert_ne!(copy.with_alternative(Alternative::Greater), result);
+    }
+
+    #[test]
+    fn alternative_test() {
+        // R: t.test(extra ~ group, data = sleep, alternative = "less") gives
+        // p-value = 0.0397, half the two-sided 0.07939
+        let samp_1 =
+            SampleStatistics::from_array(&[0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0]);
+        let samp_2 =
+            SampleStatistics::from_array(&[1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4]);
+        let less = welch_t_test(&samp_1, &samp_2).with_alternative(Alternative::Less);
+        assert_close(less.p_value, 0.039697, 1e-6);
+
+        // Group 2 sleeps longer, so only the upper tail is small
+        let two_sided = welch_t_test(&samp_2, &samp_1);
+        let greater = welch_t_test(&samp_2, &samp_1).with_alternative(Alternative::Greater);
+        let less = welch_t_test(&samp_2, &samp_1).with_alternative(Alternative::Less);
+        assert!(greater.p_value < 0.05 && less.p_value > 0.95);
+        assert_close(greater.p_value + less.p_value, 1.0, 1e-14);
+        assert_close(
+            two_sided.p_value,
+            2.0 * greater.p_value.min(less.p_value),
+            1e-14,
+        );
+        assert_eq!(greater.t, two_sided.t);
+
+        let differences = [1.2, 2.4, 1.3, 1.3, 0.0, 1.0, 1.8, 0.8, 4.6, 1.4];
+        let paired = one_samp_t_test(&differences, 0.0).unwrap();
+        let greater = one_samp_t_test(&differences, 0.0)
+            .unwrap()
+            .with_alternative(Alternative::Greater);
+        // R: t.test(extra ~ group, data = sleep, paired = TRUE) gives
+        // p-value = 0.002833
+        assert_close(paired.p_value, 0.00283289, 1e-7);
+        assert_close(greater.p_value, paired.p_value / 2.0, 1e-15);
+        let two_sided = greater.with_alternative(Alternative::TwoSided);
+        assert_close(two_sided.p_value, paired.p_value, 1e-15);
+    }
+
+    #[test]
+    fn report_test() {
+        // R: t.test(extra ~ group, data = sleep) gives t = -1.8608,
+        // df = 17.776, p-value = 0.07939 and CI -3.3654832 0.2054832
+        let samp_1 =
+            SampleStatistics::from_array(&[0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0]);
+        let samp_2 =
+            SampleStatistics::from_array(&[1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4]);
+        assert_eq!(
+            two_samp_t_test_report(&samp_1, &samp_2, TTestKind::UnequalVariance),
+            "Welch two-sample t-test\n\
+             t(17.8) = -1.86, p = 0.0794\n\
+             mean difference = -1.58, standard error = 0.849\n\
+             95% CI [-3.37, 0.205]\n\
+             means = 0.75 and 2.33\n\
+             Cohen's d = -0.832"
+        );
+        let pooled = pooled_t_test(&samp_1, &samp_2);
+        assert_eq!(
+            pooled.report(),
+            "t(18) = -1.86, p = 0.0792\n\
+             mean difference = -1.58, standard error = 0.849\n\
+             95% CI [-3.36, 0.204]"
+        );
+    }
+
+    #[test]
+    fn two_samp_t_test_test() {
+        let samp_1 = SampleStatistics::from_array(&[1.0, 2.0, 3.0]);
+        let samp_2 = SampleStatistics::from_array(&[4.0, 4.0, 5.0, 6.0, 6.0]);
+        let result = two_samp_t_test(samp_1, samp_2);
+        assert_close(result.t, -4.10791918128875, 1e-12);
+        assert_close(result.df, 4.33898305084746, 1e-12);
+        assert_close(result.p_value, 0.0124842261161969, 1e-9);
+
+        let samp_1 = SampleStatistics::from_array(&[1.0, 2.0, 3.0]);
+        let samp_2 = SampleStatistics::from_array(&[4.0, 5.0, 6.0]);
+        let result = two_samp_t_test(samp_1, samp_2);
+        assert_eq!(result.df, 4.0);
+        assert_close(result.p_value, 0.0213116411287567, 1e-9);
+    }
+
+    #[test]
+    fn two_samp_t_test_welch_reference_test() {
+        // R: t.test(extra ~ group, data = sleep)
+        let samp_1 =
+            SampleStatistics::from_array(&[0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0]);
+        let samp_2 =
+            SampleStatistics::from_array(&[1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4]);
+        let result = two_samp_t_test(samp_1, samp_2);
+        assert_close(result.t, -1.860813467486853, 1e-12);
+        assert_close(result.df, 17.77647351617849, 1e-12);
+        assert_close(result.p_value, 0.07939414018735814, 1e-9);
+
+        let samp_1 = SampleStatistics::from_array(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let samp_2 = SampleStatistics::from_array(&[2.0, 4.0, 6.0, 8.0, 10.0, 12.0]);
+        let result = two_samp_t_test(samp_1, samp_2);
+        assert_close(result.t, -2.376354103144018, 1e-12);
+        assert_close(result.df, 6.972255729794934, 1e-12);
+        assert_close(result.p_value, 0.04928433820673052, 1e-9);
+    }
+
+    #[test]
+    fn welch_t_test_unequal_variance_test() {
+        let samp_1 =
+            SampleStatistics::from_array(&[9.0, 10.0, 11.0, 10.5, 9.5, 10.2, 9.8, 10.1, 9.9, 10.0]);
+        let samp_2 = SampleStatistics::from_array(&[-100.0, 50.0, 200.0, 10.0, 120.0]);
+        let result = welch_t_test(&samp_1, &samp_2);
+        assert_close(result.t, -0.9080861688810857, 1e-12);
+        // Dominated by the high-variance sample, so df collapses to about n_2 - 1
+        assert_close(result.df, 4.000090066965649, 1e-12);
+        assert_close(result.p_value, 0.415194252246686, 1e-9);
+    }
+
+    #[test]
+    fn pooled_t_test_test() {
+        // R: t.test(extra ~ group, data = sleep, var.equal = TRUE)
+        let samp_1 =
+            SampleStatistics::from_array(&[0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0]);
+        let samp_2 =
+            SampleStatistics::from_array(&[1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4]);
+        let result = two_samp_t_test_with(&samp_1, &samp_2, TTestKind::EqualVariance);
+        assert_close(result.t, -1.860813467486853, 1e-12);
+        assert_eq!(result.df, 18.0);
+        assert_close(result.p_value, 0.0791867142159381, 1e-9);
+    }
+
+    #[test]
+    fn t_test_kinds_test() {
+        let samp_1 = SampleStatistics::from_array(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let samp_2 = SampleStatistics::from_array(&[2.0, 4.0, 6.0, 8.0, 10.0, 12.0]);
+        let pooled = two_samp_t_test_with(&samp_1, &samp_2, TTestKind::EqualVariance);
+        let welch = two_samp_t_test_with(&samp_1, &samp_2, TTestKind::UnequalVariance);
+        assert_close(pooled.t, -2.215646837627989, 1e-12);
+        assert_close(pooled.p_value, 0.05394592050940717, 1e-9);
+        assert_close(welch.t, -2.376354103144018, 1e-12);
+        assert!(pooled.df != welch.df);
+
+        // With equal sizes and variances the two tests coincide
+        let samp_1 = SampleStatistics::from_array(&[1.0, 2.0, 3.0]);
+        let samp_2 = SampleStatistics::from_array(&[5.0, 6.0, 7.0]);
+        let pooled = two_samp_t_test_with(&samp_1, &samp_2, TTestKind::EqualVariance);
+        let welch = two_samp_t_test_with(&samp_1, &samp_2, TTestKind::UnequalVariance);
+        assert_close(pooled.t, welch.t, 1e-12);
+        assert_close(pooled.df, welch.df, 1e-12);
+        assert_close(pooled.p_value, welch.p_value, 1e-12);
+        assert_close(pooled.p_value, 0.00804989310083772, 1e-9);
+    }
+
+    #[test]
+    fn two_samp_t_test_extreme_t_test() {
+        let samp_1 = SampleStatistics::from_array(&[1.0, 2.0, 3.0]);
+        let samp_2 = SampleStatistics::from_array(&[1.0, 2.0, 3.0]);
+        let result = two_samp_t_test(samp_1, samp_2);
+        assert_eq!(result.t, 0.0);
+        assert_eq!(result.p_value, 1.0);
+
+        let samp_1 = SampleStatistics::from_array(&[1e6, 1e6 + 1.0, 1e6 + 2.0]);
+        let samp_2 = SampleStatistics::from_array(&[1.0, 2.0, 3.0]);
+        let result = two_samp_t_test(samp_1, samp_2);
+        assert!(!result.p_value.is_nan());
+        assert!(result.p_value < 1e-10);
+    }
+
+    #[test]
+    fn one_samp_t_test_test() {
+        let result = one_samp_t_test(&[5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 4.7, 5.5], 5.0).unwrap();
+        assert_close(result.t, 2.293675494561535, 1e-12);
+        assert_eq!(result.df, 7.0);
+        assert_close(result.p_value, 0.05550602704909647, 1e-9);
+
+        let sleep = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+        let result = one_samp_t_test(&sleep, 0.0).unwrap();
+        assert_close(result.t, 1.325710140713821, 1e-12);
+        assert_eq!(result.df, 9.0);
+        assert_close(result.p_value, 0.2175977800684489, 1e-9);
+
+        let result = one_samp_t_test(&[1.0, 2.0, 3.0, 4.0, 5.0], 3.0).unwrap();
+        assert_eq!(result.t, 0.0);
+        assert_eq!(result.p_value, 1.0);
+    }
+
+    #[test]
+    fn one_samp_t_test_difference_test() {
+        let result = one_samp_t_test(&[5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 4.7, 5.5], 5.0).unwrap();
+        assert_close(result.mean_difference, 0.3625, 1e-12);
+        assert_close(result.standard_error, 0.1580432806905392, 1e-12);
+    }
+
+    #[test]
+    fn one_samp_t_test_from_stats_test() {
+        let samp = SampleStatistics::from_array(&[5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 4.7, 5.5]);
+        let result = one_samp_t_test_from_stats(&samp, 5.0).unwrap();
+        assert_close(result.t, 2.293675494561535, 1e-12);
+    }
+
+    #[test]
+    fn one_samp_t_test_insufficient_data_test() {
+        assert_eq!(
+            one_samp_t_test(&[4.2], 4.0).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            one_samp_t_test(&[], 4.0).err(),
+            Some(StatsError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn paired_t_test_test() {
+        // R: t.test(sleep$extra[1:10], sleep$extra[11:20], paired = TRUE)
+        let before = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+        let after = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+        let result = paired_t_test(&before, &after).unwrap();
+        assert_close(result.t, -4.062127683382037, 1e-12);
+        assert_eq!(result.df, 9.0);
+        assert_close(result.p_value, 0.002832890197384271, 1e-9);
+        assert_close(result.mean_difference, -1.58, 1e-12);
+        assert_close(result.standard_error, 0.3889587238883952, 1e-12);
+
+        let before = [200.0, 190.0, 210.0, 205.0, 198.0, 202.0];
+        let after = [195.0, 188.0, 200.0, 199.0, 199.0, 195.0];
+        let result = paired_t_test(&before, &after).unwrap();
+        assert_close(result.t, 3.060270596650173, 1e-12);
+        assert_close(result.p_value, 0.02809334977783191, 1e-9);
+    }
+
+    #[test]
+    fn paired_statistics_test() {
+        // R: cor(before, after) and sd(before - after) on the sleep data
+        let before = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+        let after = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+        let stats = PairedStatistics::from_arrays(&before, &after).unwrap();
+        assert_eq!(stats.n, 10);
+        assert_close(stats.mean_x, 0.75, 1e-15);
+        assert_close(stats.mean_y, 2.33, 1e-15);
+        assert_close(stats.mean_difference, -1.58, 1e-15);
+        assert_close(stats.sd_x, 1.7890096577591625, 1e-14);
+        assert_close(stats.sd_y, 2.002248735796829, 1e-14);
+        assert_close(stats.correlation, 0.7951702058335778, 1e-14);
+        assert_close(stats.sd_difference, 1.2299954832798732, 1e-14);
+        assert_close(stats.standard_error, 0.3889587238883952, 1e-14);
+        let result = paired_t_test_from_stats(&stats).unwrap();
+        assert_eq!(result, paired_t_test(&before, &after).unwrap());
+        assert_close(result.t, -4.062127683382037, 1e-12);
+    }
+
+    #[test]
+    fn paired_statistics_correlation_test() {
+        // The same two sets of values, paired in the same order and then in
+        // a shuffled order: the means and SDs are unchanged, but the less
+        // correlated pairing has a larger SE of the difference
+        let x = [12.0, 15.0, 9.0, 20.0, 17.0, 11.0, 14.0, 18.0];
+        let aligned = [13.5, 16.0, 10.0, 22.0, 17.5, 12.0, 15.5, 19.0];
+        let shuffled = [13.5, 16.0, 10.0, 12.0, 17.5, 22.0, 19.0, 15.5];
+        let high = PairedStatistics::from_arrays(&x, &aligned).unwrap();
+        let low = PairedStatistics::from_arrays(&x, &shuffled).unwrap();
+        assert!(high.correlation > 0.9, "{}", high.correlation);
+        assert!(low.correlation.abs() < 0.1, "{}", low.correlation);
+        assert_close(high.mean_difference, low.mean_difference, 1e-13);
+        assert_close(high.sd_y, low.sd_y, 1e-13);
+        assert!(high.standard_error < low.standard_error / 3.0);
+
+        for stats in [high, low] {
+            let variance = stats.sd_x.powi(2) + stats.sd_y.powi(2)
+                - 2.0 * stats.correlation * stats.sd_x * stats.sd_y;
+            assert_close(stats.sd_difference, variance.sqrt(), 1e-12);
+        }
+    }
+
+    #[test]
+    fn paired_statistics_large_offset_test() {
+        // Both means round to a multiple of 2^-13 near 1e12, which leaves
+        // x̄ - ȳ = 0.2333984375; the differences themselves are exact
+        let x = [1e12 + 0.1, 1e12 + 0.2, 1e12 + 0.4];
+        let y = [1e12; 3];
+        let stats = PairedStatistics::from_arrays(&x, &y).unwrap();
+        assert_close(stats.mean_difference, 0.23331705729166666, 1e-16);
+        assert_close(stats.sd_difference, 0.1527818266255023, 1e-16);
+    }
+
+    #[test]
+    fn paired_t_test_invalid_input_test() {
+        assert_eq!(
+            paired_t_test(&[1.0, 2.0, 3.0], &[1.0, 2.0]).err(),
+            Some(StatsError::MismatchedLengths { left: 3, right: 2 })
+        );
+        assert_eq!(
+            paired_t_test(&[1.0], &[2.0]).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            PairedStatistics::from_arrays(&[], &[]).err(),
+            Some(StatsError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn mean_diff_confidence_interval_test() {
+        // R: t.test(extra ~ group, data = sleep, var.equal = TRUE)$conf.int
+        let samp_1 =
+            SampleStatistics::from_array(&[0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0]);
+        let samp_2 =
+            SampleStatistics::from_array(&[1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4]);
+        let pooled =
+            mean_diff_confidence_interval(&samp_1, &samp_2, 0.95, TTestKind::EqualVariance)
+                .unwrap();
+        assert_close(pooled.lower, -3.3638740322876, 1e-12);
+        assert_close(pooled.upper, 0.203874032287599, 1e-12);
+        assert_close(pooled.point_estimate, -1.58, 1e-14);
+
+        // R: t.test(extra ~ group, data = sleep)$conf.int
+        let welch =
+            mean_diff_confidence_interval(&samp_1, &samp_2, 0.95, TTestKind::UnequalVariance)
+                .unwrap();
+        assert_close(welch.lower, -3.36548323071171, 1e-12);
+        assert_close(welch.upper, 0.205483230711711, 1e-12);
+    }
+
+    #[test]
+    fn mean_diff_confidence_interval_matches_test_test() {
+        let samp_1 =
+            SampleStatistics::from_array(&[0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0]);
+        let samp_2 =
+            SampleStatistics::from_array(&[1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4]);
+        for kind in [TTestKind::EqualVariance, TTestKind::UnequalVariance] {
+            let p_value = two_samp_t_test_with(&samp_1, &samp_2, kind).p_value;
+            // p is about 0.079, between the two levels
+            for confidence in [0.9, 0.95] {
+                let interval =
+                    mean_diff_confidence_interval(&samp_1, &samp_2, confidence, kind).unwrap();
+                assert_eq!(interval.contains(0.0), p_value >= 1.0 - confidence);
+            }
+            let interval =
+                mean_diff_confidence_interval(&samp_1, &samp_2, 1.0 - p_value, kind).unwrap();
+            assert_close(interval.upper, 0.0, 1e-12);
+        }
+
+        assert_eq!(
+            mean_diff_confidence_interval(
+                &samp_1,
+                &SampleStatistics::from_array(&[1.0]),
+                0.95,
+                TTestKind::UnequalVariance
+            ),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn tost_equivalence_test() {
+        // TOSTER: tsum_TOST on the sleep data summaries, Welch
+        let samp_1 =
+            SampleStatistics::from_array(&[0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0]);
+        let samp_2 =
+            SampleStatistics::from_array(&[1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4]);
+        let result = tost_equivalence(&samp_1, &samp_2, -2.0, 2.0, 0.05).unwrap();
+        assert_close(result.t_lower, 0.494646617939543, 1e-12);
+        assert_close(result.p_lower, 0.313453625975157, 1e-12);
+        assert_close(result.t_upper, -4.21627355291325, 1e-12);
+        assert_close(result.p_upper, 0.000265967336480007, 1e-15);
+        assert_close(result.df, 17.7764735161785, 1e-12);
+        assert_close(result.p_value, result.p_lower, 0.0);
+        assert!(!result.equivalent);
+
+        let result = tost_equivalence(&samp_1, &samp_2, -3.5, 3.5, 0.05).unwrap();
+        assert_close(result.t_lower, 2.26124168200934, 1e-12);
+        assert_close(result.p_lower, 0.0182653604627141, 1e-13);
+        assert_close(result.p_upper, 6.14979909936317e-6, 1e-16);
+        assert!(result.equivalent);
+
+        // Both tails stay accurate far out, t = ±40 on 10 df; mpmath
+        let result = tost(0.0, 1.0, 10.0, -40.0, 40.0, 0.05).unwrap();
+        assert_close(result.p_lower, 1.1404288715428774e-12, 1e-24);
+        assert_close(result.p_upper, 1.1404288715428774e-12, 1e-24);
+    }
+
+    #[test]
+    fn tost_paired_test() {
+        let before = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+        let after = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+        let result = tost_paired(&before, &after, -2.5, 0.5, 0.05).unwrap();
+        assert_close(result.t_lower, 2.36528953715916, 1e-12);
+        assert_close(result.p_lower, 0.0211168602465008, 1e-13);
+        assert_close(result.t_upper, -5.34761112749028, 1e-12);
+        assert_close(result.p_upper, 0.000231902704481593, 1e-15);
+        assert_eq!(result.df, 9.0);
+        assert_close(result.mean_difference, -1.58, 1e-14);
+        assert!(result.equivalent);
+        assert!(
+            !tost_paired(&before, &after, -2.5, 0.5, 0.01)
+                .unwrap()
+                .equivalent
+        );
+
+        assert!(matches!(
+            tost_paired(&before, &after, 1.0, -1.0, 0.05),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            tost_paired(&before, &after[..3], -1.0, 1.0, 0.05).err(),
+            Some(StatsError::MismatchedLengths { left: 10, right: 3 })
+        );
+    }
+}
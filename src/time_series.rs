@@ -0,0 +1,604 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::distributions::ChiSquare;
+use crate::format::write_test_line;
+use crate::{mean, HypothesisTest, StatsError};
+
+/// Sample autocorrelations at lags 0 to `max_lag`, with the biased estimator
+/// r_k = Σ (x_t - x̄)(x_(t+k) - x̄) / Σ (x_t - x̄)² about the overall mean, as
+/// statsmodels' `acf`. The first entry is always 1.
+pub fn acf(data: &[f64], max_lag: usize) -> Result<Vec<f64>, StatsError> {
+    validate_lags(data, max_lag)?;
+    let data_mean = mean(data);
+    let deviations: Vec<f64> = data.iter().map(|x| x - data_mean).collect();
+    let variance: f64 = deviations.iter().map(|d| d * d).sum();
+    if variance == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "autocorrelation is undefined for constant data",
+        ));
+    }
+    Ok((0..=max_lag)
+        .map(|lag| {
+            let covariance: f64 = deviations
+                .iter()
+                .zip(&deviations[lag..])
+                .map(|(a, b)| a * b)
+                .sum();
+            covariance / variance
+        })
+        .collect())
+}
+
+/// Sample partial autocorrelations at lags 0 to `max_lag`, solving the
+/// Yule–Walker equations of increasing order on `acf` by the Durbin–Levinson
+/// recursion, as statsmodels' `pacf(method="ldb")`. The first entry is 1.
+pub fn pacf(data: &[f64], max_lag: usize) -> Result<Vec<f64>, StatsError> {
+    let r = acf(data, max_lag)?;
+    let mut partial = vec![1.0];
+    // AR coefficients of the current order
+    let mut phi: Vec<f64> = Vec::with_capacity(max_lag);
+    for k in 1..=max_lag {
+        let numerator = r[k]
+            - phi
+                .iter()
+                .zip(r[1..k].iter().rev())
+                .map(|(p, r)| p * r)
+                .sum::<f64>();
+        let denominator = 1.0 - phi.iter().zip(&r[1..k]).map(|(p, r)| p * r).sum::<f64>();
+        let reflection = numerator / denominator;
+        let previous = phi.clone();
+        for (j, coefficient) in phi.iter_mut().enumerate() {
+            *coefficient -= reflection * previous[k - 2 - j];
+        }
+        phi.push(reflection);
+        partial.push(reflection);
+    }
+    Ok(partial)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LjungBoxResult {
+    /// Q = n (n + 2) Σ r_k² / (n - k) over lags 1 to h.
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for LjungBoxResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "Q", &[self.df], self.statistic, self.p_value)
+    }
+}
+
+impl HypothesisTest for LjungBoxResult {
+    fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Ljung-Box test"
+    }
+}
+
+/// Ljung–Box portmanteau test that the first `lags` autocorrelations are all
+/// zero, with a chi-square reference on `lags` degrees of freedom, as
+/// statsmodels' `acorr_ljungbox`.
+pub fn ljung_box(data: &[f64], lags: usize) -> Result<LjungBoxResult, StatsError> {
+    if lags == 0 {
+        return Err(StatsError::InvalidParameter(
+            "Ljung-Box test needs at least one lag",
+        ));
+    }
+    let r = acf(data, lags)?;
+    let n = data.len() as f64;
+    let statistic = n
+        * (n + 2.0)
+        * r.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(k, r)| r * r / (n - k as f64))
+            .sum::<f64>();
+    let df = lags as f64;
+    Ok(LjungBoxResult {
+        statistic,
+        df,
+        p_value: ChiSquare::new(df).sf(statistic),
+    })
+}
+
+/// `f` applied to each window of `window` consecutive values, in order,
+/// giving n - window + 1 results.
+pub fn rolling_apply<F: FnMut(&[f64]) -> f64>(
+    data: &[f64],
+    window: usize,
+    f: F,
+) -> Result<Vec<f64>, StatsError> {
+    validate_window(data, window)?;
+    Ok(data.windows(window).map(f).collect())
+}
+
+/// The mean of each window, updated in constant time per step.
+pub fn rolling_mean(data: &[f64], window: usize) -> Result<Vec<f64>, StatsError> {
+    Ok(rolling_moments(data, window)?
+        .into_iter()
+        .map(|(mean, _)| mean)
+        .collect())
+}
+
+/// The sample standard deviation of each window, updated in constant time
+/// per step.
+pub fn rolling_std(data: &[f64], window: usize) -> Result<Vec<f64>, StatsError> {
+    if window < 2 {
+        return Err(StatsError::InvalidParameter(
+            "a rolling standard deviation needs a window of at least two",
+        ));
+    }
+    let divisor = (window - 1) as f64;
+    Ok(rolling_moments(data, window)?
+        .into_iter()
+        .map(|(_, squared_deviations)| (squared_deviations.max(0.0) / divisor).sqrt())
+        .collect())
+}
+
+/// The minimum of each window, in linear time overall.
+pub fn rolling_min(data: &[f64], window: usize) -> Result<Vec<f64>, StatsError> {
+    rolling_extreme(data, window, |kept, new| kept <= new)
+}
+
+/// The maximum of each window, in linear time overall.
+pub fn rolling_max(data: &[f64], window: usize) -> Result<Vec<f64>, StatsError> {
+    rolling_extreme(data, window, |kept, new| kept >= new)
+}
+
+/// The mean and sum of squared deviations of each window. Each step adds one
+/// value and drops one with Welford's update, and every `window` steps both
+/// are recomputed from scratch so rounding error cannot accumulate; the
+/// recomputations cost O(n) in total.
+fn rolling_moments(data: &[f64], window: usize) -> Result<Vec<(f64, f64)>, StatsError> {
+    validate_window(data, window)?;
+    let w = window as f64;
+    let exact = |start: usize| {
+        let values = &data[start..start + window];
+        let values_mean = mean(values);
+        let squared_deviations = values.iter().map(|x| (x - values_mean).powi(2)).sum();
+        (values_mean, squared_deviations)
+    };
+
+    let (mut current_mean, mut squared_deviations) = exact(0);
+    let mut moments = Vec::with_capacity(data.len() - window + 1);
+    moments.push((current_mean, squared_deviations));
+    for start in 1..=data.len() - window {
+        if start % window == 0 {
+            (current_mean, squared_deviations) = exact(start);
+        } else {
+            let (old, new) = (data[start - 1], data[start + window - 1]);
+            let previous_mean = current_mean;
+            current_mean += (new - old) / w;
+            squared_deviations += (new - old) * (new - current_mean + old - previous_mean);
+        }
+        moments.push((current_mean, squared_deviations));
+    }
+    Ok(moments)
+}
+
+/// Sliding-window extreme with a monotonic deque of indices. An earlier
+/// value stays a candidate while `dominates(earlier, newer)` holds; the rest
+/// can never be the extreme again and are popped from the back.
+fn rolling_extreme(
+    data: &[f64],
+    window: usize,
+    dominates: impl Fn(f64, f64) -> bool,
+) -> Result<Vec<f64>, StatsError> {
+    validate_window(data, window)?;
+    let mut candidates: VecDeque<usize> = VecDeque::with_capacity(window);
+    let mut extremes = Vec::with_capacity(data.len() - window + 1);
+    for (i, &x) in data.iter().enumerate() {
+        while candidates.back().is_some_and(|&j| !dominates(data[j], x)) {
+            candidates.pop_back();
+        }
+        candidates.push_back(i);
+        if candidates[0] + window <= i {
+            candidates.pop_front();
+        }
+        if i + 1 >= window {
+            extremes.push(data[candidates[0]]);
+        }
+    }
+    Ok(extremes)
+}
+
+fn validate_window(data: &[f64], window: usize) -> Result<(), StatsError> {
+    if window == 0 {
+        return Err(StatsError::InvalidParameter(
+            "rolling window must not be empty",
+        ));
+    }
+    if window > data.len() {
+        return Err(StatsError::InsufficientData {
+            required: window,
+            actual: data.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Exponentially weighted mean and variance of a stream, following pandas'
+/// `ewm`. With `adjust` the weights are (1 - α)^i for the value i steps
+/// back, normalized over the values seen so far, which removes the bias
+/// toward the first value in short series; without it the mean is the
+/// recursion m = (1 - α) m + α x.
+pub struct Ewma {
+    alpha: f64,
+    adjust: bool,
+    n: usize,
+    mean: f64,
+    /// Weighted mean of squared deviations, before the bias correction.
+    biased_variance: f64,
+    /// Total weight of the values seen so far, and of its squares.
+    sum_weights: f64,
+    sum_squared_weights: f64,
+}
+
+impl Ewma {
+    /// Smoothing factor `alpha` in (0, 1], the weight of each new value.
+    pub fn new(alpha: f64, adjust: bool) -> Result<Self, StatsError> {
+        if !(alpha > 0.0 && alpha <= 1.0) {
+            return Err(StatsError::InvalidParameter(
+                "smoothing factor must be in (0, 1]",
+            ));
+        }
+        Ok(Ewma {
+            alpha,
+            adjust,
+            n: 0,
+            mean: f64::NAN,
+            biased_variance: 0.0,
+            sum_weights: 0.0,
+            sum_squared_weights: 0.0,
+        })
+    }
+
+    /// Smoothing over a span of s >= 1 values, α = 2 / (s + 1).
+    pub fn from_span(span: f64, adjust: bool) -> Result<Self, StatsError> {
+        if span.is_nan() || span < 1.0 {
+            return Err(StatsError::InvalidParameter("span must be at least 1"));
+        }
+        Ewma::new(2.0 / (span + 1.0), adjust)
+    }
+
+    /// Smoothing with weights that halve every `half_life` values,
+    /// α = 1 - exp(-ln 2 / half_life).
+    pub fn from_half_life(half_life: f64, adjust: bool) -> Result<Self, StatsError> {
+        if half_life.is_nan() || half_life <= 0.0 {
+            return Err(StatsError::InvalidParameter("half-life must be positive"));
+        }
+        Ewma::new(1.0 - (-std::f64::consts::LN_2 / half_life).exp(), adjust)
+    }
+
+    /// An `Ewma` that has been updated with each value of `data` in order.
+    pub fn from_slice(data: &[f64], alpha: f64, adjust: bool) -> Result<Self, StatsError> {
+        let mut ewma = Ewma::new(alpha, adjust)?;
+        for &x in data {
+            ewma.update(x);
+        }
+        Ok(ewma)
+    }
+
+    pub fn update(&mut self, x: f64) {
+        self.n += 1;
+        if self.n == 1 {
+            self.mean = x;
+            self.sum_weights = 1.0;
+            self.sum_squared_weights = 1.0;
+            return;
+        }
+        let decay = 1.0 - self.alpha;
+        let new_weight = if self.adjust { 1.0 } else { self.alpha };
+        let old_weight = self.sum_weights * decay;
+        let total = old_weight + new_weight;
+
+        let previous_mean = self.mean;
+        self.mean = (old_weight * previous_mean + new_weight * x) / total;
+        self.biased_variance = (old_weight
+            * (self.biased_variance + (previous_mean - self.mean).powi(2))
+            + new_weight * (x - self.mean).powi(2))
+            / total;
+        self.sum_weights = total;
+        self.sum_squared_weights =
+            self.sum_squared_weights * decay * decay + new_weight * new_weight;
+        if !self.adjust {
+            self.sum_squared_weights /= total * total;
+            self.sum_weights = 1.0;
+        }
+    }
+
+    /// The number of values seen.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// The current weighted mean; NaN before the first value.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// The weighted variance with the bias correction (Σw)² / ((Σw)² - Σw²)
+    /// of pandas' `var()`; NaN before the second value.
+    pub fn variance(&self) -> f64 {
+        let squared_total = self.sum_weights * self.sum_weights;
+        let denominator = squared_total - self.sum_squared_weights;
+        if self.n < 2 || denominator <= 0.0 {
+            return f64::NAN;
+        }
+        squared_total / denominator * self.biased_variance
+    }
+
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+fn validate_lags(data: &[f64], max_lag: usize) -> Result<(), StatsError> {
+    if data.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: data.len(),
+        });
+    }
+    if max_lag >= data.len() {
+        return Err(StatsError::InvalidParameter(
+            "lag must be less than the length of the series",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, sample_variance};
+
+    // x_t = 0.8 x_(t-1) + e_t, rounded to two decimals
+    const AR1: [f64; 30] = [
+        0.31, -1.17, -0.17, 1.81, 1.12, 1.02, -0.06, 2.36, 2.43, 1.87, 2.73, 0.47, 1.04, 1.81, 1.2,
+        2.58, 2.11, 1.08, 2.23, 2.67, 1.62, 1.74, 2.47, 0.72, 0.75, 1.53, 0.84, 2.18, 0.9, 1.01,
+    ];
+
+    #[test]
+    fn acf_test() {
+        // statsmodels.tsa.stattools.acf(x, nlags=5)
+        let expected = [
+            1.0,
+            0.27658400208343,
+            0.0232475815478439,
+            0.120376310983024,
+            -0.0267633702590318,
+            0.196205500739031,
+        ];
+        let r = acf(&AR1, 5).unwrap();
+        assert_eq!(r.len(), 6);
+        for (actual, expected) in r.iter().zip(expected) {
+            assert_close(*actual, expected, 1e-14);
+        }
+    }
+
+    #[test]
+    fn pacf_test() {
+        // statsmodels.tsa.stattools.pacf(x, nlags=5, method="ldb")
+        let expected = [
+            1.0,
+            0.27658400208343,
+            -0.0576622136311958,
+            0.140721168030186,
+            -0.110371758342436,
+            0.274189417292036,
+        ];
+        let partial = pacf(&AR1, 5).unwrap();
+        for (actual, expected) in partial.iter().zip(expected) {
+            assert_close(*actual, expected, 1e-13);
+        }
+    }
+
+    #[test]
+    fn ljung_box_test() {
+        // statsmodels.stats.diagnostic.acorr_ljungbox(x, lags=[1, 5, 10])
+        let expected = [
+            (1, 2.53237109655681, 0.111532506883561),
+            (5, 4.57083360497102, 0.470462444737655),
+            (10, 7.14951530977713, 0.711258428800238),
+        ];
+        for (lags, statistic, p_value) in expected {
+            let result = ljung_box(&AR1, lags).unwrap();
+            assert_close(result.statistic, statistic, 1e-12);
+            assert_eq!(result.df, lags as f64);
+            assert_close(result.p_value, p_value, 1e-12);
+        }
+    }
+
+    #[test]
+    fn rolling_statistics_test() {
+        for window in [1, 2, 3, 7, 30] {
+            let means = rolling_mean(&AR1, window).unwrap();
+            let mins = rolling_min(&AR1, window).unwrap();
+            let maxes = rolling_max(&AR1, window).unwrap();
+            assert_eq!(means.len(), AR1.len() - window + 1);
+            for (i, values) in AR1.windows(window).enumerate() {
+                assert_close(means[i], mean(values), 1e-14);
+                assert_eq!(
+                    mins[i],
+                    values.iter().copied().fold(f64::INFINITY, f64::min)
+                );
+                assert_eq!(
+                    maxes[i],
+                    values.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+                );
+            }
+            if window > 1 {
+                let stds = rolling_std(&AR1, window).unwrap();
+                for (i, values) in AR1.windows(window).enumerate() {
+                    assert_close(stds[i], sample_variance(values).sqrt(), 1e-13);
+                }
+            }
+        }
+
+        let ranges = rolling_apply(&AR1, 4, |w| w[3] - w[0]).unwrap();
+        assert_eq!(ranges.len(), 27);
+        assert_close(ranges[0], 1.5, 1e-15);
+    }
+
+    #[test]
+    fn rolling_std_drift_test() {
+        // A large offset makes the incremental update lose precision quickly
+        // without the periodic recomputation
+        let data: Vec<f64> = (0..100_000)
+            .map(|i| 1e8 + f64::from(i % 17) + f64::from(i % 5) * 0.25)
+            .collect();
+        let window = 50;
+        let stds = rolling_std(&data, window).unwrap();
+        for i in [0, 49_999, data.len() - window] {
+            let exact = sample_variance(&data[i..i + window]).sqrt();
+            assert_close(stds[i], exact, 1e-7);
+        }
+    }
+
+    #[test]
+    fn ewma_test() {
+        // pandas: pd.Series(x).ewm(span=3, adjust=adjust).mean() and .var()
+        let adjusted_means = [
+            0.31,
+            -0.676666666666667,
+            -0.387142857142857,
+            0.784666666666667,
+            0.957741935483871,
+            0.989365079365079,
+            0.460551181102362,
+            1.414,
+            1.92299412915851,
+            1.89647116324536,
+        ];
+        let adjusted_variances = [
+            f64::NAN,
+            1.0952,
+            0.475085714285714,
+            2.13458285714286,
+            1.03966709677419,
+            0.504541321044547,
+            0.664455493063367,
+            1.68788220472441,
+            1.22848505583055,
+            0.614094658854182,
+        ];
+        let recursive_means = [
+            0.31,
+            -0.43,
+            -0.3,
+            0.755,
+            0.9375,
+            0.97875,
+            0.459375,
+            1.4096875,
+            1.91984375,
+            1.894921875,
+        ];
+        let recursive_variances = [
+            f64::NAN,
+            1.0952,
+            0.46512,
+            1.91752380952381,
+            0.997637647058824,
+            0.499910850439883,
+            0.654496703296703,
+            1.68191188427028,
+            1.23131250171664,
+            0.616580858539042,
+        ];
+        for (adjust, means, variances) in [
+            (true, adjusted_means, adjusted_variances),
+            (false, recursive_means, recursive_variances),
+        ] {
+            let mut ewma = Ewma::from_span(3.0, adjust).unwrap();
+            assert!(ewma.mean().is_nan());
+            for (i, &x) in AR1[..10].iter().enumerate() {
+                ewma.update(x);
+                assert_close(ewma.mean(), means[i], 1e-14);
+                if i == 0 {
+                    assert!(ewma.variance().is_nan());
+                } else {
+                    assert_close(ewma.variance(), variances[i], 1e-13);
+                }
+            }
+            let batch = Ewma::from_slice(&AR1[..10], 0.5, adjust).unwrap();
+            assert_eq!(batch.n(), 10);
+            assert_close(batch.mean(), ewma.mean(), 1e-15);
+            assert_close(batch.standard_deviation(), variances[9].sqrt(), 1e-13);
+        }
+    }
+
+    #[test]
+    fn ewma_parameters_test() {
+        assert_close(Ewma::from_half_life(1.0, true).unwrap().alpha, 0.5, 1e-15);
+        assert_eq!(
+            Ewma::from_slice(&[4.0, 4.0], 1.0, true).unwrap().mean(),
+            4.0
+        );
+        for alpha in [0.0, 1.5, f64::NAN] {
+            assert!(matches!(
+                Ewma::new(alpha, true),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+        assert!(Ewma::from_span(0.5, true).is_err());
+        assert!(Ewma::from_half_life(0.0, false).is_err());
+    }
+
+    #[test]
+    fn rolling_invalid_input_test() {
+        assert!(matches!(
+            rolling_mean(&AR1, 0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            rolling_max(&AR1, 31),
+            Err(StatsError::InsufficientData {
+                required: 31,
+                actual: 30
+            })
+        );
+        assert!(matches!(
+            rolling_std(&AR1, 1),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_input_test() {
+        assert!(matches!(
+            acf(&AR1, 30),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            pacf(&[2.0; 5], 2),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            ljung_box(&AR1, 0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            acf(&[1.0], 0),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use std::fmt;
+
+use crate::confidence::validate_confidence;
+use crate::distributions::{ChiSquare, NoncentralT, Normal};
+use crate::format::significant;
+use crate::{GetStatistics, SampleStatistics, StatsError};
+
+/// Subintervals of the Simpson's rule integral for the two-sided k factor.
+const TOLERANCE_INTEGRAL_STEPS: usize = 200;
+
+/// Doublings of the search interval before the k factor search gives up.
+const MAX_BRACKET_EXPANSIONS: usize = 64;
+
+/// Which bounds a tolerance interval has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Sides {
+    /// x̄ ± k s, covering the central part of the population.
+    TwoSided,
+    /// A lower bound x̄ - k s, with everything above it covered.
+    Lower,
+    /// An upper bound x̄ + k s, with everything below it covered.
+    Upper,
+}
+
+/// An interval claimed, with the given confidence, to contain at least
+/// `coverage` of a normal population. The open side of a one-sided
+/// interval is infinite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ToleranceInterval {
+    pub lower: f64,
+    pub upper: f64,
+    /// The tolerance factor: the bounds are x̄ ± k s.
+    pub k: f64,
+    pub mean: f64,
+    /// Sample standard deviation.
+    pub sd: f64,
+    pub n: usize,
+    pub coverage: f64,
+    pub confidence: f64,
+    pub sides: Sides,
+}
+
+impl fmt::Display for ToleranceInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}% / {}% tolerance interval [{}, {}]",
+            significant(self.coverage * 100.0, 3),
+            significant(self.confidence * 100.0, 3),
+            significant(self.lower, 3),
+            significant(self.upper, 3)
+        )
+    }
+}
+
+/// Tolerance interval for normal `data`: with probability `confidence` the
+/// interval holds at least a `coverage` share of the population, as R's
+/// `tolerance::normtol.int` with `method = "EXACT"` for two sides. The k
+/// factors are exact. A one-sided k is a quantile of the noncentral t,
+/// t'(confidence; n - 1, z_coverage sqrt(n)) / sqrt(n). The two-sided k
+/// solves the integral equation of Odeh (1978) numerically; the common
+/// approximations are slightly small, e.g. Howe's 3.382 against the exact
+/// 3.393 for n = 10 at 95% / 95%.
+///
+/// Returns `StatsError::InsufficientData` for fewer than two values,
+/// `StatsError::NanInInput` if `data` contains NaN and
+/// `StatsError::InvalidParameter` unless `coverage` and `confidence` are
+/// strictly between 0 and 1, and `StatsError::NoConvergence` if the k
+/// factor cannot be bracketed.
+pub fn normal_tolerance_interval(
+    data: &[f64],
+    coverage: f64,
+    confidence: f64,
+    sides: Sides,
+) -> Result<ToleranceInterval, StatsError> {
+    if data.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: data.len(),
+        });
+    }
+    if let Some(index) = data.iter().position(|x| x.is_nan()) {
+        return Err(StatsError::NanInInput { index });
+    }
+    if !(coverage > 0.0 && coverage < 1.0) {
+        return Err(StatsError::InvalidParameter(
+            "coverage must be between 0 and 1",
+        ));
+    }
+    validate_confidence(confidence)?;
+
+    let stats = SampleStatistics::from_array(data);
+    let k = match sides {
+        Sides::TwoSided => two_sided_k(stats.n, coverage, confidence)?,
+        Sides::Lower | Sides::Upper => one_sided_k(stats.n, coverage, confidence)?,
+    };
+    let margin = k * stats.standard_deviation;
+    let (lower, upper) = match sides {
+        Sides::TwoSided => (stats.sample_mean - margin, stats.sample_mean + margin),
+        Sides::Lower => (stats.sample_mean - margin, f64::INFINITY),
+        Sides::Upper => (f64::NEG_INFINITY, stats.sample_mean + margin),
+    };
+    Ok(ToleranceInterval {
+        lower,
+        upper,
+        k,
+        mean: stats.sample_mean,
+        sd: stats.standard_deviation,
+        n: stats.n,
+        coverage,
+        confidence,
+        sides,
+    })
+}
+
+/// t'(confidence; n - 1, z_coverage sqrt(n)) / sqrt(n), the noncentral t
+/// quantile found by bisection on its CDF.
+fn one_sided_k(n: usize, coverage: f64, confidence: f64) -> Result<f64, StatsError> {
+    let root_n = (n as f64).sqrt();
+    let ncp = Normal::standard().quantile(coverage) * root_n;
+    let t = NoncentralT::new(n as f64 - 1.0, ncp);
+    let lower_than = |x: f64| t.cdf(x) < confidence;
+    Ok(bisect_increasing(lower_than, ncp)? / root_n)
+}
+
+/// The k with conf(k) = confidence, where conf(k) is the probability that
+/// x̄ ± k s covers `coverage` of the population:
+/// sqrt(2 n / π) ∫ P(χ²(ν) > ν r(x)² / k²) exp(-n x² / 2) dx over x >= 0,
+/// with ν = n - 1 and r(x) solving Φ(x + r) - Φ(x - r) = coverage.
+fn two_sided_k(n: usize, coverage: f64, confidence: f64) -> Result<f64, StatsError> {
+    let nf = n as f64;
+    let df = nf - 1.0;
+    let chi_square = ChiSquare::new(df);
+    // The Gaussian weight is below e^-32 beyond 8 / sqrt(n)
+    let upper = 8.0 / nf.sqrt();
+    let h = upper / TOLERANCE_INTEGRAL_STEPS as f64;
+    let nodes: Vec<(f64, f64)> = (0..=TOLERANCE_INTEGRAL_STEPS)
+        .map(|i| {
+            let x = i as f64 * h;
+            let simpson = match i {
+                0 | TOLERANCE_INTEGRAL_STEPS => 1.0,
+                i if i % 2 == 1 => 4.0,
+                _ => 2.0,
+            };
+            let weight = simpson * h / 3.0 * (-nf * x * x / 2.0).exp();
+            (weight, df * covering_half_width(x, coverage).powi(2))
+        })
+        .collect();
+    let scale = (2.0 * nf / std::f64::consts::PI).sqrt();
+    let covered = |k: f64| {
+        // An interval of no width covers nothing; the integrand would be
+        // sf(u / 0), NaN where u is 0, and a negative k would mirror k > 0
+        if k <= 0.0 {
+            return 0.0;
+        }
+        scale
+            * nodes
+                .iter()
+                .map(|&(weight, u)| weight * chi_square.sf(u / (k * k)))
+                .sum::<f64>()
+    };
+    bisect_increasing(|k| covered(k) < confidence, 1.0)
+}
+
+/// The half-width r of the interval x ± r holding `coverage` of the
+/// standard normal, by Newton's method on Φ(x + r) - Φ(x - r) = coverage
+/// written in tail areas so that coverages near 1 stay accurate. Moving
+/// the interval off centre only loses coverage, and x ± (x + z) holds all
+/// of [-z, z], so r lies in [z, x + z] for z = Φ⁻¹((1 + coverage) / 2). A
+/// Newton step leaving that bracket, as it can for low coverage, is
+/// replaced by bisection.
+fn covering_half_width(x: f64, coverage: f64) -> f64 {
+    let normal = Normal::standard();
+    let z = normal.quantile((1.0 + coverage) / 2.0);
+    let (mut low, mut high) = (z, x + z);
+    let mut r = high;
+    for _ in 0..100 {
+        let outside = normal.sf(x + r) + normal.cdf(x - r) - (1.0 - coverage);
+        if outside > 0.0 {
+            low = r;
+        } else {
+            high = r;
+        }
+        let newton = r + outside / (normal.pdf(x + r) + normal.pdf(x - r));
+        let next = if newton > low && newton < high {
+            newton
+        } else {
+            (low + high) / 2.0
+        };
+        let converged = (next - r).abs() <= 1e-15 * next || high - low <= 1e-15 * high;
+        r = next;
+        if converged {
+            break;
+        }
+    }
+    r
+}
+
+/// The point where an increasing function crosses its target, given
+/// `below(x)`, whether the function is still under the target at x, and a
+/// starting guess. Returns `StatsError::NoConvergence` if the crossing is
+/// not bracketed within `MAX_BRACKET_EXPANSIONS` doublings.
+fn bisect_increasing(below: impl Fn(f64) -> bool, guess: f64) -> Result<f64, StatsError> {
+    let mut width = 1.0;
+    let (mut low, mut high) = (guess - width, guess + width);
+    let mut expansions = 0;
+    while !below(low) || below(high) {
+        if expansions == MAX_BRACKET_EXPANSIONS {
+            return Err(StatsError::NoConvergence {
+                iterations: MAX_BRACKET_EXPANSIONS,
+            });
+        }
+        expansions += 1;
+        width *= 2.0;
+        if !below(low) {
+            low = guess - width;
+        }
+        if below(high) {
+            high = guess + width;
+        }
+    }
+    while high - low > 1e-13 * high.abs().max(1.0) {
+        let mid = (low + high) / 2.0;
+        if below(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok((low + high) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn tolerance_k_factor_test() {
+        // R: tolerance::K.factor(10, alpha = 0.05, P = 0.95, side = 2,
+        //                         method = "EXACT") and side = 1
+        let k = |n, coverage, confidence, sides| {
+            let data: Vec<f64> = (0..n).map(f64::from).collect();
+            normal_tolerance_interval(&data, coverage, confidence, sides)
+                .unwrap()
+                .k
+        };
+        assert_close(k(10, 0.95, 0.95, Sides::TwoSided), 3.3934294787126076, 1e-9);
+        assert_close(k(10, 0.95, 0.95, Sides::Lower), 2.910963413078167, 1e-9);
+        assert_close(k(10, 0.99, 0.95, Sides::TwoSided), 4.436908728948543, 1e-9);
+        assert_close(k(10, 0.99, 0.95, Sides::Upper), 3.98111784527306, 1e-9);
+
+        // Low coverage, where k < 1 and the interval is narrower than s;
+        // the reference is the same integral evaluated in mpmath
+        assert_close(k(30, 0.5, 0.95, Sides::TwoSided), 0.88060897255491, 1e-9);
+        for (n, coverage) in [(10, 0.5), (30, 0.1), (5, 0.25)] {
+            let two_sided = k(n, coverage, 0.95, Sides::TwoSided);
+            assert!(two_sided > 0.0 && two_sided < 1.5, "{two_sided}");
+            assert!(k(n, coverage, 0.95, Sides::Lower) < two_sided);
+        }
+    }
+
+    #[test]
+    fn normal_tolerance_interval_test() {
+        let data = [9.8, 10.4, 10.1, 9.6, 10.9, 10.2, 9.9, 10.5, 10.0, 10.3];
+        let stats = SampleStatistics::from_array(&data);
+        let interval = normal_tolerance_interval(&data, 0.99, 0.95, Sides::TwoSided).unwrap();
+        assert_eq!(interval.n, 10);
+        assert_eq!(
+            (interval.mean, interval.sd),
+            (stats.sample_mean, stats.standard_deviation)
+        );
+        assert_close(
+            interval.lower,
+            stats.sample_mean - interval.k * stats.standard_deviation,
+            1e-12,
+        );
+        assert_close(
+            interval.upper,
+            stats.sample_mean + interval.k * stats.standard_deviation,
+            1e-12,
+        );
+        assert_eq!(
+            interval.to_string(),
+            "99% / 95% tolerance interval [8.5, 11.8]"
+        );
+
+        let lower = normal_tolerance_interval(&data, 0.99, 0.95, Sides::Lower).unwrap();
+        let upper = normal_tolerance_interval(&data, 0.99, 0.95, Sides::Upper).unwrap();
+        assert_eq!(lower.k, upper.k);
+        assert!(lower.k < interval.k);
+        assert_eq!(
+            (lower.upper, upper.lower),
+            (f64::INFINITY, f64::NEG_INFINITY)
+        );
+        assert_close(
+            lower.lower,
+            stats.sample_mean - lower.k * stats.standard_deviation,
+            1e-12,
+        );
+
+        // Larger samples pin the population down, and k falls towards the
+        // normal quantile
+        let large: Vec<f64> = (0..1000).map(f64::from).collect();
+        let k = normal_tolerance_interval(&large, 0.95, 0.95, Sides::TwoSided)
+            .unwrap()
+            .k;
+        assert!(k > 1.96 && k < 2.1, "{k}");
+    }
+
+    #[test]
+    fn tolerance_coverage_simulation_test() {
+        // The interval covers at least 90% of the population in about 95%
+        // of samples. k depends only on n, so it is computed once
+        let normal = Normal::standard();
+        let mut simulator = crate::simulate::Simulator::new(139);
+        let draw = |simulator: &mut crate::simulate::Simulator| -> Vec<f64> {
+            (0..8)
+                .map(|_| simulator.normal(0.0, 1.0).unwrap())
+                .collect()
+        };
+        let k = normal_tolerance_interval(&draw(&mut simulator), 0.9, 0.95, Sides::TwoSided)
+            .unwrap()
+            .k;
+        let trials = 4000;
+        let mut covering = 0;
+        for _ in 0..trials {
+            let stats = SampleStatistics::from_array(&draw(&mut simulator));
+            let margin = k * stats.standard_deviation;
+            let covered =
+                normal.cdf(stats.sample_mean + margin) - normal.cdf(stats.sample_mean - margin);
+            if covered >= 0.9 {
+                covering += 1;
+            }
+        }
+        let share = covering as f64 / trials as f64;
+        assert!((share - 0.95).abs() < 0.015, "{share}");
+    }
+
+    #[test]
+    fn tolerance_invalid_input_test() {
+        assert_eq!(
+            normal_tolerance_interval(&[1.0], 0.9, 0.95, Sides::TwoSided),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            normal_tolerance_interval(&[1.0, f64::NAN], 0.9, 0.95, Sides::Lower),
+            Err(StatsError::NanInInput { index: 1 })
+        );
+        for (coverage, confidence) in [(0.0, 0.95), (1.0, 0.95), (f64::NAN, 0.95), (0.9, 1.0)] {
+            assert!(matches!(
+                normal_tolerance_interval(&[1.0, 2.0, 3.0], coverage, confidence, Sides::Upper),
+                Err(StatsError::InvalidParameter(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,397 @@
+use std::fmt;
+
+use crate::correlation::validate_pairs;
+use crate::distributions::Normal;
+use crate::format::{significant, write_test_line};
+use crate::rank::{merge_sort_exchanges, TieCounts};
+use crate::{median, ConfidenceInterval, HypothesisTest, StatsError};
+
+/// Confidence level of the slope interval reported by `theil_sen`.
+const THEIL_SEN_CONFIDENCE: f64 = 0.95;
+
+/// Significance level at which `mann_kendall` reports a trend.
+const MANN_KENDALL_ALPHA: f64 = 0.05;
+
+/// Direction of a monotonic trend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Trend {
+    Increasing,
+    Decreasing,
+    NoTrend,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MannKendallResult {
+    /// S = Σ_{i<j} sign(x_j - x_i).
+    pub s: f64,
+    /// Variance of S under no trend, corrected for tied values.
+    pub variance: f64,
+    /// Continuity-corrected z = (S - sign(S)) / sqrt(variance).
+    pub z: f64,
+    /// Two-sided p-value.
+    pub p_value: f64,
+    /// Kendall's tau between the series and time, S / (n(n-1)/2).
+    pub tau: f64,
+    /// Direction of the trend if significant at the 5% level.
+    pub trend: Trend,
+}
+
+impl fmt::Display for MannKendallResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "S = {}, ", significant(self.s, 3))?;
+        write_test_line(f, "z", &[], self.z, self.p_value)
+    }
+}
+
+impl HypothesisTest for MannKendallResult {
+    fn statistic(&self) -> f64 {
+        self.z
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Mann-Kendall trend test"
+    }
+}
+
+/// Mann-Kendall test for a monotonic trend in a series observed at equally
+/// spaced times, matching `pymannkendall.original_test`.
+pub fn mann_kendall(data: &[f64]) -> Result<MannKendallResult, StatsError> {
+    if data.len() < 3 {
+        return Err(StatsError::InsufficientData {
+            required: 3,
+            actual: data.len(),
+        });
+    }
+
+    // Against time there are no ties in x, so S = C - D with
+    // C = total - tied pairs - D, and D counted as merge sort exchanges
+    let mut sorted = data.to_vec();
+    let mut buffer = vec![0.0; data.len()];
+    let discordant = merge_sort_exchanges(&mut sorted, &mut buffer) as f64;
+    let ties = TieCounts::from_sorted(&sorted);
+
+    let n = data.len() as f64;
+    let total = n * (n - 1.0) / 2.0;
+    let s = total - ties.pairs - 2.0 * discordant;
+    let variance = (n * (n - 1.0) * (2.0 * n + 5.0) - ties.v1) / 18.0;
+    let z = if s > 0.0 {
+        (s - 1.0) / variance.sqrt()
+    } else if s < 0.0 {
+        (s + 1.0) / variance.sqrt()
+    } else {
+        0.0
+    };
+    let p_value = Normal::standard().two_tailed(z);
+
+    let trend = if p_value >= MANN_KENDALL_ALPHA {
+        Trend::NoTrend
+    } else if z > 0.0 {
+        Trend::Increasing
+    } else {
+        Trend::Decreasing
+    };
+
+    Ok(MannKendallResult {
+        s,
+        variance,
+        z,
+        p_value,
+        tau: s / total,
+        trend,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrendAnalysis {
+    pub mann_kendall: MannKendallResult,
+    /// Theil-Sen line against the time index 0, 1, 2, ...
+    pub theil_sen: TheilSenResult,
+}
+
+/// Mann-Kendall test for whether there is a trend together with the
+/// Theil-Sen slope for how large it is, per time step.
+pub fn trend_analysis(data: &[f64]) -> Result<TrendAnalysis, StatsError> {
+    let mann_kendall = mann_kendall(data)?;
+    let time: Vec<f64> = (0..data.len()).map(|i| i as f64).collect();
+    Ok(TrendAnalysis {
+        mann_kendall,
+        theil_sen: theil_sen(&time, data)?,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TheilSenResult {
+    /// Median of the slopes between every pair of points with distinct x.
+    pub slope: f64,
+    /// Median of y - slope * x.
+    pub intercept: f64,
+    /// 95% interval for the slope from the order statistics of the pairwise
+    /// slopes, using the tie-corrected variance of Kendall's S (Sen, 1968).
+    pub confidence_interval: ConfidenceInterval,
+}
+
+/// Theil-Sen robust line, as scipy's `theilslopes` with `method="joint"`.
+///
+/// None of the n(n-1)/2 pairwise slopes are stored. Each order statistic is
+/// found by bisecting on the slope value, counting the pairs above a trial
+/// slope as inversions of y - t x with a merge sort, so the fit takes
+/// O(n log n) time per bisection step (at most 64 of them) and O(n) memory.
+pub fn theil_sen(x: &[f64], y: &[f64]) -> Result<TheilSenResult, StatsError> {
+    validate_pairs(x, y, 2)?;
+    let slopes = PairwiseSlopes::new(x, y);
+    if slopes.count == 0 {
+        return Err(StatsError::InvalidParameter(
+            "slope is undefined when every x is identical",
+        ));
+    }
+
+    let count = slopes.count;
+    let slope = if count % 2 == 1 {
+        slopes.nth(count / 2)
+    } else {
+        (slopes.nth(count / 2 - 1) + slopes.nth(count / 2)) / 2.0
+    };
+    let residuals: Vec<f64> = x.iter().zip(y).map(|(a, b)| b - slope * a).collect();
+
+    let mut sorted_y = y.to_vec();
+    sorted_y.sort_by(f64::total_cmp);
+    let n = x.len() as f64;
+    let sigma = ((n * (n - 1.0) * (2.0 * n + 5.0)
+        - TieCounts::from_sorted(&slopes.x).v1
+        - TieCounts::from_sorted(&sorted_y).v1)
+        / 18.0)
+        .sqrt();
+    let z = Normal::standard().quantile(0.5 + THEIL_SEN_CONFIDENCE / 2.0);
+    let total = count as f64;
+    let lower_rank = (((total - z * sigma) / 2.0).round_ties_even() - 1.0).max(0.0) as u64;
+    let upper_rank = (((total + z * sigma) / 2.0).round_ties_even() as u64).min(count - 1);
+
+    Ok(TheilSenResult {
+        slope,
+        intercept: median(&residuals)?,
+        confidence_interval: ConfidenceInterval {
+            lower: slopes.nth(lower_rank),
+            upper: slopes.nth(upper_rank),
+            point_estimate: slope,
+            confidence: THEIL_SEN_CONFIDENCE,
+        },
+    })
+}
+
+/// Order statistics of the slopes between points with distinct x, without
+/// materializing them.
+struct PairwiseSlopes {
+    /// Points sorted by x.
+    x: Vec<f64>,
+    y: Vec<f64>,
+    /// Number of pairs with distinct x.
+    count: u64,
+    /// Every slope lies within [-bound, bound].
+    bound: f64,
+}
+
+impl PairwiseSlopes {
+    fn new(x: &[f64], y: &[f64]) -> Self {
+        let mut order: Vec<usize> = (0..x.len()).collect();
+        order.sort_by(|&i, &j| x[i].total_cmp(&x[j]));
+        let x: Vec<f64> = order.iter().map(|&i| x[i]).collect();
+        let y: Vec<f64> = order.iter().map(|&i| y[i]).collect();
+
+        let n = x.len() as u64;
+        let tied_pairs = TieCounts::from_sorted(&x).pairs as u64;
+
+        let min_gap = x
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .filter(|&gap| gap > 0.0)
+            .fold(f64::INFINITY, f64::min);
+        let y_min = y.iter().copied().fold(f64::INFINITY, f64::min);
+        let y_max = y.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        PairwiseSlopes {
+            x,
+            y,
+            count: n * (n - 1) / 2 - tied_pairs,
+            // Doubled so rounding in y - t x cannot push a slope past it
+            bound: 2.0 * (y_max - y_min) / min_gap + 1.0,
+        }
+    }
+
+    /// Number of slopes strictly greater than `t`.
+    fn count_above(&self, t: f64) -> u64 {
+        // A pair i < j in x order has slope > t exactly when
+        // y_j - t x_j > y_i - t x_i. Sorting tied x by descending y - t x
+        // keeps pairs with equal x from ever counting.
+        let z: Vec<f64> = self.x.iter().zip(&self.y).map(|(a, b)| b - t * a).collect();
+        let mut order: Vec<usize> = (0..z.len()).collect();
+        order.sort_by(|&i, &j| self.x[i].total_cmp(&self.x[j]).then(z[j].total_cmp(&z[i])));
+
+        let mut negated: Vec<f64> = order.iter().map(|&i| -z[i]).collect();
+        let mut buffer = vec![0.0; negated.len()];
+        merge_sort_exchanges(&mut negated, &mut buffer)
+    }
+
+    /// The slope at zero-based position `rank` in ascending order.
+    fn nth(&self, rank: u64) -> f64 {
+        // Bisect over the f64 values in order, mapped onto ordered integers
+        let mut lo = ordered_key(-self.bound);
+        let mut hi = ordered_key(self.bound);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.count - self.count_above(from_ordered_key(mid)) > rank {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        from_ordered_key(lo)
+    }
+}
+
+/// Maps an f64 onto a u64 with the same ordering.
+fn ordered_key(value: f64) -> u64 {
+    let bits = value.to_bits();
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits | 1 << 63
+    }
+}
+
+fn from_ordered_key(key: u64) -> f64 {
+    if key >> 63 == 1 {
+        f64::from_bits(key & !(1 << 63))
+    } else {
+        f64::from_bits(!key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    #[test]
+    fn theil_sen_test() {
+        // scipy.stats.theilslopes(y, x, method="joint"); the outlier at x = 6
+        // barely moves the slope
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+        let y = [2.1, 3.9, 6.2, 8.1, 9.8, 30.0, 14.2, 15.9, 18.1, 20.2];
+        let result = theil_sen(&x, &y).unwrap();
+        assert_close(result.slope, 2.0, 1e-12);
+        assert_close(result.intercept, 0.1, 1e-12);
+        assert_close(result.confidence_interval.lower, 1.95, 1e-12);
+        assert_close(result.confidence_interval.upper, 2.08, 1e-12);
+    }
+
+    #[test]
+    fn theil_sen_tied_x_test() {
+        let x = [1.0, 2.0, 2.0, 3.0, 4.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+        let y = [1.0, 2.5, 1.9, 3.8, 5.1, 4.6, 6.0, -3.0, 8.2, 9.1, 9.9];
+        let result = theil_sen(&x, &y).unwrap();
+        assert_close(result.slope, 1.1, 1e-12);
+        assert_close(result.intercept, 0.3, 1e-12);
+        assert_close(result.confidence_interval.lower, 0.96, 1e-12);
+        assert_close(result.confidence_interval.upper, 1.3, 1e-12);
+    }
+
+    #[test]
+    fn pairwise_slopes_match_naive_test() {
+        let x = [0.3, 1.7, 1.7, 2.2, 4.0, 5.5, 5.5, 6.1];
+        let y = [1.0, -2.0, 3.5, 0.25, 7.0, 2.0, 2.0, -1.5];
+        let mut naive = Vec::new();
+        for i in 0..x.len() {
+            for j in i + 1..x.len() {
+                if x[i] != x[j] {
+                    naive.push((y[j] - y[i]) / (x[j] - x[i]));
+                }
+            }
+        }
+        naive.sort_by(f64::total_cmp);
+
+        let slopes = PairwiseSlopes::new(&x, &y);
+        assert_eq!(slopes.count, naive.len() as u64);
+        for (rank, expected) in naive.iter().enumerate() {
+            assert_close(slopes.nth(rank as u64), *expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn theil_sen_invalid_input_test() {
+        assert!(matches!(
+            theil_sen(&[3.0, 3.0, 3.0], &[1.0, 2.0, 3.0]),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert_eq!(
+            theil_sen(&[1.0, 2.0], &[1.0]).err(),
+            Some(StatsError::MismatchedLengths { left: 2, right: 1 })
+        );
+    }
+
+    #[test]
+    fn mann_kendall_monotone_test() {
+        // pymannkendall.original_test
+        let data = [1.2, 1.5, 1.4, 2.0, 2.3, 2.2, 2.9, 3.1, 3.0, 3.6, 3.9, 4.2];
+        let result = mann_kendall(&data).unwrap();
+        assert_eq!(result.s, 60.0);
+        assert_close(result.variance, 212.666666666667, 1e-12);
+        assert_close(result.z, 4.04578033981008, 1e-13);
+        assert_close(result.p_value, 5.21491158244026e-5, 1e-16);
+        assert_close(result.tau, 0.909090909090909, 1e-14);
+        assert_eq!(result.trend, Trend::Increasing);
+    }
+
+    #[test]
+    fn mann_kendall_noise_test() {
+        let data = [
+            0.3, -1.2, 0.8, 0.1, -0.4, 1.1, -0.9, 0.2, 0.5, -0.6, 0.0, -0.2, 0.7, -1.0, 0.4,
+        ];
+        let result = mann_kendall(&data).unwrap();
+        assert_eq!(result.s, -3.0);
+        assert_close(result.variance, 408.333333333333, 1e-12);
+        assert_close(result.z, -0.0989743318610787, 1e-14);
+        assert_close(result.p_value, 0.921158650225025, 1e-13);
+        assert_eq!(result.trend, Trend::NoTrend);
+    }
+
+    #[test]
+    fn mann_kendall_ties_test() {
+        let data = [5.0, 5.0, 4.0, 4.0, 4.0, 3.0, 3.0, 2.0, 6.0, 1.0, 1.0, 1.0];
+        let result = mann_kendall(&data).unwrap();
+        assert_eq!(result.s, -42.0);
+        assert_close(result.variance, 203.333333333333, 1e-12);
+        assert_close(result.z, -2.87527618060493, 1e-13);
+        assert_close(result.p_value, 0.00403674230915108, 1e-15);
+        assert_eq!(result.trend, Trend::Decreasing);
+
+        assert_eq!(
+            mann_kendall(&[1.0, 2.0]).err(),
+            Some(StatsError::InsufficientData {
+                required: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn trend_analysis_test() {
+        let data = [1.2, 1.5, 1.4, 2.0, 2.3, 2.2, 2.9, 3.1, 3.0, 3.6, 3.9, 4.2];
+        let analysis = trend_analysis(&data).unwrap();
+        assert_eq!(analysis.mann_kendall.trend, Trend::Increasing);
+        let time: Vec<f64> = (0..12).map(f64::from).collect();
+        assert_eq!(
+            analysis.theil_sen.slope,
+            theil_sen(&time, &data).unwrap().slope
+        );
+        assert!(analysis.theil_sen.slope > 0.0);
+    }
+}
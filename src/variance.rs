@@ -0,0 +1,726 @@
+use std::fmt;
+
+use crate::confidence::validate_confidence;
+use crate::distributions::{ChiSquare, FisherF};
+use crate::format::write_test_line;
+use crate::{
+    mean, median, one_way_anova, sample_variance, Alternative, ConfidenceInterval, HypothesisTest,
+    StatsError,
+};
+
+/// The center Levene's test measures absolute deviations from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Center {
+    Mean,
+    /// The Brown-Forsythe variant, more robust for skewed data.
+    Median,
+    /// The mean after trimming this proportion from each end of every group.
+    /// Deviations are then taken over the trimmed data, as scipy does.
+    TrimmedMean(f64),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeveneResult {
+    pub w: f64,
+    pub df_between: f64,
+    pub df_within: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for LeveneResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(
+            f,
+            "W",
+            &[self.df_between, self.df_within],
+            self.w,
+            self.p_value,
+        )
+    }
+}
+
+impl HypothesisTest for LeveneResult {
+    fn statistic(&self) -> f64 {
+        self.w
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Levene's test"
+    }
+}
+
+/// Levene's test that two or more groups have equal variances: a one-way
+/// ANOVA on each observation's absolute deviation from its group center.
+pub fn levene_test(groups: &[&[f64]], center: Center) -> Result<LeveneResult, StatsError> {
+    let deviations: Vec<Vec<f64>> = groups
+        .iter()
+        .map(|group| {
+            let group = match center {
+                Center::TrimmedMean(proportion) => trim(group, proportion)?,
+                _ => group.to_vec(),
+            };
+            let middle = match center {
+                Center::Median if !group.is_empty() => median(&group)?,
+                _ => mean(&group),
+            };
+            Ok(group.iter().map(|x| (x - middle).abs()).collect())
+        })
+        .collect::<Result<_, StatsError>>()?;
+    let deviations: Vec<&[f64]> = deviations.iter().map(|d| d.as_slice()).collect();
+
+    let anova = one_way_anova(&deviations)?;
+    Ok(LeveneResult {
+        w: anova.f,
+        df_between: anova.df_between,
+        df_within: anova.df_within,
+        p_value: anova.p_value,
+    })
+}
+
+/// One group's row in a Brown-Forsythe variance-homogeneity table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupSpread {
+    pub n: usize,
+    pub median: f64,
+    /// Mean absolute deviation of the group from its median.
+    pub mean_absolute_deviation: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BrownForsytheResult {
+    pub f: f64,
+    pub df_between: f64,
+    pub df_within: f64,
+    pub p_value: f64,
+    /// The spread of each group, in the order the groups were given.
+    pub groups: Vec<GroupSpread>,
+}
+
+impl fmt::Display for BrownForsytheResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(
+            f,
+            "F",
+            &[self.df_between, self.df_within],
+            self.f,
+            self.p_value,
+        )
+    }
+}
+
+impl HypothesisTest for BrownForsytheResult {
+    fn statistic(&self) -> f64 {
+        self.f
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Brown-Forsythe test"
+    }
+}
+
+/// The Brown-Forsythe test that two or more groups have equal variances:
+/// `levene_test` with `Center::Median`, also reporting each group's size,
+/// median and mean absolute deviation from the median. Every group needs at
+/// least two observations; `StatsError::InsufficientGroupData` names the
+/// first that has fewer.
+pub fn brown_forsythe(groups: &[&[f64]]) -> Result<BrownForsytheResult, StatsError> {
+    if groups.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: groups.len(),
+        });
+    }
+    if let Some(group) = groups.iter().position(|g| g.len() < 2) {
+        return Err(StatsError::InsufficientGroupData {
+            group,
+            required: 2,
+            actual: groups[group].len(),
+        });
+    }
+
+    let mut spreads = Vec::with_capacity(groups.len());
+    let mut deviations = Vec::with_capacity(groups.len());
+    for group in groups {
+        let middle = median(group)?;
+        let group_deviations: Vec<f64> = group.iter().map(|x| (x - middle).abs()).collect();
+        spreads.push(GroupSpread {
+            n: group.len(),
+            median: middle,
+            mean_absolute_deviation: mean(&group_deviations),
+        });
+        deviations.push(group_deviations);
+    }
+    let deviations: Vec<&[f64]> = deviations.iter().map(|d| d.as_slice()).collect();
+
+    let anova = one_way_anova(&deviations)?;
+    Ok(BrownForsytheResult {
+        f: anova.f,
+        df_between: anova.df_between,
+        df_within: anova.df_within,
+        p_value: anova.p_value,
+        groups: spreads,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BartlettResult {
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for BartlettResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "K²", &[self.df], self.statistic, self.p_value)
+    }
+}
+
+impl HypothesisTest for BartlettResult {
+    fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "Bartlett's test"
+    }
+}
+
+/// Bartlett's test that two or more normally distributed groups have equal
+/// variances, comparing the pooled variance against the per-group sample
+/// variances with Bartlett's correction factor. Sensitive to non-normality;
+/// prefer `levene_test` with `Center::Median` for skewed data.
+pub fn bartlett_test(groups: &[&[f64]]) -> Result<BartlettResult, StatsError> {
+    if groups.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: groups.len(),
+        });
+    }
+    if let Some(group) = groups.iter().find(|g| g.len() < 2) {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: group.len(),
+        });
+    }
+
+    let variances: Vec<f64> = groups.iter().map(|g| sample_variance(g)).collect();
+    if variances.contains(&0.0) {
+        return Err(StatsError::InvalidParameter(
+            "every group needs a non-zero variance",
+        ));
+    }
+
+    let k = groups.len() as f64;
+    let df_within: f64 = groups.iter().map(|g| g.len() as f64 - 1.0).sum();
+    let pooled = groups
+        .iter()
+        .zip(&variances)
+        .map(|(g, v)| (g.len() as f64 - 1.0) * v)
+        .sum::<f64>()
+        / df_within;
+    let numerator = df_within * pooled.ln()
+        - groups
+            .iter()
+            .zip(&variances)
+            .map(|(g, v)| (g.len() as f64 - 1.0) * v.ln())
+            .sum::<f64>();
+    let reciprocal_sum: f64 = groups.iter().map(|g| 1.0 / (g.len() as f64 - 1.0)).sum();
+    let correction = 1.0 + (reciprocal_sum - 1.0 / df_within) / (3.0 * (k - 1.0));
+
+    let statistic = numerator / correction;
+    let df = k - 1.0;
+    Ok(BartlettResult {
+        statistic,
+        df,
+        p_value: ChiSquare::new(df).sf(statistic),
+    })
+}
+
+/// Confidence level of the interval reported by `var_f_test`.
+const VAR_F_TEST_CONFIDENCE: f64 = 0.95;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VarFTestResult {
+    /// The variance ratio s1² / s2².
+    pub f: f64,
+    pub df1: f64,
+    pub df2: f64,
+    pub p_value: f64,
+    /// 95% confidence interval for the ratio of the population variances,
+    /// one-sided when the alternative is.
+    pub confidence_interval: ConfidenceInterval,
+}
+
+impl fmt::Display for VarFTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "F", &[self.df1, self.df2], self.f, self.p_value)
+    }
+}
+
+impl HypothesisTest for VarFTestResult {
+    fn statistic(&self) -> f64 {
+        self.f
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "F-test of equal variances"
+    }
+}
+
+/// F-test that two normal populations have equal variances. The two-sided
+/// p-value is 2 min(P(F <= f), P(F >= f)), as in R's `var.test`, and
+/// `Alternative::Greater` tests whether the first variance is larger.
+pub fn var_f_test(
+    sample_1: &[f64],
+    sample_2: &[f64],
+    alternative: Alternative,
+) -> Result<VarFTestResult, StatsError> {
+    for sample in [sample_1, sample_2] {
+        if sample.len() < 2 {
+            return Err(StatsError::InsufficientData {
+                required: 2,
+                actual: sample.len(),
+            });
+        }
+    }
+    let variance_2 = sample_variance(sample_2);
+    if variance_2 == 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "the second sample needs a non-zero variance",
+        ));
+    }
+
+    let f = sample_variance(sample_1) / variance_2;
+    let df1 = sample_1.len() as f64 - 1.0;
+    let df2 = sample_2.len() as f64 - 1.0;
+    let distribution = FisherF::new(df1, df2);
+    let lower_tail = distribution.cdf(f);
+    let upper_tail = distribution.sf(f);
+
+    let confidence = VAR_F_TEST_CONFIDENCE;
+    let (p_value, lower, upper) = match alternative {
+        Alternative::TwoSided => {
+            let beta = (1.0 - confidence) / 2.0;
+            (
+                (2.0 * lower_tail.min(upper_tail)).min(1.0),
+                f / distribution.quantile(1.0 - beta),
+                f / distribution.quantile(beta),
+            )
+        }
+        Alternative::Less => (lower_tail, 0.0, f / distribution.quantile(1.0 - confidence)),
+        Alternative::Greater => (
+            upper_tail,
+            f / distribution.quantile(confidence),
+            f64::INFINITY,
+        ),
+    };
+
+    Ok(VarFTestResult {
+        f,
+        df1,
+        df2,
+        p_value,
+        confidence_interval: ConfidenceInterval {
+            lower,
+            upper,
+            point_estimate: f,
+            confidence,
+        },
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VarChiSquareResult {
+    /// (n - 1) s² / sigma0².
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+}
+
+impl fmt::Display for VarChiSquareResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "χ²", &[self.df], self.statistic, self.p_value)
+    }
+}
+
+impl HypothesisTest for VarChiSquareResult {
+    fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        Some(self.df)
+    }
+
+    fn test_name(&self) -> &'static str {
+        "chi-square test of a variance"
+    }
+}
+
+/// Chi-square test that a normal population has variance `sigma0_sq`.
+/// `Alternative::Greater` tests whether the variance exceeds it.
+pub fn var_chi_square_test(
+    data: &[f64],
+    sigma0_sq: f64,
+    alternative: Alternative,
+) -> Result<VarChiSquareResult, StatsError> {
+    if data.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: data.len(),
+        });
+    }
+    if sigma0_sq.is_nan() || sigma0_sq <= 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "hypothesized variance must be positive",
+        ));
+    }
+
+    let df = data.len() as f64 - 1.0;
+    let statistic = df * sample_variance(data) / sigma0_sq;
+    let distribution = ChiSquare::new(df);
+    let p_value = match alternative {
+        Alternative::Less => distribution.cdf(statistic),
+        Alternative::Greater => distribution.sf(statistic),
+        Alternative::TwoSided => {
+            (2.0 * distribution.cdf(statistic).min(distribution.sf(statistic))).min(1.0)
+        }
+    };
+
+    Ok(VarChiSquareResult {
+        statistic,
+        df,
+        p_value,
+    })
+}
+
+/// Two-sided confidence interval for the variance of a normal population,
+/// [(n - 1) s² / chi²(1 - alpha / 2), (n - 1) s² / chi²(alpha / 2)].
+pub fn variance_confidence_interval(
+    data: &[f64],
+    confidence: f64,
+) -> Result<ConfidenceInterval, StatsError> {
+    if data.len() < 2 {
+        return Err(StatsError::InsufficientData {
+            required: 2,
+            actual: data.len(),
+        });
+    }
+    validate_confidence(confidence)?;
+
+    let df = data.len() as f64 - 1.0;
+    let variance = sample_variance(data);
+    let sum_squares = df * variance;
+    let distribution = ChiSquare::new(df);
+    let alpha = 1.0 - confidence;
+    Ok(ConfidenceInterval {
+        lower: sum_squares / distribution.quantile(1.0 - alpha / 2.0),
+        upper: sum_squares / distribution.quantile(alpha / 2.0),
+        point_estimate: variance,
+        confidence,
+    })
+}
+
+/// Two-sided confidence interval for the standard deviation of a normal
+/// population: the square roots of the `variance_confidence_interval`
+/// endpoints.
+pub fn sd_confidence_interval(
+    data: &[f64],
+    confidence: f64,
+) -> Result<ConfidenceInterval, StatsError> {
+    let variance = variance_confidence_interval(data, confidence)?;
+    Ok(ConfidenceInterval {
+        lower: variance.lower.sqrt(),
+        upper: variance.upper.sqrt(),
+        point_estimate: variance.point_estimate.sqrt(),
+        confidence,
+    })
+}
+
+/// The sorted data with floor(proportion * n) values cut from each end.
+fn trim(data: &[f64], proportion: f64) -> Result<Vec<f64>, StatsError> {
+    if !(0.0..0.5).contains(&proportion) {
+        return Err(StatsError::InvalidParameter(
+            "trim proportion must be in [0, 0.5)",
+        ));
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let cut = (proportion * data.len() as f64) as usize;
+    Ok(sorted[cut..data.len() - cut].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_close;
+
+    const A: [f64; 10] = [8.88, 9.12, 9.04, 8.98, 9.00, 9.08, 9.01, 8.85, 9.06, 8.99];
+    const B: [f64; 10] = [8.88, 8.95, 9.29, 9.44, 9.15, 9.58, 8.36, 9.18, 8.67, 9.05];
+    const C: [f64; 10] = [8.95, 9.12, 8.95, 8.85, 9.03, 8.84, 9.07, 8.98, 8.86, 8.98];
+
+    #[test]
+    fn levene_test_test() {
+        // scipy.stats.levene(a, b, c, center=...)
+        let result = levene_test(&[&A, &B, &C], Center::Median).unwrap();
+        assert_close(result.w, 7.58495275450167, 1e-11);
+        assert_eq!(result.df_between, 2.0);
+        assert_eq!(result.df_within, 27.0);
+        assert_close(result.p_value, 0.00243150596724966, 1e-13);
+
+        let result = levene_test(&[&A, &B, &C], Center::Mean).unwrap();
+        assert_close(result.w, 7.90519448344205, 1e-11);
+        assert_close(result.p_value, 0.00198379581747273, 1e-13);
+    }
+
+    #[test]
+    fn levene_test_trimmed_test() {
+        // With ten observations a 5% trim removes nothing
+        let result = levene_test(&[&A, &B, &C], Center::TrimmedMean(0.05)).unwrap();
+        assert_close(result.w, 7.90519448344205, 1e-11);
+
+        let result = levene_test(&[&A, &B, &C], Center::TrimmedMean(0.1)).unwrap();
+        assert_close(result.w, 7.0196188340807, 1e-11);
+        assert_eq!(result.df_within, 21.0);
+        assert_close(result.p_value, 0.00462891047728776, 1e-13);
+
+        assert!(matches!(
+            levene_test(&[&A, &B], Center::TrimmedMean(0.5)),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn brown_forsythe_test() {
+        // scipy.stats.levene(a, b, c, center='median')
+        let result = brown_forsythe(&[&A, &B, &C]).unwrap();
+        assert_close(result.f, 7.58495275450167, 1e-11);
+        assert_eq!(result.df_between, 2.0);
+        assert_eq!(result.df_within, 27.0);
+        assert_close(result.p_value, 0.00243150596724966, 1e-13);
+        assert_eq!(result.test_name(), "Brown-Forsythe test");
+        assert_eq!(result.statistic(), result.f);
+
+        let expected = [(9.005, 0.061), (9.100000000000001, 0.273), (8.965, 0.073)];
+        assert_eq!(result.groups.len(), 3);
+        for (spread, (median, deviation)) in result.groups.iter().zip(expected) {
+            assert_eq!(spread.n, 10);
+            assert_close(spread.median, median, 1e-14);
+            assert_close(spread.mean_absolute_deviation, deviation, 1e-14);
+        }
+        assert_eq!(result.to_string(), "F(2, 27) = 7.58, p = 0.00243");
+    }
+
+    #[test]
+    fn brown_forsythe_unequal_groups_test() {
+        // scipy.stats.levene(x, y, z, center='median')
+        let x = [2.1, 3.4, 1.9, 5.6, 2.2];
+        let y = [1.0, 7.5, 3.3, 9.1, 0.4, 6.6, 2.8];
+        let z = [4.4, 4.1, 4.9];
+        let result = brown_forsythe(&[&x, &y, &z]).unwrap();
+        assert_close(result.f, 2.889149764630673, 1e-12);
+        assert_eq!(result.df_within, 12.0);
+        assert_close(result.p_value, 0.09456842643972253, 1e-12);
+        let summary: Vec<(usize, f64)> = result.groups.iter().map(|g| (g.n, g.median)).collect();
+        assert_eq!(summary, [(5, 2.2), (7, 3.3), (3, 4.4)]);
+        assert_close(result.groups[0].mean_absolute_deviation, 1.0, 1e-15);
+        assert_close(result.groups[1].mean_absolute_deviation, 19.0 / 7.0, 1e-15);
+        assert_close(result.groups[2].mean_absolute_deviation, 0.8 / 3.0, 1e-15);
+    }
+
+    #[test]
+    fn brown_forsythe_small_group_test() {
+        assert_eq!(
+            brown_forsythe(&[&A, &[1.0], &C]).err(),
+            Some(StatsError::InsufficientGroupData {
+                group: 1,
+                required: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            brown_forsythe(&[&A, &B, &[]]).err(),
+            Some(StatsError::InsufficientGroupData {
+                group: 2,
+                required: 2,
+                actual: 0
+            })
+        );
+        assert_eq!(
+            brown_forsythe(&[&A]).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn bartlett_test_test() {
+        // R: bartlett.test(weight ~ group, data = PlantGrowth)
+        let ctrl = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+        let trt1 = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+        let trt2 = [6.31, 5.12, 5.54, 5.50, 5.37, 5.29, 4.92, 6.15, 5.80, 5.26];
+        let result = bartlett_test(&[&ctrl, &trt1, &trt2]).unwrap();
+        assert_close(result.statistic, 2.87857378723609, 1e-12);
+        assert_eq!(result.df, 2.0);
+        assert_close(result.p_value, 0.237096773634558, 1e-12);
+
+        // Unequal variances
+        let result = bartlett_test(&[&A, &B, &C]).unwrap();
+        assert_close(result.statistic, 22.7894348137268, 1e-11);
+        assert_close(result.p_value, 1.12547825188346e-5, 1e-16);
+    }
+
+    #[test]
+    fn bartlett_test_unequal_sizes_test() {
+        let result = bartlett_test(&[
+            &[1.1, 2.3, 1.9, 3.2, 2.8],
+            &[2.0, 3.1, 2.5, 2.2, 3.6, 2.9, 1.7],
+            &[5.5, 1.2, 3.8, 0.4, 6.1, 2.9],
+        ])
+        .unwrap();
+        assert_close(result.statistic, 8.49059836575881, 1e-12);
+        assert_close(result.p_value, 0.0143314453144656, 1e-12);
+
+        assert_eq!(
+            bartlett_test(&[&A, &[1.0]]).err(),
+            Some(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn var_f_test_test() {
+        // R: var.test(ctrl, trt1)
+        let ctrl = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+        let trt1 = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+        let result = var_f_test(&ctrl, &trt1, Alternative::TwoSided).unwrap();
+        assert_close(result.f, 0.539743071883781, 1e-13);
+        assert_eq!(result.df1, 9.0);
+        assert_eq!(result.df2, 9.0);
+        assert_close(result.p_value, 0.371896271225331, 1e-12);
+        assert_close(result.confidence_interval.lower, 0.134064544225263, 1e-12);
+        assert_close(result.confidence_interval.upper, 2.17300245437781, 1e-11);
+        assert_eq!(result.confidence_interval.confidence, 0.95);
+    }
+
+    #[test]
+    fn var_f_test_one_sided_test() {
+        let ctrl = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+        let trt1 = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+        let result = var_f_test(&ctrl, &trt1, Alternative::Less).unwrap();
+        assert_close(result.p_value, 0.185948135612665, 1e-12);
+        assert_eq!(result.confidence_interval.lower, 0.0);
+        assert_close(result.confidence_interval.upper, 1.71578552939048, 1e-11);
+
+        let result = var_f_test(&ctrl, &trt1, Alternative::Greater).unwrap();
+        assert_close(result.p_value, 0.814051864387335, 1e-12);
+        assert_close(result.confidence_interval.lower, 0.169789626183659, 1e-12);
+        assert_eq!(result.confidence_interval.upper, f64::INFINITY);
+    }
+
+    const FILL_WEIGHTS: [f64; 12] = [
+        10.2, 9.7, 10.5, 10.1, 9.9, 10.8, 9.6, 10.3, 10.0, 10.4, 9.8, 10.6,
+    ];
+
+    #[test]
+    fn var_chi_square_test_test() {
+        // R: EnvStats::varTest(x, sigma.squared = 0.09)
+        let result = var_chi_square_test(&FILL_WEIGHTS, 0.09, Alternative::TwoSided).unwrap();
+        assert_close(result.statistic, 17.212962962963, 1e-11);
+        assert_eq!(result.df, 11.0);
+        assert_close(result.p_value, 0.203464235204966, 1e-12);
+
+        let result = var_chi_square_test(&FILL_WEIGHTS, 0.09, Alternative::Greater).unwrap();
+        assert_close(result.p_value, 0.101732117602483, 1e-12);
+        let result = var_chi_square_test(&FILL_WEIGHTS, 0.09, Alternative::Less).unwrap();
+        assert_close(result.p_value, 0.898267882397517, 1e-12);
+    }
+
+    #[test]
+    fn variance_confidence_interval_test() {
+        // s² = 0.1408333, so the interval is 11 s² / chi²(11) quantiles
+        let interval = variance_confidence_interval(&FILL_WEIGHTS, 0.95).unwrap();
+        assert_close(interval.lower, 0.0706735029752618, 1e-13);
+        assert_close(interval.upper, 0.405992891632415, 1e-12);
+
+        let interval = variance_confidence_interval(&FILL_WEIGHTS, 0.9).unwrap();
+        assert_close(interval.lower, 0.0787372724050262, 1e-13);
+        assert_close(interval.upper, 0.338629500223468, 1e-12);
+
+        assert!(matches!(
+            variance_confidence_interval(&FILL_WEIGHTS, 1.0),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn sd_confidence_interval_test() {
+        // Textbook example: n = 20, s = 2.5, 95% gives (1.90, 3.65) for sigma
+        // and (3.61, 13.33) for sigma². Ten values either side of the mean
+        // give s² = 20 a² / 19.
+        let a = 2.5 * (19.0f64 / 20.0).sqrt();
+        let data: Vec<f64> = (0..20)
+            .map(|i| if i % 2 == 0 { 10.0 + a } else { 10.0 - a })
+            .collect();
+
+        let variance = variance_confidence_interval(&data, 0.95).unwrap();
+        assert_close(variance.point_estimate, 6.25, 1e-13);
+        assert_close(variance.lower, 3.61466024917505, 1e-12);
+        assert_close(variance.upper, 13.3329344014748, 1e-11);
+
+        let sd = sd_confidence_interval(&data, 0.95).unwrap();
+        assert_close(sd.point_estimate, 2.5, 1e-14);
+        assert_close(sd.lower, 1.90122598582469, 1e-12);
+        assert_close(sd.upper, 3.65142909029805, 1e-12);
+        assert_eq!(sd.confidence, 0.95);
+
+        assert_eq!(
+            sd_confidence_interval(&[1.0], 0.95),
+            Err(StatsError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,425 @@
+use std::fmt;
+
+use crate::distributions::Normal;
+use crate::format::write_test_line;
+use crate::{mean, Alternative, HypothesisTest, SampleStatistics, StatsError};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZTestResult {
+    pub z: f64,
+    pub p_value: f64,
+    /// The estimated difference being tested, e.g. x̄ - μ0.
+    pub mean_difference: f64,
+    /// Standard error of `mean_difference` under the null hypothesis.
+    pub standard_error: f64,
+}
+
+impl fmt::Display for ZTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "z", &[], self.z, self.p_value)
+    }
+}
+
+impl HypothesisTest for ZTestResult {
+    fn statistic(&self) -> f64 {
+        self.z
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "z-test"
+    }
+}
+
+impl ZTestResult {
+    /// The same test against `alternative`, with the p-value recomputed from
+    /// `z`; see `TTestResult::with_alternative`.
+    pub fn with_alternative(self, alternative: Alternative) -> ZTestResult {
+        ZTestResult {
+            p_value: Normal::standard().p_value(self.z, alternative),
+            ..self
+        }
+    }
+}
+
+/// One-sample z-test for a known population standard deviation `sigma`.
+pub fn one_samp_z_test(sample_mean: f64, n: usize, mu0: f64, sigma: f64) -> ZTestResult {
+    let mean_difference = sample_mean - mu0;
+    let standard_error = sigma / (n as f64).sqrt();
+    let z = mean_difference / standard_error;
+    let p_value = Normal::standard().two_tailed(z);
+
+    ZTestResult {
+        z,
+        p_value,
+        mean_difference,
+        standard_error,
+    }
+}
+
+pub fn one_samp_z_test_from_array(array: &[f64], mu0: f64, sigma: f64) -> ZTestResult {
+    one_samp_z_test(mean(array), array.len(), mu0, sigma)
+}
+
+/// Two-sample z-test using the normal approximation, suitable when both
+/// samples are large.
+pub fn two_samp_z_test(samp_1: &SampleStatistics, samp_2: &SampleStatistics) -> ZTestResult {
+    let mean_difference = samp_1.sample_mean - samp_2.sample_mean;
+    let standard_error =
+        (samp_1.variance() / samp_1.n as f64 + samp_2.variance() / samp_2.n as f64).sqrt();
+    let z = mean_difference / standard_error;
+    let p_value = Normal::standard().two_tailed(z);
+
+    ZTestResult {
+        z,
+        p_value,
+        mean_difference,
+        standard_error,
+    }
+}
+
+/// One-proportion z-test of H0: p = p0, using the null standard error
+/// sqrt(p0 (1 - p0) / n). With `continuity_correction` the Yates correction
+/// is applied, matching R's `prop.test(correct = TRUE)`. The test is
+/// two-sided; as the correction depends on the alternative, one-sided tests
+/// should use `one_prop_z_test_with` rather than `with_alternative`.
+pub fn one_prop_z_test(
+    successes: usize,
+    n: usize,
+    p0: f64,
+    continuity_correction: bool,
+) -> Result<ZTestResult, StatsError> {
+    one_prop_z_test_with(
+        successes,
+        n,
+        p0,
+        Alternative::TwoSided,
+        continuity_correction,
+    )
+}
+
+/// One-proportion z-test against `alternative`. The continuity correction
+/// moves x - n p0 by a half toward the tail being approximated: down for
+/// `Greater`, as P(X >= x) is P(X > x - 0.5), up for `Less`, and toward
+/// zero, by at most |x - n p0|, for `TwoSided`.
+pub fn one_prop_z_test_with(
+    successes: usize,
+    n: usize,
+    p0: f64,
+    alternative: Alternative,
+    continuity_correction: bool,
+) -> Result<ZTestResult, StatsError> {
+    if n == 0 {
+        return Err(StatsError::EmptyInput);
+    }
+    if successes > n {
+        return Err(StatsError::InvalidParameter(
+            "successes must not exceed the number of trials",
+        ));
+    }
+    if !(p0 > 0.0 && p0 < 1.0) {
+        return Err(StatsError::InvalidParameter(
+            "p0 must be strictly between 0 and 1",
+        ));
+    }
+
+    let n_f = n as f64;
+    let deviation = successes as f64 - n_f * p0;
+    let correction = match (continuity_correction, alternative) {
+        (false, _) => 0.0,
+        (true, Alternative::Greater) => -0.5,
+        (true, Alternative::Less) => 0.5,
+        (true, Alternative::TwoSided) => -deviation.signum() * deviation.abs().min(0.5),
+    };
+
+    let count_standard_error = (n_f * p0 * (1.0 - p0)).sqrt();
+    let z = (deviation + correction) / count_standard_error;
+    let p_value = Normal::standard().p_value(z, alternative);
+
+    Ok(ZTestResult {
+        z,
+        p_value,
+        mean_difference: deviation / n_f,
+        standard_error: count_standard_error / n_f,
+    })
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoPropZTestResult {
+    pub z: f64,
+    pub p_value: f64,
+    pub proportion_1: f64,
+    pub proportion_2: f64,
+    /// proportion_1 - proportion_2
+    pub difference: f64,
+    pub pooled_proportion: f64,
+}
+
+impl fmt::Display for TwoPropZTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_test_line(f, "z", &[], self.z, self.p_value)
+    }
+}
+
+impl HypothesisTest for TwoPropZTestResult {
+    fn statistic(&self) -> f64 {
+        self.z
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+
+    fn test_name(&self) -> &'static str {
+        "two-proportion z-test"
+    }
+}
+
+impl TwoPropZTestResult {
+    /// The same test against `alternative`, where `Greater` tests p1 > p2.
+    pub fn with_alternative(self, alternative: Alternative) -> TwoPropZTestResult {
+        TwoPropZTestResult {
+            p_value: Normal::standard().p_value(self.z, alternative),
+            ..self
+        }
+    }
+}
+
+/// Two-proportion z-test of H0: p1 = p2 using the pooled proportion.
+pub fn two_prop_z_test(
+    successes_1: usize,
+    n_1: usize,
+    successes_2: usize,
+    n_2: usize,
+) -> Result<TwoPropZTestResult, StatsError> {
+    if n_1 == 0 || n_2 == 0 {
+        return Err(StatsError::EmptyInput);
+    }
+    if successes_1 > n_1 || successes_2 > n_2 {
+        return Err(StatsError::InvalidParameter(
+            "successes must not exceed the number of trials",
+        ));
+    }
+
+    let n_1 = n_1 as f64;
+    let n_2 = n_2 as f64;
+    let proportion_1 = successes_1 as f64 / n_1;
+    let proportion_2 = successes_2 as f64 / n_2;
+    let difference = proportion_1 - proportion_2;
+    let pooled_proportion = (successes_1 + successes_2) as f64 / (n_1 + n_2);
+
+    // With identical proportions the pooled standard error may be zero (e.g. no
+    // successes in either arm), but there is no evidence against the null.
+    let z = if difference == 0.0 {
+        0.0
+    } else {
+        let standard_error =
+            (pooled_proportion * (1.0 - pooled_proportion) * (1.0 / n_1 + 1.0 / n_2)).sqrt();
+        difference / standard_error
+    };
+    let p_value = Normal::standard().two_tailed(z);
+
+    Ok(TwoPropZTestResult {
+        z,
+        p_value,
+        proportion_1,
+        proportion_2,
+        difference,
+        pooled_proportion,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_close, welch_t_test, GetStatistics};
+
+    #[test]
+    fn one_samp_z_test_test() {
+        let result = one_samp_z_test(102.5, 36, 100.0, 6.0);
+        assert_close(result.z, 2.5, 1e-12);
+        assert_close(result.p_value, 0.0124193306515523, 1e-12);
+        assert_close(result.standard_error, 1.0, 1e-12);
+
+        let result = one_samp_z_test(100.0, 36, 100.0, 6.0);
+        assert_eq!(result.z, 0.0);
+        assert_eq!(result.p_value, 1.0);
+    }
+
+    #[test]
+    fn one_samp_z_test_from_array_test() {
+        let data = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 4.7, 5.5];
+        let result = one_samp_z_test_from_array(&data, 5.0, 0.5);
+        assert_close(result.z, 2.05060966544099, 1e-12);
+        assert_close(result.p_value, 0.0403049743625408, 1e-12);
+    }
+
+    #[test]
+    fn two_samp_z_test_test() {
+        let samp_1 = SampleStatistics::from_array(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let samp_2 = SampleStatistics::from_array(&[2.0, 4.0, 6.0, 8.0, 10.0, 12.0]);
+        let result = two_samp_z_test(&samp_1, &samp_2);
+        assert_close(result.z, -2.376354103144018, 1e-12);
+        assert_close(result.mean_difference, -4.0, 1e-12);
+    }
+
+    #[test]
+    fn two_samp_z_test_agrees_with_welch_at_large_n_test() {
+        let array_1: Vec<f64> = (0..20_000).map(|i| (i as f64 * 0.37).sin()).collect();
+        let array_2: Vec<f64> = (0..30_000)
+            .map(|i| 0.02 + 1.5 * (i as f64 * 0.71).cos())
+            .collect();
+        let samp_1 = SampleStatistics::from_array(&array_1);
+        let samp_2 = SampleStatistics::from_array(&array_2);
+
+        let z = two_samp_z_test(&samp_1, &samp_2);
+        let t = welch_t_test(&samp_1, &samp_2);
+        assert_close(z.z, t.t, 1e-12);
+        assert_close(z.p_value, t.p_value, 1e-4);
+    }
+
+    #[test]
+    fn one_prop_z_test_test() {
+        // R: prop.test(60, 100, p = 0.5, correct = FALSE) reports X-squared = z^2 = 4
+        let result = one_prop_z_test(60, 100, 0.5, false).unwrap();
+        assert_close(result.z, 2.0, 1e-12);
+        assert_close(result.z.powi(2), 4.0, 1e-12);
+        assert_close(result.p_value, 0.0455002638963584, 1e-12);
+        assert_close(result.mean_difference, 0.1, 1e-12);
+        assert_close(result.standard_error, 0.05, 1e-12);
+
+        // R: prop.test(60, 100, p = 0.5, correct = TRUE) reports X-squared = 3.61
+        let result = one_prop_z_test(60, 100, 0.5, true).unwrap();
+        assert_close(result.z.powi(2), 3.61, 1e-12);
+        assert_close(result.p_value, 0.0574331196320036, 1e-12);
+
+        let result = one_prop_z_test(15, 20, 0.3, false).unwrap();
+        assert_close(result.z, 4.3915503282684, 1e-12);
+        assert_close(result.p_value, 1.12545276538421e-5, 1e-15);
+    }
+
+    #[test]
+    fn one_prop_z_test_one_sided_test() {
+        // The corrected z is (x - n p0 -/+ 0.5) / sqrt(n p0 (1 - p0)), moved
+        // toward the tail each alternative approximates
+        let cases = [
+            (60, Alternative::Greater, 1.9, 0.028716559816001807),
+            (60, Alternative::Less, 2.1, 0.9821355794371834),
+            (40, Alternative::Greater, -2.1, 0.9821355794371834),
+            (40, Alternative::Less, -1.9, 0.028716559816001807),
+        ];
+        for (successes, alternative, z, p_value) in cases {
+            let result = one_prop_z_test_with(successes, 100, 0.5, alternative, true).unwrap();
+            assert_close(result.z, z, 1e-14);
+            assert_close(result.p_value, p_value, 1e-14);
+        }
+
+        // Uncorrected, the one-sided p-values halve the two-sided one
+        let two_sided = one_prop_z_test(15, 20, 0.3, false).unwrap();
+        let greater = one_prop_z_test_with(15, 20, 0.3, Alternative::Greater, false).unwrap();
+        assert_eq!(greater.z, two_sided.z);
+        assert_close(greater.p_value, two_sided.p_value / 2.0, 1e-18);
+        let corrected = one_prop_z_test_with(60, 100, 0.5, Alternative::TwoSided, true).unwrap();
+        assert_eq!(
+            corrected.p_value,
+            one_prop_z_test(60, 100, 0.5, true).unwrap().p_value
+        );
+    }
+
+    #[test]
+    fn one_prop_z_test_invalid_input_test() {
+        assert!(matches!(
+            one_prop_z_test(11, 10, 0.5, false),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            one_prop_z_test(5, 10, 0.0, false),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            one_prop_z_test(5, 10, 1.0, false),
+            Err(StatsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            one_prop_z_test(0, 0, 0.5, false),
+            Err(StatsError::EmptyInput)
+        ));
+    }
+
+    #[test]
+    fn two_prop_z_test_test() {
+        // R: prop.test(c(45, 30), c(100, 100), correct = FALSE) reports X-squared = 4.8
+        let result = two_prop_z_test(45, 100, 30, 100).unwrap();
+        assert_close(result.z, 2.19089023002066, 1e-12);
+        assert_close(result.z.powi(2), 4.8, 1e-12);
+        assert_close(result.p_value, 0.0284597369163106, 1e-12);
+        assert_close(result.proportion_1, 0.45, 1e-15);
+        assert_close(result.proportion_2, 0.3, 1e-15);
+        assert_close(result.difference, 0.15, 1e-15);
+        assert_close(result.pooled_proportion, 0.375, 1e-15);
+
+        let result = two_prop_z_test(12, 150, 30, 250).unwrap();
+        assert_close(result.z, -1.2633958975454, 1e-12);
+        assert_close(result.p_value, 0.206446937752605, 1e-12);
+    }
+
+    #[test]
+    fn alternative_test() {
+        // R: prop.test(c(45, 30), c(100, 100), correct = FALSE,
+        // alternative = "greater")
+        let result = two_prop_z_test(45, 100, 30, 100).unwrap();
+        let greater = two_prop_z_test(45, 100, 30, 100)
+            .unwrap()
+            .with_alternative(Alternative::Greater);
+        assert_close(greater.p_value, 0.0142298684581553, 1e-12);
+        assert_close(greater.p_value, result.p_value / 2.0, 1e-15);
+        assert!(greater.with_alternative(Alternative::Less).p_value > 0.98);
+
+        let result = one_samp_z_test(103.0, 25, 100.0, 10.0);
+        let greater =
+            one_samp_z_test(103.0, 25, 100.0, 10.0).with_alternative(Alternative::Greater);
+        let less = one_samp_z_test(103.0, 25, 100.0, 10.0).with_alternative(Alternative::Less);
+        // z = 1.5
+        assert_close(greater.p_value, 0.0668072012688581, 1e-12);
+        assert_close(greater.p_value + less.p_value, 1.0, 1e-15);
+        assert_close(
+            result.p_value,
+            2.0 * greater.p_value.min(less.p_value),
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn two_prop_z_test_edge_cases_test() {
+        let result = two_prop_z_test(0, 50, 0, 80).unwrap();
+        assert_eq!(result.z, 0.0);
+        assert_eq!(result.p_value, 1.0);
+
+        let result = two_prop_z_test(50, 50, 80, 80).unwrap();
+        assert_eq!(result.z, 0.0);
+
+        assert!(matches!(
+            two_prop_z_test(0, 0, 3, 10),
+            Err(StatsError::EmptyInput)
+        ));
+        assert!(matches!(
+            two_prop_z_test(3, 10, 0, 0),
+            Err(StatsError::EmptyInput)
+        ));
+        assert!(matches!(
+            two_prop_z_test(3, 10, 11, 10),
+            Err(StatsError::InvalidParameter(_))
+        ));
+    }
+}